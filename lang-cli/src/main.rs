@@ -22,7 +22,7 @@ use argh::FromArgs;
 use hlsl_lang::{
     ast::{NodeDisplay, TranslationUnit},
     lexer::full::fs::PreprocessorExt,
-    parse::IntoParseBuilderExt,
+    parse::{HlslVersion, IntoParseBuilderExt, ParseOptions, ShaderModel},
 };
 
 fn output_text(output: &mut dyn std::io::Write, tu: TranslationUnit) -> std::io::Result<()> {
@@ -58,6 +58,14 @@ struct Opts {
     /// output format (text, json or hlsl)
     format: String,
 
+    #[argh(option, short = 'T')]
+    /// target shader model (e.g. ps_6_6)
+    target: Option<ShaderModel>,
+
+    #[argh(option, default = "HlslVersion::default()")]
+    /// HLSL language version (2016, 2018, 2021 or 202x)
+    hlsl_version: HlslVersion,
+
     #[argh(positional)]
     /// input file path
     path: Option<String>,
@@ -94,7 +102,11 @@ impl<I: std::error::Error> std::fmt::Display for ParseError<I> {
 }
 
 use miette::{NamedSource, Result};
-fn parse_tu(source: &str, path: &str) -> Result<hlsl_lang::ast::TranslationUnit> {
+fn parse_tu(
+    source: &str,
+    path: &str,
+    opts: &ParseOptions,
+) -> Result<hlsl_lang::ast::TranslationUnit> {
     let mut processor = hlsl_lang_pp::processor::fs::StdProcessor::new();
     let tu: Result<hlsl_lang::ast::TranslationUnit, _> = processor
         .open_source(
@@ -102,6 +114,7 @@ fn parse_tu(source: &str, path: &str) -> Result<hlsl_lang::ast::TranslationUnit>
             Path::new(path).parent().unwrap_or_else(|| Path::new(".")),
         )
         .builder()
+        .opts(opts)
         .parse()
         .map(|(mut tu, _, iter)| {
             iter.into_directives().inject(&mut tu);
@@ -172,6 +185,11 @@ fn main() -> Result<(), std::io::Error> {
             .as_ref()
             .map(String::to_owned)
             .unwrap_or_else(|| "standard input".to_owned()),
+        &ParseOptions {
+            hlsl_version: args.hlsl_version,
+            shader_model: args.target,
+            ..Default::default()
+        },
     ) {
         Ok(tu) => {
             output_fn(&mut std::io::stdout(), tu)?;
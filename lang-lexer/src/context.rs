@@ -6,13 +6,17 @@ use lang_util::{FileId, SmolStr};
 
 use hlsl_lang_types::ast;
 
+use crate::token::Token;
+
+pub use hlsl_lang_pp::types::{HlslVersion, ShaderModel, ShaderStage};
+
 /// Parsing options
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ParseOptions {
-    /// Default HLSL version number to parse source as
-    pub default_version: u16,
-    /// `true` if the HLSL target should be Vulkan instead of OpenGL
-    pub target_vulkan: bool,
+    /// HLSL language version the source is written in
+    pub hlsl_version: HlslVersion,
+    /// Target shader model (e.g. `ps_6_6`), if known
+    pub shader_model: Option<ShaderModel>,
     /// Unique source identifier for token positions
     pub source_id: FileId,
     /// Allow Rust quoting identifiers (`#(ident)`) in the source
@@ -22,8 +26,8 @@ pub struct ParseOptions {
 impl Default for ParseOptions {
     fn default() -> Self {
         Self {
-            default_version: 460,
-            target_vulkan: false,
+            hlsl_version: HlslVersion::default(),
+            shader_model: None,
             source_id: FileId::new(0),
             allow_rs_ident: false,
         }
@@ -129,8 +133,14 @@ impl From<ParseContextData> for ParseContext {
 pub struct ParseContextData {
     /// List of known type names
     names: Vec<HashSet<SmolStr>>,
+    /// Whether the last token was `{`, whose nesting level starts with the next token
+    pending_scope: bool,
     /// List of parsed comments (or `None` to disable comment parsing)
     comments: Option<CommentList>,
+    /// Language version used to gate version-specific syntax
+    hlsl_version: HlslVersion,
+    /// Target shader model, if known
+    shader_model: Option<ShaderModel>,
 
     policy: Rc<dyn TypeTablePolicy>,
 }
@@ -179,13 +189,36 @@ impl ParseContextData {
     pub fn comments(&self) -> Option<&CommentList> {
         self.comments.as_ref()
     }
+
+    /// Get the HLSL language version this context parses
+    pub fn hlsl_version(&self) -> HlslVersion {
+        self.hlsl_version
+    }
+
+    /// Set the HLSL language version this context parses
+    pub fn set_hlsl_version(&mut self, version: HlslVersion) {
+        self.hlsl_version = version;
+    }
+
+    /// Get the target shader model this context parses for
+    pub fn shader_model(&self) -> Option<ShaderModel> {
+        self.shader_model
+    }
+
+    /// Set the target shader model this context parses for
+    pub fn set_shader_model(&mut self, shader_model: Option<ShaderModel>) {
+        self.shader_model = shader_model;
+    }
 }
 
 impl Default for ParseContextData {
     fn default() -> Self {
         Self {
             names: vec![HashSet::new()],
+            pending_scope: false,
             comments: Default::default(),
+            hlsl_version: Default::default(),
+            shader_model: None,
             policy: Rc::new(GlslTypeTablePolicy),
         }
     }
@@ -229,6 +262,11 @@ impl ParseContext {
         self.data.borrow().is_type_name(name)
     }
 
+    /// HLSL language version the source is parsed as
+    pub fn hlsl_version(&self) -> HlslVersion {
+        self.data.borrow().hlsl_version()
+    }
+
     /// Register `name` as a new type name
     pub fn add_type_name(&self, name: ast::Identifier) -> ast::TypeName {
         self.data.borrow_mut().add_type_name(name)
@@ -244,6 +282,24 @@ impl ParseContext {
         self.data.borrow_mut().pop_scope();
     }
 
+    /// Update the nesting levels for a token returned by the lexer
+    ///
+    /// The level opened by `{` only starts with the next token. The parser reduces rules using
+    /// `{` as its lookahead, so names registered by these rules, such as the name of a struct,
+    /// belong to the enclosing level.
+    pub fn scope_token(&self, token: &Token) {
+        let mut data = self.data.borrow_mut();
+        if std::mem::take(&mut data.pending_scope) {
+            data.push_scope();
+        }
+
+        match token {
+            Token::LeftBrace => data.pending_scope = true,
+            Token::RightBrace => data.pop_scope(),
+            _ => {}
+        }
+    }
+
     /// Update the context data with a new identifier in a given context
     pub fn new_identifier(&self, name: &ast::Identifier, ctx: IdentifierContext) {
         self.data.borrow_mut().new_identifier(name, ctx)
@@ -1,6 +1,10 @@
 //! hlsl-lang-pp/full based lexer
 
-use hlsl_lang_pp::{processor, types};
+use hlsl_lang_pp::{
+    exts::Registry,
+    processor::{self, ProcessorState, ProcessorStateBuilder},
+    types,
+};
 
 use lang_util::{
     located::Located,
@@ -8,7 +12,7 @@ use lang_util::{
     TextSize,
 };
 
-use super::Token;
+use super::{ParseOptions, Token};
 
 mod core;
 
@@ -18,6 +22,15 @@ pub use directives::*;
 pub mod fs;
 pub mod str;
 
+/// Preprocessor state with the predefined macros for the language version and target selected
+/// by the parsing options
+fn default_state(registry: &Registry, opts: &ParseOptions) -> ProcessorState {
+    ProcessorStateBuilder::new(registry)
+        .hlsl_version(opts.hlsl_version)
+        .shader_model(opts.shader_model)
+        .finish()
+}
+
 /// Lexical analysis error
 #[derive(Debug)]
 pub enum LexicalError<E: std::error::Error + 'static> {
@@ -172,11 +172,7 @@ impl LexerCore {
                             }
                         }
                         _ => {
-                            if token.1 == Token::LeftBrace {
-                                self.ctx.push_scope();
-                            } else if token.1 == Token::RightBrace {
-                                self.ctx.pop_scope();
-                            }
+                            self.ctx.scope_token(&token.1);
 
                             token_state.push_item(Ok(token));
                         }
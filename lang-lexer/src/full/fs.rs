@@ -33,7 +33,7 @@ pub struct Lexer<'r, 'p, F: FileSystem> {
 impl<'r, 'p, F: FileSystem> Lexer<'r, 'p, F> {
     fn new(inner: ExpandStack<'p, F>, registry: &'r Registry, opts: &ParseOptions) -> Self {
         Self {
-            inner: inner.tokenize(opts.default_version, opts.target_vulkan, registry),
+            inner: inner.tokenize(registry),
             current_file: Default::default(),
             handle_token: Default::default(),
             opts: *opts,
@@ -136,11 +136,12 @@ where
     type Iter = LexerIterator<'r, 'p, F>;
 
     fn new(source: Self::Input, opts: &ParseOptions) -> Self {
-        Lexer::new(
-            source.inner.process(source.state.unwrap_or_default()),
-            source.registry.unwrap_or(&DEFAULT_REGISTRY),
-            opts,
-        )
+        let registry = source.registry.unwrap_or(&DEFAULT_REGISTRY);
+        let state = source
+            .state
+            .unwrap_or_else(|| super::default_state(registry, opts));
+
+        Lexer::new(source.inner.process(state), registry, opts)
     }
 
     fn run(self, ctx: ParseContext) -> Self::Iter {
@@ -34,11 +34,7 @@ impl<'i> Lexer<'i> {
         state: ProcessorState,
     ) -> Self {
         Self {
-            inner: processor::str::process(source, state).tokenize(
-                opts.default_version,
-                opts.target_vulkan,
-                registry,
-            ),
+            inner: processor::str::process(source, state).tokenize(registry),
             handle_token: Default::default(),
             opts: *opts,
         }
@@ -133,7 +129,12 @@ impl<'i> LangLexer<'i> for Lexer<'i> {
     type Iter = LexerIterator<'i>;
 
     fn new(source: Self::Input, opts: &ParseOptions) -> Self {
-        Self::new_with_state(source, &DEFAULT_REGISTRY, opts, ProcessorState::default())
+        Self::new_with_state(
+            source,
+            &DEFAULT_REGISTRY,
+            opts,
+            super::default_state(&DEFAULT_REGISTRY, opts),
+        )
     }
 
     fn run(self, ctx: ParseContext) -> Self::Iter {
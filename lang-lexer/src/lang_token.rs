@@ -56,13 +56,6 @@ pub fn lang_token(
                 types::TypeName::MAT4X3 => Token::Mat4x3,
                 types::TypeName::MAT4X4 => Token::Mat44,
                 types::TypeName::MATRIX => Token::Mat44, // matrix is equivalent to float4x4
-                types::TypeName::MAT1X1 => Token::Mat22, // Map to closest equivalent
-                types::TypeName::MAT1X2 => Token::Mat2x3, // Map to closest equivalent
-                types::TypeName::MAT1X3 => Token::Mat2x3, // Map to closest equivalent
-                types::TypeName::MAT1X4 => Token::Mat2x4, // Map to closest equivalent
-                types::TypeName::MAT2X1 => Token::Mat3x2, // Map to closest equivalent
-                types::TypeName::MAT3X1 => Token::Mat3x2, // Map to closest equivalent
-                types::TypeName::MAT4X1 => Token::Mat4x2, // Map to closest equivalent
                 // Basic HLSL sampler type
                 types::TypeName::SAMPLER => Token::Sampler,
                 // HLSL texture types
@@ -79,6 +72,8 @@ pub fn lang_token(
                 types::TypeName::TEXTURE_BUFFER => Token::TextureBuffer,
                 types::TypeName::STRUCTUREDBUFFER => Token::StructuredBuffer,
                 types::TypeName::RWSTRUCTUREDBUFFER => Token::RWStructuredBuffer,
+                types::TypeName::STRING => Token::String,
+                // Matrices with a single row or column, like `float2x1`, have no GLSL token
                 other => Token::TypeName(other.to_string().into()),
             },
             types::Token::FLOAT_CONST(val) => Token::FloatConstant(val),
@@ -87,6 +82,7 @@ pub fn lang_token(
             types::Token::BOOL_CONST(val) => Token::BoolConstant(val),
             types::Token::DOUBLE_CONST(val) => Token::DoubleConstant(val),
             types::Token::STRING_CONST(val) => Token::StringConstant(val),
+            types::Token::CHAR_CONST(val) => Token::CharConstant(val),
             types::Token::LEFT_OP => Token::LeftOp,
             types::Token::RIGHT_OP => Token::RightOp,
             types::Token::INC_OP => Token::IncOp,
@@ -108,6 +104,7 @@ pub fn lang_token(
             types::Token::XOR_ASSIGN => Token::XorAssign,
             types::Token::OR_ASSIGN => Token::OrAssign,
             types::Token::SUB_ASSIGN => Token::SubAssign,
+            types::Token::SCOPE_OP => Token::ScopeOp,
             types::Token::LPAREN => Token::LeftParen,
             types::Token::RPAREN => Token::RightParen,
             types::Token::LBRACKET => Token::LeftBracket,
@@ -140,22 +137,13 @@ pub fn lang_token(
             types::Token::EXTERN => Token::Extern,
             types::Token::INLINE => Token::Inline,
             types::Token::UNIFORM => Token::Uniform,
-            types::Token::SHARED => Token::Shared,
-            types::Token::COHERENT => Token::Coherent,
             types::Token::VOLATILE => Token::Volatile,
-            types::Token::RESTRICT => Token::Restrict,
-            types::Token::READONLY => Token::ReadOnly,
-            types::Token::WRITEONLY => Token::WriteOnly,
             types::Token::REGISTER => Token::Register,
             types::Token::PACKOFFSET => Token::Packoffset,
-            types::Token::LAYOUT => Token::Layout,
             types::Token::CENTROID => Token::Centroid,
-            types::Token::FLAT => Token::Flat,
-            types::Token::SMOOTH => Token::Smooth,
             types::Token::NOPERSPECTIVE => Token::NoPerspective,
-            types::Token::PATCH => Token::Patch,
-            types::Token::SAMPLE => Token::Sample,
-            types::Token::INVARIANT => Token::Invariant,
+            types::Token::LINEAR => Token::Linear,
+            types::Token::NOINTERPOLATION => Token::NoInterpolation,
             types::Token::PRECISE => Token::Precise,
             types::Token::BREAK => Token::Break,
             types::Token::CONTINUE => Token::Continue,
@@ -167,48 +155,37 @@ pub fn lang_token(
             types::Token::DEFAULT => Token::Default,
             types::Token::IF => Token::If,
             types::Token::ELSE => Token::Else,
-            types::Token::SUBROUTINE => Token::Subroutine,
             types::Token::IN => Token::In,
             types::Token::OUT => Token::Out,
             types::Token::INOUT => Token::InOut,
             types::Token::DISCARD => Token::Discard,
             types::Token::RETURN => Token::Return,
-            types::Token::LOWP => Token::LowPrecision,
-            types::Token::MEDIUMP => Token::MediumPrecision,
-            types::Token::HIGHP => Token::HighPrecision,
-            types::Token::PRECISION => Token::Precision,
             types::Token::STRUCT => Token::Struct,
+            types::Token::CLASS => Token::Class,
+            types::Token::INTERFACE => Token::Interface,
             types::Token::CBUFFER => Token::CBuffer,
             types::Token::SAMPLER_STATE => Token::SamplerState,
             types::Token::SAMPLER_COMPARISON_STATE => Token::SamplerComparisonState,
-            types::Token::ATTRIBUTE => Token::Attribute,
-            types::Token::VARYING => Token::Varying,
-            types::Token::COMMON
-            | types::Token::PARTITION
-            | types::Token::ACTIVE
-            | types::Token::ASM
-            | types::Token::CLASS
+            // `operator` is only reserved from HLSL 2021, which introduces operator overloading
+            types::Token::OPERATOR if ctx.hlsl_version().supports_operator_overloads() => {
+                Token::Operator
+            }
+            types::Token::OPERATOR => Token::Identifier("operator".into()),
+            types::Token::TEMPLATE if ctx.hlsl_version().supports_templates() => Token::Template,
+            types::Token::ASM
             | types::Token::UNION
             | types::Token::ENUM
             | types::Token::TYPEDEF
             | types::Token::TEMPLATE
             | types::Token::THIS
-            | types::Token::RESOURCE
             | types::Token::GOTO
             | types::Token::NOINLINE
             | types::Token::PUBLIC
-            | types::Token::EXTERNAL
-            | types::Token::INTERFACE
             | types::Token::LONG
             | types::Token::SHORT
             | types::Token::HALF
-            | types::Token::FIXED
             | types::Token::UNSIGNED
-            | types::Token::SUPERP
-            | types::Token::OUTPUT
-            | types::Token::FILTER
             | types::Token::SIZEOF
-            | types::Token::CAST
             | types::Token::NAMESPACE
             | types::Token::USING => {
                 return Err((token_kind, types::token::ErrorKind::InvalidToken));
@@ -236,19 +236,14 @@ impl<'i> Iterator for LexerIterator<'i> {
                 }
             }
 
-            let (token, _type_name_state) = hlsl_lang_pp::types::Token::from_token(
-                source_token,
-                self.inner.input(),
-                self.opts.default_version,
-                self.opts.target_vulkan,
-                |name| {
+            let (token, _type_name_state) =
+                hlsl_lang_pp::types::Token::from_token(source_token, self.inner.input(), |name| {
                     if self.ctx.is_type_name(name.as_ref()) {
                         TypeNameState::Type
                     } else {
                         TypeNameState::Ident
                     }
-                },
-            );
+                });
 
             let text = text.into_unescaped();
             let text = text.try_as_str().unwrap();
@@ -298,11 +293,7 @@ impl<'i> Iterator for LexerIterator<'i> {
                             }))
                         }
                         _ => {
-                            if token.1 == Token::LeftBrace {
-                                self.ctx.push_scope();
-                            } else if token.1 == Token::RightBrace {
-                                self.ctx.pop_scope();
-                            }
+                            self.ctx.scope_token(&token.1);
 
                             return Some(Ok(token));
                         }
@@ -87,36 +87,6 @@ pub enum Token {
     UMat43,
     #[lang_util(token = "uint4x4", kind = "type name")]
     UMat44,
-    #[lang_util(token = "dvec2", kind = "type name")]
-    DVec2,
-    #[lang_util(token = "dvec3", kind = "type name")]
-    DVec3,
-    #[lang_util(token = "dvec4", kind = "type name")]
-    DVec4,
-    #[lang_util(token = "dmat2", kind = "type name")]
-    DMat2,
-    #[lang_util(token = "dmat3", kind = "type name")]
-    DMat3,
-    #[lang_util(token = "dmat4", kind = "type name")]
-    DMat4,
-    #[lang_util(token = "dmat2x2", kind = "type name")]
-    DMat2x2,
-    #[lang_util(token = "dmat2x3", kind = "type name")]
-    DMat2x3,
-    #[lang_util(token = "dmat2x4", kind = "type name")]
-    DMat2x4,
-    #[lang_util(token = "dmat3x2", kind = "type name")]
-    DMat3x2,
-    #[lang_util(token = "dmat3x3", kind = "type name")]
-    DMat3x3,
-    #[lang_util(token = "dmat3x4", kind = "type name")]
-    DMat3x4,
-    #[lang_util(token = "dmat4x2", kind = "type name")]
-    DMat4x2,
-    #[lang_util(token = "dmat4x3", kind = "type name")]
-    DMat4x3,
-    #[lang_util(token = "dmat4x4", kind = "type name")]
-    DMat4x4,
     #[lang_util(
         token = "centroid",
         kind = "storage qualifier",
@@ -131,50 +101,12 @@ pub enum Token {
     InOut,
     #[lang_util(token = "uniform", kind = "storage qualifier", kind = "type qualifier")]
     Uniform,
-    #[lang_util(token = "patch", kind = "storage qualifier", kind = "type qualifier")]
-    Patch,
-    #[lang_util(token = "sample", kind = "storage qualifier", kind = "type qualifier")]
-    Sample,
-    #[lang_util(token = "shared", kind = "storage qualifier", kind = "type qualifier")]
-    Shared,
-    #[lang_util(
-        token = "coherent",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    Coherent,
     #[lang_util(
         token = "volatile",
         kind = "storage qualifier",
         kind = "type qualifier"
     )]
     Volatile,
-    #[lang_util(
-        token = "restrict",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    Restrict,
-    #[lang_util(
-        token = "readonly",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    ReadOnly,
-    #[lang_util(
-        token = "writeonly",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    WriteOnly,
-    #[lang_util(
-        token = "attribute",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    Attribute,
-    #[lang_util(token = "varying", kind = "storage qualifier", kind = "type qualifier")]
-    Varying,
     #[lang_util(token = "cbuffer", kind = "storage qualifier", kind = "type qualifier")]
     CBuffer,
     #[lang_util(token = "tbuffer", kind = "storage qualifier", kind = "type qualifier")]
@@ -183,14 +115,6 @@ pub enum Token {
     Register,
     #[lang_util(token = "packoffset", kind = "storage qualifier", kind = "type qualifier")]
     Packoffset,
-    #[lang_util(token = "SV_Position", kind = "semantic")]
-    SvPosition,
-    #[lang_util(token = "SV_Target", kind = "semantic")]
-    SvTarget,
-    #[lang_util(token = "SV_VertexID", kind = "semantic")]
-    SvVertexId,
-    #[lang_util(token = "SV_InstanceID", kind = "semantic")]
-    SvInstanceId,
     #[lang_util(
         token = "noperspective",
         kind = "interpolation qualifier",
@@ -198,283 +122,43 @@ pub enum Token {
     )]
     NoPerspective,
     #[lang_util(
-        token = "flat",
+        token = "linear",
         kind = "interpolation qualifier",
         kind = "type qualifier"
     )]
-    Flat,
+    Linear,
     #[lang_util(
-        token = "smooth",
+        token = "nointerpolation",
         kind = "interpolation qualifier",
         kind = "type qualifier"
     )]
-    Smooth,
-    #[lang_util(token = "layout", kind = "layout qualifier", kind = "type qualifier")]
-    Layout,
-    #[lang_util(token = "atomic_uint", kind = "type name")]
-    AtomicUInt,
+    NoInterpolation,
     #[lang_util(token = "Texture1D", kind = "type name")]
     Texture1D,
-    #[lang_util(
-        token = "sampler1DShadow",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    Sampler1DShadow,
-    #[lang_util(
-        token = "sampler1DArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    Sampler1DArray,
-    #[lang_util(
-        token = "sampler1DArrayShadow",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    Sampler1DArrayShadow,
-    #[lang_util(token = "isampler1D", kind = "type name")]
-    ISampler1D,
-    #[lang_util(
-        token = "isampler1DArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    ISampler1DArray,
-    #[lang_util(token = "usampler1D", kind = "type name")]
-    USampler1D,
-    #[lang_util(
-        token = "usampler1DArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    USampler1DArray,
     #[lang_util(token = "Texture2D", kind = "type name")]
     Texture2D,
-    #[lang_util(
-        token = "sampler2DShadow",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    Sampler2DShadow,
-    #[lang_util(
-        token = "sampler2DArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    Sampler2DArray,
-    #[lang_util(
-        token = "sampler2DArrayShadow",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    Sampler2DArrayShadow,
-    #[lang_util(token = "isampler2D", kind = "type name")]
-    ISampler2D,
-    #[lang_util(
-        token = "isampler2DArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    ISampler2DArray,
-    #[lang_util(token = "usampler2D", kind = "type name")]
-    USampler2D,
-    #[lang_util(
-        token = "usampler2DArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    USampler2DArray,
-    #[lang_util(token = "sampler2DRect", kind = "type name")]
-    Sampler2DRect,
-    #[lang_util(
-        token = "sampler2DRectShadow",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    Sampler2DRectShadow,
-    #[lang_util(
-        token = "isampler2DRect",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    ISampler2DRect,
-    #[lang_util(
-        token = "usampler2DRect",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    USampler2DRect,
-    #[lang_util(token = "sampler2DMS", kind = "type name")]
-    Sampler2DMs,
-    #[lang_util(token = "isampler2DMS", kind = "type name")]
-    ISampler2DMs,
-    #[lang_util(token = "usampler2DMS", kind = "type name")]
-    USampler2DMs,
-    #[lang_util(
-        token = "sampler2DMSArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    Sampler2DMsArray,
-    #[lang_util(
-        token = "isampler2DMSArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    ISampler2DMsArray,
-    #[lang_util(
-        token = "usampler2DMSArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    USampler2DMsArray,
     #[lang_util(token = "Texture3D", kind = "type name")]
     Texture3D,
-    #[lang_util(token = "isampler3D", kind = "type name")]
-    ISampler3D,
-    #[lang_util(token = "usampler3D", kind = "type name")]
-    USampler3D,
     #[lang_util(token = "TextureCube", kind = "type name")]
     TextureCube,
-    #[lang_util(
-        token = "samplerCubeShadow",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    SamplerCubeShadow,
-    #[lang_util(token = "isamplerCube", kind = "type name")]
-    ISamplerCube,
-    #[lang_util(token = "usamplerCube", kind = "type name")]
-    USamplerCube,
-    #[lang_util(
-        token = "samplerCubeArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    SamplerCubeArray,
-    #[lang_util(
-        token = "samplerCubeArrayShadow",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    SamplerCubeArrayShadow,
-    #[lang_util(
-        token = "isamplerCubeArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    ISamplerCubeArray,
-    #[lang_util(
-        token = "usamplerCubeArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    USamplerCubeArray,
     #[lang_util(token = "samplerBuffer", kind = "type name")]
     SamplerBuffer,
-    #[lang_util(
-        token = "isamplerBuffer",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    ISamplerBuffer,
-    #[lang_util(
-        token = "usamplerBuffer",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    USamplerBuffer,
     #[lang_util(token = "image1D", kind = "type name")]
     Image1D,
-    #[lang_util(token = "iimage1D", kind = "type name")]
-    IImage1D,
-    #[lang_util(token = "uimage1D", kind = "type name")]
-    UImage1D,
     #[lang_util(token = "image1DArray", kind = "type name")]
     Image1DArray,
-    #[lang_util(token = "iimage1DArray", kind = "type name")]
-    IImage1DArray,
-    #[lang_util(token = "uimage1DArray", kind = "type name")]
-    UImage1DArray,
     #[lang_util(token = "image2D", kind = "type name")]
     Image2D,
-    #[lang_util(token = "iimage2D", kind = "type name")]
-    IImage2D,
-    #[lang_util(token = "uimage2D", kind = "type name")]
-    UImage2D,
     #[lang_util(token = "image2DArray", kind = "type name")]
     Image2DArray,
-    #[lang_util(token = "iimage2DArray", kind = "type name")]
-    IImage2DArray,
-    #[lang_util(token = "uimage2DArray", kind = "type name")]
-    UImage2DArray,
     #[lang_util(token = "image2DRect", kind = "type name")]
     Image2DRect,
-    #[lang_util(token = "iimage2DRect", kind = "type name")]
-    IImage2DRect,
-    #[lang_util(token = "uimage2DRect", kind = "type name")]
-    UImage2DRect,
-    #[lang_util(token = "image2DMS", kind = "type name")]
-    Image2DMs,
-    #[lang_util(token = "iimage2DMS", kind = "type name")]
-    IImage2DMs,
-    #[lang_util(token = "uimage2DMS", kind = "type name")]
-    UImage2DMs,
-    #[lang_util(
-        token = "image2DMSArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    Image2DMsArray,
-    #[lang_util(
-        token = "iimage2DMSArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    IImage2DMsArray,
-    #[lang_util(
-        token = "uimage2DMSArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    UImage2DMsArray,
     #[lang_util(token = "image3D", kind = "type name")]
     Image3D,
-    #[lang_util(token = "iimage3D", kind = "type name")]
-    IImage3D,
-    #[lang_util(token = "uimage3D", kind = "type name")]
-    UImage3D,
     #[lang_util(token = "imageCube", kind = "type name")]
     ImageCube,
-    #[lang_util(token = "iimageCube", kind = "type name")]
-    IImageCube,
-    #[lang_util(token = "uimageCube", kind = "type name")]
-    UImageCube,
-    #[lang_util(
-        token = "imageCubeArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    ImageCubeArray,
-    #[lang_util(
-        token = "iimageCubeArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    IImageCubeArray,
-    #[lang_util(
-        token = "uimageCubeArray",
-        kind = "type name",
-        kind = "vulkan type name"
-    )]
-    UImageCubeArray,
     #[lang_util(token = "imageBuffer", kind = "type name")]
     ImageBuffer,
-    #[lang_util(token = "iimageBuffer", kind = "type name")]
-    IImageBuffer,
-    #[lang_util(token = "uimageBuffer", kind = "type name")]
-    UImageBuffer,
 
     // HLSL-specific sampler types
     #[lang_util(token = "sampler", kind = "type name")]
@@ -493,8 +177,6 @@ pub enum Token {
     Texture2DMS,
     #[lang_util(token = "Texture2DMSArray", kind = "type name")]
     Texture2DMSArray,
-    #[lang_util(token = "sampler2D", kind = "type name")]
-    Sampler2D,
     #[lang_util(token = "Texture2DRect", kind = "type name")]
     Texture2DRect,
     #[lang_util(token = "TextureBuffer", kind = "type name")]
@@ -503,16 +185,20 @@ pub enum Token {
     StructuredBuffer,
     #[lang_util(token = "RWStructuredBuffer", kind = "type name")]
     RWStructuredBuffer,
-    #[lang_util(token = "sampler1D", kind = "type name")]
-    Sampler1D,
-    #[lang_util(token = "sampler3D", kind = "type name")]
-    Sampler3D,
-    #[lang_util(token = "samplerCube", kind = "type name")]
-    SamplerCube,
+    #[lang_util(token = "string", kind = "type name")]
+    String,
 
     
     #[lang_util(token = "struct", kind = "struct", kind = "keyword")]
     Struct,
+    #[lang_util(token = "class", kind = "struct", kind = "keyword")]
+    Class,
+    #[lang_util(token = "interface", kind = "struct", kind = "keyword")]
+    Interface,
+    #[lang_util(token = "operator", kind = "keyword")]
+    Operator,
+    #[lang_util(token = "template", kind = "keyword")]
+    Template,
     #[lang_util(token = "void", kind = "type name")]
     Void,
     #[lang_util(token = "while", kind = "keyword")]
@@ -539,12 +225,6 @@ pub enum Token {
     Case,
     #[lang_util(token = "default", kind = "keyword")]
     Default,
-    #[lang_util(
-        token = "subroutine",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    Subroutine,
     #[lang_util(parser = "ident", kind = "identifier")]
     Identifier(SmolStr),
     #[lang_util(parser = "ty_name", kind = "type name")]
@@ -561,6 +241,8 @@ pub enum Token {
     DoubleConstant(f64),
     #[lang_util(parser = "string_constant", kind = "literal")]
     StringConstant(SmolStr),
+    #[lang_util(parser = "char_constant", kind = "literal")]
+    CharConstant(char),
     #[lang_util(token = "<<", kind = "binary operator", kind = "operator")]
     LeftOp,
     #[lang_util(token = ">>", kind = "binary operator", kind = "operator")]
@@ -603,6 +285,8 @@ pub enum Token {
     OrAssign,
     #[lang_util(token = "-=", kind = "binary operator", kind = "operator")]
     SubAssign,
+    #[lang_util(token = "::", kind = "operator")]
+    ScopeOp,
     #[lang_util(token = "(")]
     LeftParen,
     #[lang_util(token = ")")]
@@ -661,22 +345,8 @@ pub enum Token {
     Ampersand,
     #[lang_util(token = "?", kind = "operator")]
     Question,
-    #[lang_util(token = "invariant")]
-    Invariant,
     #[lang_util(token = "precise", kind = "type qualifier")]
     Precise,
-    #[lang_util(token = "highp", kind = "precision qualifier", kind = "type qualifier")]
-    HighPrecision,
-    #[lang_util(
-        token = "mediump",
-        kind = "precision qualifier",
-        kind = "type qualifier"
-    )]
-    MediumPrecision,
-    #[lang_util(token = "lowp", kind = "precision qualifier", kind = "type qualifier")]
-    LowPrecision,
-    #[lang_util(token = "precision")]
-    Precision,
 
     // TODO: Line continuation can happen inside tokens
     #[lang_util(display = "<whitespace>", parser(display), kind = "trivia")]
@@ -805,6 +475,7 @@ impl_from!(f32 => FloatConstant);
 impl_from!(f64 => DoubleConstant);
 impl_from!(bool => BoolConstant);
 impl_from!(SmolStr => StringConstant);
+impl_from!(char => CharConstant);
 
 impl From<Token> for String {
     fn from(value: Token) -> Self {
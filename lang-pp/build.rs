@@ -79,6 +79,7 @@ fn main() {
             "TextureBuffer",
             "StructuredBuffer",
             "RWStructuredBuffer",
+            "string",
         ])
         .write_to_file(&out_dir.join("type_names.rs"))
         .expect("failed to generate atoms");
@@ -87,24 +88,13 @@ fn main() {
         .atoms(&[
             "const",
             "uniform",
-            "shared",
-            "attribute",
-            "varying",
-            "coherent",
             "volatile",
-            "restrict",
-            "readonly",
-            "writeonly",
             "register",
             "packoffset",
-            "layout",
             "centroid",
-            "flat",
-            "smooth",
             "noperspective",
-            "patch",
-            "sample",
-            "invariant",
+            "linear",
+            "nointerpolation",
             "precise",
             "break",
             "continue",
@@ -116,7 +106,6 @@ fn main() {
             "default",
             "if",
             "else",
-            "subroutine",
             "in",
             "out",
             "inout",
@@ -124,18 +113,12 @@ fn main() {
             "false",
             "discard",
             "return",
-            "lowp",
-            "mediump",
-            "highp",
-            "precision",
             "struct",
             "cbuffer",
             "SamplerState",
             "SamplerComparisonState",
+            "operator",
             // Reserved for future use
-            "common",
-            "partition",
-            "active",
             "asm",
             "class",
             "union",
@@ -143,25 +126,18 @@ fn main() {
             "typedef",
             "template",
             "this",
-            "resource",
             "goto",
             "inline",
             "noinline",
             "public",
             "static",
             "extern",
-            "external",
             "interface",
             "long",
             "short",
             "half",
-            "fixed",
             "unsigned",
-            "superp",
-            "output",
-            "filter",
             "sizeof",
-            "cast",
             "namespace",
             "using",
         ])
@@ -79,18 +79,14 @@ struct TypeTable<'r> {
     type_names: HashMap<TypeNameAtom, Option<(ExtNameAtom, ExtensionBehavior)>>,
     extensions: HashMap<ExtNameAtom, ExtensionBehavior>,
     registry: &'r Registry,
-    target_vulkan: bool,
-    current_version: u16,
 }
 
 impl<'r> TypeTable<'r> {
-    fn new(registry: &'r Registry, current_version: u16, target_vulkan: bool) -> Self {
+    fn new(registry: &'r Registry) -> Self {
         Self {
             type_names: Default::default(),
             extensions: Default::default(),
             registry,
-            target_vulkan,
-            current_version,
         }
     }
 
@@ -160,10 +156,7 @@ impl<'r> TypeTable<'r> {
         token: &impl TokenLike,
         location: &ExpandLocation,
     ) -> (Token, Option<TypeNameState>, Option<Error>) {
-        let (token_kind, state) =
-            token::token_from_syntax_kind(token, self.current_version, self.target_vulkan, |tn| {
-                self.is_type_name(tn)
-            });
+        let (token_kind, state) = token::token_from_syntax_kind(token, |tn| self.is_type_name(tn));
 
         let error = if let Some(TypeNameState::WarnType(extension)) = &state {
             Some(
@@ -222,15 +215,10 @@ pub struct Tokenizer<'r, I> {
 }
 
 impl<'r, I: LocatedIterator> Tokenizer<'r, I> {
-    pub fn new(
-        inner: impl IntoIterator<IntoIter = I>,
-        current_version: u16,
-        target_vulkan: bool,
-        registry: &'r Registry,
-    ) -> Self {
+    pub fn new(inner: impl IntoIterator<IntoIter = I>, registry: &'r Registry) -> Self {
         Self {
             inner: inner.into_iter(),
-            type_table: TypeTable::new(registry, current_version, target_vulkan),
+            type_table: TypeTable::new(registry),
             pending_error: None,
         }
     }
@@ -292,25 +280,19 @@ impl<'r, E, I: Iterator<Item = Result<event::Event, E>> + LocatedIterator> Itera
                 }
                 event::Event::Directive { directive, masked } => {
                     if !masked {
-                        match directive.kind() {
-                            DirectiveKind::Version(version) => {
-                                self.type_table.current_version = version.number;
+                        if let DirectiveKind::Extension(extension) = directive.kind() {
+                            if !self.type_table.handle_extension(extension) {
+                                self.pending_error = Some(
+                                    Error::builder()
+                                        .pos(directive.text_range())
+                                        .resolve_file(self.inner.location())
+                                        .finish(ErrorKind::unsupported_ext(
+                                            extension.name.clone(),
+                                            directive.text_range(),
+                                            self.inner.location(),
+                                        )),
+                                );
                             }
-                            DirectiveKind::Extension(extension) => {
-                                if !self.type_table.handle_extension(extension) {
-                                    self.pending_error = Some(
-                                        Error::builder()
-                                            .pos(directive.text_range())
-                                            .resolve_file(self.inner.location())
-                                            .finish(ErrorKind::unsupported_ext(
-                                                extension.name.clone(),
-                                                directive.text_range(),
-                                                self.inner.location(),
-                                            )),
-                                    );
-                                }
-                            }
-                            _ => {}
                         }
                     }
 
@@ -348,7 +330,7 @@ mod tests {
 
         fn parse(src: &str) -> Vec<super::Token> {
             crate::processor::str::process(src, crate::processor::ProcessorState::default())
-                .tokenize(100, false, &crate::exts::DEFAULT_REGISTRY)
+                .tokenize(&crate::exts::DEFAULT_REGISTRY)
                 .filter_map(|evt| evt.as_token().map(|(_, kind, _)| kind.clone()))
                 .collect()
         }
@@ -434,7 +416,7 @@ mod tests {
 
             let mut tokenizer =
                 crate::processor::str::process(input, crate::processor::ProcessorState::default())
-                    .tokenize(100, false, &crate::exts::DEFAULT_REGISTRY);
+                    .tokenize(&crate::exts::DEFAULT_REGISTRY);
 
             #[allow(clippy::while_let_on_iterator)]
             while let Some(result) = tokenizer.next() {
@@ -465,4 +447,48 @@ mod tests {
             assert_eq!(&tokens, output);
         }
     }
+
+    #[test]
+    fn test_target_definitions() {
+        use super::Token::*;
+
+        fn parse(src: &str, state: crate::processor::ProcessorState) -> Vec<super::Token> {
+            crate::processor::str::process(src, state)
+                .tokenize(&crate::exts::DEFAULT_REGISTRY)
+                .filter_map(|evt| evt.as_token().map(|(_, kind, _)| kind.clone()))
+                .filter(|kind| *kind != WS)
+                .collect()
+        }
+
+        let src = "__HLSL_VERSION __SHADER_TARGET_STAGE __SHADER_STAGE_PIXEL __SHADER_TARGET_MAJOR __SHADER_TARGET_MINOR";
+
+        assert_eq!(
+            parse(
+                src,
+                crate::processor::ProcessorState::builder()
+                    .hlsl_version(crate::types::HlslVersion::V2018)
+                    .shader_model("ps_6_6".parse::<crate::types::ShaderModel>().unwrap())
+                    .finish()
+            ),
+            &[
+                INT_CONST(2018),
+                INT_CONST(0),
+                INT_CONST(0),
+                INT_CONST(6),
+                INT_CONST(6)
+            ]
+        );
+
+        // Without a target, only the stage constants are defined
+        assert_eq!(
+            parse(src, crate::processor::ProcessorState::default()),
+            &[
+                INT_CONST(2021),
+                IDENT("__SHADER_TARGET_STAGE".into()),
+                INT_CONST(0),
+                IDENT("__SHADER_TARGET_MAJOR".into()),
+                IDENT("__SHADER_TARGET_MINOR".into())
+            ]
+        );
+    }
 }
@@ -11,8 +11,6 @@ use crate::{
 
 pub(super) fn token_from_syntax_kind(
     value: &impl TokenLike,
-    version: u16,
-    target_vulkan: bool,
     is_type_name: impl Fn(&TypeNameAtom) -> TypeNameState,
 ) -> (Token, Option<TypeNameState>) {
     use Token::*;
@@ -67,6 +65,9 @@ pub(super) fn token_from_syntax_kind(
             };
             return (STRING_CONST(string_content.into()), None);
         }
+        SyntaxKind::QUOTE_CHAR => {
+            return (Token::parse_char(&value.text().to_string()), None);
+        }
         // Those need further processing
         SyntaxKind::IDENT_KW => {}
         SyntaxKind::DIGITS => {}
@@ -133,6 +134,9 @@ pub(super) fn token_from_syntax_kind(
         SyntaxKind::SUB_ASSIGN => {
             return (SUB_ASSIGN, None);
         }
+        SyntaxKind::SCOPE_OP => {
+            return (SCOPE_OP, None);
+        }
         SyntaxKind::LPAREN => {
             return (LPAREN, None);
         }
@@ -227,9 +231,7 @@ pub(super) fn token_from_syntax_kind(
         }
 
         // Else it might be a built-in type name
-        if let Some((type_name, state)) =
-            TypeName::parse(text.as_ref(), version, target_vulkan, is_type_name)
-        {
+        if let Some((type_name, state)) = TypeName::parse(text.as_ref(), is_type_name) {
             return (TYPE_NAME(type_name), state);
         }
 
@@ -205,6 +205,10 @@ impl<'i> Iterator for Lexer<'i> {
                     InputToken::HASH => Some(PP_CONCAT),
                     _ => None,
                 }),
+                InputToken::COLON => self.maybe_concat(token, |input| match input {
+                    InputToken::COLON => Some(SCOPE_OP),
+                    _ => None,
+                }),
                 InputToken::PERIOD => self.maybe_concat(token, |input| match input {
                     InputToken::DIGITS => Some(DIGITS),
                     _ => None,
@@ -121,6 +121,10 @@ pub enum Token {
     SUB_ASSIGN = 57,
     /// ##
     PP_CONCAT = 58,
+    /// 'c'
+    QUOTE_CHAR = 59,
+    /// ::
+    SCOPE_OP = 60,
 }
 
 impl Token {
@@ -34,6 +34,8 @@ enum State {
     Whitespace,
     /// Inside a quote string
     QuoteString,
+    /// Inside a character literal
+    QuoteChar,
     /// Insite an angle string
     AngleString,
 }
@@ -52,6 +54,7 @@ impl Default for State {
 /// * Eliminate backslash-escaped newlines
 /// * Identify single and multi-line comments
 /// * Tokenize double-quoted strings and (when asked to) angle-quoted strings
+/// * Tokenize single-quoted character literals
 #[derive(Debug, Clone)]
 pub struct PreLexer<'i> {
     source: &'i str,
@@ -164,6 +167,7 @@ impl<'i> Iterator for PreLexer<'i> {
                                     self.expect_angle_string = false;
                                     State::QuoteString
                                 }
+                                "'" => State::QuoteChar,
                                 "<" if self.expect_angle_string => {
                                     // Clear the string flag
                                     self.expect_angle_string = false;
@@ -438,6 +442,7 @@ impl<'i> Iterator for PreLexer<'i> {
                     if let Some((token, text)) = self.peek_token() {
                         // Release text borrow
                         let end_quote = text == "\"";
+                        let escape = text == "\\";
 
                         // Always consume the token
                         self.next_token();
@@ -445,6 +450,11 @@ impl<'i> Iterator for PreLexer<'i> {
                         // Extend the range
                         self.start = TextRange::new(self.start.start(), token.range.end());
 
+                        // An escape sequence also consumes the escaped char
+                        if escape {
+                            self.next_token();
+                        }
+
                         if end_quote {
                             return Some(TextToken::new(QUOTE_STRING, self.start));
                         } else {
@@ -456,6 +466,36 @@ impl<'i> Iterator for PreLexer<'i> {
                     }
                 }
 
+                State::QuoteChar => {
+                    if let Some((token, text)) = self.peek_token() {
+                        // Release text borrow
+                        let end_quote = text == "'";
+                        let escape = text == "\\";
+
+                        // Newlines are not allowed in character literals
+                        if token.token == NewlineTokenKind::NEWLINE {
+                            return Some(TextToken::new(ERROR, self.start));
+                        }
+
+                        // Always consume the token
+                        self.next_token();
+
+                        // An escape sequence also consumes the escaped char
+                        if escape {
+                            self.next_token();
+                        }
+
+                        if end_quote {
+                            return Some(TextToken::new(QUOTE_CHAR, self.start));
+                        } else {
+                            State::QuoteChar
+                        }
+                    } else {
+                        // No more tokens, bump an error
+                        return Some(TextToken::new(ERROR, self.start));
+                    }
+                }
+
                 State::AngleString => {
                     if let Some((token, text)) = self.peek_token() {
                         // Release text borrow
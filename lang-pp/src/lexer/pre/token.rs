@@ -74,6 +74,8 @@ pub enum Token {
     LINECONT = 35,
     /// Invalid token
     ERROR = 36,
+    /// 'c'
+    QUOTE_CHAR = 59,
 }
 
 impl Token {
@@ -74,7 +74,7 @@ fn test_whitespace_sequence() {
 #[test]
 fn test_glued_tokens() {
     assert_eq!(
-        &tokenize("<<>>++--<=>===!=&&||^^*=/=+=%=<<=>>=&=^=|=-=##")[..],
+        &tokenize("<<>>++--<=>===!=&&||^^*=/=+=%=<<=>>=&=^=|=-=##::")[..],
         &[
             LEFT_OP,
             RIGHT_OP,
@@ -97,7 +97,8 @@ fn test_glued_tokens() {
             XOR_ASSIGN,
             OR_ASSIGN,
             SUB_ASSIGN,
-            PP_CONCAT
+            PP_CONCAT,
+            SCOPE_OP
         ]
     );
 }
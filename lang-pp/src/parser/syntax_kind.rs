@@ -123,6 +123,10 @@ pub enum SyntaxKind {
     SUB_ASSIGN = 57,
     /// ##
     PP_CONCAT_OP = 58,
+    /// 'c'
+    QUOTE_CHAR = 59,
+    /// ::
+    SCOPE_OP = 60,
     // Extra types
     /// Concatenation expression
     PP_CONCAT,
@@ -272,6 +276,10 @@ impl SyntaxKind {
                 HASH => PP_CONCAT_OP,
                 _ => ERROR,
             },
+            COLON => match rhs {
+                COLON => SCOPE_OP,
+                _ => ERROR,
+            },
             _ => ERROR,
         }
     }
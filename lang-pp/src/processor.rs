@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
 use lang_util::{FileId, SmolStr};
 
@@ -19,6 +19,7 @@ use nodes::{Define, DefineObject, Version};
 use crate::{
     exts::Registry,
     processor::nodes::{ExtensionBehavior, ExtensionName},
+    types::{HlslVersion, ShaderModel, ShaderStage},
 };
 
 pub mod str;
@@ -145,9 +146,8 @@ impl Default for ProcessorState {
 
 #[derive(Clone)]
 pub struct ProcessorStateBuilder<'r> {
-    core_profile: bool,
-    compatibility_profile: bool,
-    es_profile: bool,
+    hlsl_version: HlslVersion,
+    shader_model: Option<ShaderModel>,
     extensions: Vec<(ExtensionName, ExtensionBehavior)>,
     definitions: Vec<Define>,
     registry: &'r Registry,
@@ -165,32 +165,27 @@ impl<'r> ProcessorStateBuilder<'r> {
     pub fn registry<'s>(self, registry: &'s Registry) -> ProcessorStateBuilder<'s> {
         ProcessorStateBuilder::<'s> {
             registry,
-            core_profile: self.core_profile,
-            compatibility_profile: self.compatibility_profile,
-            es_profile: self.es_profile,
+            hlsl_version: self.hlsl_version,
+            shader_model: self.shader_model,
             extensions: self.extensions,
             definitions: self.definitions,
         }
     }
 
-    pub fn core_profile(self, core_profile: bool) -> Self {
+    pub fn hlsl_version(self, hlsl_version: HlslVersion) -> Self {
         Self {
-            core_profile,
+            hlsl_version,
             ..self
         }
     }
 
-    pub fn compatibility_profile(self, compatibility_profile: bool) -> Self {
+    pub fn shader_model(self, shader_model: impl Into<Option<ShaderModel>>) -> Self {
         Self {
-            compatibility_profile,
+            shader_model: shader_model.into(),
             ..self
         }
     }
 
-    pub fn es_profile(self, es_profile: bool) -> Self {
-        Self { es_profile, ..self }
-    }
-
     pub fn extension(
         mut self,
         name: impl Into<ExtensionName>,
@@ -205,41 +200,67 @@ impl<'r> ProcessorStateBuilder<'r> {
         self
     }
 
+    /// Predefined macros describing the language version and compilation target, as DXC
+    /// defines them
+    fn target_definitions(&self) -> Vec<Define> {
+        let number = |value: u32| DefineObject::from_str(&value.to_string()).unwrap();
+
+        let mut definitions = vec![Define::object(
+            "__HLSL_VERSION".into(),
+            number(self.hlsl_version.number()),
+            true,
+        )];
+
+        definitions.extend(ShaderStage::ALL.into_iter().map(|stage| {
+            Define::object(stage.macro_name().into(), number(stage.macro_value()), true)
+        }));
+
+        if let Some(shader_model) = self.shader_model {
+            definitions.extend([
+                Define::object(
+                    "__SHADER_TARGET_STAGE".into(),
+                    number(shader_model.stage.macro_value()),
+                    true,
+                ),
+                Define::object(
+                    "__SHADER_TARGET_MAJOR".into(),
+                    number(shader_model.major.into()),
+                    true,
+                ),
+                Define::object(
+                    "__SHADER_TARGET_MINOR".into(),
+                    number(shader_model.minor.into()),
+                    true,
+                ),
+            ]);
+        }
+
+        definitions
+    }
+
     pub fn finish(self) -> ProcessorState {
         let one = DefineObject::one();
 
-        let mut state =
-            ProcessorState {
-                // Google #include extensions enabled by default
-                include_mode: IncludeMode::default(),
-                // Spec 3.3, "There is a built-in macro definition for each profile the implementation
-                // supports. All implementations provide the following macro:
-                // `#define GL_core_profile 1`
-                definitions: self
-                    .core_profile
-                    .then(|| Define::object("GL_core_profile".into(), one.clone(), true))
-                    .into_iter()
-                    .chain(self.compatibility_profile.then(|| {
-                        Define::object("GL_compatibility_profile".into(), one.clone(), true)
-                    }))
-                    .chain(
-                        self.es_profile
-                            .then(|| Define::object("GL_es_profile".into(), one.clone(), true)),
+        let mut state = ProcessorState {
+            // Google #include extensions enabled by default
+            include_mode: IncludeMode::default(),
+            definitions: self
+                .target_definitions()
+                .into_iter()
+                .chain(self.definitions)
+                .map(|definition| Definition::Regular(definition.into(), FileId::default()))
+                .chain([Definition::Line, Definition::File, Definition::Version])
+                .chain(self.registry.all().map(|spec| {
+                    Definition::Regular(
+                        Define::object(spec.name().as_ref().into(), one.clone(), true).into(),
+                        FileId::default(),
                     )
-                    .chain(self.definitions)
-                    .map(|definition| Definition::Regular(definition.into(), FileId::default()))
-                    .chain([Definition::Line, Definition::File, Definition::Version])
-                    .chain(self.registry.all().map(|spec| {
-                        Definition::Regular(
-                            Define::object(spec.name().as_ref().into(), one.clone(), true).into(),
-                            FileId::default(),
-                        )
-                    }))
-                    .map(|definition| (definition.name().into(), definition))
-                    .collect(),
-                version: Version::default(),
-                cpp_style_line: true,
-            };
+                }))
+                .map(|definition| (definition.name().into(), definition))
+                .collect(),
+            version: Version::default(),
+            cpp_style_line: true,
+        };
 
         for (name, behavior) in self.extensions {
             state.add_extension(&name, behavior);
@@ -258,9 +279,8 @@ impl From<ProcessorStateBuilder<'_>> for ProcessorState {
 impl Default for ProcessorStateBuilder<'static> {
     fn default() -> Self {
         Self {
-            core_profile: true,
-            compatibility_profile: false,
-            es_profile: false,
+            hlsl_version: HlslVersion::default(),
+            shader_model: None,
             extensions: Default::default(),
             definitions: Default::default(),
             registry: &*crate::exts::DEFAULT_REGISTRY,
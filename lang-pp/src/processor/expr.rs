@@ -77,6 +77,14 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
                     return Some(Ok(value));
                 }
             }
+            QUOTE_CHAR => {
+                if let Token::CHAR_CONST(value) =
+                    Token::parse_char(&Unescaped::new(token.text()).to_string())
+                {
+                    self.bump();
+                    return Some(Ok(value as i32));
+                }
+            }
             IDENT_KW => {
                 self.bump();
                 return Some(Ok(0));
@@ -501,6 +509,8 @@ mod tests {
         assert_eq!(&eval("0"), &[Constant(Ok(0))]);
         assert_eq!(&eval("1"), &[Constant(Ok(1))]);
         assert_eq!(&eval("FOO"), &[Constant(Ok(0))]);
+        assert_eq!(&eval("'A'"), &[Constant(Ok(65))]);
+        assert_eq!(&eval("'\\n'"), &[Constant(Ok(10))]);
     }
 
     #[test]
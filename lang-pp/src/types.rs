@@ -3,6 +3,9 @@ pub mod keywords;
 #[macro_use]
 pub mod type_names;
 
+pub mod target;
+pub use target::{HlslVersion, ShaderModel, ShaderStage};
+
 pub mod token;
 pub use token::{Token, TypeName};
 
@@ -0,0 +1,286 @@
+//! HLSL language version and compilation target definitions
+
+use std::str::FromStr;
+
+/// HLSL language version, as selected by DXC's `-HV` flag
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HlslVersion {
+    /// HLSL 2016
+    V2016,
+    /// HLSL 2018
+    V2018,
+    /// HLSL 2021: operator overloading, bitfields, templates and short-circuiting logical
+    /// operators
+    #[default]
+    V2021,
+    /// HLSL 202x (in development)
+    V202x,
+}
+
+impl HlslVersion {
+    /// Value of the `__HLSL_VERSION` predefined macro for this version
+    pub fn number(self) -> u32 {
+        match self {
+            Self::V2016 => 2016,
+            Self::V2018 => 2018,
+            Self::V2021 => 2021,
+            // DXC reports 202x as 2029
+            Self::V202x => 2029,
+        }
+    }
+
+    /// `true` if user-defined templates are allowed
+    pub fn supports_templates(self) -> bool {
+        self >= Self::V2021
+    }
+
+    /// `true` if struct and class members can overload operators
+    pub fn supports_operator_overloads(self) -> bool {
+        self >= Self::V2021
+    }
+
+    /// `true` if struct fields can be declared as bitfields
+    pub fn supports_bitfields(self) -> bool {
+        self >= Self::V2021
+    }
+
+    /// `true` if `&&`, `||` and `?:` short-circuit and only accept scalar operands. Earlier
+    /// versions evaluate both sides and operate component-wise on vectors.
+    pub fn short_circuits_logical_ops(self) -> bool {
+        self >= Self::V2021
+    }
+}
+
+/// Error returned when parsing an invalid HLSL language version
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid HLSL version: {version} (expected 2016, 2018, 2021 or 202x)")]
+pub struct InvalidHlslVersion {
+    /// Version string which failed to parse
+    pub version: String,
+}
+
+impl FromStr for HlslVersion {
+    type Err = InvalidHlslVersion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "2016" => Ok(Self::V2016),
+            "2018" => Ok(Self::V2018),
+            "2021" => Ok(Self::V2021),
+            "202x" => Ok(Self::V202x),
+            _ => Err(InvalidHlslVersion {
+                version: s.to_owned(),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for HlslVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::V202x => write!(f, "202x"),
+            other => write!(f, "{}", other.number()),
+        }
+    }
+}
+
+/// Shader stage of a compilation target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderStage {
+    /// Pixel shader (`ps`)
+    Pixel,
+    /// Vertex shader (`vs`)
+    Vertex,
+    /// Geometry shader (`gs`)
+    Geometry,
+    /// Hull shader (`hs`)
+    Hull,
+    /// Domain shader (`ds`)
+    Domain,
+    /// Compute shader (`cs`)
+    Compute,
+    /// Shader library (`lib`)
+    Library,
+    /// Mesh shader (`ms`)
+    Mesh,
+    /// Amplification shader (`as`)
+    Amplification,
+}
+
+impl ShaderStage {
+    /// All the shader stages which can be used as a compilation target
+    pub const ALL: [Self; 9] = [
+        Self::Pixel,
+        Self::Vertex,
+        Self::Geometry,
+        Self::Hull,
+        Self::Domain,
+        Self::Compute,
+        Self::Library,
+        Self::Mesh,
+        Self::Amplification,
+    ];
+
+    /// Target profile prefix for this stage, e.g. `ps`
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::Pixel => "ps",
+            Self::Vertex => "vs",
+            Self::Geometry => "gs",
+            Self::Hull => "hs",
+            Self::Domain => "ds",
+            Self::Compute => "cs",
+            Self::Library => "lib",
+            Self::Mesh => "ms",
+            Self::Amplification => "as",
+        }
+    }
+
+    /// Name of the `__SHADER_STAGE_*` predefined macro for this stage
+    pub fn macro_name(self) -> &'static str {
+        match self {
+            Self::Pixel => "__SHADER_STAGE_PIXEL",
+            Self::Vertex => "__SHADER_STAGE_VERTEX",
+            Self::Geometry => "__SHADER_STAGE_GEOMETRY",
+            Self::Hull => "__SHADER_STAGE_HULL",
+            Self::Domain => "__SHADER_STAGE_DOMAIN",
+            Self::Compute => "__SHADER_STAGE_COMPUTE",
+            Self::Library => "__SHADER_STAGE_LIBRARY",
+            Self::Mesh => "__SHADER_STAGE_MESH",
+            Self::Amplification => "__SHADER_STAGE_AMPLIFICATION",
+        }
+    }
+
+    /// Value of the `__SHADER_STAGE_*` predefined macro, matching DXIL's shader kind
+    pub fn macro_value(self) -> u32 {
+        match self {
+            Self::Pixel => 0,
+            Self::Vertex => 1,
+            Self::Geometry => 2,
+            Self::Hull => 3,
+            Self::Domain => 4,
+            Self::Compute => 5,
+            Self::Library => 6,
+            Self::Mesh => 13,
+            Self::Amplification => 14,
+        }
+    }
+}
+
+impl std::fmt::Display for ShaderStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.prefix())
+    }
+}
+
+/// Target shader model, as selected by DXC's `-T` flag (e.g. `ps_6_6`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShaderModel {
+    /// Stage being compiled
+    pub stage: ShaderStage,
+    /// Major shader model version
+    pub major: u8,
+    /// Minor shader model version
+    pub minor: u8,
+}
+
+impl ShaderModel {
+    /// Create a new shader model target
+    pub fn new(stage: ShaderStage, major: u8, minor: u8) -> Self {
+        Self {
+            stage,
+            major,
+            minor,
+        }
+    }
+
+    /// `true` if this target is at least shader model `major.minor`
+    pub fn at_least(&self, major: u8, minor: u8) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+}
+
+/// Error returned when parsing an invalid target profile
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid target profile: {profile}")]
+pub struct InvalidShaderModel {
+    /// Profile string which failed to parse
+    pub profile: String,
+}
+
+impl FromStr for ShaderModel {
+    type Err = InvalidShaderModel;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || InvalidShaderModel {
+            profile: s.to_owned(),
+        };
+
+        let mut parts = s.split('_');
+        let (Some(prefix), Some(major), Some(minor), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(err());
+        };
+
+        let stage = ShaderStage::ALL
+            .into_iter()
+            .find(|stage| stage.prefix() == prefix)
+            .ok_or_else(err)?;
+
+        // Offline libraries (lib_6_x) are the only targets with a non-numeric minor
+        let minor = if stage == ShaderStage::Library && minor == "x" {
+            15
+        } else {
+            minor.parse().map_err(|_| err())?
+        };
+
+        Ok(Self::new(stage, major.parse().map_err(|_| err())?, minor))
+    }
+}
+
+impl std::fmt::Display for ShaderModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}_{}", self.stage, self.major, self.minor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_shader_model() {
+        assert_eq!(
+            "ps_6_6".parse(),
+            Ok(ShaderModel::new(ShaderStage::Pixel, 6, 6))
+        );
+        assert_eq!(
+            "cs_5_0".parse(),
+            Ok(ShaderModel::new(ShaderStage::Compute, 5, 0))
+        );
+        assert_eq!(
+            "as_6_5".parse(),
+            Ok(ShaderModel::new(ShaderStage::Amplification, 6, 5))
+        );
+        assert_eq!("lib_6_x".parse::<ShaderModel>().unwrap().minor, 15);
+        assert!("ps_6".parse::<ShaderModel>().is_err());
+        assert!("xs_6_0".parse::<ShaderModel>().is_err());
+        assert!("vs_6_0_1".parse::<ShaderModel>().is_err());
+    }
+
+    #[test]
+    fn display_shader_model() {
+        let sm = ShaderModel::new(ShaderStage::Vertex, 6, 2);
+        assert_eq!(sm.to_string(), "vs_6_2");
+        assert!(sm.at_least(6, 0));
+        assert!(!sm.at_least(6, 3));
+    }
+
+    #[test]
+    fn parse_hlsl_version() {
+        assert_eq!("202x".parse(), Ok(HlslVersion::V202x));
+        assert_eq!(HlslVersion::V2018.to_string(), "2018");
+        assert!("2017".parse::<HlslVersion>().is_err());
+    }
+}
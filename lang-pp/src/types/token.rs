@@ -166,6 +166,9 @@ pub enum TypeName {
     /// "RWStructuredBuffer" (HLSL buffer type)
     #[display("RWStructuredBuffer")]
     RWSTRUCTUREDBUFFER,
+    /// "string" (HLSL string type)
+    #[display("string")]
+    STRING,
     /// Reserved for future use
     RESERVED(TypeNameAtom),
     /// Generic type name
@@ -202,117 +205,115 @@ impl TypeName {
 
     pub(crate) fn parse(
         name: &str,
-        version: u16,
-        target_vulkan: bool,
         is_type_name: impl Fn(&TypeNameAtom) -> TypeNameState,
     ) -> Option<(Self, Option<TypeNameState>)> {
-        // TODO: Check type names rules for OpenGL ES
-
         use TypeName::*;
 
         let type_name_atom = TypeNameAtom::from(name);
 
         if type_name_atom == type_name!("void") {
-            return VOID.gate(version >= 100, false, type_name_atom, is_type_name);
+            return VOID.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("int") {
-            return INT.gate(version >= 100, false, type_name_atom, is_type_name);
+            return INT.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("bool") {
-            return BOOL.gate(version >= 100, false, type_name_atom, is_type_name);
+            return BOOL.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float") {
-            return FLOAT.gate(version >= 100, false, type_name_atom, is_type_name);
+            return FLOAT.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float2") {
-            return VEC2.gate(version >= 100, false, type_name_atom, is_type_name);
+            return VEC2.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float3") {
-            return VEC3.gate(version >= 100, false, type_name_atom, is_type_name);
+            return VEC3.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float4") {
-            return VEC4.gate(version >= 100, false, type_name_atom, is_type_name);
+            return VEC4.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("int2") {
-            return IVEC2.gate(version >= 100, false, type_name_atom, is_type_name);
+            return IVEC2.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("int3") {
-            return IVEC3.gate(version >= 100, false, type_name_atom, is_type_name);
+            return IVEC3.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("int4") {
-            return IVEC4.gate(version >= 100, false, type_name_atom, is_type_name);
+            return IVEC4.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("bool2") {
-            return BVEC2.gate(version >= 100, false, type_name_atom, is_type_name);
+            return BVEC2.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("bool3") {
-            return BVEC3.gate(version >= 100, false, type_name_atom, is_type_name);
+            return BVEC3.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("bool4") {
-            return BVEC4.gate(version >= 100, false, type_name_atom, is_type_name);
+            return BVEC4.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float2x2") {
-            return MAT2X2.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT2X2.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float3x3") {
-            return MAT3X3.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT3X3.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float4x4") {
-            return MAT4X4.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT4X4.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("sampler") {
-            return SAMPLER.gate(version >= 100, false, type_name_atom, is_type_name);
+            return SAMPLER.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("Texture1D") {
-            return TEXTURE_1D.gate(version >= 100, false, type_name_atom, is_type_name);
+            return TEXTURE_1D.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("Texture2D") {
-            return TEXTURE_2D.gate(version >= 100, false, type_name_atom, is_type_name);
+            return TEXTURE_2D.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("Texture3D") {
-            return TEXTURE_3D.gate(version >= 100, false, type_name_atom, is_type_name);
+            return TEXTURE_3D.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("TextureCube") {
-            return TEXTURE_CUBE.gate(version >= 100, false, type_name_atom, is_type_name);
+            return TEXTURE_CUBE.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("Texture1DArray") {
-            return TEXTURE_1D_ARRAY.gate(version >= 100, false, type_name_atom, is_type_name);
+            return TEXTURE_1D_ARRAY.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("Texture2DArray") {
-            return TEXTURE_2D_ARRAY.gate(version >= 100, false, type_name_atom, is_type_name);
+            return TEXTURE_2D_ARRAY.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("TextureCubeArray") {
-            return TEXTURE_CUBE_ARRAY.gate(version >= 100, false, type_name_atom, is_type_name);
+            return TEXTURE_CUBE_ARRAY.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("Texture2DMS") {
-            return TEXTURE_2D_MS.gate(version >= 100, false, type_name_atom, is_type_name);
+            return TEXTURE_2D_MS.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("Texture2DMSArray") {
-            return TEXTURE_2D_MS_ARRAY.gate(version >= 100, false, type_name_atom, is_type_name);
+            return TEXTURE_2D_MS_ARRAY.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("Texture2DRect") {
-            return TEXTURE_2D_RECT.gate(version >= 100, false, type_name_atom, is_type_name);
+            return TEXTURE_2D_RECT.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("TextureBuffer") {
-            return TEXTURE_BUFFER.gate(version >= 100, false, type_name_atom, is_type_name);
+            return TEXTURE_BUFFER.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("StructuredBuffer") {
-            return STRUCTUREDBUFFER.gate(version >= 100, false, type_name_atom, is_type_name);
+            return STRUCTUREDBUFFER.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("RWStructuredBuffer") {
-            return RWSTRUCTUREDBUFFER.gate(version >= 100, false, type_name_atom, is_type_name);
+            return RWSTRUCTUREDBUFFER.gate(true, false, type_name_atom, is_type_name);
+        } else if type_name_atom == type_name!("string") {
+            return STRING.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("uint") {
-            return UINT.gate(version >= 100, false, type_name_atom, is_type_name);
+            return UINT.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("uint2") {
-            return UVEC2.gate(version >= 100, false, type_name_atom, is_type_name);
+            return UVEC2.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("uint3") {
-            return UVEC3.gate(version >= 100, false, type_name_atom, is_type_name);
+            return UVEC3.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("uint4") {
-            return UVEC4.gate(version >= 100, false, type_name_atom, is_type_name);
+            return UVEC4.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("double") {
-            return DOUBLE.gate(version >= 100, false, type_name_atom, is_type_name);
+            return DOUBLE.gate(true, false, type_name_atom, is_type_name);
         }
 
         // Additional HLSL matrix types
         if type_name_atom == type_name!("float2x1") {
-            return MAT2X1.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT2X1.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float2x3") {
-            return MAT2X3.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT2X3.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float2x4") {
-            return MAT2X4.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT2X4.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float3x2") {
-            return MAT3X2.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT3X2.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float3x4") {
-            return MAT3X4.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT3X4.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float4x2") {
-            return MAT4X2.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT4X2.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float4x3") {
-            return MAT4X3.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT4X3.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float1x1") {
-            return MAT1X1.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT1X1.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float1x2") {
-            return MAT1X2.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT1X2.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float1x3") {
-            return MAT1X3.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT1X3.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float1x4") {
-            return MAT1X4.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT1X4.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float3x1") {
-            return MAT3X1.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT3X1.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("float4x1") {
-            return MAT4X1.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MAT4X1.gate(true, false, type_name_atom, is_type_name);
         } else if type_name_atom == type_name!("matrix") {
-            return MATRIX.gate(version >= 100, false, type_name_atom, is_type_name);
+            return MATRIX.gate(true, false, type_name_atom, is_type_name);
         }
 
         let result = is_type_name(&type_name_atom);
@@ -351,6 +352,9 @@ pub enum Token {
     /// String constant
     #[lang_util(parser = "STRING_CONST", kind = "string constant", kind = "literal")]
     STRING_CONST(SmolStr),
+    /// Character constant
+    #[lang_util(parser = "CHAR_CONST", kind = "char constant", kind = "literal")]
+    CHAR_CONST(char),
     // Multi-char tokens
     /// <<
     #[lang_util(token = "<<", kind = "binary operator", kind = "operator")]
@@ -415,6 +419,9 @@ pub enum Token {
     /// -=
     #[lang_util(token = "-=", kind = "binary operator", kind = "operator")]
     SUB_ASSIGN,
+    /// ::
+    #[lang_util(token = "::", kind = "operator")]
+    SCOPE_OP,
     // Single-char tokens
     /// (
     #[lang_util(token = "(")]
@@ -508,26 +515,6 @@ pub enum Token {
     /// "uniform"
     #[lang_util(token = "uniform", kind = "storage qualifier", kind = "type qualifier")]
     UNIFORM,
-    /// "shared"
-    #[lang_util(token = "shared", kind = "storage qualifier", kind = "type qualifier")]
-    SHARED,
-    /// "attribute"
-    #[lang_util(
-        token = "attribute",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    ATTRIBUTE,
-    /// "varying"
-    #[lang_util(token = "varying", kind = "storage qualifier", kind = "type qualifier")]
-    VARYING,
-    /// "coherent"
-    #[lang_util(
-        token = "coherent",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    COHERENT,
     /// "volatile"
     #[lang_util(
         token = "volatile",
@@ -535,27 +522,6 @@ pub enum Token {
         kind = "type qualifier"
     )]
     VOLATILE,
-    /// "restrict"
-    #[lang_util(
-        token = "restrict",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    RESTRICT,
-    /// "readonly"
-    #[lang_util(
-        token = "readonly",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    READONLY,
-    /// "writeonly"
-    #[lang_util(
-        token = "writeonly",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    WRITEONLY,
     /// "register"
     #[lang_util(
         token = "register",
@@ -570,9 +536,6 @@ pub enum Token {
         kind = "type qualifier"
     )]
     PACKOFFSET,
-    /// "layout"
-    #[lang_util(token = "layout", kind = "layout qualifier", kind = "type qualifier")]
-    LAYOUT,
     /// "centroid"
     #[lang_util(
         token = "centroid",
@@ -580,36 +543,27 @@ pub enum Token {
         kind = "type qualifier"
     )]
     CENTROID,
-    /// "flat"
+    /// "noperspective"
     #[lang_util(
-        token = "flat",
+        token = "noperspective",
         kind = "interpolation qualifier",
         kind = "type qualifier"
     )]
-    FLAT,
-    /// "smooth"
+    NOPERSPECTIVE,
+    /// "linear"
     #[lang_util(
-        token = "smooth",
+        token = "linear",
         kind = "interpolation qualifier",
         kind = "type qualifier"
     )]
-    SMOOTH,
-    /// "noperspective"
+    LINEAR,
+    /// "nointerpolation"
     #[lang_util(
-        token = "noperspective",
+        token = "nointerpolation",
         kind = "interpolation qualifier",
         kind = "type qualifier"
     )]
-    NOPERSPECTIVE,
-    /// "patch"
-    #[lang_util(token = "patch", kind = "storage qualifier", kind = "type qualifier")]
-    PATCH,
-    /// "sample"
-    #[lang_util(token = "sample", kind = "storage qualifier", kind = "type qualifier")]
-    SAMPLE,
-    /// "invariant"
-    #[lang_util(token = "invariant", kind = "type qualifier")]
-    INVARIANT,
+    NOINTERPOLATION,
     /// "precise"
     #[lang_util(token = "precise", kind = "type qualifier")]
     PRECISE,
@@ -643,13 +597,6 @@ pub enum Token {
     /// "else"
     #[lang_util(token = "else", kind = "keyword")]
     ELSE,
-    /// "subroutine"
-    #[lang_util(
-        token = "subroutine",
-        kind = "storage qualifier",
-        kind = "type qualifier"
-    )]
-    SUBROUTINE,
     /// "in"
     #[lang_util(token = "in", kind = "storage qualifier", kind = "type qualifier")]
     IN,
@@ -665,22 +612,6 @@ pub enum Token {
     /// "return"
     #[lang_util(token = "return", kind = "keyword")]
     RETURN,
-    /// "lowp"
-    #[lang_util(token = "lowp", kind = "precision qualifier", kind = "type qualifier")]
-    LOWP,
-    /// "mediump"
-    #[lang_util(
-        token = "mediump",
-        kind = "precision qualifier",
-        kind = "type qualifier"
-    )]
-    MEDIUMP,
-    /// "highp"
-    #[lang_util(token = "highp", kind = "precision qualifier", kind = "type qualifier")]
-    HIGHP,
-    /// "precision"
-    #[lang_util(token = "precision")]
-    PRECISION,
     /// "struct"
     #[lang_util(token = "struct", kind = "struct", kind = "keyword")]
     STRUCT,
@@ -693,21 +624,15 @@ pub enum Token {
     /// "SamplerComparisonState"
     #[lang_util(token = "SamplerComparisonState", kind = "type name")]
     SAMPLER_COMPARISON_STATE,
+    /// "operator"
+    #[lang_util(token = "operator", kind = "keyword")]
+    OPERATOR,
     // Reserved for future use
-    /// "common"
-    #[lang_util(token = "common", kind = "reserved keyword")]
-    COMMON,
-    /// "partition"
-    #[lang_util(token = "partition", kind = "reserved keyword")]
-    PARTITION,
-    /// "active"
-    #[lang_util(token = "active", kind = "reserved keyword")]
-    ACTIVE,
     /// "asm"
     #[lang_util(token = "asm", kind = "reserved keyword")]
     ASM,
     /// "class"
-    #[lang_util(token = "class", kind = "reserved keyword")]
+    #[lang_util(token = "class", kind = "struct", kind = "keyword")]
     CLASS,
     /// "union"
     #[lang_util(token = "union", kind = "reserved keyword")]
@@ -724,9 +649,6 @@ pub enum Token {
     /// "this"
     #[lang_util(token = "this", kind = "reserved keyword")]
     THIS,
-    /// "resource"
-    #[lang_util(token = "resource", kind = "reserved keyword")]
-    RESOURCE,
     /// "goto"
     #[lang_util(token = "goto", kind = "reserved keyword")]
     GOTO,
@@ -745,11 +667,8 @@ pub enum Token {
     /// "extern"
     #[lang_util(token = "extern", kind = "reserved keyword")]
     EXTERN,
-    /// "external"
-    #[lang_util(token = "external", kind = "reserved keyword")]
-    EXTERNAL,
     /// "interface"
-    #[lang_util(token = "interface", kind = "reserved keyword")]
+    #[lang_util(token = "interface", kind = "struct", kind = "keyword")]
     INTERFACE,
     /// "long"
     #[lang_util(token = "long", kind = "reserved keyword")]
@@ -760,28 +679,13 @@ pub enum Token {
     /// "half"
     #[lang_util(token = "half", kind = "reserved keyword")]
     HALF,
-    /// "fixed"
-    #[lang_util(token = "fixed", kind = "reserved keyword")]
-    FIXED,
     /// "unsigned"
     #[lang_util(token = "unsigned", kind = "reserved keyword")]
     UNSIGNED,
-    /// "superp"
-    #[lang_util(token = "superp", kind = "reserved keyword")]
-    SUPERP,
 
-    /// "output"
-    #[lang_util(token = "output", kind = "reserved keyword")]
-    OUTPUT,
-    /// "filter"
-    #[lang_util(token = "filter", kind = "reserved keyword")]
-    FILTER,
     /// "sizeof"
     #[lang_util(token = "sizeof", kind = "reserved keyword")]
     SIZEOF,
-    /// "cast"
-    #[lang_util(token = "cast", kind = "reserved keyword")]
-    CAST,
     /// "namespace"
     #[lang_util(token = "namespace", kind = "reserved keyword")]
     NAMESPACE,
@@ -812,14 +716,14 @@ pub enum ErrorKind {
     InvalidFloatLiteral,
     #[display("invalid double literal")]
     InvalidDoubleLiteral,
+    #[display("invalid char literal")]
+    InvalidCharLiteral,
 }
 
 impl Token {
     pub fn from_token(
         token: lexer::TextToken,
         source: &str,
-        version: u16,
-        target_vulkan: bool,
         is_type_name: impl Fn(&TypeNameAtom) -> TypeNameState,
     ) -> (Token, Option<TypeNameState>) {
 
@@ -970,6 +874,9 @@ impl Token {
             lexer::Token::SUB_ASSIGN => {
                 return (SUB_ASSIGN, None);
             }
+            lexer::Token::SCOPE_OP => {
+                return (SCOPE_OP, None);
+            }
             lexer::Token::DEFINED
             | lexer::Token::LINECONT
             | lexer::Token::NEWLINE
@@ -987,6 +894,12 @@ impl Token {
                 };
                 return (STRING_CONST(string_content.to_string().into()), None);
             }
+            lexer::Token::QUOTE_CHAR => {
+                return (
+                    Token::parse_char(&Unescaped::new(token.raw(source)).to_string()),
+                    None,
+                );
+            }
             lexer::Token::ANGLE_STRING
             | lexer::Token::BACKSLASH
             | lexer::Token::ERROR
@@ -1007,7 +920,7 @@ impl Token {
 
             // Else it might be a built-in type name
             if let Some((type_name, state)) =
-                TypeName::parse(text.as_ref(), version, target_vulkan, is_type_name)
+                TypeName::parse(text.as_ref(), is_type_name)
             {
                 return (TYPE_NAME(type_name), state);
             }
@@ -1030,40 +943,20 @@ impl Token {
             Some(CONST)
         } else         if *keyword_atom == keyword!("uniform") {
             Some(UNIFORM)
-        } else if *keyword_atom == keyword!("shared") {
-            Some(SHARED)
-        } else if *keyword_atom == keyword!("attribute") {
-            Some(ATTRIBUTE)
-        } else if *keyword_atom == keyword!("varying") {
-            Some(VARYING)
-        } else if *keyword_atom == keyword!("coherent") {
-            Some(COHERENT)
         } else if *keyword_atom == keyword!("volatile") {
             Some(VOLATILE)
-        } else if *keyword_atom == keyword!("restrict") {
-            Some(RESTRICT)
-        } else if *keyword_atom == keyword!("readonly") {
-            Some(READONLY)
-        } else if *keyword_atom == keyword!("writeonly") {
-            Some(WRITEONLY)
         } else if *keyword_atom == keyword!("register") {
             Some(REGISTER)
-        } else if *keyword_atom == keyword!("layout") {
-            Some(LAYOUT)
+        } else if *keyword_atom == keyword!("packoffset") {
+            Some(PACKOFFSET)
         } else if *keyword_atom == keyword!("centroid") {
             Some(CENTROID)
-        } else if *keyword_atom == keyword!("flat") {
-            Some(FLAT)
-        } else if *keyword_atom == keyword!("smooth") {
-            Some(SMOOTH)
         } else if *keyword_atom == keyword!("noperspective") {
             Some(NOPERSPECTIVE)
-        } else if *keyword_atom == keyword!("patch") {
-            Some(PATCH)
-        } else if *keyword_atom == keyword!("sample") {
-            Some(SAMPLE)
-        } else if *keyword_atom == keyword!("invariant") {
-            Some(INVARIANT)
+        } else if *keyword_atom == keyword!("linear") {
+            Some(LINEAR)
+        } else if *keyword_atom == keyword!("nointerpolation") {
+            Some(NOINTERPOLATION)
         } else if *keyword_atom == keyword!("precise") {
             Some(PRECISE)
         } else if *keyword_atom == keyword!("break") {
@@ -1086,8 +979,6 @@ impl Token {
             Some(IF)
         } else if *keyword_atom == keyword!("else") {
             Some(ELSE)
-        } else if *keyword_atom == keyword!("subroutine") {
-            Some(SUBROUTINE)
         } else if *keyword_atom == keyword!("in") {
             Some(IN)
         } else if *keyword_atom == keyword!("out") {
@@ -1102,14 +993,6 @@ impl Token {
             Some(DISCARD)
         } else if *keyword_atom == keyword!("return") {
             Some(RETURN)
-        } else if *keyword_atom == keyword!("lowp") {
-            Some(LOWP)
-        } else if *keyword_atom == keyword!("mediump") {
-            Some(MEDIUMP)
-        } else if *keyword_atom == keyword!("highp") {
-            Some(HIGHP)
-        } else if *keyword_atom == keyword!("precision") {
-            Some(PRECISION)
         } else if *keyword_atom == keyword!("struct") {
             Some(STRUCT)
         } else if *keyword_atom == keyword!("cbuffer") {
@@ -1118,15 +1001,11 @@ impl Token {
             Some(SAMPLER_STATE)
         } else if *keyword_atom == keyword!("SamplerComparisonState") {
             Some(SAMPLER_COMPARISON_STATE)
+        } else if *keyword_atom == keyword!("operator") {
+            Some(OPERATOR)
         }
         // Reserved for future use
-        else if *keyword_atom == keyword!("common") {
-            Some(COMMON)
-        } else if *keyword_atom == keyword!("partition") {
-            Some(PARTITION)
-        } else if *keyword_atom == keyword!("active") {
-            Some(ACTIVE)
-        } else if *keyword_atom == keyword!("asm") {
+        else if *keyword_atom == keyword!("asm") {
             Some(ASM)
         } else if *keyword_atom == keyword!("class") {
             Some(CLASS)
@@ -1140,8 +1019,6 @@ impl Token {
             Some(TEMPLATE)
         } else if *keyword_atom == keyword!("this") {
             Some(THIS)
-        } else if *keyword_atom == keyword!("resource") {
-            Some(RESOURCE)
         } else if *keyword_atom == keyword!("goto") {
             Some(GOTO)
         } else if *keyword_atom == keyword!("inline") {
@@ -1154,8 +1031,6 @@ impl Token {
             Some(STATIC)
         } else if *keyword_atom == keyword!("extern") {
             Some(EXTERN)
-        } else if *keyword_atom == keyword!("external") {
-            Some(EXTERNAL)
         } else if *keyword_atom == keyword!("interface") {
             Some(INTERFACE)
         } else if *keyword_atom == keyword!("long") {
@@ -1164,20 +1039,10 @@ impl Token {
             Some(SHORT)
         } else if *keyword_atom == keyword!("half") {
             Some(HALF)
-        } else if *keyword_atom == keyword!("fixed") {
-            Some(FIXED)
         } else if *keyword_atom == keyword!("unsigned") {
             Some(UNSIGNED)
-        } else if *keyword_atom == keyword!("superp") {
-            Some(SUPERP)
-        } else if *keyword_atom == keyword!("output") {
-            Some(OUTPUT)
-        } else if *keyword_atom == keyword!("filter") {
-            Some(FILTER)
         } else if *keyword_atom == keyword!("sizeof") {
             Some(SIZEOF)
-        } else if *keyword_atom == keyword!("cast") {
-            Some(CAST)
         } else if *keyword_atom == keyword!("namespace") {
             Some(NAMESPACE)
         } else if *keyword_atom == keyword!("using") {
@@ -1216,6 +1081,68 @@ impl Token {
         }
     }
 
+    /// Parse a single-quoted character literal, including its quotes
+    ///
+    /// Simple escape sequences (`\\n`, `\\t`, ...), octal escapes of up to three digits
+    /// (`\\101`) and hexadecimal escapes (`\\x41`) are decoded; numeric escapes must fit in a
+    /// byte. Like DXC, an unknown escape sequence evaluates to the escaped character itself.
+    pub(crate) fn parse_char(text: &str) -> Self {
+        use Token::*;
+
+        let mut chars = match text
+            .strip_prefix('\'')
+            .and_then(|text| text.strip_suffix('\''))
+        {
+            Some(inner) => inner.chars(),
+            None => return ERROR(ErrorKind::InvalidCharLiteral),
+        };
+
+        // Numeric escapes denote a single byte
+        let byte = |digits: &str, radix| {
+            u32::from_str_radix(digits, radix)
+                .ok()
+                .filter(|value| *value <= 0xff)
+                .and_then(char::from_u32)
+        };
+
+        let value = match chars.next() {
+            Some('\\') => {
+                let escape = chars.as_str();
+                match chars.next() {
+                    Some('a') => Some('\u{07}'),
+                    Some('b') => Some('\u{08}'),
+                    Some('t') => Some('\t'),
+                    Some('n') => Some('\n'),
+                    Some('v') => Some('\u{0b}'),
+                    Some('f') => Some('\u{0c}'),
+                    Some('r') => Some('\r'),
+                    Some('0'..='7') => {
+                        let len = escape
+                            .chars()
+                            .take(3)
+                            .take_while(|c| c.is_digit(8))
+                            .count();
+                        chars = escape[len..].chars();
+                        byte(&escape[..len], 8)
+                    }
+                    Some('x') => {
+                        let digits = &escape[1..];
+                        let len = digits.chars().take_while(char::is_ascii_hexdigit).count();
+                        chars = digits[len..].chars();
+                        byte(&digits[..len], 16)
+                    }
+                    other => other,
+                }
+            }
+            other => other,
+        };
+
+        match value {
+            Some(value) if chars.next().is_none() => CHAR_CONST(value),
+            _ => ERROR(ErrorKind::InvalidCharLiteral),
+        }
+    }
+
     pub(crate) fn parse_digits(text: &str) -> Self {
         use Token::*;
 
@@ -1309,6 +1236,45 @@ mod tests {
         assert_eq!(Token::parse_digits("1E-34F"), FLOAT_CONST(1E-34));
     }
 
+    #[test]
+    fn test_parse_char_constant() {
+        assert_eq!(Token::parse_char("'A'"), CHAR_CONST('A'));
+        assert_eq!(Token::parse_char("'0'"), CHAR_CONST('0'));
+        assert_eq!(Token::parse_char("'\\a'"), CHAR_CONST('\u{07}'));
+        assert_eq!(Token::parse_char("'\\n'"), CHAR_CONST('\n'));
+        assert_eq!(Token::parse_char("'\\''"), CHAR_CONST('\''));
+        assert_eq!(Token::parse_char("'\\c'"), CHAR_CONST('c'));
+        assert_eq!(Token::parse_char("'\\0'"), CHAR_CONST('\0'));
+        assert_eq!(Token::parse_char("'\\101'"), CHAR_CONST('A'));
+        assert_eq!(Token::parse_char("'\\377'"), CHAR_CONST('\u{ff}'));
+        assert_eq!(Token::parse_char("'\\x41'"), CHAR_CONST('A'));
+        assert_eq!(Token::parse_char("'\\xfF'"), CHAR_CONST('\u{ff}'));
+        assert_eq!(
+            Token::parse_char("'\\1011'"),
+            ERROR(super::ErrorKind::InvalidCharLiteral)
+        );
+        assert_eq!(
+            Token::parse_char("'\\400'"),
+            ERROR(super::ErrorKind::InvalidCharLiteral)
+        );
+        assert_eq!(
+            Token::parse_char("'\\x'"),
+            ERROR(super::ErrorKind::InvalidCharLiteral)
+        );
+        assert_eq!(
+            Token::parse_char("'\\x100'"),
+            ERROR(super::ErrorKind::InvalidCharLiteral)
+        );
+        assert_eq!(
+            Token::parse_char("''"),
+            ERROR(super::ErrorKind::InvalidCharLiteral)
+        );
+        assert_eq!(
+            Token::parse_char("'ab'"),
+            ERROR(super::ErrorKind::InvalidCharLiteral)
+        );
+    }
+
     #[test]
     fn test_parse_double_constant() {
         assert_eq!(Token::parse_digits("0.lf"), DOUBLE_CONST(0.));
@@ -113,7 +113,10 @@ pub fn test_file(path: impl AsRef<Path>) {
 
     let mut critical_error_count = 0;
 
-    for result in parsed.into_iter().tokenize(100, false, &DEFAULT_REGISTRY) {
+    for result in parsed
+        .into_iter()
+        .tokenize(&DEFAULT_REGISTRY)
+    {
         // Redact repository path from EnterFile events
         let debug_formatted = format!("{:?}", result);
 
@@ -656,6 +656,9 @@ pub enum TypeSpecifierNonArrayData {
     /// `RWStructuredBuffer` type specifier
     #[lang_util(display(extra = "RWStructuredBuffer"))]
     RWStructuredBuffer,
+    /// `string` type specifier
+    #[lang_util(display(extra = "string"))]
+    String,
     /// `StructuredBuffer<T>` template type specifier
     #[lang_util(display(extra = "StructuredBuffer"))]
     StructuredBufferTemplate(Box<TypeSpecifier>),
@@ -714,10 +717,16 @@ impl From<TypeSpecifierNonArrayData> for TypeSpecifierData {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "rserde"))]
 pub struct StructSpecifierData {
+    /// Keyword used to declare the type (HLSL)
+    pub kind: StructKind,
     /// Structure name
     pub name: Option<TypeName>,
+    /// Base types this type inherits from or implements (HLSL)
+    pub bases: Vec<TypeName>,
     /// Field specifications
     pub fields: Vec<StructFieldSpecifier>,
+    /// Member functions (HLSL)
+    pub methods: Vec<Method>,
 }
 
 impl_node_content! {
@@ -725,11 +734,47 @@ impl_node_content! {
     pub type StructSpecifier = Node<StructSpecifierData>;
 }
 
+/// Keyword introducing a user-defined type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum StructKind {
+    /// `struct`
+    #[lang_util(display(extra = "struct"))]
+    Struct,
+    /// `class` (HLSL, SM5 dynamic linkage)
+    #[lang_util(display(extra = "class"))]
+    Class,
+    /// `interface` (HLSL, SM5 dynamic linkage)
+    #[lang_util(display(extra = "interface"))]
+    Interface,
+}
+
+/// Member function declared in a struct, class or interface body (HLSL).
+#[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct MethodData {
+    /// HLSL attributes (optional)
+    pub attributes: Option<Vec<Attribute>>,
+    /// Method prototype
+    pub prototype: FunctionPrototype,
+    /// Method body, if the method is defined inline
+    pub statement: Option<CompoundStatement>,
+}
+
+impl_node_content! {
+    /// Type alias for `Node<MethodData>`.
+    pub type Method = Node<MethodData>;
+}
+
 /// Struct field specifier. Used to add fields to struct specifiers.
 #[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "rserde"))]
 pub struct StructFieldSpecifierData {
+    /// HLSL attributes (optional)
+    pub attributes: Option<Vec<Attribute>>,
     /// Type qualifiers for the field
     pub qualifier: Option<TypeQualifier>,
     /// Type of the field
@@ -748,7 +793,13 @@ impl_node_content! {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "rserde"))]
 #[lang_util(display(leaf))]
-pub struct SemanticData(#[lang_util(display(extra))] pub SmolStr);
+pub struct SemanticData {
+    /// Semantic name, without its trailing index (e.g. `TEXCOORD` for `TEXCOORD7`)
+    #[lang_util(display(extra))]
+    pub name: SmolStr,
+    /// Semantic index, if one was written (e.g. `7` for `TEXCOORD7`)
+    pub index: Option<u32>,
+}
 
 impl_node_content! {
     /// Type alias for `Node<SemanticData>`.
@@ -756,21 +807,240 @@ impl_node_content! {
 }
 
 impl SemanticData {
-    /// Returns this semantic as a string slice
-    pub fn as_str(&self) -> &str {
-        self.0.as_str()
+    /// Create a new semantic from its name and index
+    pub fn new(name: impl Into<SmolStr>, index: Option<u32>) -> Self {
+        Self {
+            name: name.into(),
+            index,
+        }
+    }
+
+    /// Returns the index of this semantic, which defaults to 0 when none was written
+    pub fn index(&self) -> u32 {
+        self.index.unwrap_or(0)
+    }
+
+    /// Returns the system value this semantic refers to, if it is an `SV_` semantic
+    pub fn system_value(&self) -> Option<SystemValue> {
+        SystemValue::from_name(&self.name)
+    }
+
+    /// Returns `true` if this is a system-value semantic
+    pub fn is_system_value(&self) -> bool {
+        self.system_value().is_some()
     }
 }
 
 impl From<&str> for SemanticData {
     fn from(semantic: &str) -> Self {
-        Self(semantic.into())
+        // Semantics are an identifier followed by an optional decimal index
+        let name = semantic.trim_end_matches(|c: char| c.is_ascii_digit());
+        match semantic[name.len()..].parse() {
+            Ok(index) if !name.is_empty() => Self::new(name, Some(index)),
+            _ => Self::new(semantic, None),
+        }
     }
 }
 
 impl fmt::Display for SemanticData {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        self.0.fmt(f)
+        f.write_str(&self.name)?;
+
+        if let Some(index) = self.index {
+            write!(f, "{}", index)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A system-value semantic (HLSL)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum SystemValue {
+    /// `SV_Barycentrics`
+    Barycentrics,
+    /// `SV_ClipDistance`
+    ClipDistance,
+    /// `SV_Coverage`
+    Coverage,
+    /// `SV_CullDistance`
+    CullDistance,
+    /// `SV_CullPrimitive`
+    CullPrimitive,
+    /// `SV_Depth`
+    Depth,
+    /// `SV_DepthGreaterEqual`
+    DepthGreaterEqual,
+    /// `SV_DepthLessEqual`
+    DepthLessEqual,
+    /// `SV_DispatchThreadID`
+    DispatchThreadId,
+    /// `SV_DomainLocation`
+    DomainLocation,
+    /// `SV_GroupID`
+    GroupId,
+    /// `SV_GroupIndex`
+    GroupIndex,
+    /// `SV_GroupThreadID`
+    GroupThreadId,
+    /// `SV_GSInstanceID`
+    GsInstanceId,
+    /// `SV_InnerCoverage`
+    InnerCoverage,
+    /// `SV_InsideTessFactor`
+    InsideTessFactor,
+    /// `SV_InstanceID`
+    InstanceId,
+    /// `SV_IsFrontFace`
+    IsFrontFace,
+    /// `SV_OutputControlPointID`
+    OutputControlPointId,
+    /// `SV_Position`
+    Position,
+    /// `SV_PrimitiveID`
+    PrimitiveId,
+    /// `SV_RenderTargetArrayIndex`
+    RenderTargetArrayIndex,
+    /// `SV_SampleIndex`
+    SampleIndex,
+    /// `SV_ShadingRate`
+    ShadingRate,
+    /// `SV_StartInstanceLocation`
+    StartInstanceLocation,
+    /// `SV_StartVertexLocation`
+    StartVertexLocation,
+    /// `SV_StencilRef`
+    StencilRef,
+    /// `SV_Target`
+    Target,
+    /// `SV_TessFactor`
+    TessFactor,
+    /// `SV_VertexID`
+    VertexId,
+    /// `SV_ViewID`
+    ViewId,
+    /// `SV_ViewportArrayIndex`
+    ViewportArrayIndex,
+}
+
+impl SystemValue {
+    /// All the system-value semantics
+    pub const ALL: [Self; 32] = [
+        Self::Barycentrics,
+        Self::ClipDistance,
+        Self::Coverage,
+        Self::CullDistance,
+        Self::CullPrimitive,
+        Self::Depth,
+        Self::DepthGreaterEqual,
+        Self::DepthLessEqual,
+        Self::DispatchThreadId,
+        Self::DomainLocation,
+        Self::GroupId,
+        Self::GroupIndex,
+        Self::GroupThreadId,
+        Self::GsInstanceId,
+        Self::InnerCoverage,
+        Self::InsideTessFactor,
+        Self::InstanceId,
+        Self::IsFrontFace,
+        Self::OutputControlPointId,
+        Self::Position,
+        Self::PrimitiveId,
+        Self::RenderTargetArrayIndex,
+        Self::SampleIndex,
+        Self::ShadingRate,
+        Self::StartInstanceLocation,
+        Self::StartVertexLocation,
+        Self::StencilRef,
+        Self::Target,
+        Self::TessFactor,
+        Self::VertexId,
+        Self::ViewId,
+        Self::ViewportArrayIndex,
+    ];
+
+    /// Canonical spelling of this system value
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Barycentrics => "SV_Barycentrics",
+            Self::ClipDistance => "SV_ClipDistance",
+            Self::Coverage => "SV_Coverage",
+            Self::CullDistance => "SV_CullDistance",
+            Self::CullPrimitive => "SV_CullPrimitive",
+            Self::Depth => "SV_Depth",
+            Self::DepthGreaterEqual => "SV_DepthGreaterEqual",
+            Self::DepthLessEqual => "SV_DepthLessEqual",
+            Self::DispatchThreadId => "SV_DispatchThreadID",
+            Self::DomainLocation => "SV_DomainLocation",
+            Self::GroupId => "SV_GroupID",
+            Self::GroupIndex => "SV_GroupIndex",
+            Self::GroupThreadId => "SV_GroupThreadID",
+            Self::GsInstanceId => "SV_GSInstanceID",
+            Self::InnerCoverage => "SV_InnerCoverage",
+            Self::InsideTessFactor => "SV_InsideTessFactor",
+            Self::InstanceId => "SV_InstanceID",
+            Self::IsFrontFace => "SV_IsFrontFace",
+            Self::OutputControlPointId => "SV_OutputControlPointID",
+            Self::Position => "SV_Position",
+            Self::PrimitiveId => "SV_PrimitiveID",
+            Self::RenderTargetArrayIndex => "SV_RenderTargetArrayIndex",
+            Self::SampleIndex => "SV_SampleIndex",
+            Self::ShadingRate => "SV_ShadingRate",
+            Self::StartInstanceLocation => "SV_StartInstanceLocation",
+            Self::StartVertexLocation => "SV_StartVertexLocation",
+            Self::StencilRef => "SV_StencilRef",
+            Self::Target => "SV_Target",
+            Self::TessFactor => "SV_TessFactor",
+            Self::VertexId => "SV_VertexID",
+            Self::ViewId => "SV_ViewID",
+            Self::ViewportArrayIndex => "SV_ViewportArrayIndex",
+        }
+    }
+
+    /// Look up a system value by semantic name, without its index. Semantic names are case
+    /// insensitive.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|sv| sv.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns `false` for system values which are read from dedicated input registers rather
+    /// than passed through an input or output signature, such as compute shader thread
+    /// identifiers
+    pub fn is_signature_element(self) -> bool {
+        !matches!(
+            self,
+            Self::DispatchThreadId
+                | Self::GroupId
+                | Self::GroupThreadId
+                | Self::GroupIndex
+                | Self::DomainLocation
+                | Self::OutputControlPointId
+                | Self::GsInstanceId
+        )
+    }
+
+    /// Returns `true` for pixel shader outputs which are written to a dedicated register rather
+    /// than a signature register
+    pub fn has_dedicated_register(self) -> bool {
+        matches!(
+            self,
+            Self::Depth
+                | Self::DepthGreaterEqual
+                | Self::DepthLessEqual
+                | Self::Coverage
+                | Self::StencilRef
+        )
+    }
+}
+
+impl fmt::Display for SystemValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.write_str(self.name())
     }
 }
 
@@ -785,6 +1055,10 @@ pub struct ArrayedIdentifierData {
     pub array_spec: Option<ArraySpecifier>,
     /// Attached semantic specification (HLSL)
     pub semantic: Option<Semantic>,
+    /// Attached packing offset, for cbuffer members (HLSL)
+    pub packoffset: Option<PackOffset>,
+    /// Bitfield width, for struct members (HLSL 2021)
+    pub bit_width: Option<Expr>,
 }
 
 impl_node_content! {
@@ -803,6 +1077,8 @@ impl ArrayedIdentifierData {
             ident: ident.into(),
             array_spec: array_spec.into(),
             semantic: None,
+            packoffset: None,
+            bit_width: None,
         }
     }
 
@@ -817,6 +1093,8 @@ impl ArrayedIdentifierData {
             ident: ident.into(),
             array_spec: array_spec.into(),
             semantic: semantic.into(),
+            packoffset: None,
+            bit_width: None,
         }
     }
 }
@@ -827,6 +1105,8 @@ impl From<&str> for ArrayedIdentifierData {
             ident: IdentifierData::from(ident).into(),
             array_spec: None,
             semantic: None,
+            packoffset: None,
+            bit_width: None,
         }
     }
 }
@@ -904,6 +1184,27 @@ pub enum StorageQualifierData {
     /// `sample` storage qualifier
     #[lang_util(display(extra = "sample"))]
     Sample,
+    /// `point` geometry shader input primitive
+    #[lang_util(display(extra = "point"))]
+    Point,
+    /// `line` geometry shader input primitive
+    #[lang_util(display(extra = "line"))]
+    Line,
+    /// `triangle` geometry shader input primitive
+    #[lang_util(display(extra = "triangle"))]
+    Triangle,
+    /// `lineadj` geometry shader input primitive
+    #[lang_util(display(extra = "lineadj"))]
+    LineAdj,
+    /// `triangleadj` geometry shader input primitive
+    #[lang_util(display(extra = "triangleadj"))]
+    TriangleAdj,
+    /// `row_major` matrix packing order
+    #[lang_util(display(extra = "row_major"))]
+    RowMajor,
+    /// `column_major` matrix packing order
+    #[lang_util(display(extra = "column_major"))]
+    ColumnMajor,
     /// `uniform` storage qualifier
     #[lang_util(display(extra = "uniform"))]
     Uniform,
@@ -1010,6 +1311,12 @@ pub enum InterpolationQualifierData {
     /// `noperspective` interpolation qualifier
     #[lang_util(display(extra = "noperspective"))]
     NoPerspective,
+    /// `linear` interpolation qualifier
+    #[lang_util(display(extra = "linear"))]
+    Linear,
+    /// `nointerpolation` interpolation qualifier
+    #[lang_util(display(extra = "nointerpolation"))]
+    NoInterpolation,
 }
 
 impl_node_content! {
@@ -1138,6 +1445,8 @@ impl_node_content! {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "rserde"))]
 pub struct CBufferData {
+    /// HLSL attributes (optional)
+    pub attributes: Option<Vec<Attribute>>,
     /// CBuffer name
     pub name: Identifier,
     /// Optional resource binding
@@ -1246,19 +1555,71 @@ impl FunIdentifierData {
 pub struct FunctionPrototypeData {
     /// Return type
     pub ty: FullySpecifiedType,
+    /// Type the function is a member of, for out-of-line method definitions such as
+    /// `float3 Light::Illuminate()` (HLSL)
+    pub scope: Option<TypeName>,
     /// Function name
     pub name: Identifier,
+    /// Operator overloaded by this function, in which case `name` is spelled `operator<op>`
+    /// (HLSL 2021)
+    pub operator: Option<OverloadedOperator>,
     /// Function parameters
     pub parameters: Vec<FunctionParameterDeclaration>,
     /// Function return semantic (HLSL)
     pub semantic: Option<Semantic>,
 }
 
+impl FunctionPrototypeData {
+    /// Number of arguments a call must provide, i.e. the number of parameters before the first
+    /// one with a default value
+    pub fn required_parameter_count(&self) -> usize {
+        self.parameters
+            .iter()
+            .take_while(|param| param.default_value().is_none())
+            .count()
+    }
+
+    /// Default arguments filling in the trailing parameters omitted by a call passing `arg_count`
+    /// arguments
+    ///
+    /// Returns `None` if a call with `arg_count` arguments cannot target this prototype, i.e. if it
+    /// passes too many arguments, or if one of the omitted parameters has no default value.
+    pub fn omitted_arguments(&self, arg_count: usize) -> Option<Vec<DefaultArgument<'_>>> {
+        if arg_count > self.parameters.len() {
+            return None;
+        }
+
+        self.parameters
+            .iter()
+            .enumerate()
+            .skip(arg_count)
+            .map(|(index, param)| {
+                param.default_value().map(|value| DefaultArgument {
+                    index,
+                    parameter: param,
+                    value,
+                })
+            })
+            .collect()
+    }
+}
+
 impl_node_content! {
     /// Type alias for `Node<FunctionPrototypeData>`.
     pub type FunctionPrototype = Node<FunctionPrototypeData>;
 }
 
+/// A default value filling in a parameter omitted at a call site.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DefaultArgument<'a> {
+    /// Index of the omitted parameter
+    pub index: usize,
+    /// Declaration of the omitted parameter
+    pub parameter: &'a FunctionParameterDeclaration,
+    /// Default value used for this parameter
+    pub value: &'a Initializer,
+}
+
 /// Function parameter declaration.
 #[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1270,6 +1631,16 @@ pub enum FunctionParameterDeclarationData {
     Unnamed(Option<TypeQualifier>, TypeSpecifier),
 }
 
+impl FunctionParameterDeclarationData {
+    /// Default value of this parameter, if any
+    pub fn default_value(&self) -> Option<&Initializer> {
+        match self {
+            Self::Named(_, declarator) => declarator.default_value.as_ref(),
+            Self::Unnamed(_, _) => None,
+        }
+    }
+}
+
 impl_node_content! {
     /// Type alias for `Node<FunctionParameterDeclarationData>`.
     pub type FunctionParameterDeclaration = Node<FunctionParameterDeclarationData>;
@@ -1280,10 +1651,14 @@ impl_node_content! {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "rserde"))]
 pub struct FunctionParameterDeclaratorData {
+    /// HLSL attributes (optional)
+    pub attributes: Option<Vec<Attribute>>,
     /// Parameter type
     pub ty: TypeSpecifier,
     /// Parameter name
     pub ident: ArrayedIdentifier,
+    /// Default value (HLSL)
+    pub default_value: Option<Initializer>,
 }
 
 impl_node_content! {
@@ -1312,6 +1687,8 @@ impl_node_content! {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "rserde"))]
 pub struct SingleDeclarationData {
+    /// HLSL attributes (optional)
+    pub attributes: Option<Vec<Attribute>>,
     /// Declaration type
     pub ty: FullySpecifiedType,
     /// Declared identifier
@@ -1400,8 +1777,15 @@ pub enum ExprData {
     DoubleConst(f64),
     /// String constant expression.
     StringConst(String),
+    /// Adjacent string literals, which are concatenated (HLSL). Each piece is a
+    /// [ExprData::StringConst] expression.
+    StringConcat(Vec<Expr>),
+    /// Character constant expression, which evaluates to the integer value of the character.
+    CharConst(char),
     /// A unary expression, gathering a single expression and a unary operator.
     Unary(UnaryOp, Box<Expr>),
+    /// A C-style cast expression, e.g. `(float3)x` (HLSL).
+    Cast(TypeSpecifier, Box<Expr>),
     /// A binary expression, gathering two expressions and a binary operator.
     Binary(BinaryOp, Box<Expr>, Box<Expr>),
     /// A ternary conditional expression, gathering three expressions.
@@ -1441,6 +1825,15 @@ impl ExprData {
             _ => None,
         }
     }
+
+    /// Value of a string literal, or of adjacent string literals once concatenated
+    pub fn as_string(&self) -> Option<String> {
+        match self {
+            Self::StringConst(value) => Some(value.clone()),
+            Self::StringConcat(pieces) => pieces.iter().map(|piece| piece.as_string()).collect(),
+            _ => None,
+        }
+    }
 }
 
 impl From<i32> for ExprData {
@@ -1479,6 +1872,12 @@ impl From<String> for ExprData {
     }
 }
 
+impl From<char> for ExprData {
+    fn from(x: char) -> ExprData {
+        Self::CharConst(x)
+    }
+}
+
 /// All unary operators that exist in GLSL.
 #[derive(Clone, Debug, PartialEq, Eq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1578,6 +1977,67 @@ impl_node_content! {
     pub type BinaryOp = Node<BinaryOpData>;
 }
 
+/// Operator overloaded by a member function (HLSL 2021).
+#[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum OverloadedOperatorData {
+    /// Unary operator, e.g. `Vec operator-()`
+    Unary(UnaryOp),
+    /// Binary operator, e.g. `Vec operator+(Vec rhs)`
+    Binary(BinaryOp),
+    /// Function call operator, `operator()`
+    #[lang_util(display(extra = "()"))]
+    Call,
+    /// Array subscript operator, `operator[]`
+    #[lang_util(display(extra = "[]"))]
+    Subscript,
+}
+
+impl_node_content! {
+    /// Type alias for `Node<OverloadedOperatorData>`.
+    pub type OverloadedOperator = Node<OverloadedOperatorData>;
+}
+
+impl OverloadedOperatorData {
+    /// Source spelling of the operator, as it appears after the `operator` keyword
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Unary(op) => match **op {
+                UnaryOpData::Inc => "++",
+                UnaryOpData::Dec => "--",
+                UnaryOpData::Add => "+",
+                UnaryOpData::Minus => "-",
+                UnaryOpData::Not => "!",
+                UnaryOpData::Complement => "~",
+            },
+            Self::Binary(op) => match **op {
+                BinaryOpData::Or => "||",
+                BinaryOpData::Xor => "^^",
+                BinaryOpData::And => "&&",
+                BinaryOpData::BitOr => "|",
+                BinaryOpData::BitXor => "^",
+                BinaryOpData::BitAnd => "&",
+                BinaryOpData::Equal => "==",
+                BinaryOpData::NonEqual => "!=",
+                BinaryOpData::Lt => "<",
+                BinaryOpData::Gt => ">",
+                BinaryOpData::Lte => "<=",
+                BinaryOpData::Gte => ">=",
+                BinaryOpData::LShift => "<<",
+                BinaryOpData::RShift => ">>",
+                BinaryOpData::Add => "+",
+                BinaryOpData::Sub => "-",
+                BinaryOpData::Mult => "*",
+                BinaryOpData::Div => "/",
+                BinaryOpData::Mod => "%",
+            },
+            Self::Call => "()",
+            Self::Subscript => "[]",
+        }
+    }
+}
+
 /// All possible operators for assigning expressions.
 #[derive(Clone, Debug, PartialEq, Eq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1642,6 +2102,8 @@ pub enum ExternalDeclarationData {
     FunctionDefinition(FunctionDefinition),
     /// Declaration
     Declaration(Declaration),
+    /// Template declaration (HLSL 2021)
+    Template(TemplateDeclaration),
 }
 
 impl_node_content! {
@@ -1649,27 +2111,67 @@ impl_node_content! {
     pub type ExternalDeclaration = Node<ExternalDeclarationData>;
 }
 
+/// Template declaration (e.g., `template<typename T> T twice(T x) { return x * 2; }`)
+#[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct TemplateDeclarationData {
+    /// Type parameters
+    pub parameters: Vec<TypeName>,
+    /// Templated function definition or declaration
+    pub declaration: Box<ExternalDeclaration>,
+}
+
+impl_node_content! {
+    /// Type alias for `Node<TemplateDeclarationData>`.
+    pub type TemplateDeclaration = Node<TemplateDeclarationData>;
+}
+
 /// HLSL attribute specification.
 #[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "rserde"))]
 pub struct AttributeSpecData {
+    /// Attribute namespace (e.g., `vk` in `[[vk::binding(0)]]`)
+    pub namespace: Option<Identifier>,
     /// Attribute name
     pub name: Identifier,
     /// Attribute parameters (optional)
     pub params: Option<Vec<Expr>>,
 }
 
+impl AttributeSpecData {
+    /// Returns true if this attribute has the given namespace and name
+    pub fn is(&self, namespace: Option<&str>, name: &str) -> bool {
+        self.namespace.as_ref().map(|ns| ns.as_str()) == namespace && self.name.as_str() == name
+    }
+}
+
 impl_node_content! {
     /// Type alias for `Node<AttributeSpecData>`.
     pub type AttributeSpec = Node<AttributeSpecData>;
 }
 
+/// Syntax used to write an attribute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum AttributeSyntax {
+    /// HLSL attribute, e.g. `[numthreads(8, 8, 1)]`
+    #[lang_util(display(extra = "[]"))]
+    Bracket,
+    /// C++11-style attribute, e.g. `[[vk::binding(0, 1)]]`
+    #[lang_util(display(extra = "[[]]"))]
+    DoubleBracket,
+}
+
 /// HLSL attribute (e.g., [domain("isoline")]).
 #[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(crate = "rserde"))]
 pub struct AttributeData {
+    /// Syntax this attribute was written with
+    pub syntax: AttributeSyntax,
     /// The attribute specification
     pub spec: AttributeSpec,
 }
@@ -1743,6 +2245,9 @@ pub enum StatementData {
     Jump(JumpStatement),
     /// Statement block
     Compound(CompoundStatement),
+    /// Selection, `switch` or iteration statement preceded by attributes, e.g. `[unroll(4)] for
+    /// (...)`
+    Attributed(Vec<Attribute>, Box<Statement>),
 }
 
 impl_node_content! {
@@ -1767,6 +2272,7 @@ impl StatementData {
             DeclarationData::InitDeclaratorList(
                 InitDeclaratorListData {
                     head: SingleDeclarationData {
+                        attributes: None,
                         ty: ty.into().into(),
                         name: Some(name.into().into()),
                         array_specifier: array_specifier.into(),
@@ -2318,9 +2824,27 @@ pub struct ResourceBindingData {
     pub register_type: Identifier,
     /// Register index
     pub index: Expr,
+    /// Register space (e.g., `1` for `space1`), if specified
+    pub space: Option<Expr>,
 }
 
 impl_node_content! {
     /// Type alias for `Node<ResourceBindingData>`.
     pub type ResourceBinding = Node<ResourceBindingData>;
 }
+
+/// A packing offset specification for a cbuffer member (e.g., packoffset(c4.y))
+#[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct PackOffsetData {
+    /// Constant register index
+    pub index: Expr,
+    /// Starting component within the register (e.g., 'y')
+    pub component: Option<Identifier>,
+}
+
+impl_node_content! {
+    /// Type alias for `Node<PackOffsetData>`.
+    pub type PackOffset = Node<PackOffsetData>;
+}
@@ -145,6 +145,20 @@ forward_display!(u16 => "ShortConst");
 forward_display!(u32 => "UIntConst");
 forward_display!(bool => "BoolConst");
 
+impl NodeContentDisplay for char {
+    fn name() -> Option<&'static str> {
+        Some("CharConst")
+    }
+
+    fn display_extra(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, " `{}`", self.escape_default())
+    }
+
+    fn display_children(&self, _level: usize, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
+
 /// Trait for displaying a syntax node
 pub trait NodeDisplay: Sized {
     /// Name of the syntax node's type
@@ -0,0 +1,538 @@
+//! Typed view of HLSL function and statement attributes
+//!
+//! The parser stores attributes as a name and a list of argument expressions. This module decodes
+//! the attributes which matter for entry points, such as `[numthreads(8, 8, 1)]`, into
+//! [FunctionAttribute] values, and the loop and branch hints which may precede a statement, such as
+//! `[unroll(4)]`, into [StatementAttribute] values. Wrong arities and non-constant arguments are
+//! reported along with their spans. Arguments are
+//! [constant expressions](crate::semantic::consteval), which may refer to `static const` globals
+//! when decoded with a symbol table.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, attributes::FunctionAttribute, parse::Parsable};
+//!
+//! let def = ast::FunctionDefinition::parse("[numthreads(8, 8, 1)] void main() {}").unwrap();
+//! let attr = &def.attributes.as_ref().unwrap()[0];
+//!
+//! assert_eq!(
+//!     FunctionAttribute::decode(attr),
+//!     Ok(FunctionAttribute::NumThreads { x: 8, y: 8, z: 1 })
+//! );
+//! ```
+
+use crate::{
+    ast::{self, NodeSpan},
+    resolver::SymbolTable,
+    semantic::consteval,
+};
+
+/// A decoded function attribute
+#[derive(Clone, Debug, PartialEq)]
+pub enum FunctionAttribute {
+    /// `[numthreads(x, y, z)]`: thread group size of a compute, mesh or amplification shader
+    NumThreads {
+        /// Number of threads in the X dimension
+        x: u32,
+        /// Number of threads in the Y dimension
+        y: u32,
+        /// Number of threads in the Z dimension
+        z: u32,
+    },
+    /// `[earlydepthstencil]`: force depth-stencil testing before the pixel shader runs
+    EarlyDepthStencil,
+    /// `[maxvertexcount(n)]`: maximum number of vertices emitted by a geometry shader
+    MaxVertexCount(u32),
+    /// `[instance(n)]`: number of geometry shader instances
+    Instance(u32),
+    /// `[WaveSize(min, max, preferred)]`: wave sizes a compute shader is compatible with
+    WaveSize {
+        /// Minimum (or only) supported wave size
+        min: u32,
+        /// Maximum supported wave size (shader model 6.8)
+        max: Option<u32>,
+        /// Preferred wave size (shader model 6.8)
+        preferred: Option<u32>,
+    },
+    /// `[RootSignature("...")]`: root signature definition string
+    RootSignature(String),
+    /// `[domain("...")]`: patch type of a hull or domain shader
+    Domain(String),
+    /// `[partitioning("...")]`: tessellation partitioning scheme
+    Partitioning(String),
+    /// `[outputtopology("...")]`: output primitive topology of a hull or mesh shader
+    OutputTopology(String),
+    /// `[outputcontrolpoints(n)]`: number of control points output by a hull shader
+    OutputControlPoints(u32),
+    /// `[patchconstantfunc("...")]`: name of the hull shader patch constant function
+    PatchConstantFunc(String),
+    /// `[maxtessfactor(f)]`: maximum tessellation factor
+    MaxTessFactor(f32),
+    /// `[shader("...")]`: stage of a shader library entry point
+    Shader(String),
+    /// Any other attribute, including namespaced ones such as `[[vk::binding(0)]]`
+    Unknown(ast::AttributeSpec),
+}
+
+/// Error raised when decoding an attribute with invalid arguments
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("{kind}")]
+pub struct AttributeError {
+    /// Kind of error
+    pub kind: AttributeErrorKind,
+    /// Span of the attribute or argument that caused the error
+    pub span: Option<NodeSpan>,
+}
+
+/// Kind of attribute decoding error
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum AttributeErrorKind {
+    /// The attribute was given the wrong number of arguments
+    #[error("attribute `{name}` expects {expected} argument(s), found {found}")]
+    WrongArity {
+        /// Attribute name
+        name: &'static str,
+        /// Expected number of arguments, e.g. `"1 to 3"`
+        expected: &'static str,
+        /// Number of arguments found
+        found: usize,
+    },
+    /// An argument is not a constant of the expected type
+    #[error("argument {index} of attribute `{name}` must be {expected}")]
+    InvalidArgument {
+        /// Attribute name
+        name: &'static str,
+        /// Zero-based index of the argument
+        index: usize,
+        /// Description of the expected argument, e.g. `"a constant unsigned integer"`
+        expected: &'static str,
+    },
+}
+
+impl FunctionAttribute {
+    /// Decode an attribute
+    pub fn decode(attr: &ast::Attribute) -> Result<Self, AttributeError> {
+        Self::decode_spec(&attr.spec)
+    }
+
+    /// Decode an attribute whose arguments may refer to constants declared in `symbols`
+    pub fn decode_with_symbols(
+        attr: &ast::Attribute,
+        symbols: &SymbolTable<'_>,
+    ) -> Result<Self, AttributeError> {
+        Self::decode_spec_with_symbols(&attr.spec, symbols)
+    }
+
+    /// Decode an attribute specification
+    pub fn decode_spec(spec: &ast::AttributeSpec) -> Result<Self, AttributeError> {
+        Self::decode_spec_with_symbols(spec, &SymbolTable::default())
+    }
+
+    /// Decode an attribute specification whose arguments may refer to constants declared in
+    /// `symbols`
+    pub fn decode_spec_with_symbols(
+        spec: &ast::AttributeSpec,
+        symbols: &SymbolTable<'_>,
+    ) -> Result<Self, AttributeError> {
+        if spec.namespace.is_some() {
+            return Ok(Self::Unknown(spec.clone()));
+        }
+
+        let args = |name| Args {
+            spec,
+            name,
+            symbols,
+        };
+
+        // HLSL attribute names are case-insensitive
+        let name = spec.name.as_str().to_ascii_lowercase();
+        Ok(match name.as_str() {
+            "numthreads" => {
+                let args = args("numthreads").arity(3, 3, "3")?;
+                Self::NumThreads {
+                    x: args.uint(0)?,
+                    y: args.uint(1)?,
+                    z: args.uint(2)?,
+                }
+            }
+            "earlydepthstencil" => {
+                args("earlydepthstencil").arity(0, 0, "0")?;
+                Self::EarlyDepthStencil
+            }
+            "maxvertexcount" => Self::MaxVertexCount(args("maxvertexcount").single()?.uint(0)?),
+            "instance" => Self::Instance(args("instance").single()?.uint(0)?),
+            "wavesize" => {
+                let args = args("WaveSize").arity(1, 3, "1 to 3")?;
+                Self::WaveSize {
+                    min: args.uint(0)?,
+                    max: args.opt_uint(1)?,
+                    preferred: args.opt_uint(2)?,
+                }
+            }
+            "rootsignature" => Self::RootSignature(args("RootSignature").single()?.string(0)?),
+            "domain" => Self::Domain(args("domain").single()?.string(0)?),
+            "partitioning" => Self::Partitioning(args("partitioning").single()?.string(0)?),
+            "outputtopology" => Self::OutputTopology(args("outputtopology").single()?.string(0)?),
+            "outputcontrolpoints" => {
+                Self::OutputControlPoints(args("outputcontrolpoints").single()?.uint(0)?)
+            }
+            "patchconstantfunc" => {
+                Self::PatchConstantFunc(args("patchconstantfunc").single()?.string(0)?)
+            }
+            "maxtessfactor" => Self::MaxTessFactor(args("maxtessfactor").single()?.float(0)?),
+            "shader" => Self::Shader(args("shader").single()?.string(0)?),
+            _ => Self::Unknown(spec.clone()),
+        })
+    }
+
+    /// Decode all the attributes of a function definition, in order
+    pub fn decode_all(
+        def: &ast::FunctionDefinition,
+    ) -> impl Iterator<Item = Result<Self, AttributeError>> + '_ {
+        def.attributes.iter().flatten().map(Self::decode)
+    }
+
+    /// Decode all the attributes of a function definition, resolving constants through `symbols`
+    pub fn decode_all_with_symbols<'d>(
+        def: &'d ast::FunctionDefinition,
+        symbols: &'d SymbolTable<'_>,
+    ) -> impl Iterator<Item = Result<Self, AttributeError>> + 'd {
+        def.attributes
+            .iter()
+            .flatten()
+            .map(|attr| Self::decode_with_symbols(attr, symbols))
+    }
+}
+
+/// A decoded statement attribute, which tells the compiler how to emit a loop, `if` or `switch`
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatementAttribute {
+    /// `[unroll]` or `[unroll(n)]`: unroll a loop, at most `n` times if given
+    Unroll(Option<u32>),
+    /// `[loop]`: do not unroll a loop
+    Loop,
+    /// `[fastopt]`: spend less time optimizing a loop
+    FastOpt,
+    /// `[allow_uav_condition]`: allow the condition of a loop to depend on a UAV read
+    AllowUavCondition,
+    /// `[branch]`: only evaluate the taken side of an `if`
+    Branch,
+    /// `[flatten]`: evaluate both sides of an `if`
+    Flatten,
+    /// `[forcecase]`: compile a `switch` as a jump table
+    ForceCase,
+    /// `[call]`: compile the cases of a `switch` as subroutines
+    Call,
+    /// Any other attribute, including namespaced ones
+    Unknown(ast::AttributeSpec),
+}
+
+impl StatementAttribute {
+    /// Decode an attribute
+    pub fn decode(attr: &ast::Attribute) -> Result<Self, AttributeError> {
+        Self::decode_spec(&attr.spec)
+    }
+
+    /// Decode an attribute whose arguments may refer to constants declared in `symbols`
+    pub fn decode_with_symbols(
+        attr: &ast::Attribute,
+        symbols: &SymbolTable<'_>,
+    ) -> Result<Self, AttributeError> {
+        Self::decode_spec_with_symbols(&attr.spec, symbols)
+    }
+
+    /// Decode an attribute specification
+    pub fn decode_spec(spec: &ast::AttributeSpec) -> Result<Self, AttributeError> {
+        Self::decode_spec_with_symbols(spec, &SymbolTable::default())
+    }
+
+    /// Decode an attribute specification whose arguments may refer to constants declared in
+    /// `symbols`
+    pub fn decode_spec_with_symbols(
+        spec: &ast::AttributeSpec,
+        symbols: &SymbolTable<'_>,
+    ) -> Result<Self, AttributeError> {
+        if spec.namespace.is_some() {
+            return Ok(Self::Unknown(spec.clone()));
+        }
+
+        let args = |name| Args {
+            spec,
+            name,
+            symbols,
+        };
+        let flag = |name, value| args(name).arity(0, 0, "0").map(|_| value);
+
+        // HLSL attribute names are case-insensitive
+        let name = spec.name.as_str().to_ascii_lowercase();
+        match name.as_str() {
+            "unroll" => Ok(Self::Unroll(
+                args("unroll").arity(0, 1, "0 or 1")?.opt_uint(0)?,
+            )),
+            "loop" => flag("loop", Self::Loop),
+            "fastopt" => flag("fastopt", Self::FastOpt),
+            "allow_uav_condition" => flag("allow_uav_condition", Self::AllowUavCondition),
+            "branch" => flag("branch", Self::Branch),
+            "flatten" => flag("flatten", Self::Flatten),
+            "forcecase" => flag("forcecase", Self::ForceCase),
+            "call" => flag("call", Self::Call),
+            _ => Ok(Self::Unknown(spec.clone())),
+        }
+    }
+}
+
+/// Arguments of a known attribute being decoded
+struct Args<'a, 's> {
+    spec: &'a ast::AttributeSpec,
+    name: &'static str,
+    symbols: &'a SymbolTable<'s>,
+}
+
+impl Args<'_, '_> {
+    fn params(&self) -> &[ast::Expr] {
+        self.spec.params.as_deref().unwrap_or(&[])
+    }
+
+    fn arity(self, min: usize, max: usize, expected: &'static str) -> Result<Self, AttributeError> {
+        let found = self.params().len();
+        if found < min || found > max {
+            return Err(AttributeError {
+                kind: AttributeErrorKind::WrongArity {
+                    name: self.name,
+                    expected,
+                    found,
+                },
+                span: self.spec.span,
+            });
+        }
+
+        Ok(self)
+    }
+
+    fn single(self) -> Result<Self, AttributeError> {
+        self.arity(1, 1, "1")
+    }
+
+    fn invalid(&self, index: usize, expected: &'static str) -> AttributeError {
+        AttributeError {
+            kind: AttributeErrorKind::InvalidArgument {
+                name: self.name,
+                index,
+                expected,
+            },
+            span: self.params()[index].span,
+        }
+    }
+
+    fn eval(&self, index: usize) -> Option<consteval::Constant> {
+        consteval::eval(&self.params()[index], self.symbols)
+    }
+
+    fn uint(&self, index: usize) -> Result<u32, AttributeError> {
+        self.eval(index)
+            .and_then(|value| value.as_int())
+            .and_then(|value| u32::try_from(value).ok())
+            .ok_or_else(|| self.invalid(index, "a constant unsigned integer"))
+    }
+
+    fn opt_uint(&self, index: usize) -> Result<Option<u32>, AttributeError> {
+        if index < self.params().len() {
+            self.uint(index).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn float(&self, index: usize) -> Result<f32, AttributeError> {
+        self.eval(index)
+            .and_then(|value| value.as_float())
+            .map(|value| value as f32)
+            .ok_or_else(|| self.invalid(index, "a constant number"))
+    }
+
+    fn string(&self, index: usize) -> Result<String, AttributeError> {
+        self.params()[index]
+            .as_string()
+            .ok_or_else(|| self.invalid(index, "a string literal"))
+    }
+}
+
+/// Fold an integer constant expression which does not refer to any symbol
+pub(crate) fn eval_int(expr: &ast::Expr) -> Option<i64> {
+    consteval::eval(expr, &SymbolTable::default())?.as_int()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Parsable;
+
+    fn decode(src: &str) -> Vec<Result<FunctionAttribute, AttributeError>> {
+        let def = ast::FunctionDefinition::parse(src).unwrap();
+        FunctionAttribute::decode_all(&def).collect()
+    }
+
+    #[test]
+    fn decode_entry_point_attributes() {
+        assert_eq!(
+            decode(
+                "[numthreads(8, 4 * 2, 1)] [WaveSize(32)] [RootSignature(\"RootFlags(0)\")]
+                 void main() {}"
+            ),
+            vec![
+                Ok(FunctionAttribute::NumThreads { x: 8, y: 8, z: 1 }),
+                Ok(FunctionAttribute::WaveSize {
+                    min: 32,
+                    max: None,
+                    preferred: None,
+                }),
+                Ok(FunctionAttribute::RootSignature("RootFlags(0)".to_owned())),
+            ]
+        );
+
+        assert_eq!(
+            decode("[EarlyDepthStencil] [maxvertexcount(3)] [maxtessfactor(15.0)] void main() {}"),
+            vec![
+                Ok(FunctionAttribute::EarlyDepthStencil),
+                Ok(FunctionAttribute::MaxVertexCount(3)),
+                Ok(FunctionAttribute::MaxTessFactor(15.0)),
+            ]
+        );
+
+        assert!(matches!(
+            &decode("[[vk::location(0)]] [fastopt] void main() {}")[..],
+            [
+                Ok(FunctionAttribute::Unknown(_)),
+                Ok(FunctionAttribute::Unknown(_))
+            ]
+        ));
+    }
+
+    #[test]
+    fn decode_statement_attributes() {
+        let tu = ast::TranslationUnit::parse(
+            "static const uint N = 4;
+             void main(uint i : SV_GroupIndex) {
+                 [unroll(N * 2)] [FastOpt] for (;;) {}
+                 [unroll] [loop] while (true) {}
+                 [branch] if (i) {} [forcecase] switch (i) {}
+                 [unroll(i)] [flatten(1)] do {} while (false);
+             }",
+        )
+        .unwrap();
+        let symbols = crate::resolver::resolve(&tu);
+        let ast::ExternalDeclarationData::FunctionDefinition(def) = &*tu.0[1] else {
+            panic!();
+        };
+
+        let decoded: Vec<_> = def
+            .statement
+            .statement_list
+            .iter()
+            .flat_map(|statement| match &**statement {
+                ast::StatementData::Attributed(attributes, _) => attributes.as_slice(),
+                _ => panic!("expected an attributed statement"),
+            })
+            .map(|attr| StatementAttribute::decode_with_symbols(attr, &symbols))
+            .collect();
+
+        assert_eq!(
+            &decoded[..6],
+            &[
+                Ok(StatementAttribute::Unroll(Some(8))),
+                Ok(StatementAttribute::FastOpt),
+                Ok(StatementAttribute::Unroll(None)),
+                Ok(StatementAttribute::Loop),
+                Ok(StatementAttribute::Branch),
+                Ok(StatementAttribute::ForceCase),
+            ]
+        );
+        assert_eq!(
+            decoded[6..]
+                .iter()
+                .map(|result| result.clone().unwrap_err().kind)
+                .collect::<Vec<_>>(),
+            vec![
+                AttributeErrorKind::InvalidArgument {
+                    name: "unroll",
+                    index: 0,
+                    expected: "a constant unsigned integer",
+                },
+                AttributeErrorKind::WrongArity {
+                    name: "flatten",
+                    expected: "0",
+                    found: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_with_constants() {
+        let tu = ast::TranslationUnit::parse(
+            "static const uint GROUP = 8; [numthreads(GROUP, max(GROUP / 4, 1), 1)] void main() {}",
+        )
+        .unwrap();
+        let symbols = crate::resolver::resolve(&tu);
+        let ast::ExternalDeclarationData::FunctionDefinition(def) = &*tu.0[1] else {
+            panic!();
+        };
+
+        assert_eq!(
+            FunctionAttribute::decode_all_with_symbols(def, &symbols).collect::<Vec<_>>(),
+            vec![Ok(FunctionAttribute::NumThreads { x: 8, y: 2, z: 1 })]
+        );
+
+        // Without symbols, names are not constant
+        assert!(FunctionAttribute::decode_all(def).all(|attr| attr.is_err()));
+    }
+
+    #[test]
+    fn decode_attribute_errors() {
+        let errors: Vec<_> = decode(
+            "[numthreads(8, 8)] [maxvertexcount(n)] [domain(3)] [WaveSize(-32)] void main() {}",
+        )
+        .into_iter()
+        .map(|result| result.unwrap_err())
+        .collect();
+
+        assert_eq!(
+            errors.iter().map(|e| e.kind.clone()).collect::<Vec<_>>(),
+            vec![
+                AttributeErrorKind::WrongArity {
+                    name: "numthreads",
+                    expected: "3",
+                    found: 2,
+                },
+                AttributeErrorKind::InvalidArgument {
+                    name: "maxvertexcount",
+                    index: 0,
+                    expected: "a constant unsigned integer",
+                },
+                AttributeErrorKind::InvalidArgument {
+                    name: "domain",
+                    index: 0,
+                    expected: "a string literal",
+                },
+                AttributeErrorKind::InvalidArgument {
+                    name: "WaveSize",
+                    index: 0,
+                    expected: "a constant unsigned integer",
+                },
+            ]
+        );
+
+        // Arity errors point at the attribute, argument errors at the argument
+        let span = |e: &AttributeError| {
+            let range = e.span.unwrap().range();
+            (u32::from(range.start()), u32::from(range.end()))
+        };
+        assert_eq!(span(&errors[0]), (1, 17));
+        assert_eq!(span(&errors[1]), (35, 36));
+        assert_eq!(
+            errors[0].to_string(),
+            "attribute `numthreads` expects 3 argument(s), found 2"
+        );
+    }
+}
@@ -0,0 +1,395 @@
+//! Dead code elimination
+//!
+//! [eliminate] prunes a translation unit down to what an entry point needs: the functions it
+//! calls, the globals, resources and constant buffers they read, and the structs their types
+//! refer to, transitively. Constant buffers and structs are kept whole, since removing members
+//! would change their layout, and a struct keeps all its member functions. Calls keep the overload
+//! selected by the [type checker](crate::semantic::check), or every overload of the called name
+//! when it could not select one, and the patch constant function of a hull shader is kept along
+//! with it. Preprocessor directives, such as `#pragma pack_matrix`, are kept as is.
+//!
+//! Declarations which declare several names, such as `float a, b;`, are kept whole if any of the
+//! names is used.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, dead_code, parse::Parsable, resolver, semantic::check};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     r#"
+//!     float square(float x) { return x * x; }
+//!     float cube(float x) { return x * x * x; }
+//!     float4 main(float x : X) : SV_Target { return square(x); }
+//!     "#,
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//! let types = check::check(&tu, &symbols);
+//! let pruned = dead_code::eliminate(&tu, &symbols, &types, "main").unwrap();
+//!
+//! assert_eq!(pruned.0.len(), 2);
+//! ```
+
+use std::collections::HashSet;
+
+use crate::{
+    ast,
+    attributes::FunctionAttribute,
+    entry_point::{definitions, EntryPointError},
+    resolver::{Declarator, Resolution, ScopeId, SymbolId, SymbolTable},
+    semantic::{check::TypeTable, overload::Callee},
+    visitor::{Host, Visit, Visitor},
+};
+
+/// Translation unit reduced to the declarations reachable from the entry point `entry`
+pub fn eliminate(
+    tu: &ast::TranslationUnit,
+    symbols: &SymbolTable<'_>,
+    types: &TypeTable<'_>,
+    entry: &str,
+) -> Result<ast::TranslationUnit, EntryPointError> {
+    let id = definitions(tu)
+        .find(|def| def.prototype.name.as_str() == entry)
+        .and_then(|def| symbols.declared_by(&def.prototype.name))
+        .ok_or_else(|| EntryPointError::NotFound(entry.into()))?;
+
+    let reached = reachable(symbols, types, id);
+    let decls =
+        tu.0.iter()
+            .filter(|decl| is_kept(decl, symbols, &reached))
+            .cloned()
+            .collect();
+
+    Ok(ast::TranslationUnit(decls))
+}
+
+/// Whether an external declaration declares any of the `reached` symbols
+fn is_kept(
+    decl: &ast::ExternalDeclaration,
+    symbols: &SymbolTable<'_>,
+    reached: &HashSet<SymbolId>,
+) -> bool {
+    let is_reached = |ident: &ast::Identifier| {
+        symbols
+            .declared_by(ident)
+            .is_some_and(|id| reached.contains(&id))
+    };
+
+    match &**decl {
+        ast::ExternalDeclarationData::Preprocessor(_) => true,
+        ast::ExternalDeclarationData::FunctionDefinition(def) => is_reached(&def.prototype.name),
+        ast::ExternalDeclarationData::Declaration(decl) => match &**decl {
+            ast::DeclarationData::FunctionPrototype(proto) => is_reached(&proto.name),
+            ast::DeclarationData::InitDeclaratorList(list) => {
+                let declares_struct = match &*list.head.ty.ty.ty {
+                    ast::TypeSpecifierNonArrayData::Struct(spec) => symbols
+                        .struct_specifier(spec)
+                        .is_some_and(|id| reached.contains(&id)),
+                    _ => false,
+                };
+
+                declares_struct
+                    || list.head.name.as_ref().is_some_and(is_reached)
+                    || list.tail.iter().any(|decl| is_reached(&decl.ident.ident))
+            }
+            ast::DeclarationData::CBuffer(cbuffer) => is_reached(&cbuffer.name),
+            ast::DeclarationData::SamplerState(sampler) => is_reached(&sampler.name),
+            _ => true,
+        },
+        ast::ExternalDeclarationData::Template(template) => {
+            is_kept(&template.declaration, symbols, reached)
+        }
+    }
+}
+
+/// Symbols reachable from the given one, including itself
+pub fn reachable(
+    symbols: &SymbolTable<'_>,
+    types: &TypeTable<'_>,
+    id: SymbolId,
+) -> HashSet<SymbolId> {
+    let mut reached = HashSet::new();
+    let mut queue = vec![id];
+
+    while let Some(id) = queue.pop() {
+        if !reached.insert(id) {
+            continue;
+        }
+
+        let symbol = symbols.symbol(id);
+        queue.extend(symbol.parent);
+
+        let mut refs = References {
+            symbols,
+            types,
+            scope: symbol.scope,
+            found: &mut queue,
+        };
+
+        match symbol.declarator {
+            Declarator::Variable(decl) => decl.visit(&mut refs),
+            Declarator::Declarator { head, declarator } => {
+                head.ty.visit(&mut refs);
+                declarator.visit(&mut refs);
+            }
+            Declarator::Condition(cond) => cond.visit(&mut refs),
+            Declarator::Parameter(param) => param.visit(&mut refs),
+            Declarator::Field { field, .. } => field.visit(&mut refs),
+            Declarator::CBuffer(cbuffer) => cbuffer.visit(&mut refs),
+            Declarator::SamplerState(sampler) => sampler.visit(&mut refs),
+            Declarator::Struct(spec) => {
+                spec.visit(&mut refs);
+                queue.extend(symbols.members(id));
+            }
+            Declarator::Function {
+                prototype,
+                definition,
+            } => {
+                prototype.visit(&mut refs);
+                if let Some(def) = definition {
+                    def.visit(&mut refs);
+                }
+            }
+            Declarator::Method { method, definition } => {
+                method.visit(&mut refs);
+                if let Some(def) = definition {
+                    def.visit(&mut refs);
+                }
+            }
+        }
+    }
+
+    reached
+}
+
+/// Collects the symbols referred to by a node
+struct References<'s, 'a, 'q> {
+    symbols: &'s SymbolTable<'a>,
+    types: &'s TypeTable<'a>,
+    scope: ScopeId,
+    found: &'q mut Vec<SymbolId>,
+}
+
+impl Visitor for References<'_, '_, '_> {
+    fn visit_identifier(&mut self, ident: &ast::Identifier) -> Visit {
+        match self.symbols.resolution(ident) {
+            // Overloads are narrowed down by the call they are the callee of
+            Some(Resolution::Overloads(_)) | None => {}
+            Some(resolution) => self.found.extend(resolution.symbols()),
+        }
+
+        Visit::Children
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) -> Visit {
+        let ast::ExprData::FunCall(fun, _) = &**expr else {
+            return Visit::Children;
+        };
+
+        match self.types.call(expr).map(|call| &call.callee) {
+            Some(Callee::Function(id)) => self.found.push(*id),
+            Some(Callee::Intrinsic(..)) => {}
+            None => {
+                if let ast::FunIdentifierData::Expr(callee) = &**fun {
+                    if let ast::ExprData::Variable(name) = &***callee {
+                        if let Some(resolution) = self.symbols.resolution(name) {
+                            self.found.extend(resolution.symbols());
+                        }
+                    }
+                }
+            }
+        }
+
+        Visit::Children
+    }
+
+    fn visit_type_name(&mut self, name: &ast::TypeName) -> Visit {
+        self.found.extend(self.symbols.type_name(name));
+        Visit::Children
+    }
+
+    fn visit_struct_specifier(&mut self, spec: &ast::StructSpecifier) -> Visit {
+        self.found.extend(self.symbols.struct_specifier(spec));
+        Visit::Children
+    }
+
+    fn visit_attribute(&mut self, attr: &ast::Attribute) -> Visit {
+        // Patch constant functions are named by a string
+        if let Ok(FunctionAttribute::PatchConstantFunc(name)) =
+            FunctionAttribute::decode_with_symbols(attr, self.symbols)
+        {
+            if let Some(resolution) = self.symbols.lookup(self.scope, &name) {
+                self.found.extend(resolution.symbols());
+            }
+        }
+
+        Visit::Children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::DefaultParse, resolver, semantic::check};
+
+    /// Names declared by the top-level declarations of the pruned translation unit
+    fn pruned(src: &str, entry: &str) -> Vec<String> {
+        let (mut tu, _, iter) =
+            <ast::TranslationUnit as DefaultParse>::parse_with_options(src, &Default::default())
+                .unwrap();
+        iter.into_directives().inject(&mut tu);
+
+        let symbols = resolver::resolve(&tu);
+        let types = check::check(&tu, &symbols);
+        let pruned = eliminate(&tu, &symbols, &types, entry).unwrap();
+        pruned
+            .0
+            .iter()
+            .map(|decl| match &**decl {
+                ast::ExternalDeclarationData::Preprocessor(_) => "#".to_owned(),
+                ast::ExternalDeclarationData::FunctionDefinition(def) => {
+                    format!("{}()", def.prototype.name.as_str())
+                }
+                ast::ExternalDeclarationData::Declaration(decl) => match &**decl {
+                    ast::DeclarationData::FunctionPrototype(proto) => {
+                        format!("{};", proto.name.as_str())
+                    }
+                    ast::DeclarationData::InitDeclaratorList(list) => match &list.head.name {
+                        Some(name) => name.as_str().to_owned(),
+                        None => match &*list.head.ty.ty.ty {
+                            ast::TypeSpecifierNonArrayData::Struct(spec) => format!(
+                                "struct {}",
+                                spec.name.as_ref().map_or("", |name| name.as_str())
+                            ),
+                            _ => String::new(),
+                        },
+                    },
+                    ast::DeclarationData::CBuffer(cbuffer) => {
+                        format!("cbuffer {}", cbuffer.name.as_str())
+                    }
+                    ast::DeclarationData::SamplerState(sampler) => sampler.name.as_str().to_owned(),
+                    _ => String::new(),
+                },
+                ast::ExternalDeclarationData::Template(_) => "template".to_owned(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn eliminate_unreachable_declarations() {
+        let src = r#"
+            #pragma pack_matrix(row_major)
+            struct Light { float3 dir; float3 color; };
+            struct Unused { float x; };
+            struct Material { float4 albedo; };
+            cbuffer Frame { float4x4 viewProj; Light light; };
+            cbuffer Other { float4 unused; };
+            static const int COUNT = 4;
+            static const float PI = 3.14159;
+            Texture2D tex;
+            Texture2D other;
+            SamplerState samp : register(s0);
+            float3 shade(Light l, float3 n);
+            float helper(float x) { return x * COUNT; }
+            float unusedHelper() { return PI; }
+            float3 shade(Light l, float3 n) { return l.color * saturate(dot(n, -l.dir)); }
+            float4 other_main() : SV_Target { return other.Sample(samp, 0); }
+            float4 main(float3 n : NORMAL, float2 uv : TEXCOORD0) : SV_Target {
+                Material m;
+                float w[COUNT];
+                m.albedo = tex.Sample(samp, uv);
+                return m.albedo * float4(shade(light, n), helper(1.0));
+            }
+        "#;
+
+        assert_eq!(
+            pruned(src, "main"),
+            [
+                "#",
+                "struct Light",
+                "struct Material",
+                "cbuffer Frame",
+                "COUNT",
+                "tex",
+                "samp",
+                "shade;",
+                "helper()",
+                "shade()",
+                "main()",
+            ]
+        );
+
+        assert_eq!(
+            pruned(src, "other_main"),
+            ["#", "other", "samp", "other_main()"]
+        );
+    }
+
+    #[test]
+    fn keep_patch_constant_functions_and_methods() {
+        let src = r#"
+            struct Counter {
+                int value;
+                int Next();
+            };
+            int Counter::Next() { return value + 1; }
+            struct Patch { float edges[3] : SV_TessFactor; float inside : SV_InsideTessFactor; };
+            Patch patch() { Patch p; return p; }
+            [domain("tri")] [partitioning("integer")] [outputtopology("triangle_cw")]
+            [outputcontrolpoints(3)] [patchconstantfunc("patch")]
+            float4 hs() : SV_Position { Counter c; c.Next(); return 0; }
+            void unrelated() {}
+        "#;
+
+        assert_eq!(
+            pruned(src, "hs"),
+            [
+                "struct Counter",
+                "Next()",
+                "struct Patch",
+                "patch()",
+                "hs()"
+            ]
+        );
+    }
+
+    #[test]
+    fn keep_called_overloads() {
+        let src = r#"
+            float helper(float x) { return x; }
+            int helper(int x) { return x; }
+            float scale(float x);
+            float scale(float x, float y) { return x * y; }
+            float scale(float x) { return scale(x, 2.0); }
+            float4 main(float x : X) : SV_Target { return helper(x) + scale(x); }
+        "#;
+
+        assert_eq!(
+            pruned(src, "main"),
+            ["helper()", "scale;", "scale()", "scale()", "main()"]
+        );
+    }
+
+    #[test]
+    fn keep_called_templates() {
+        let src = r#"
+            template<typename T> T twice(T x) { return x * 2; }
+            template<typename T> T thrice(T x) { return x * 3; }
+            float4 main() : SV_Target { return twice(1.0); }
+        "#;
+
+        assert_eq!(pruned(src, "main"), ["template", "main()"]);
+    }
+
+    #[test]
+    fn missing_entry_point() {
+        let tu = ast::TranslationUnit::parse("void main() {}").unwrap();
+        let symbols = resolver::resolve(&tu);
+        let types = check::check(&tu, &symbols);
+        assert_eq!(
+            eliminate(&tu, &symbols, &types, "vs"),
+            Err(EntryPointError::NotFound("vs".into()))
+        );
+    }
+}
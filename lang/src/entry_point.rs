@@ -0,0 +1,607 @@
+//! Entry point discovery and shader stage inference
+//!
+//! [entry_points] lists the functions of a translation unit which look like entry points: global
+//! functions which are never called, and whose signature carries semantics or whose attributes
+//! only make sense on an entry point. Patch constant functions named by a
+//! `[patchconstantfunc("...")]` attribute are not entry points on their own.
+//!
+//! The stage of an entry point is [inferred](infer_stage) from its attributes first, e.g.
+//! `[numthreads]` for compute shaders or `[maxvertexcount]` for geometry shaders, and then from
+//! the system-value semantics of its signature, e.g. an `SV_Target` output for pixel shaders.
+//! When compiling with an explicit entry point and target profile (`-E main -T ps_6_0`),
+//! [entry_point] looks up the function and checks that its inferred stage agrees with the
+//! profile.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, entry_point, parse::{Parsable, ShaderStage}, resolver};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     r#"
+//!     float4 shade(float2 uv) { return float4(uv, 0, 1); }
+//!     float4 vs(uint id : SV_VertexID) : SV_Position { return 0; }
+//!     float4 ps(float4 pos : SV_Position) : SV_Target { return shade(pos.xy); }
+//!     [numthreads(8, 8, 1)] void cs() {}
+//!     "#,
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//!
+//! let stages: Vec<_> = entry_point::entry_points(&tu, &symbols)
+//!     .into_iter()
+//!     .map(|entry| (entry.name, entry.stage))
+//!     .collect();
+//! assert_eq!(
+//!     stages,
+//!     [
+//!         ("vs".into(), Some(ShaderStage::Vertex)),
+//!         ("ps".into(), Some(ShaderStage::Pixel)),
+//!         ("cs".into(), Some(ShaderStage::Compute)),
+//!     ]
+//! );
+//! ```
+
+use std::collections::HashSet;
+
+use crate::{
+    ast::{self, SmolStr},
+    attributes::FunctionAttribute,
+    parse::{ShaderModel, ShaderStage},
+    resolver::{Declarator, SymbolId, SymbolTable},
+    visitor::{Host, Visit, Visitor},
+};
+
+/// A shader entry point
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntryPoint<'a> {
+    /// Function name
+    pub name: SmolStr,
+    /// Function definition
+    pub definition: &'a ast::FunctionDefinition,
+    /// Stage of the entry point, if it could be inferred
+    pub stage: Option<ShaderStage>,
+    /// What the stage was inferred from
+    pub evidence: Option<StageEvidence>,
+}
+
+/// Reason a stage was assigned to an entry point
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StageEvidence {
+    /// The stage is given by the target profile
+    Target(ShaderModel),
+    /// The stage is implied by a function attribute, e.g. `numthreads`
+    Attribute(&'static str),
+    /// The stage is implied by a call to an intrinsic only available to it, e.g. `DispatchMesh`
+    Intrinsic(&'static str),
+    /// The stage is implied by a system-value semantic of the signature, e.g. `SV_Target`
+    Semantic(SmolStr),
+}
+
+/// Error raised when looking up an explicit entry point
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum EntryPointError {
+    /// No function with the given name is defined
+    #[error("entry point `{0}` not found")]
+    NotFound(SmolStr),
+    /// The entry point looks like it belongs to another stage than the target profile
+    #[error("entry point `{name}` looks like a `{inferred}` shader, but the target profile is `{target}`")]
+    StageMismatch {
+        /// Entry point name
+        name: SmolStr,
+        /// Stage inferred from the entry point
+        inferred: ShaderStage,
+        /// Target profile
+        target: ShaderModel,
+        /// What the stage was inferred from
+        evidence: StageEvidence,
+    },
+}
+
+/// Candidate entry points of a translation unit, in declaration order
+pub fn entry_points<'a>(
+    tu: &'a ast::TranslationUnit,
+    symbols: &SymbolTable<'a>,
+) -> Vec<EntryPoint<'a>> {
+    let definitions: Vec<_> = definitions(tu).collect();
+
+    let patch_constant_functions: HashSet<_> = definitions
+        .iter()
+        .flat_map(|def| FunctionAttribute::decode_all_with_symbols(def, symbols))
+        .filter_map(|attr| match attr {
+            Ok(FunctionAttribute::PatchConstantFunc(name)) => Some(name),
+            _ => None,
+        })
+        .collect();
+
+    definitions
+        .into_iter()
+        .filter(|def| {
+            let name = &def.prototype.name;
+            !patch_constant_functions.contains(name.as_str())
+                && !symbols
+                    .declared_by(name)
+                    .is_some_and(|id| symbols.uses_of(id).next().is_some())
+        })
+        .filter_map(|def| {
+            let inferred = infer_stage(def, symbols);
+            if inferred.is_none() && semantics(&def.prototype, symbols).is_empty() {
+                return None;
+            }
+
+            let (stage, evidence) = inferred.unzip();
+            Some(EntryPoint {
+                name: def.prototype.name.0.clone(),
+                definition: def,
+                stage,
+                evidence,
+            })
+        })
+        .collect()
+}
+
+/// Entry point `name` compiled for `target`, as selected by DXC's `-E` and `-T` flags.
+///
+/// For library targets, the stage of the entry point is the inferred one, and defaults to
+/// [ShaderStage::Library]. Otherwise, it is the stage of the target, which must agree with the
+/// inferred stage if there is one.
+pub fn entry_point<'a>(
+    tu: &'a ast::TranslationUnit,
+    symbols: &SymbolTable<'a>,
+    name: &str,
+    target: ShaderModel,
+) -> Result<EntryPoint<'a>, EntryPointError> {
+    let def = definitions(tu)
+        .find(|def| def.prototype.name.as_str() == name)
+        .ok_or_else(|| EntryPointError::NotFound(name.into()))?;
+
+    let (stage, evidence) = match infer_stage(def, symbols) {
+        Some((stage, evidence)) if target.stage == ShaderStage::Library => (stage, evidence),
+        Some((inferred, evidence)) if inferred != target.stage => {
+            return Err(EntryPointError::StageMismatch {
+                name: name.into(),
+                inferred,
+                target,
+                evidence,
+            });
+        }
+        _ => (target.stage, StageEvidence::Target(target)),
+    };
+
+    Ok(EntryPoint {
+        name: name.into(),
+        definition: def,
+        stage: Some(stage),
+        evidence: Some(evidence),
+    })
+}
+
+/// Infer the stage of an entry point from its attributes, and then from the system-value
+/// semantics of its signature
+pub fn infer_stage(
+    def: &ast::FunctionDefinition,
+    symbols: &SymbolTable<'_>,
+) -> Option<(ShaderStage, StageEvidence)> {
+    infer_from_attributes(def, symbols).or_else(|| {
+        let semantics = semantics(&def.prototype, symbols);
+
+        // Outputs are more telling than inputs: pixel shaders also read `SV_Position`
+        [true, false].into_iter().find_map(|output| {
+            semantics
+                .iter()
+                .filter(|(_, is_output)| *is_output == output)
+                .find_map(|(semantic, _)| {
+                    let sv = ast::SystemValue::from_name(semantic)?;
+                    let stage = semantic_stage(sv, output)?;
+                    Some((stage, StageEvidence::Semantic(semantic.clone())))
+                })
+        })
+    })
+}
+
+fn infer_from_attributes(
+    def: &ast::FunctionDefinition,
+    symbols: &SymbolTable<'_>,
+) -> Option<(ShaderStage, StageEvidence)> {
+    let attrs: Vec<_> = FunctionAttribute::decode_all_with_symbols(def, symbols)
+        .filter_map(Result::ok)
+        .collect();
+    let has = |pred: fn(&FunctionAttribute) -> bool| attrs.iter().any(pred);
+    let attribute = |stage, name| Some((stage, StageEvidence::Attribute(name)));
+
+    if let Some(stage) = attrs.iter().find_map(|attr| match attr {
+        FunctionAttribute::Shader(stage) => Some(stage),
+        _ => None,
+    }) {
+        let stage = match stage.to_ascii_lowercase().as_str() {
+            "pixel" => ShaderStage::Pixel,
+            "vertex" => ShaderStage::Vertex,
+            "geometry" => ShaderStage::Geometry,
+            "hull" => ShaderStage::Hull,
+            "domain" => ShaderStage::Domain,
+            "compute" => ShaderStage::Compute,
+            "mesh" => ShaderStage::Mesh,
+            "amplification" => ShaderStage::Amplification,
+            // Ray tracing and work graph node shaders only exist in libraries
+            _ => ShaderStage::Library,
+        };
+
+        return attribute(stage, "shader");
+    }
+
+    let hull = attrs.iter().find_map(|attr| match attr {
+        FunctionAttribute::Partitioning(_) => Some("partitioning"),
+        FunctionAttribute::OutputControlPoints(_) => Some("outputcontrolpoints"),
+        FunctionAttribute::PatchConstantFunc(_) => Some("patchconstantfunc"),
+        FunctionAttribute::OutputTopology(_) => Some("outputtopology"),
+        _ => None,
+    });
+
+    if has(|attr| matches!(attr, FunctionAttribute::NumThreads { .. })) {
+        if has(|attr| matches!(attr, FunctionAttribute::OutputTopology(_))) {
+            attribute(ShaderStage::Mesh, "outputtopology")
+        } else if calls(def, "DispatchMesh") {
+            Some((
+                ShaderStage::Amplification,
+                StageEvidence::Intrinsic("DispatchMesh"),
+            ))
+        } else {
+            attribute(ShaderStage::Compute, "numthreads")
+        }
+    } else if has(|attr| matches!(attr, FunctionAttribute::MaxVertexCount(_))) {
+        attribute(ShaderStage::Geometry, "maxvertexcount")
+    } else if let Some(name) = hull {
+        attribute(ShaderStage::Hull, name)
+    } else if has(|attr| matches!(attr, FunctionAttribute::Domain(_))) {
+        attribute(ShaderStage::Domain, "domain")
+    } else if has(|attr| matches!(attr, FunctionAttribute::EarlyDepthStencil)) {
+        attribute(ShaderStage::Pixel, "earlydepthstencil")
+    } else {
+        None
+    }
+}
+
+/// Stage implied by a system value, when used as an input or an output
+fn semantic_stage(sv: ast::SystemValue, output: bool) -> Option<ShaderStage> {
+    use ast::SystemValue as Sv;
+
+    Some(match (sv, output) {
+        (
+            Sv::Target
+            | Sv::Depth
+            | Sv::DepthGreaterEqual
+            | Sv::DepthLessEqual
+            | Sv::Coverage
+            | Sv::StencilRef,
+            true,
+        ) => ShaderStage::Pixel,
+        (
+            Sv::Position
+            | Sv::ClipDistance
+            | Sv::CullDistance
+            | Sv::RenderTargetArrayIndex
+            | Sv::ViewportArrayIndex,
+            true,
+        ) => ShaderStage::Vertex,
+        (Sv::VertexId | Sv::InstanceId, false) => ShaderStage::Vertex,
+        (
+            Sv::Position | Sv::IsFrontFace | Sv::SampleIndex | Sv::Coverage | Sv::Barycentrics,
+            false,
+        ) => ShaderStage::Pixel,
+        (Sv::DispatchThreadId | Sv::GroupId | Sv::GroupThreadId | Sv::GroupIndex, false) => {
+            ShaderStage::Compute
+        }
+        (Sv::DomainLocation, false) => ShaderStage::Domain,
+        (Sv::OutputControlPointId, false) => ShaderStage::Hull,
+        (Sv::GsInstanceId, false) => ShaderStage::Geometry,
+        _ => return None,
+    })
+}
+
+/// Function definitions at global scope, excluding methods and operators
+pub(crate) fn definitions(
+    tu: &ast::TranslationUnit,
+) -> impl Iterator<Item = &ast::FunctionDefinition> {
+    tu.0.iter().filter_map(|decl| match &**decl {
+        ast::ExternalDeclarationData::FunctionDefinition(def)
+            if def.prototype.scope.is_none() && def.prototype.operator.is_none() =>
+        {
+            Some(def)
+        }
+        _ => None,
+    })
+}
+
+/// Semantics of a function signature, including those of struct fields, with `true` for outputs
+fn semantics(proto: &ast::FunctionPrototype, symbols: &SymbolTable<'_>) -> Vec<(SmolStr, bool)> {
+    let mut semantics = Vec::new();
+    let mut structs = Vec::new();
+
+    match &proto.semantic {
+        Some(semantic) => semantics.push((semantic.name.clone(), true)),
+        None => struct_semantics(&proto.ty.ty, true, symbols, &mut structs, &mut semantics),
+    }
+
+    for param in &proto.parameters {
+        let ast::FunctionParameterDeclarationData::Named(qualifier, declarator) = &**param else {
+            continue;
+        };
+
+        let (input, output) = direction(qualifier.as_ref());
+        for (is_output, used) in [(false, input), (true, output)] {
+            if !used {
+                continue;
+            }
+
+            match &declarator.ident.semantic {
+                Some(semantic) => semantics.push((semantic.name.clone(), is_output)),
+                None => struct_semantics(
+                    &declarator.ty,
+                    is_output,
+                    symbols,
+                    &mut structs,
+                    &mut semantics,
+                ),
+            }
+        }
+    }
+
+    semantics
+}
+
+/// Semantics of the fields of a struct type. `structs` holds the structs whose fields are being
+/// visited, so that a struct which contains itself is not visited again.
+fn struct_semantics(
+    ty: &ast::TypeSpecifier,
+    output: bool,
+    symbols: &SymbolTable<'_>,
+    structs: &mut Vec<SymbolId>,
+    semantics: &mut Vec<(SmolStr, bool)>,
+) {
+    if let Some(id) = symbols.struct_type(&ty.ty) {
+        field_semantics(id, output, symbols, structs, semantics);
+    }
+}
+
+fn field_semantics(
+    id: SymbolId,
+    output: bool,
+    symbols: &SymbolTable<'_>,
+    structs: &mut Vec<SymbolId>,
+    semantics: &mut Vec<(SmolStr, bool)>,
+) {
+    if structs.contains(&id) {
+        return;
+    }
+
+    structs.push(id);
+    for base in symbols.bases(id) {
+        field_semantics(*base, output, symbols, structs, semantics);
+    }
+
+    for member in symbols.members(id) {
+        if let Declarator::Field { field, ident } = symbols.symbol(*member).declarator {
+            match &ident.semantic {
+                Some(semantic) => semantics.push((semantic.name.clone(), output)),
+                None => struct_semantics(&field.ty, output, symbols, structs, semantics),
+            }
+        }
+    }
+    structs.pop();
+}
+
+/// Whether a parameter with the given qualifier is an input and an output
+pub(crate) fn direction(qualifier: Option<&ast::TypeQualifier>) -> (bool, bool) {
+    let storage = qualifier
+        .into_iter()
+        .flat_map(|qualifier| &qualifier.qualifiers)
+        .find_map(|spec| match &**spec {
+            ast::TypeQualifierSpecData::Storage(storage) => match **storage {
+                ast::StorageQualifierData::In => Some((true, false)),
+                ast::StorageQualifierData::Out => Some((false, true)),
+                ast::StorageQualifierData::InOut => Some((true, true)),
+                _ => None,
+            },
+            _ => None,
+        });
+
+    storage.unwrap_or((true, false))
+}
+
+/// `true` if the body of a function calls the named function
+fn calls(def: &ast::FunctionDefinition, name: &str) -> bool {
+    struct Calls<'n> {
+        name: &'n str,
+        found: bool,
+    }
+
+    impl Visitor for Calls<'_> {
+        fn visit_expr(&mut self, expr: &ast::Expr) -> Visit {
+            if let ast::ExprData::FunCall(fun, _) = &**expr {
+                if let ast::FunIdentifierData::Expr(callee) = &**fun {
+                    if let ast::ExprData::Variable(ident) = &***callee {
+                        self.found |= ident.as_str() == self.name;
+                    }
+                }
+            }
+
+            Visit::Children
+        }
+    }
+
+    let mut visitor = Calls { name, found: false };
+    def.statement.visit(&mut visitor);
+    visitor.found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::Parsable, resolver};
+
+    fn stages(src: &str) -> Vec<(String, Option<ShaderStage>, Option<StageEvidence>)> {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        entry_points(&tu, &symbols)
+            .into_iter()
+            .map(|entry| (entry.name.to_string(), entry.stage, entry.evidence))
+            .collect()
+    }
+
+    #[test]
+    fn infer_from_attributes() {
+        let attribute = |stage, name| (Some(stage), Some(StageEvidence::Attribute(name)));
+        let found: Vec<_> = stages(
+            r#"
+            [numthreads(64, 1, 1)] void cs() {}
+            [numthreads(32, 1, 1)] [outputtopology("triangle")] void ms() {}
+            [numthreads(32, 1, 1)] void as() { DispatchMesh(1, 1, 1, 0); }
+            [maxvertexcount(3)] void gs() {}
+            [domain("tri")] [partitioning("integer")] [outputtopology("triangle_cw")]
+            [outputcontrolpoints(3)] [patchconstantfunc("patch")] void hs() {}
+            float patch() : SV_TessFactor { return 1; }
+            [domain("tri")] void ds() {}
+            [earlydepthstencil] void ps() {}
+            [shader("closesthit")] void hit() {}
+            [shader("vertex")] void vs() {}
+            "#,
+        )
+        .into_iter()
+        .map(|(name, stage, evidence)| (name, (stage, evidence)))
+        .collect();
+
+        assert_eq!(
+            found,
+            [
+                (
+                    "cs".to_owned(),
+                    attribute(ShaderStage::Compute, "numthreads")
+                ),
+                (
+                    "ms".to_owned(),
+                    attribute(ShaderStage::Mesh, "outputtopology")
+                ),
+                (
+                    "as".to_owned(),
+                    (
+                        Some(ShaderStage::Amplification),
+                        Some(StageEvidence::Intrinsic("DispatchMesh"))
+                    )
+                ),
+                (
+                    "gs".to_owned(),
+                    attribute(ShaderStage::Geometry, "maxvertexcount")
+                ),
+                (
+                    "hs".to_owned(),
+                    attribute(ShaderStage::Hull, "partitioning")
+                ),
+                ("ds".to_owned(), attribute(ShaderStage::Domain, "domain")),
+                (
+                    "ps".to_owned(),
+                    attribute(ShaderStage::Pixel, "earlydepthstencil")
+                ),
+                ("hit".to_owned(), attribute(ShaderStage::Library, "shader")),
+                ("vs".to_owned(), attribute(ShaderStage::Vertex, "shader")),
+            ]
+        );
+    }
+
+    #[test]
+    fn infer_from_semantics() {
+        let semantic =
+            |stage, name: &str| (Some(stage), Some(StageEvidence::Semantic(name.into())));
+        let found: Vec<_> = stages(
+            r#"
+            struct VSOut { float4 pos : SV_Position; float2 uv : TEXCOORD0; };
+            struct PSOut { float4 color : SV_Target0; float depth : SV_Depth; };
+            VSOut vs(float3 pos : POSITION) { VSOut o; return o; }
+            PSOut ps(VSOut i) { PSOut o; return o; }
+            void ps2(float4 pos : SV_Position, out float4 color : SV_Target) {}
+            void cs(uint3 id : SV_DispatchThreadID) {}
+            float4 legacy(float4 pos : POSITION) : POSITION { return pos; }
+            float4 helper(float4 x) { return x; }
+            float4 vs2(uint id : SV_VertexID) : SV_Position { return helper(0); }
+            "#,
+        )
+        .into_iter()
+        .map(|(name, stage, evidence)| (name, (stage, evidence)))
+        .collect();
+
+        assert_eq!(
+            found,
+            [
+                (
+                    "vs".to_owned(),
+                    semantic(ShaderStage::Vertex, "SV_Position")
+                ),
+                ("ps".to_owned(), semantic(ShaderStage::Pixel, "SV_Target")),
+                ("ps2".to_owned(), semantic(ShaderStage::Pixel, "SV_Target")),
+                (
+                    "cs".to_owned(),
+                    semantic(ShaderStage::Compute, "SV_DispatchThreadID")
+                ),
+                ("legacy".to_owned(), (None, None)),
+                (
+                    "vs2".to_owned(),
+                    semantic(ShaderStage::Vertex, "SV_Position")
+                ),
+            ]
+        );
+
+        // A struct which contains itself is only visited once
+        assert_eq!(
+            stages(
+                "struct A { float4 pos : SV_Position; A inner; };
+                float4 ps(A a) : SV_Target { return a.pos; }"
+            ),
+            [(
+                "ps".to_owned(),
+                Some(ShaderStage::Pixel),
+                Some(StageEvidence::Semantic("SV_Target".into()))
+            )]
+        );
+    }
+
+    #[test]
+    fn explicit_entry_point() {
+        let tu = ast::TranslationUnit::parse(
+            r#"
+            float4 main(float4 pos : POSITION) : POSITION { return pos; }
+            [numthreads(8, 8, 1)] void cs() {}
+            [shader("pixel")] float4 lib() : SV_Target { return 0; }
+            "#,
+        )
+        .unwrap();
+        let symbols = resolver::resolve(&tu);
+        let target = |profile: &str| profile.parse::<ShaderModel>().unwrap();
+
+        let entry = entry_point(&tu, &symbols, "main", target("vs_6_0")).unwrap();
+        assert_eq!(entry.stage, Some(ShaderStage::Vertex));
+        assert_eq!(
+            entry.evidence,
+            Some(StageEvidence::Target(target("vs_6_0")))
+        );
+
+        let entry = entry_point(&tu, &symbols, "cs", target("cs_6_0")).unwrap();
+        assert_eq!(
+            entry.evidence,
+            Some(StageEvidence::Target(target("cs_6_0")))
+        );
+
+        let entry = entry_point(&tu, &symbols, "lib", target("lib_6_3")).unwrap();
+        assert_eq!(entry.stage, Some(ShaderStage::Pixel));
+        assert_eq!(entry.evidence, Some(StageEvidence::Attribute("shader")));
+
+        assert_eq!(
+            entry_point(&tu, &symbols, "cs", target("ps_6_0"))
+                .unwrap_err()
+                .to_string(),
+            "entry point `cs` looks like a `cs` shader, but the target profile is `ps_6_0`"
+        );
+        assert_eq!(
+            entry_point(&tu, &symbols, "missing", target("ps_6_0")),
+            Err(EntryPointError::NotFound("missing".into()))
+        );
+    }
+}
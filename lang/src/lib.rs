@@ -58,7 +58,15 @@ lalrpop_mod!(
     #[allow(clippy::all)]
     parser
 );
+pub mod attributes;
+pub mod dead_code;
+pub mod entry_point;
+pub mod lint;
 pub mod parse;
+pub mod reflection;
+pub mod resolver;
+pub mod root_signature;
+pub mod semantic;
 pub mod transpiler;
 pub mod visitor;
 
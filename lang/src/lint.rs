@@ -0,0 +1,324 @@
+//! Lints
+//!
+//! A [Linter] runs a set of [Rule]s over a translation unit, along with the results of
+//! [name resolution](crate::resolver) and [type checking](crate::semantic::check). Each rule has
+//! a [Level]: rules at [Level::Allow] do not run, and the findings of the other rules are
+//! reported as warnings or errors. Rules are identified by a kebab-case name, such as
+//! `unused-variable`, so their levels can be read from a configuration file.
+//!
+//! The [built-in rules](rules) are enabled by default. Other rules can be added by implementing
+//! [Rule], usually with a [Visitor](crate::visitor::Visitor) over the translation unit.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, lint, parse::Parsable, resolver, semantic::check};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     "bool f(float a, float b) { float c = a; return a == b; }",
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//! let types = check::check(&tu, &symbols);
+//!
+//! let mut linter = lint::Linter::default();
+//! linter.set_level("float-equality", lint::Level::Deny).unwrap();
+//! let lints = linter.run(&tu, &symbols, &types);
+//!
+//! assert_eq!(lints[0].to_string(), "unused variable `c`");
+//! assert_eq!(lints[1].rule, "float-equality");
+//! assert_eq!(lints[1].severity, hlsl_lang::semantic::Severity::Error);
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    ast::{self, NodeSpan},
+    resolver::SymbolTable,
+    semantic::{check::TypeTable, Severity},
+};
+
+pub mod rules;
+
+/// Level of a lint rule
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// The rule does not run
+    Allow,
+    /// Findings are reported as warnings
+    Warn,
+    /// Findings are reported as errors
+    Deny,
+}
+
+impl Level {
+    /// Severity of the findings of a rule at this level, if it runs
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            Self::Allow => None,
+            Self::Warn => Some(Severity::Warning),
+            Self::Deny => Some(Severity::Error),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Allow => f.write_str("allow"),
+            Self::Warn => f.write_str("warn"),
+            Self::Deny => f.write_str("deny"),
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = LintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "warn" => Ok(Self::Warn),
+            "deny" => Ok(Self::Deny),
+            _ => Err(LintError::UnknownLevel(s.to_owned())),
+        }
+    }
+}
+
+/// Error raised when configuring a [Linter]
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum LintError {
+    /// No rule has the given name
+    #[error("unknown lint rule `{0}`")]
+    UnknownRule(String),
+    /// A level is not one of `allow`, `warn` or `deny`
+    #[error("unknown lint level `{0}`; expected `allow`, `warn` or `deny`")]
+    UnknownLevel(String),
+}
+
+/// Translation unit and semantic information a rule runs on
+#[derive(Clone, Copy)]
+pub struct Context<'s, 'a> {
+    /// Linted translation unit
+    pub tu: &'a ast::TranslationUnit,
+    /// Symbols of the translation unit
+    pub symbols: &'s SymbolTable<'a>,
+    /// Types of the expressions of the translation unit
+    pub types: &'s TypeTable<'a>,
+}
+
+/// A lint rule
+pub trait Rule {
+    /// Name of the rule, in kebab case
+    fn name(&self) -> &'static str;
+
+    /// One-line description of what the rule reports
+    fn description(&self) -> &'static str;
+
+    /// Level of the rule, unless configured otherwise
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    /// Report the findings of the rule on a translation unit
+    fn check(&self, cx: &Context<'_, '_>, report: &mut Report<'_>);
+}
+
+/// Collects the findings of a rule
+pub struct Report<'l> {
+    rule: &'static str,
+    severity: Severity,
+    lints: &'l mut Vec<Lint>,
+}
+
+impl Report<'_> {
+    /// Report a finding
+    pub fn push(&mut self, message: impl Into<String>, span: Option<NodeSpan>) {
+        self.lints.push(Lint {
+            rule: self.rule,
+            severity: self.severity,
+            message: message.into(),
+            span,
+        });
+    }
+}
+
+/// A finding of a lint rule
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("{message}")]
+pub struct Lint {
+    /// Name of the rule
+    pub rule: &'static str,
+    /// Severity, given by the level of the rule
+    pub severity: Severity,
+    /// Description of the finding
+    pub message: String,
+    /// Span of the offending node
+    pub span: Option<NodeSpan>,
+}
+
+/// Set of rules with their levels
+pub struct Linter {
+    rules: Vec<(Box<dyn Rule>, Level)>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        let mut linter = Self::new();
+        for rule in rules::all() {
+            linter.add(rule);
+        }
+
+        linter
+    }
+}
+
+impl fmt::Debug for Linter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.rules.iter().map(|(rule, level)| (rule.name(), level)))
+            .finish()
+    }
+}
+
+impl Linter {
+    /// Create a linter without any rules
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Add a rule at its default level, replacing any rule of the same name
+    pub fn add(&mut self, rule: Box<dyn Rule>) -> &mut Self {
+        let level = rule.default_level();
+        match self.rules.iter_mut().find(|(r, _)| r.name() == rule.name()) {
+            Some(entry) => *entry = (rule, level),
+            None => self.rules.push((rule, level)),
+        }
+
+        self
+    }
+
+    /// Rules of this linter with their levels, in the order they run
+    pub fn rules(&self) -> impl Iterator<Item = (&dyn Rule, Level)> + '_ {
+        self.rules.iter().map(|(rule, level)| (&**rule, *level))
+    }
+
+    /// Level of the named rule
+    pub fn level(&self, name: &str) -> Option<Level> {
+        self.rules
+            .iter()
+            .find(|(rule, _)| rule.name() == name)
+            .map(|(_, level)| *level)
+    }
+
+    /// Set the level of the named rule
+    pub fn set_level(&mut self, name: &str, level: Level) -> Result<&mut Self, LintError> {
+        match self.rules.iter_mut().find(|(rule, _)| rule.name() == name) {
+            Some((_, l)) => {
+                *l = level;
+                Ok(self)
+            }
+            None => Err(LintError::UnknownRule(name.to_owned())),
+        }
+    }
+
+    /// Run the rules which are not allowed on a translation unit. Findings are sorted by
+    /// position.
+    pub fn run<'a>(
+        &self,
+        tu: &'a ast::TranslationUnit,
+        symbols: &SymbolTable<'a>,
+        types: &TypeTable<'a>,
+    ) -> Vec<Lint> {
+        let cx = Context { tu, symbols, types };
+
+        let mut lints = Vec::new();
+        for (rule, level) in &self.rules {
+            if let Some(severity) = level.severity() {
+                rule.check(
+                    &cx,
+                    &mut Report {
+                        rule: rule.name(),
+                        severity,
+                        lints: &mut lints,
+                    },
+                );
+            }
+        }
+
+        lints.sort_by_key(|lint| {
+            lint.span
+                .map(|span| (span.source_id(), span.range().start()))
+        });
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::Parsable, resolver, semantic::check};
+
+    fn run(linter: &Linter, src: &str) -> Vec<(&'static str, Severity)> {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        let types = check::check(&tu, &symbols);
+        linter
+            .run(&tu, &symbols, &types)
+            .iter()
+            .map(|lint| (lint.rule, lint.severity))
+            .collect()
+    }
+
+    #[test]
+    fn levels() {
+        assert_eq!("allow".parse(), Ok(Level::Allow));
+        assert_eq!("warn".parse(), Ok(Level::Warn));
+        assert_eq!("deny".parse(), Ok(Level::Deny));
+        assert_eq!(
+            "Deny".parse::<Level>(),
+            Err(LintError::UnknownLevel("Deny".into()))
+        );
+        assert_eq!(
+            LintError::UnknownLevel("error".into()).to_string(),
+            "unknown lint level `error`; expected `allow`, `warn` or `deny`"
+        );
+
+        for level in [Level::Allow, Level::Warn, Level::Deny] {
+            assert_eq!(level.to_string().parse(), Ok(level));
+        }
+    }
+
+    #[test]
+    fn configure_rules() {
+        let src = "bool f(float a) { float b; return a == 1.0; }";
+
+        let mut linter = Linter::default();
+        assert_eq!(linter.level("unused-variable"), Some(Level::Warn));
+        assert_eq!(
+            run(&linter, src),
+            [
+                ("unused-variable", Severity::Warning),
+                ("float-equality", Severity::Warning),
+            ]
+        );
+
+        linter
+            .set_level("unused-variable", Level::Allow)
+            .unwrap()
+            .set_level("float-equality", Level::Deny)
+            .unwrap();
+        assert_eq!(linter.level("unused-variable"), Some(Level::Allow));
+        assert_eq!(run(&linter, src), [("float-equality", Severity::Error)]);
+
+        assert_eq!(
+            linter.set_level("unused-variables", Level::Deny).err(),
+            Some(LintError::UnknownRule("unused-variables".into()))
+        );
+        assert_eq!(linter.level("unused-variables"), None);
+        assert_eq!(
+            LintError::UnknownRule("x".into()).to_string(),
+            "unknown lint rule `x`"
+        );
+    }
+}
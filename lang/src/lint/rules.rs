@@ -0,0 +1,528 @@
+//! Built-in lint rules
+//!
+//! | Rule | Reports |
+//! |------|---------|
+//! | `implicit-truncation` | vectors and matrices implicitly converted to a smaller type |
+//! | `unused-variable` | local variables which are never referenced |
+//! | `unused-parameter` | parameters which are never referenced, unless they have a semantic |
+//! | `shadowed-local` | local variables which hide a local variable or parameter of an enclosing scope |
+//! | `unwritten-out-parameter` | `out` parameters which are not written on every path out of their function |
+//! | `float-equality` | floating-point values compared with `==` or `!=` |
+//! | `mul-order` | `mul` calls which multiply a vector by a square matrix in the opposite order to the other calls of the translation unit |
+//!
+//! The `unwritten-out-parameter` rule runs the definite assignment analysis of
+//! [flow](crate::semantic::flow) over the [control-flow graph](crate::semantic::cfg) of each
+//! function: passing a parameter as an `out` argument writes it, and writing any component or
+//! element of it writes all of it.
+
+use crate::{
+    ast,
+    entry_point::direction,
+    resolver::{Declarator, SymbolKind, SymbolTable},
+    semantic::{
+        cfg::{Cfg, Terminator},
+        check::TypeTable,
+        flow::Assignments,
+        overload::Callee,
+        types::{ScalarType, Type},
+        DiagnosticKind,
+    },
+    visitor::{Host, Visit, Visitor},
+};
+
+use super::{Context, Report, Rule};
+
+/// All the built-in rules
+pub fn all() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(ImplicitTruncation),
+        Box::new(UnusedVariable),
+        Box::new(UnusedParameter),
+        Box::new(ShadowedLocal),
+        Box::new(UnwrittenOutParameter),
+        Box::new(FloatEquality),
+        Box::new(MulOrder),
+    ]
+}
+
+/// Functions and methods which have a body, with their prototype
+fn bodies<'s, 'a>(
+    symbols: &'s SymbolTable<'a>,
+) -> impl Iterator<Item = (&'a ast::FunctionPrototype, &'a ast::CompoundStatement)> + 's {
+    symbols
+        .symbols()
+        .iter()
+        .filter_map(|symbol| match symbol.declarator {
+            Declarator::Function {
+                definition: Some(def),
+                ..
+            }
+            | Declarator::Method {
+                definition: Some(def),
+                ..
+            } => Some((&def.prototype, &def.statement)),
+            Declarator::Method { method, .. } => method
+                .statement
+                .as_ref()
+                .map(|statement| (&method.prototype, statement)),
+            _ => None,
+        })
+}
+
+/// Reports implicit truncations found by the type checker
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ImplicitTruncation;
+
+impl Rule for ImplicitTruncation {
+    fn name(&self) -> &'static str {
+        "implicit-truncation"
+    }
+
+    fn description(&self) -> &'static str {
+        "vector or matrix implicitly converted to a type with fewer components"
+    }
+
+    fn check(&self, cx: &Context<'_, '_>, report: &mut Report<'_>) {
+        for diagnostic in cx.types.diagnostics() {
+            if let DiagnosticKind::ImplicitTruncation { .. } = diagnostic.kind {
+                report.push(diagnostic.to_string(), diagnostic.span);
+            }
+        }
+    }
+}
+
+/// Reports local variables which are never referenced
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnusedVariable;
+
+impl Rule for UnusedVariable {
+    fn name(&self) -> &'static str {
+        "unused-variable"
+    }
+
+    fn description(&self) -> &'static str {
+        "local variable which is never referenced"
+    }
+
+    fn check(&self, cx: &Context<'_, '_>, report: &mut Report<'_>) {
+        for scope in cx.symbols.scopes() {
+            for &id in &scope.symbols {
+                let symbol = cx.symbols.symbol(id);
+                if symbol.kind == SymbolKind::Local && cx.symbols.uses_of(id).next().is_none() {
+                    report.push(format!("unused variable `{}`", symbol.name), symbol.span);
+                }
+            }
+        }
+    }
+}
+
+/// Reports parameters which are never referenced. Parameters with a semantic are part of the
+/// signature of an entry point, and `out` parameters are reported by [UnwrittenOutParameter].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnusedParameter;
+
+impl Rule for UnusedParameter {
+    fn name(&self) -> &'static str {
+        "unused-parameter"
+    }
+
+    fn description(&self) -> &'static str {
+        "function parameter which is never referenced"
+    }
+
+    fn check(&self, cx: &Context<'_, '_>, report: &mut Report<'_>) {
+        for (proto, _) in bodies(cx.symbols) {
+            for param in &proto.parameters {
+                let ast::FunctionParameterDeclarationData::Named(qualifier, declarator) = &**param
+                else {
+                    continue;
+                };
+
+                if declarator.ident.semantic.is_some()
+                    || direction(qualifier.as_ref()) == (false, true)
+                {
+                    continue;
+                }
+
+                if let Some(id) = cx.symbols.declared_by(&declarator.ident.ident) {
+                    if cx.symbols.uses_of(id).next().is_none() {
+                        let symbol = cx.symbols.symbol(id);
+                        report.push(format!("unused parameter `{}`", symbol.name), symbol.span);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Reports local variables which hide a local variable or parameter of an enclosing scope
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ShadowedLocal;
+
+impl Rule for ShadowedLocal {
+    fn name(&self) -> &'static str {
+        "shadowed-local"
+    }
+
+    fn description(&self) -> &'static str {
+        "local variable hiding another local variable or parameter"
+    }
+
+    fn check(&self, cx: &Context<'_, '_>, report: &mut Report<'_>) {
+        for symbol in cx.symbols.symbols() {
+            let Some(shadowed) = symbol.shadows else {
+                continue;
+            };
+
+            if symbol.kind != SymbolKind::Local {
+                continue;
+            }
+
+            let what = match cx.symbols.symbol(shadowed).kind {
+                SymbolKind::Local => "local variable",
+                SymbolKind::Parameter => "parameter",
+                _ => continue,
+            };
+
+            report.push(
+                format!("declaration of `{}` shadows a {}", symbol.name, what),
+                symbol.span,
+            );
+        }
+    }
+}
+
+/// Reports `out` parameters which are not written on every path out of their function
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnwrittenOutParameter;
+
+impl Rule for UnwrittenOutParameter {
+    fn name(&self) -> &'static str {
+        "unwritten-out-parameter"
+    }
+
+    fn description(&self) -> &'static str {
+        "`out` parameter which is not written on all paths"
+    }
+
+    fn check(&self, cx: &Context<'_, '_>, report: &mut Report<'_>) {
+        for (proto, body) in bodies(cx.symbols) {
+            let params: Vec<_> = proto
+                .parameters
+                .iter()
+                .filter_map(|param| match &**param {
+                    ast::FunctionParameterDeclarationData::Named(qualifier, declarator)
+                        if direction(qualifier.as_ref()) == (false, true) =>
+                    {
+                        cx.symbols.declared_by(&declarator.ident.ident)
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            if params.is_empty() {
+                continue;
+            }
+
+            let cfg = Cfg::build(body, cx.symbols);
+            let tracked = params.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+            let outputs =
+                Assignments::new(cx.symbols, cx.types, &cfg, tracked).outputs(&cfg.reachable());
+
+            // Parameters must be written at every `return` and at the end of the body, but not
+            // before a `discard`
+            let mut unwritten = vec![false; params.len()];
+            for (block, output) in cfg.blocks.iter().zip(&outputs) {
+                let Some(written) = output else {
+                    continue;
+                };
+
+                if matches!(
+                    block.terminator,
+                    Terminator::Return(_) | Terminator::FallOff
+                ) {
+                    for (unwritten, written) in unwritten.iter_mut().zip(written) {
+                        *unwritten |= !written;
+                    }
+                }
+            }
+
+            for (id, _) in params
+                .iter()
+                .zip(unwritten)
+                .filter(|(_, unwritten)| *unwritten)
+            {
+                let symbol = cx.symbols.symbol(*id);
+                report.push(
+                    format!(
+                        "`out` parameter `{}` is not written on all paths",
+                        symbol.name
+                    ),
+                    symbol.span,
+                );
+            }
+        }
+    }
+}
+
+/// Reports comparisons of floating-point values for equality
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FloatEquality;
+
+impl Rule for FloatEquality {
+    fn name(&self) -> &'static str {
+        "float-equality"
+    }
+
+    fn description(&self) -> &'static str {
+        "floating-point values compared with `==` or `!=`"
+    }
+
+    fn check(&self, cx: &Context<'_, '_>, report: &mut Report<'_>) {
+        struct Comparisons<'c, 'r, 'l> {
+            types: &'c TypeTable<'c>,
+            report: &'r mut Report<'l>,
+        }
+
+        impl Visitor for Comparisons<'_, '_, '_> {
+            fn visit_expr(&mut self, expr: &ast::Expr) -> Visit {
+                if let ast::ExprData::Binary(op, left, right) = &**expr {
+                    let op = match **op {
+                        ast::BinaryOpData::Equal => "==",
+                        ast::BinaryOpData::NonEqual => "!=",
+                        _ => return Visit::Children,
+                    };
+
+                    let is_float = |expr: &ast::Expr| {
+                        self.types
+                            .type_of(expr)
+                            .and_then(Type::scalar)
+                            .is_some_and(ScalarType::is_float)
+                    };
+
+                    if is_float(left) || is_float(right) {
+                        self.report.push(
+                            format!("floating-point values compared with `{}`", op),
+                            expr.span,
+                        );
+                    }
+                }
+
+                Visit::Children
+            }
+        }
+
+        cx.tu.visit(&mut Comparisons {
+            types: cx.types,
+            report,
+        });
+    }
+}
+
+/// Reports `mul` calls with a vector and a square matrix whose argument order differs from the
+/// other such calls of the translation unit. Both orders are valid, but one multiplies by the
+/// transpose of the other, so mixing them is usually a mistake. When both orders are equally
+/// common, the vector-first calls are reported.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MulOrder;
+
+impl Rule for MulOrder {
+    fn name(&self) -> &'static str {
+        "mul-order"
+    }
+
+    fn description(&self) -> &'static str {
+        "`mul` of a vector and a square matrix in an inconsistent order"
+    }
+
+    fn check(&self, cx: &Context<'_, '_>, report: &mut Report<'_>) {
+        /// `mul` calls of a vector and a square matrix, with `true` if the vector comes first
+        struct Calls<'c> {
+            types: &'c TypeTable<'c>,
+            found: Vec<(bool, Option<ast::NodeSpan>, String)>,
+        }
+
+        impl Visitor for Calls<'_> {
+            fn visit_expr(&mut self, expr: &ast::Expr) -> Visit {
+                if let Some((vector_first, call)) = mul_order(self.types, expr) {
+                    self.found.push((vector_first, expr.span, call));
+                }
+
+                Visit::Children
+            }
+        }
+
+        let mut calls = Calls {
+            types: cx.types,
+            found: Vec::new(),
+        };
+        cx.tu.visit(&mut calls);
+
+        let vector_first = calls.found.iter().filter(|(first, ..)| *first).count();
+        let minority = vector_first * 2 <= calls.found.len();
+        for (vector_first, span, call) in calls.found {
+            if vector_first != minority {
+                continue;
+            }
+
+            let (here, elsewhere) = if vector_first {
+                ("row", "column")
+            } else {
+                ("column", "row")
+            };
+
+            report.push(
+                format!(
+                    "`{}` treats the vector as a {} vector, while other calls treat it as a {} vector",
+                    call, here, elsewhere
+                ),
+                span,
+            );
+        }
+    }
+}
+
+/// Order of the arguments of a `mul` call of a vector and a square matrix: `true` if the vector
+/// comes first, along with the call signature
+fn mul_order(types: &TypeTable<'_>, expr: &ast::Expr) -> Option<(bool, String)> {
+    let ast::ExprData::FunCall(_, args) = &**expr else {
+        return None;
+    };
+
+    match types.call(expr)?.callee {
+        Callee::Intrinsic(intrinsic, _) if intrinsic.name == "mul" => {}
+        _ => return None,
+    }
+
+    let [left, right] = &args[..] else {
+        return None;
+    };
+
+    let left = types.type_of(left)?;
+    let right = types.type_of(right)?;
+    let vector_first = match (left, right) {
+        (Type::Vector(_, n), Type::Matrix(_, r, c)) if n == r && r == c => true,
+        (Type::Matrix(_, r, c), Type::Vector(_, n)) if n == c && r == c => false,
+        _ => return None,
+    };
+
+    Some((vector_first, format!("mul({}, {})", left, right)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lint::Linter, parse::Parsable, resolver, semantic::check};
+
+    use super::*;
+
+    fn lint(src: &str, rule: &str) -> Vec<String> {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        let types = check::check(&tu, &symbols);
+
+        let mut linter = Linter::new();
+        for r in all() {
+            if r.name() == rule {
+                linter.add(r);
+            }
+        }
+
+        let lints = linter.run(&tu, &symbols, &types);
+        assert!(lints
+            .iter()
+            .all(|lint| lint.rule == rule && lint.span.is_some()));
+        lints.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn unused_and_shadowed_variables() {
+        let src = r#"
+            float f(float a, float b, float c : TEXCOORD0, out float d) {
+                float x = a;
+                float y;
+                float z;
+                { float x = 1; y = x; }
+                for (int i = 0; i < 2; i++) { int a = i; d = a; }
+                return x + y;
+            }
+        "#;
+
+        assert_eq!(lint(src, "unused-variable"), ["unused variable `z`"]);
+        assert_eq!(lint(src, "unused-parameter"), ["unused parameter `b`"]);
+        assert_eq!(
+            lint(src, "shadowed-local"),
+            [
+                "declaration of `x` shadows a local variable",
+                "declaration of `a` shadows a parameter",
+            ]
+        );
+    }
+
+    #[test]
+    fn unwritten_out_parameters() {
+        let src = r#"
+            void set(out float x) { x = 1; }
+            float get(float x) { return x; }
+            void written(bool c, int k, out float a, out float4 v, out float s, out float t, out float u) {
+                if (c) { a = 1; } else { a = 2; }
+                v.xy = 0;
+                sincos(1.0, s, t);
+                switch (k) { case 0: set(u); break; default: u = 2; }
+                if (c) discard;
+            }
+            void early(bool c, out float a) { if (c) return; a = 1; }
+            void branch(bool c, out float b) { if (c) b = 1; }
+            void sw(int k, out float c) { switch (k) { case 0: c = 1; break; case 1: c = 2; } }
+            void loops(int k, out float d, out float e) {
+                while (k > 0) { d = 1; k--; }
+                do { e = 1; } while (k > 0);
+            }
+            void calls(out float f, out float g) { set(f); get(g); }
+            void exits(int k, out float h) { for (;;) { if (k > 0) { h = 1; break; } k++; } }
+            void returns(bool c, out float i) { if (c) { i = 1; return; } i = 2; }
+        "#;
+
+        assert_eq!(
+            lint(src, "unwritten-out-parameter"),
+            [
+                "`out` parameter `a` is not written on all paths",
+                "`out` parameter `b` is not written on all paths",
+                "`out` parameter `c` is not written on all paths",
+                "`out` parameter `d` is not written on all paths",
+                "`out` parameter `g` is not written on all paths",
+            ]
+        );
+    }
+
+    #[test]
+    fn suspicious_expressions() {
+        let src = r#"
+            float4x4 m;
+            float3x3 n;
+            bool f(float a, int i, float4 v, float3 w) {
+                float4 p = mul(m, v);
+                float4 q = mul(m, p);
+                float3 r = mul(w, n);
+                float3 t = q;
+                return a == 1.0 || i == 2 || i != a || all(v == p) || t.x < r.x;
+            }
+        "#;
+
+        assert_eq!(
+            lint(src, "implicit-truncation"),
+            ["implicit truncation of `float4` to `float3`"]
+        );
+        assert_eq!(
+            lint(src, "float-equality"),
+            [
+                "floating-point values compared with `==`",
+                "floating-point values compared with `!=`",
+                "floating-point values compared with `==`",
+            ]
+        );
+        assert_eq!(
+            lint(src, "mul-order"),
+            ["`mul(float3, float3x3)` treats the vector as a row vector, while other calls treat it as a column vector"]
+        );
+    }
+}
@@ -6,7 +6,9 @@ use crate::{ast, parser};
 
 use hlsl_lang_lexer::{HasLexerError, LangLexer, Token};
 
-pub use hlsl_lang_lexer::{ParseContext, ParseContextData, ParseOptions};
+pub use hlsl_lang_lexer::{
+    HlslVersion, ParseContext, ParseContextData, ParseOptions, ShaderModel, ShaderStage,
+};
 
 mod builder;
 pub use builder::*;
@@ -69,6 +69,14 @@ impl<'i, 'o, 'c, 'p, L: hlsl_lang_lexer::LangLexer<'i>, T: HasParser> ParseBuild
             Default::default()
         };
 
+        // The parser gates version-specific syntax on the context
+        cloned_context
+            .data_mut()
+            .set_hlsl_version(opts.hlsl_version);
+        cloned_context
+            .data_mut()
+            .set_shader_model(opts.shader_model);
+
         // Create the lexer
         let lexer = if let Some(lexer) = lexer.take() {
             lexer
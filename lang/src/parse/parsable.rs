@@ -425,37 +425,7 @@ impl Extractable<ast::TranslationUnit> for ast::InterpolationQualifier {
 
 impl Extractable<ast::TranslationUnit> for ast::ArrayedIdentifier {
     fn wrap(source: &str) -> Cow<str> {
-        format!("uniform Block {{ float x; }} {};", source).into()
-    }
-
-    fn extract(ast::TranslationUnit(extdecls): ast::TranslationUnit) -> Option<Self> {
-        if let ast::Node {
-            content:
-                ast::ExternalDeclarationData::Declaration(ast::Node {
-                    content:
-                        ast::DeclarationData::Block(ast::Block {
-                            content:
-                                ast::BlockData {
-                                    identifier: Some(a),
-                                    ..
-                                },
-                            ..
-                        }),
-                    ..
-                }),
-            ..
-        } = extdecls.into_iter().next().unwrap()
-        {
-            return Some(a);
-        }
-
-        None
-    }
-}
-
-impl Extractable<ast::TranslationUnit> for ast::PrecisionQualifier {
-    fn wrap(source: &str) -> Cow<str> {
-        format!("{} float x;", source).into()
+        format!("float x, {};", source).into()
     }
 
     fn extract(ast::TranslationUnit(extdecls): ast::TranslationUnit) -> Option<Self> {
@@ -464,34 +434,7 @@ impl Extractable<ast::TranslationUnit> for ast::PrecisionQualifier {
                 ast::ExternalDeclarationData::Declaration(ast::Node {
                     content:
                         ast::DeclarationData::InitDeclaratorList(ast::InitDeclaratorList {
-                            content:
-                                ast::InitDeclaratorListData {
-                                    head:
-                                        ast::SingleDeclaration {
-                                            content:
-                                                ast::SingleDeclarationData {
-                                                    ty:
-                                                        ast::FullySpecifiedType {
-                                                            content:
-                                                                ast::FullySpecifiedTypeData {
-                                                                    qualifier:
-                                                                        Some(ast::TypeQualifier {
-                                                                            content:
-                                                                                ast::TypeQualifierData {
-                                                                                    qualifiers,
-                                                                                },
-                                                                            ..
-                                                                        }),
-                                                                    ..
-                                                                },
-                                                            ..
-                                                        },
-                                                    ..
-                                                },
-                                            ..
-                                        },
-                                    ..
-                                },
+                            content: ast::InitDeclaratorListData { tail, .. },
                             ..
                         }),
                     ..
@@ -499,11 +442,7 @@ impl Extractable<ast::TranslationUnit> for ast::PrecisionQualifier {
             ..
         } = extdecls.into_iter().next().unwrap()
         {
-            if let ast::TypeQualifierSpecData::Precision(q) =
-                qualifiers.into_iter().next().unwrap().content
-            {
-                return Some(q);
-            }
+            return tail.into_iter().next().map(|decl| decl.content.ident);
         }
 
         None
@@ -567,63 +506,6 @@ impl Extractable<ast::TranslationUnit> for ast::StorageQualifier {
     }
 }
 
-impl Extractable<ast::TranslationUnit> for ast::LayoutQualifier {
-    fn wrap(source: &str) -> Cow<str> {
-        format!("{} float x;", source).into()
-    }
-
-    fn extract(ast::TranslationUnit(extdecls): ast::TranslationUnit) -> Option<Self> {
-        if let ast::Node {
-            content:
-                ast::ExternalDeclarationData::Declaration(ast::Node {
-                    content:
-                        ast::DeclarationData::InitDeclaratorList(ast::InitDeclaratorList {
-                            content:
-                                ast::InitDeclaratorListData {
-                                    head:
-                                        ast::SingleDeclaration {
-                                            content:
-                                                ast::SingleDeclarationData {
-                                                    ty:
-                                                        ast::FullySpecifiedType {
-                                                            content:
-                                                                ast::FullySpecifiedTypeData {
-                                                                    qualifier:
-                                                                        Some(ast::TypeQualifier {
-                                                                            content:
-                                                                                ast::TypeQualifierData {
-                                                                                    qualifiers,
-                                                                                },
-                                                                            ..
-                                                                        }),
-                                                                    ..
-                                                                },
-                                                            ..
-                                                        },
-                                                    ..
-                                                },
-                                            ..
-                                        },
-                                    ..
-                                },
-                            ..
-                        }),
-                    ..
-                }),
-            ..
-        } = extdecls.into_iter().next().unwrap()
-        {
-            if let ast::TypeQualifierSpecData::Layout(q) =
-                qualifiers.into_iter().next().unwrap().content
-            {
-                return Some(q);
-            }
-        }
-
-        None
-    }
-}
-
 impl Extractable<ast::TranslationUnit> for ast::TypeQualifier {
     fn wrap(source: &str) -> Cow<str> {
         format!("{} float x;", source).into()
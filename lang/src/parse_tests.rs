@@ -58,16 +58,15 @@ fn parse_unary_op() {
     assert_eq!(ast::UnaryOp::parse("--"), Ok(ast::UnaryOpData::Dec.into()));
 }
 
-
 #[test]
 fn parse_interpolation_qualifier() {
     assert_eq!(
-        ast::InterpolationQualifier::parse("smooth"),
-        Ok(ast::InterpolationQualifierData::Smooth.into())
+        ast::InterpolationQualifier::parse("linear"),
+        Ok(ast::InterpolationQualifierData::Linear.into())
     );
     assert_eq!(
-        ast::InterpolationQualifier::parse("flat"),
-        Ok(ast::InterpolationQualifierData::Flat.into())
+        ast::InterpolationQualifier::parse("nointerpolation"),
+        Ok(ast::InterpolationQualifierData::NoInterpolation.into())
     );
     assert_eq!(
         ast::InterpolationQualifier::parse("noperspective"),
@@ -75,22 +74,6 @@ fn parse_interpolation_qualifier() {
     );
 }
 
-#[test]
-fn parse_precision_qualifier() {
-    assert_eq!(
-        ast::PrecisionQualifier::parse("highp"),
-        Ok(ast::PrecisionQualifierData::High.into())
-    );
-    assert_eq!(
-        ast::PrecisionQualifier::parse("mediump"),
-        Ok(ast::PrecisionQualifierData::Medium.into())
-    );
-    assert_eq!(
-        ast::PrecisionQualifier::parse("lowp"),
-        Ok(ast::PrecisionQualifierData::Low.into())
-    );
-}
-
 #[test]
 fn parse_storage_qualifier() {
     assert_eq!(
@@ -125,142 +108,108 @@ fn parse_storage_qualifier() {
         ast::StorageQualifier::parse("centroid"),
         Ok(ast::StorageQualifierData::Centroid.into())
     );
-    assert_eq!(
-        ast::StorageQualifier::parse("patch"),
-        Ok(ast::StorageQualifierData::Patch.into())
-    );
     assert_eq!(
         ast::StorageQualifier::parse("sample"),
         Ok(ast::StorageQualifierData::Sample.into())
     );
     assert_eq!(
-        ast::StorageQualifier::parse("uniform"),
-        Ok(ast::StorageQualifierData::Uniform.into())
+        ast::StorageQualifier::parse("point"),
+        Ok(ast::StorageQualifierData::Point.into())
     );
-
     assert_eq!(
-        ast::StorageQualifier::parse("shared"),
-        Ok(ast::StorageQualifierData::Shared.into())
+        ast::StorageQualifier::parse("line"),
+        Ok(ast::StorageQualifierData::Line.into())
     );
     assert_eq!(
-        ast::StorageQualifier::parse("coherent"),
-        Ok(ast::StorageQualifierData::Coherent.into())
+        ast::StorageQualifier::parse("triangle"),
+        Ok(ast::StorageQualifierData::Triangle.into())
     );
     assert_eq!(
-        ast::StorageQualifier::parse("volatile"),
-        Ok(ast::StorageQualifierData::Volatile.into())
+        ast::StorageQualifier::parse("lineadj"),
+        Ok(ast::StorageQualifierData::LineAdj.into())
     );
     assert_eq!(
-        ast::StorageQualifier::parse("restrict"),
-        Ok(ast::StorageQualifierData::Restrict.into())
+        ast::StorageQualifier::parse("triangleadj"),
+        Ok(ast::StorageQualifierData::TriangleAdj.into())
     );
     assert_eq!(
-        ast::StorageQualifier::parse("readonly"),
-        Ok(ast::StorageQualifierData::ReadOnly.into())
+        ast::StorageQualifier::parse("row_major"),
+        Ok(ast::StorageQualifierData::RowMajor.into())
     );
     assert_eq!(
-        ast::StorageQualifier::parse("writeonly"),
-        Ok(ast::StorageQualifierData::WriteOnly.into())
+        ast::StorageQualifier::parse("column_major"),
+        Ok(ast::StorageQualifierData::ColumnMajor.into())
     );
     assert_eq!(
-        ast::StorageQualifier::parse("subroutine"),
-        Ok(ast::StorageQualifierData::Subroutine(vec![]).into())
-    );
-}
-
-#[test]
-fn parse_layout_qualifier_std430() {
-    let expected: ast::LayoutQualifier = ast::LayoutQualifierData {
-        ids: vec![ast::LayoutQualifierSpecData::Identifier("std430".into_node(), None).into()],
-    }
-    .into();
-
-    assert_eq!(
-        ast::LayoutQualifier::parse("layout (std430)"),
-        Ok(expected.clone())
-    );
-    assert_eq!(
-        ast::LayoutQualifier::parse("layout  (std430   )"),
-        Ok(expected.clone())
+        ast::StorageQualifier::parse("uniform"),
+        Ok(ast::StorageQualifierData::Uniform.into())
     );
     assert_eq!(
-        ast::LayoutQualifier::parse("layout \n\t (  std430  )"),
-        Ok(expected.clone())
+        ast::StorageQualifier::parse("volatile"),
+        Ok(ast::StorageQualifierData::Volatile.into())
     );
-    assert_eq!(ast::LayoutQualifier::parse("layout(std430)"), Ok(expected));
 }
 
 #[test]
-fn parse_layout_qualifier_shared() {
-    let expected: ast::LayoutQualifier = ast::LayoutQualifierData {
-        ids: vec![ast::LayoutQualifierSpecData::Shared.into()],
-    }
-    .into();
+fn parse_contextual_keywords() {
+    let tu = ast::TranslationUnit::parse(
+        "float4 sample(float4 line) { float point = line.x; return sample(line) * point; }
+        void main(triangle float3 pos[3], sample float4 color) {}",
+    )
+    .unwrap();
 
-    assert_eq!(
-        ast::LayoutQualifier::parse("layout (shared)"),
-        Ok(expected.clone())
-    );
-    assert_eq!(
-        ast::LayoutQualifier::parse("layout ( shared )"),
-        Ok(expected.clone())
-    );
-    assert_eq!(ast::LayoutQualifier::parse("layout(shared)"), Ok(expected));
-}
+    let parameters = |decl: &ast::ExternalDeclaration| match &decl.content {
+        ast::ExternalDeclarationData::FunctionDefinition(def) => (
+            def.prototype.name.0.to_string(),
+            def.prototype.parameters.clone(),
+        ),
+        other => panic!("unexpected declaration: {:?}", other),
+    };
 
-#[test]
-fn parse_layout_qualifier_list() {
-    let id_0 = ast::LayoutQualifierSpecData::Shared;
-    let id_1 = ast::LayoutQualifierSpecData::Identifier("std140".into_node(), None);
-    let id_2 = ast::LayoutQualifierSpecData::Identifier(
-        "max_vertices".into_node(),
-        Some(Box::new(ast::ExprData::IntConst(3).into())),
-    );
-    let expected: ast::LayoutQualifier = ast::LayoutQualifierData {
-        ids: vec![id_0.into(), id_1.into(), id_2.into()],
-    }
-    .into();
+    // Only keywords in qualifier position, ordinary identifiers everywhere else
+    let (name, params) = parameters(&tu.0[0]);
+    assert_eq!(name, "sample");
+    assert!(matches!(
+        &params[0].content,
+        ast::FunctionParameterDeclarationData::Named(None, d) if d.ident.ident.0 == "line"
+    ));
 
+    let (_, params) = parameters(&tu.0[1]);
+    let qualifier = |param: &ast::FunctionParameterDeclaration| match &param.content {
+        ast::FunctionParameterDeclarationData::Named(Some(q), _) => q.qualifiers[0].content.clone(),
+        other => panic!("unexpected parameter: {:?}", other),
+    };
     assert_eq!(
-        ast::LayoutQualifier::parse("layout (shared, std140, max_vertices = 3)"),
-        Ok(expected.clone())
-    );
-    assert_eq!(
-        ast::LayoutQualifier::parse("layout(shared,std140,max_vertices=3)"),
-        Ok(expected.clone())
+        qualifier(&params[0]),
+        ast::TypeQualifierSpecData::Storage(ast::StorageQualifierData::Triangle.into())
     );
     assert_eq!(
-        ast::LayoutQualifier::parse("layout\n\n\t (    shared , std140, max_vertices= 3)"),
-        Ok(expected)
+        qualifier(&params[1]),
+        ast::TypeQualifierSpecData::Storage(ast::StorageQualifierData::Sample.into())
     );
 }
 
 #[test]
 fn parse_type_qualifier() {
     let storage_qual = ast::TypeQualifierSpecData::Storage(ast::StorageQualifierData::Const.into());
-    let id_0 = ast::LayoutQualifierSpecData::Shared;
-    let id_1 = ast::LayoutQualifierSpecData::Identifier("std140".into_node(), None);
-    let id_2 = ast::LayoutQualifierSpecData::Identifier(
-        "max_vertices".into_node(),
-        Some(Box::new(ast::ExprData::IntConst(3).into())),
-    );
-    let layout_qual = ast::TypeQualifierSpecData::Layout(
-        ast::LayoutQualifierData {
-            ids: vec![id_0.into(), id_1.into(), id_2.into()],
-        }
-        .into(),
+    let interpolation_qual = ast::TypeQualifierSpecData::Interpolation(
+        ast::InterpolationQualifierData::NoInterpolation.into(),
     );
     let expected: ast::TypeQualifier = ast::TypeQualifierData {
-        qualifiers: vec![storage_qual.into(), layout_qual.into()],
+        qualifiers: vec![
+            storage_qual.into(),
+            interpolation_qual.into(),
+            ast::TypeQualifierSpecData::Precise.into(),
+        ],
     }
     .into();
 
     assert_eq!(
-        ast::TypeQualifier::parse("const layout (shared, std140, max_vertices = 3)"),
+        ast::TypeQualifier::parse("const nointerpolation precise"),
         Ok(expected.clone())
     );
     assert_eq!(
-        ast::TypeQualifier::parse("const layout(shared,std140,max_vertices=3)"),
+        ast::TypeQualifier::parse("const\n\tnointerpolation   precise"),
         Ok(expected)
     );
 }
@@ -268,6 +217,7 @@ fn parse_type_qualifier() {
 #[test]
 fn parse_struct_field_specifier() {
     let expected: ast::StructFieldSpecifier = ast::StructFieldSpecifierData {
+        attributes: None,
         qualifier: None,
         ty: ast::TypeSpecifierData {
             ty: ast::TypeSpecifierNonArrayData::Vec4.into(),
@@ -291,6 +241,7 @@ fn parse_struct_field_specifier() {
 #[test]
 fn parse_struct_field_specifier_type_name() {
     let expected: ast::StructFieldSpecifier = ast::StructFieldSpecifierData {
+        attributes: None,
         qualifier: None,
         ty: ast::TypeSpecifierData {
             ty: ast::TypeSpecifierNonArrayData::TypeName("S0238_3".into_node()).into(),
@@ -315,6 +266,7 @@ fn parse_struct_field_specifier_type_name() {
 #[test]
 fn parse_struct_field_specifier_several() {
     let expected: ast::StructFieldSpecifier = ast::StructFieldSpecifierData {
+        attributes: None,
         qualifier: None,
         ty: ast::TypeSpecifierData {
             ty: ast::TypeSpecifierNonArrayData::Vec4.into(),
@@ -338,6 +290,7 @@ fn parse_struct_field_specifier_several() {
 #[test]
 fn parse_struct_specifier_one_field() {
     let field = ast::StructFieldSpecifierData {
+        attributes: None,
         qualifier: None,
         ty: ast::TypeSpecifierData {
             ty: ast::TypeSpecifierNonArrayData::Vec4.into(),
@@ -347,8 +300,11 @@ fn parse_struct_specifier_one_field() {
         identifiers: vec!["foo".into_node()],
     };
     let expected: ast::StructSpecifier = ast::StructSpecifierData {
+        kind: ast::StructKind::Struct,
         name: Some("TestStruct".into_node()),
+        bases: vec![],
         fields: vec![field.into()],
+        methods: vec![],
     }
     .into();
 
@@ -371,6 +327,7 @@ fn get_s0238_3_ctx() -> ParseContext {
 #[test]
 fn parse_struct_specifier_multi_fields() {
     let foo_field = ast::StructFieldSpecifierData {
+        attributes: None,
         qualifier: None,
         ty: ast::TypeSpecifierData {
             ty: ast::TypeSpecifierNonArrayData::Vec4.into(),
@@ -380,6 +337,7 @@ fn parse_struct_specifier_multi_fields() {
         identifiers: vec!["foo".into_node()],
     };
     let bar = ast::StructFieldSpecifierData {
+        attributes: None,
         qualifier: None,
         ty: ast::TypeSpecifierData {
             ty: ast::TypeSpecifierNonArrayData::Float.into(),
@@ -389,6 +347,7 @@ fn parse_struct_specifier_multi_fields() {
         identifiers: vec!["bar".into_node()],
     };
     let zoo = ast::StructFieldSpecifierData {
+        attributes: None,
         qualifier: None,
         ty: ast::TypeSpecifierData {
             ty: ast::TypeSpecifierNonArrayData::UInt.into(),
@@ -398,6 +357,7 @@ fn parse_struct_specifier_multi_fields() {
         identifiers: vec!["zoo".into_node()],
     };
     let foobar = ast::StructFieldSpecifierData {
+        attributes: None,
         qualifier: None,
         ty: ast::TypeSpecifierData {
             ty: ast::TypeSpecifierNonArrayData::BVec3.into(),
@@ -407,6 +367,7 @@ fn parse_struct_specifier_multi_fields() {
         identifiers: vec!["foo_BAR_zoo3497_34".into_node()],
     };
     let s = ast::StructFieldSpecifierData {
+        attributes: None,
         qualifier: None,
         ty: ast::TypeSpecifierData {
             ty: ast::TypeSpecifierNonArrayData::TypeName("S0238_3".into_node()).into(),
@@ -416,7 +377,9 @@ fn parse_struct_specifier_multi_fields() {
         identifiers: vec!["x".into_node()],
     };
     let expected: ast::StructSpecifier = ast::StructSpecifierData {
+        kind: ast::StructKind::Struct,
         name: Some("_TestStruct_934i".into_node()),
+        bases: vec![],
         fields: vec![
             foo_field.into(),
             bar.into(),
@@ -424,6 +387,7 @@ fn parse_struct_specifier_multi_fields() {
             foobar.into(),
             s.into(),
         ],
+        methods: vec![],
     }
     .into();
 
@@ -648,19 +612,18 @@ fn parse_fully_specified_type_with_qualifier() {
     let ctx = ParseContext::new();
     let tn = ctx.add_type_name(ast::IdentifierData::from("S032_29k").into());
 
-    let qual_spec = ast::TypeQualifierSpecData::Storage(
-        ast::StorageQualifierData::Subroutine(vec![
-            ast::TypeSpecifierData::from(ast::TypeSpecifierNonArrayData::Vec2).into(),
-            ast::TypeSpecifierData::from(ast::TypeSpecifierNonArrayData::from(tn)).into(),
-        ])
-        .into(),
-    );
     let qual = ast::TypeQualifierData {
-        qualifiers: vec![qual_spec.into()],
+        qualifiers: vec![
+            ast::TypeQualifierSpecData::Interpolation(
+                ast::InterpolationQualifierData::Linear.into(),
+            )
+            .into(),
+            ast::TypeQualifierSpecData::Storage(ast::StorageQualifierData::Sample.into()).into(),
+        ],
     }
     .into();
     let ty = ast::TypeSpecifierData {
-        ty: ast::TypeSpecifierNonArrayData::Sampler.into(),
+        ty: ast::TypeSpecifierNonArrayData::from(tn).into(),
         array_specifier: None,
     };
     let expected: ast::FullySpecifiedType = ast::FullySpecifiedTypeData {
@@ -670,27 +633,17 @@ fn parse_fully_specified_type_with_qualifier() {
     .into();
 
     assert_eq!(
-        ast::FullySpecifiedType::parse_with_context(
-            "subroutine (float2, S032_29k) sampler",
-            &ctx,
-        )
-        .map(|(p, _)| p),
+        ast::FullySpecifiedType::parse_with_context("linear sample S032_29k", &ctx).map(|(p, _)| p),
         Ok(expected.clone()),
     );
     assert_eq!(
-        ast::FullySpecifiedType::parse_with_context(
-            "subroutine (  float2\t\n \t , \n S032_29k   )\n sampler ",
-            &ctx,
-        )
-        .map(|(p, _)| p),
+        ast::FullySpecifiedType::parse_with_context("linear\t\n \t sample \n S032_29k ", &ctx)
+            .map(|(p, _)| p),
         Ok(expected.clone()),
     );
     assert_eq!(
-        ast::FullySpecifiedType::parse_with_context(
-            "subroutine(float2,S032_29k)sampler",
-            &ctx,
-        )
-        .map(|(p, _)| p),
+        ast::FullySpecifiedType::parse_with_context("linear/**/sample/**/S032_29k", &ctx)
+            .map(|(p, _)| p),
         Ok(expected),
     );
 }
@@ -779,6 +732,629 @@ fn parse_primary_expr_boolconst() {
     );
 }
 
+#[test]
+fn parse_primary_expr_charconst() {
+    assert_eq!(
+        ast::Expr::parse("'A'"),
+        Ok(ast::ExprData::CharConst('A').into())
+    );
+    assert_eq!(
+        ast::Expr::parse("'\\t'"),
+        Ok(ast::ExprData::CharConst('\t').into())
+    );
+    assert_eq!(
+        ast::Expr::parse("'\\''"),
+        Ok(ast::ExprData::CharConst('\'').into())
+    );
+}
+
+#[test]
+fn parse_primary_expr_stringconst() {
+    assert_eq!(
+        ast::Expr::parse("\"it's a string\""),
+        Ok(ast::ExprData::StringConst("it's a string".to_owned()).into())
+    );
+    assert_eq!(
+        ast::Expr::parse("\"a \\\"quoted\\\" string\""),
+        Ok(ast::ExprData::StringConst("a \\\"quoted\\\" string".to_owned()).into())
+    );
+    assert_eq!(
+        ast::Expr::parse("\"a\" \"b\""),
+        Ok(ast::ExprData::StringConcat(vec![
+            ast::ExprData::StringConst("a".to_owned()).into(),
+            ast::ExprData::StringConst("b".to_owned()).into(),
+        ])
+        .into())
+    );
+}
+
+#[test]
+fn parse_function_call_string_args() {
+    assert_eq!(
+        ast::Expr::parse("printf(\"%d %c\", 1, 'x')"),
+        Ok(ast::ExprData::FunCall(
+            ast::FunIdentifierData::ident("printf").into(),
+            vec![
+                ast::ExprData::StringConst("%d %c".to_owned()).into(),
+                ast::ExprData::IntConst(1).into(),
+                ast::ExprData::CharConst('x').into(),
+            ]
+        )
+        .into())
+    );
+}
+
+#[test]
+fn parse_attribute_string_args() {
+    let fndef = ast::FunctionDefinition::parse("[domain(\"quad\")] void main() {}").unwrap();
+
+    assert_eq!(
+        fndef.attributes,
+        Some(vec![ast::AttributeData {
+            syntax: ast::AttributeSyntax::Bracket,
+            spec: ast::AttributeSpecData {
+                namespace: None,
+                name: "domain".into_node(),
+                params: Some(vec![ast::ExprData::StringConst("quad".to_owned()).into()]),
+            }
+            .into(),
+        }
+        .into()])
+    );
+}
+
+#[test]
+fn parse_cxx11_attributes() {
+    let binding: ast::Attribute = ast::AttributeData {
+        syntax: ast::AttributeSyntax::DoubleBracket,
+        spec: ast::AttributeSpecData {
+            namespace: Some("vk".into_node()),
+            name: "binding".into_node(),
+            params: Some(vec![
+                ast::ExprData::IntConst(0).into(),
+                ast::ExprData::IntConst(1).into(),
+            ]),
+        }
+        .into(),
+    }
+    .into();
+
+    let tu = ast::TranslationUnit::parse(
+        "[[vk::binding(0, 1)]] Texture2D tex;
+        [[vk::push_constant]] cbuffer pc { [[vk::offset(4)]] float a; };
+        [[vk::location(7), fast]] float4 main([[vk::location(8)]] in float4 i : A) : B { return i; }",
+    )
+    .unwrap();
+
+    let mut decls = tu.0.iter().map(|decl| &**decl);
+
+    match decls.next() {
+        Some(ast::ExternalDeclarationData::Declaration(decl)) => match &**decl {
+            ast::DeclarationData::InitDeclaratorList(list) => {
+                assert_eq!(list.head.attributes, Some(vec![binding]));
+            }
+            other => panic!("unexpected declaration: {:?}", other),
+        },
+        other => panic!("unexpected declaration: {:?}", other),
+    }
+
+    match decls.next() {
+        Some(ast::ExternalDeclarationData::Declaration(decl)) => match &**decl {
+            ast::DeclarationData::CBuffer(cbuffer) => {
+                let attrs = cbuffer.attributes.as_ref().unwrap();
+                assert!(attrs[0].spec.is(Some("vk"), "push_constant"));
+                assert_eq!(attrs[0].spec.params, None);
+
+                let field_attrs = cbuffer.fields[0].attributes.as_ref().unwrap();
+                assert!(field_attrs[0].spec.is(Some("vk"), "offset"));
+            }
+            other => panic!("unexpected declaration: {:?}", other),
+        },
+        other => panic!("unexpected declaration: {:?}", other),
+    }
+
+    match decls.next() {
+        Some(ast::ExternalDeclarationData::FunctionDefinition(fndef)) => {
+            let attrs = fndef.attributes.as_ref().unwrap();
+            assert_eq!(attrs.len(), 2);
+            assert!(attrs[0].spec.is(Some("vk"), "location"));
+            assert!(attrs[1].spec.is(None, "fast"));
+            assert_eq!(attrs[1].syntax, ast::AttributeSyntax::DoubleBracket);
+
+            // Each attribute of a list spans its own specification
+            let text = |attr: &ast::Attribute| {
+                let range = attr.span.unwrap().range();
+                u32::from(range.end() - range.start())
+            };
+            assert_eq!(text(&attrs[0]), "vk::location(7)".len() as u32);
+            assert_eq!(text(&attrs[1]), "fast".len() as u32);
+
+            match &*fndef.prototype.parameters[0] {
+                ast::FunctionParameterDeclarationData::Named(qualifier, declarator) => {
+                    assert!(qualifier.is_some());
+                    let attrs = declarator.attributes.as_ref().unwrap();
+                    assert!(attrs[0].spec.is(Some("vk"), "location"));
+                }
+                other => panic!("unexpected parameter: {:?}", other),
+            }
+        }
+        other => panic!("unexpected declaration: {:?}", other),
+    }
+}
+
+#[test]
+fn parse_cxx11_attribute_namespace_separator() {
+    let tu = ast::TranslationUnit::parse(
+        "#define BINDING [[vk::binding(0)]]
+        BINDING Texture2D tex;",
+    )
+    .unwrap();
+    let ast::ExternalDeclarationData::Declaration(decl) = &*tu.0[0] else {
+        panic!("unexpected declaration: {:?}", tu.0[0]);
+    };
+    let ast::DeclarationData::InitDeclaratorList(list) = &**decl else {
+        panic!("unexpected declaration: {:?}", decl);
+    };
+    assert!(list.head.attributes.as_ref().unwrap()[0]
+        .spec
+        .is(Some("vk"), "binding"));
+
+    for invalid in [
+        "[[vk: :binding(0)]] Texture2D tex;",
+        "[[vk:\n:binding(0)]] Texture2D tex;",
+        "[[vk:/**/:binding(0)]] Texture2D tex;",
+    ] {
+        assert!(ast::TranslationUnit::parse(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn parse_default_parameter_values() {
+    let fndef = ast::FunctionDefinition::parse(
+        "float4 Shade(float3 n, float ambient = 0.1, int2 o : OFFSET = { 1, 2 }) { return 0; }",
+    )
+    .unwrap();
+    let proto = &fndef.prototype;
+
+    assert_eq!(proto.parameters[0].default_value(), None);
+    assert_eq!(
+        proto.parameters[1].default_value(),
+        Some(&ast::InitializerData::Simple(Box::new(ast::ExprData::FloatConst(0.1).into())).into())
+    );
+    assert!(matches!(
+        proto.parameters[2].default_value().map(|init| &**init),
+        Some(ast::InitializerData::List(_))
+    ));
+
+    assert_eq!(proto.required_parameter_count(), 1);
+
+    // Too few or too many arguments
+    assert_eq!(proto.omitted_arguments(0), None);
+    assert_eq!(proto.omitted_arguments(4), None);
+
+    // All arguments provided
+    assert_eq!(proto.omitted_arguments(3), Some(vec![]));
+
+    // Trailing arguments filled by their default values
+    let omitted = proto.omitted_arguments(1).unwrap();
+    assert_eq!(
+        omitted.iter().map(|arg| arg.index).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!(Some(omitted[0].value), proto.parameters[1].default_value());
+    assert_eq!(omitted[1].parameter, &proto.parameters[2]);
+}
+
+#[test]
+fn parse_struct_name_scope() {
+    // A struct name is visible in its body and after it, until its enclosing block ends
+    ast::TranslationUnit::parse(
+        "struct A { float x; A next; }; A a;
+        void f() { struct B { A a; B next; }; B b; { struct C { B b; }; C c; } }",
+    )
+    .unwrap();
+
+    for invalid in [
+        "void f() { struct B { float x; }; } B b;",
+        "void f() { { struct C { float x; }; } C c; }",
+    ] {
+        assert!(ast::TranslationUnit::parse(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn parse_struct_inheritance_and_interfaces() {
+    let tu = ast::TranslationUnit::parse(
+        "interface ILight { float3 Illuminate(float3 n); };
+        struct Base { float a; };
+        class PointLight : ILight, Base {
+            float3 color;
+            float3 Illuminate(float3 n) { return color; }
+            float Falloff();
+        };
+        float PointLight::Falloff() { return a; }",
+    )
+    .unwrap();
+
+    let structs: Vec<_> =
+        tu.0.iter()
+            .filter_map(|decl| match &**decl {
+                ast::ExternalDeclarationData::Declaration(decl) => match &**decl {
+                    ast::DeclarationData::InitDeclaratorList(list) => match &*list.head.ty.ty.ty {
+                        ast::TypeSpecifierNonArrayData::Struct(s) => Some(s),
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
+    assert_eq!(structs.len(), 3);
+
+    let interface = structs[0];
+    assert_eq!(interface.kind, ast::StructKind::Interface);
+    assert!(interface.fields.is_empty());
+    assert_eq!(interface.methods.len(), 1);
+    assert_eq!(interface.methods[0].prototype.name.as_str(), "Illuminate");
+    assert_eq!(interface.methods[0].statement, None);
+
+    assert_eq!(structs[1].kind, ast::StructKind::Struct);
+    assert!(structs[1].bases.is_empty());
+
+    let class = structs[2];
+    assert_eq!(class.kind, ast::StructKind::Class);
+    assert_eq!(
+        class.bases.iter().map(|b| b.as_str()).collect::<Vec<_>>(),
+        vec!["ILight", "Base"]
+    );
+    assert_eq!(class.fields.len(), 1);
+    assert_eq!(class.methods.len(), 2);
+    assert!(class.methods[0].statement.is_some());
+    assert!(class.methods[1].statement.is_none());
+
+    match &*tu.0[3] {
+        ast::ExternalDeclarationData::FunctionDefinition(fndef) => {
+            assert_eq!(
+                fndef.prototype.scope.as_ref().map(|s| s.as_str()),
+                Some("PointLight")
+            );
+            assert_eq!(fndef.prototype.name.as_str(), "Falloff");
+        }
+        other => panic!("unexpected declaration: {:?}", other),
+    }
+}
+
+#[test]
+fn parse_operator_overloads() {
+    let tu = ast::TranslationUnit::parse(
+        "struct Vec {
+            float x;
+            Vec operator+(Vec rhs) { Vec r; r.x = x + rhs.x; return r; }
+            Vec operator-();
+            bool operator<=(Vec rhs);
+            float operator[](uint i);
+            float operator()(float a, float b);
+        };",
+    )
+    .unwrap();
+
+    let methods = match &*tu.0[0] {
+        ast::ExternalDeclarationData::Declaration(decl) => match &**decl {
+            ast::DeclarationData::InitDeclaratorList(list) => match &*list.head.ty.ty.ty {
+                ast::TypeSpecifierNonArrayData::Struct(s) => s.methods.clone(),
+                other => panic!("unexpected type: {:?}", other),
+            },
+            other => panic!("unexpected declaration: {:?}", other),
+        },
+        other => panic!("unexpected declaration: {:?}", other),
+    };
+
+    let operators: Vec<_> = methods
+        .iter()
+        .map(|m| {
+            (
+                m.prototype.name.as_str().to_owned(),
+                m.prototype.operator.as_ref().map(|o| o.content.clone()),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        operators,
+        vec![
+            (
+                "operator+".to_owned(),
+                Some(ast::OverloadedOperatorData::Binary(
+                    ast::BinaryOpData::Add.into()
+                ))
+            ),
+            (
+                "operator-".to_owned(),
+                Some(ast::OverloadedOperatorData::Unary(
+                    ast::UnaryOpData::Minus.into()
+                ))
+            ),
+            (
+                "operator<=".to_owned(),
+                Some(ast::OverloadedOperatorData::Binary(
+                    ast::BinaryOpData::Lte.into()
+                ))
+            ),
+            (
+                "operator[]".to_owned(),
+                Some(ast::OverloadedOperatorData::Subscript)
+            ),
+            (
+                "operator()".to_owned(),
+                Some(ast::OverloadedOperatorData::Call)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn parse_bitfields() {
+    let field = ast::StructFieldSpecifier::parse("uint flags : 4, mode : 2u, rest;").unwrap();
+
+    assert_eq!(
+        field
+            .identifiers
+            .iter()
+            .map(|i| i.bit_width.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            Some(ast::ExprData::IntConst(4).into()),
+            Some(ast::ExprData::UIntConst(2).into()),
+            None,
+        ]
+    );
+
+    // Semantics are still parsed as such
+    let field = ast::StructFieldSpecifier::parse("float4 pos : SV_Position;").unwrap();
+    assert_eq!(field.identifiers[0].bit_width, None);
+    assert!(field.identifiers[0].semantic.is_some());
+}
+
+#[test]
+fn parse_semantics() {
+    let semantics = |src: &str| {
+        ast::StructFieldSpecifier::parse(src)
+            .unwrap()
+            .identifiers
+            .iter()
+            .map(|i| i.semantic.as_ref().unwrap().content.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let parsed = semantics("float4 a : TEXCOORD7, b : SV_Target3, c : sv_position, d : COLOR;");
+    assert_eq!(
+        parsed,
+        vec![
+            ast::SemanticData::new("TEXCOORD", Some(7)),
+            ast::SemanticData::new("SV_Target", Some(3)),
+            ast::SemanticData::new("sv_position", None),
+            ast::SemanticData::new("COLOR", None),
+        ]
+    );
+
+    assert_eq!(parsed[0].system_value(), None);
+    assert_eq!(parsed[1].system_value(), Some(ast::SystemValue::Target));
+    assert_eq!(parsed[1].to_string(), "SV_Target3");
+    assert_eq!(parsed[2].system_value(), Some(ast::SystemValue::Position));
+    assert_eq!(parsed[2].index(), 0);
+    assert!(!parsed[3].is_system_value());
+
+    // System values which used to only lex as plain identifiers
+    assert_eq!(
+        semantics("uint3 id : SV_DispatchThreadID, clip : SV_ClipDistance1;")
+            .iter()
+            .map(|s| (s.system_value(), s.index))
+            .collect::<Vec<_>>(),
+        vec![
+            (Some(ast::SystemValue::DispatchThreadId), None),
+            (Some(ast::SystemValue::ClipDistance), Some(1)),
+        ]
+    );
+}
+
+#[test]
+fn parse_templates() {
+    let tu = ast::TranslationUnit::parse(
+        "template<typename T> T twice(T x) { return x * 2; }
+        template<typename K, class V> struct Pair { K key; V value; };
+        float T;",
+    )
+    .unwrap();
+
+    let templates: Vec<_> =
+        tu.0.iter()
+            .filter_map(|decl| match &**decl {
+                ast::ExternalDeclarationData::Template(template) => Some(template),
+                _ => None,
+            })
+            .collect();
+    assert_eq!(templates.len(), 2);
+
+    let parameters: Vec<Vec<_>> = templates
+        .iter()
+        .map(|t| t.parameters.iter().map(|p| p.as_str()).collect())
+        .collect();
+    assert_eq!(parameters, vec![vec!["T"], vec!["K", "V"]]);
+
+    assert!(matches!(
+        **templates[0].declaration,
+        ast::ExternalDeclarationData::FunctionDefinition(_)
+    ));
+    assert!(matches!(
+        **templates[1].declaration,
+        ast::ExternalDeclarationData::Declaration(_)
+    ));
+
+    // Type parameters are only type names within their template
+    assert!(matches!(
+        *tu.0[2],
+        ast::ExternalDeclarationData::Declaration(_)
+    ));
+}
+
+#[test]
+fn parse_hlsl_2021_syntax_gate() {
+    let opts = parse::ParseOptions {
+        hlsl_version: parse::HlslVersion::V2018,
+        ..Default::default()
+    };
+
+    assert!(ast::StructFieldSpecifier::parse_with_options("uint flags : 4;", &opts).is_err());
+    assert!(ast::StructSpecifier::parse_with_options(
+        "struct Vec { float x; Vec operator+(Vec rhs); }",
+        &opts
+    )
+    .is_err());
+
+    // Older syntax is unaffected
+    assert!(ast::StructFieldSpecifier::parse_with_options("float4 pos : POSITION;", &opts).is_ok());
+
+    assert!(ast::TranslationUnit::parse_with_options(
+        "template<typename T> T twice(T x) { return x * 2; }",
+        &opts
+    )
+    .is_err());
+
+    // `operator` is only a keyword from HLSL 2021
+    assert!(ast::Declaration::parse_with_options("float operator = 1.0;", &opts).is_ok());
+    assert!(ast::Declaration::parse("float operator = 1.0;").is_err());
+}
+
+#[cfg(feature = "lexer-full")]
+#[test]
+fn parse_shader_target_macros() {
+    let src = r#"
+#if __HLSL_VERSION >= 2021 && __SHADER_TARGET_STAGE == __SHADER_STAGE_PIXEL && __SHADER_TARGET_MAJOR == 6
+float4 main() : SV_Target { return 0; }
+#else
+float4 fallback() : SV_Target { return 0; }
+#endif
+"#;
+
+    let function_name = |opts: &parse::ParseOptions| {
+        let (tu, _) = ast::TranslationUnit::parse_with_options(src, opts).unwrap();
+        match &tu.0[0].content {
+            ast::ExternalDeclarationData::FunctionDefinition(def) => {
+                def.prototype.name.0.to_string()
+            }
+            other => panic!("unexpected declaration: {:?}", other),
+        }
+    };
+
+    assert_eq!(
+        function_name(&parse::ParseOptions {
+            shader_model: Some("ps_6_6".parse().unwrap()),
+            ..Default::default()
+        }),
+        "main"
+    );
+    assert_eq!(
+        function_name(&parse::ParseOptions {
+            hlsl_version: parse::HlslVersion::V2018,
+            shader_model: Some("ps_6_6".parse().unwrap()),
+            ..Default::default()
+        }),
+        "fallback"
+    );
+    assert_eq!(
+        function_name(&parse::ParseOptions {
+            shader_model: Some("vs_6_0".parse().unwrap()),
+            ..Default::default()
+        }),
+        "fallback"
+    );
+}
+
+#[test]
+fn parse_packoffset() {
+    let field =
+        ast::StructFieldSpecifier::parse("float2 uv : packoffset(c4.y), st : packoffset(c5);")
+            .unwrap();
+
+    let first = field.identifiers[0].packoffset.as_ref().unwrap();
+    assert_eq!(first.index, ast::ExprData::IntConst(4).into());
+    assert_eq!(first.component, Some("y".into_node()));
+    // The index spans `c4` rather than the whole clause
+    assert_eq!(u32::from(first.index.span.unwrap().len()), 2);
+
+    let second = field.identifiers[1].packoffset.as_ref().unwrap();
+    assert_eq!(second.index, ast::ExprData::IntConst(5).into());
+    assert_eq!(second.component, None);
+
+    for invalid in [
+        "float a : packoffset(cfoo);",
+        "float a : packoffset(t3);",
+        "float a : packoffset(c);",
+        "float a : packoffset(c1.q);",
+        "float a : packoffset(c1.xy);",
+    ] {
+        assert!(
+            ast::StructFieldSpecifier::parse(invalid).is_err(),
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn parse_register() {
+    let binding = |src| match ast::Declaration::parse(src).unwrap().content {
+        ast::DeclarationData::CBuffer(cbuffer) => cbuffer.content.resource_binding.unwrap(),
+        other => panic!("unexpected declaration: {:?}", other),
+    };
+
+    let reg = binding("cbuffer CB : register(b4294967295, space1) { float x; }");
+    assert_eq!(reg.register_type, "b".into_node());
+    assert_eq!(reg.index, ast::ExprData::UIntConst(u32::MAX).into());
+    assert_eq!(reg.space, Some(ast::ExprData::IntConst(1).into()));
+
+    for invalid in [
+        "cbuffer CB : register(b99999999999) { float x; }",
+        "cbuffer CB : register(b4294967296) { float x; }",
+        "cbuffer CB : register(b) { float x; }",
+        "cbuffer CB : register(b-1) { float x; }",
+        "cbuffer CB : register(bfoo) { float x; }",
+    ] {
+        assert!(ast::Declaration::parse(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn parse_cast_expr() {
+    let float3: ast::TypeSpecifier =
+        ast::TypeSpecifierData::from(ast::TypeSpecifierNonArrayData::Vec3).into();
+    let foo: Box<ast::Expr> = Box::new(ast::ExprData::variable("foo").into());
+
+    assert_eq!(
+        ast::Expr::parse("(float3)foo"),
+        Ok(ast::ExprData::Cast(float3.clone(), foo.clone()).into())
+    );
+    assert_eq!(
+        ast::Expr::parse("-(float3)foo"),
+        Ok(ast::ExprData::Unary(
+            ast::UnaryOpData::Minus.into(),
+            Box::new(ast::ExprData::Cast(float3.clone(), foo.clone()).into())
+        )
+        .into())
+    );
+
+    // Constructors are still parsed as function calls
+    assert_eq!(
+        ast::Expr::parse("(float3(foo))"),
+        Ok(ast::ExprData::FunCall(
+            ast::FunIdentifierData::TypeSpecifier(Box::new(float3)).into(),
+            vec![*foo]
+        )
+        .into())
+    );
+}
+
 #[test]
 fn parse_primary_expr_parens() {
     assert_eq!(
@@ -1217,6 +1793,7 @@ fn parse_declaration_init_declarator_list_single() {
         .into(),
     };
     let sd = ast::SingleDeclarationData {
+        attributes: None,
         ty: ty.into(),
         name: Some("foo".into_node()),
         array_specifier: None,
@@ -1254,6 +1831,7 @@ fn parse_declaration_init_declarator_list_complex() {
         .into(),
     };
     let sd = ast::SingleDeclarationData {
+        attributes: None,
         ty: ty.into(),
         name: Some("foo".into_node()),
         array_specifier: None,
@@ -1292,51 +1870,6 @@ fn parse_declaration_init_declarator_list_complex() {
     );
 }
 
-#[test]
-fn parse_declaration_precision_low() {
-    let qual = ast::PrecisionQualifierData::Low;
-    let ty = ast::TypeSpecifierData {
-        ty: ast::TypeSpecifierNonArrayData::Float.into(),
-        array_specifier: None,
-    };
-    let expected: ast::Declaration = ast::DeclarationData::Precision(qual.into(), ty.into()).into();
-
-    assert_eq!(
-        ast::Declaration::parse("precision lowp float;"),
-        Ok(expected)
-    );
-}
-
-#[test]
-fn parse_declaration_precision_medium() {
-    let qual = ast::PrecisionQualifierData::Medium;
-    let ty = ast::TypeSpecifierData {
-        ty: ast::TypeSpecifierNonArrayData::Float.into(),
-        array_specifier: None,
-    };
-    let expected: ast::Declaration = ast::DeclarationData::Precision(qual.into(), ty.into()).into();
-
-    assert_eq!(
-        ast::Declaration::parse("precision mediump float;"),
-        Ok(expected)
-    );
-}
-
-#[test]
-fn parse_declaration_precision_high() {
-    let qual = ast::PrecisionQualifierData::High;
-    let ty = ast::TypeSpecifierData {
-        ty: ast::TypeSpecifierNonArrayData::Float.into(),
-        array_specifier: None,
-    };
-    let expected: ast::Declaration = ast::DeclarationData::Precision(qual.into(), ty.into()).into();
-
-    assert_eq!(
-        ast::Declaration::parse("precision highp float;"),
-        Ok(expected)
-    );
-}
-
 
 
 #[test]
@@ -1415,6 +1948,50 @@ fn parse_selection_statement_if_else() {
     );
 }
 
+#[test]
+fn parse_attributed_statement() {
+    let st = ast::Statement::parse("[unroll(4)] [fastopt] for (;;) {}").unwrap();
+    let ast::StatementData::Attributed(attributes, inner) = &*st else {
+        panic!("expected an attributed statement, found {:?}", st);
+    };
+    assert_eq!(
+        attributes
+            .iter()
+            .map(|attr| attr.spec.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["unroll", "fastopt"]
+    );
+    assert_eq!(
+        attributes[0].spec.params,
+        Some(vec![ast::ExprData::IntConst(4).into()])
+    );
+    assert!(matches!(***inner, ast::StatementData::Iteration(_)));
+
+    // The else belongs to the attributed inner `if`
+    let st = ast::Statement::parse("if (a) [branch] if (b) {} else {}").unwrap();
+    let ast::StatementData::Selection(outer) = &*st else {
+        panic!("expected a selection statement, found {:?}", st);
+    };
+    let ast::SelectionRestStatementData::Statement(then) = &*outer.rest else {
+        panic!("expected the outer `if` not to have an else branch");
+    };
+    assert!(matches!(***then, ast::StatementData::Attributed(..)));
+
+    for valid in [
+        "[loop] while (true) {}",
+        "[unroll] do {} while (false);",
+        "[flatten] if (a) {} else {}",
+        "[forcecase] switch (a) {}",
+    ] {
+        assert!(ast::Statement::parse(valid).is_ok(), "{}", valid);
+    }
+
+    // Only control flow statements may have attributes
+    for invalid in ["[unroll] a = 1;", "[branch] { }", "[loop] return;"] {
+        assert!(ast::Statement::parse(invalid).is_err(), "{}", invalid);
+    }
+}
+
 #[test]
 fn parse_switch_statement_empty() {
     let head = Box::new(ast::ExprData::Variable("foo".into_node()).into());
@@ -1556,6 +2133,7 @@ fn parse_iteration_statement_for_empty() {
         ast::DeclarationData::InitDeclaratorList(
             ast::InitDeclaratorListData {
                 head: ast::SingleDeclarationData {
+                    attributes: None,
                     ty: ast::FullySpecifiedTypeData {
                         qualifier: None,
                         ty: ast::TypeSpecifierData {
@@ -1709,6 +2287,7 @@ fn parse_compound_statement() {
         ast::DeclarationData::InitDeclaratorList(
             ast::InitDeclaratorListData {
                 head: ast::SingleDeclarationData {
+                    attributes: None,
                     ty: ast::FullySpecifiedTypeData {
                         qualifier: None,
                         ty: ast::TypeSpecifierData {
@@ -1761,7 +2340,9 @@ fn parse_function_definition() {
     };
     let fp = ast::FunctionPrototypeData {
         ty: rt.into(),
+        scope: None,
         name: "foo".into_node(),
+        operator: None,
         parameters: Vec::new(),
         semantic: None,
     }
@@ -2240,7 +2821,7 @@ fn parse_pp_extension() {
 #[test]
 fn parse_pp_several() {
     let external_declarations =
-        parse_pp("#version 110\ninvariant x;\n#pragma once\n#pragma twice\ninvariant y;")
+        parse_pp("#version 110\nfloat x;\n#pragma once\n#pragma twice\nfloat y;")
             .unwrap()
             .collect::<Vec<_>>();
 
@@ -2256,10 +2837,8 @@ fn parse_pp_several() {
             .into(),
         )
         .into(),
-        ast::ExternalDeclarationData::Declaration(
-            ast::DeclarationData::Invariant(ast::IdentifierData("x".into()).into()).into(),
-        )
-        .into(),
+        ast::ExternalDeclarationData::Declaration(ast::Declaration::parse("float x;").unwrap())
+            .into(),
         ast::ExternalDeclarationData::Preprocessor(
             ast::PreprocessorData::Pragma(
                 ast::PreprocessorPragmaData {
@@ -2280,10 +2859,8 @@ fn parse_pp_several() {
             .into(),
         )
         .into(),
-        ast::ExternalDeclarationData::Declaration(
-            ast::DeclarationData::Invariant(ast::IdentifierData("y".into()).into()).into(),
-        )
-        .into(),
+        ast::ExternalDeclarationData::Declaration(ast::Declaration::parse("float y;").unwrap())
+            .into(),
     ];
 
     assert_eq!(external_declarations, expected_external_declarations);
@@ -2343,6 +2920,7 @@ fn parse_dot_field_expr_statement() {
         .into(),
     ));
     let sd = ast::SingleDeclarationData {
+        attributes: None,
         ty: ast::FullySpecifiedTypeData {
             qualifier: None,
             ty: ast::TypeSpecifierData {
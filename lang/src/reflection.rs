@@ -0,0 +1,11 @@
+//! Shader reflection
+//!
+//! Modules in this namespace extract what an application needs to know to feed a shader: the
+//! memory layout of its constant buffers, its resource bindings and its stage interface. The
+//! results are plain data structures, which can be serialized when the `serde` feature is
+//! enabled, e.g. to generate the application side mirrors of shader declarations.
+
+pub mod bindings;
+pub mod cbuffer;
+pub mod link;
+pub mod signature;
@@ -0,0 +1,522 @@
+//! Resource bindings
+//!
+//! [resources] lists the constant buffers, shader resource views, unordered access views and
+//! samplers declared at global scope, in declaration order, along with the register they are
+//! bound to by a `register(...)` annotation. Arrays of resources occupy one register per
+//! element, and an array declared with an unsized first dimension occupies every register from
+//! its base to the end of its space.
+//!
+//! Resources without an annotation are given a register by [assign_registers], which follows
+//! the default allocation of DXC: explicitly bound ranges are reserved first, then unbound
+//! resources are given, in declaration order, the lowest free range of their class which is
+//! large enough, and unbounded arrays are placed last, after every other range of their space.
+//! Unlike DXC, resources which are not referenced by the entry point are not eliminated
+//! beforehand, and the implicit `$Globals` constant buffer is not listed.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, parse::Parsable, reflection::bindings, resolver};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     "Texture2D albedo : register(t0); Texture2D normals[2]; Texture2D shadow;",
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//! let mut resources = bindings::resources(&symbols);
+//! bindings::assign_registers(&mut resources, 0);
+//!
+//! let registers: Vec<_> = resources
+//!     .iter()
+//!     .map(|res| (res.name.as_str(), res.register.unwrap().to_string(), res.assigned))
+//!     .collect();
+//! assert_eq!(
+//!     registers,
+//!     [
+//!         ("albedo", "t0".to_owned(), false),
+//!         ("normals", "t1".to_owned(), true),
+//!         ("shadow", "t3".to_owned(), true),
+//!     ]
+//! );
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+#[cfg(feature = "serde")]
+use rserde::{Deserialize, Serialize};
+
+use crate::{
+    ast::{self, NodeSpan},
+    resolver::{Declarator, SymbolKind, SymbolTable},
+    root_signature::{DescriptorCount, Register, RegisterClass},
+    semantic::{
+        check::symbol_type,
+        consteval,
+        types::{ObjectKind, Type},
+    },
+};
+
+/// Kind of a bound resource
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum ResourceKind {
+    /// `cbuffer` block
+    CBuffer,
+    /// Texture, buffer or sampler object
+    Object(ObjectKind),
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CBuffer => f.write_str("cbuffer"),
+            Self::Object(kind) => kind.fmt(f),
+        }
+    }
+}
+
+impl ResourceKind {
+    /// Class of the registers this resource is bound to
+    pub fn register_class(self) -> RegisterClass {
+        match self {
+            Self::CBuffer | Self::Object(ObjectKind::ConstantBuffer) => {
+                RegisterClass::ConstantBuffer
+            }
+            Self::Object(kind) if kind.is_sampler() => RegisterClass::Sampler,
+            Self::Object(kind) if kind.is_rw() => RegisterClass::UnorderedAccess,
+            Self::Object(_) => RegisterClass::ShaderResource,
+        }
+    }
+}
+
+/// A resource declared at global scope
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct ResourceBinding {
+    /// Name of the resource
+    pub name: String,
+    /// Kind of resource
+    pub kind: ResourceKind,
+    /// Element type, e.g. `float4` for `StructuredBuffer<float4>`, if the resource has one
+    pub element: Option<String>,
+    /// Number of registers occupied by the resource. Multi-dimensional arrays are flattened.
+    pub count: DescriptorCount,
+    /// Register of the resource, or of its first element for arrays. `None` until assigned if
+    /// the resource has no valid `register(...)` annotation.
+    pub register: Option<Register>,
+    /// `true` if the register was given by [assign_registers] rather than declared
+    pub assigned: bool,
+    /// Span of the resource name
+    pub span: Option<NodeSpan>,
+}
+
+impl ResourceBinding {
+    /// Class of the registers this resource is bound to
+    pub fn register_class(&self) -> RegisterClass {
+        self.kind.register_class()
+    }
+}
+
+/// Resources declared at the global scope of a resolved translation unit, in declaration order
+pub fn resources(symbols: &SymbolTable<'_>) -> Vec<ResourceBinding> {
+    annotated_resources(symbols)
+        .into_iter()
+        .map(|(res, _)| res)
+        .collect()
+}
+
+/// Resources declared at global scope, along with their `register(...)` annotation
+pub(crate) fn annotated_resources<'a>(
+    symbols: &SymbolTable<'a>,
+) -> Vec<(ResourceBinding, Option<&'a ast::ResourceBinding>)> {
+    let Some(global) = symbols.scopes().first() else {
+        return Vec::new();
+    };
+
+    global
+        .symbols
+        .iter()
+        .filter_map(|&id| {
+            let symbol = symbols.symbol(id);
+            let (kind, element, count, binding) = match symbol.declarator {
+                Declarator::CBuffer(cbuffer) => (
+                    ResourceKind::CBuffer,
+                    None,
+                    DescriptorCount::Bounded(1),
+                    cbuffer.resource_binding.as_ref(),
+                ),
+                declarator if symbol.kind == SymbolKind::Global => {
+                    let (kind, element, count) = object(&symbol_type(symbols, id))?;
+                    let binding = match declarator {
+                        Declarator::Variable(decl) => decl.resource_binding.as_ref(),
+                        Declarator::SamplerState(sampler) => sampler.resource_binding.as_ref(),
+                        _ => None,
+                    };
+
+                    (ResourceKind::Object(kind), element, count, binding)
+                }
+                _ => return None,
+            };
+
+            let res = ResourceBinding {
+                name: symbol.name.to_string(),
+                kind,
+                element,
+                count,
+                register: binding.and_then(|binding| register(binding, symbols)),
+                assigned: false,
+                span: symbol.span,
+            };
+
+            Some((res, binding))
+        })
+        .collect()
+}
+
+/// Assign a register in `space` to every resource which does not have one, following the
+/// default allocation of DXC. Assigned resources have their `assigned` flag set.
+pub fn assign_registers(resources: &mut [ResourceBinding], space: u32) {
+    let mut allocators: HashMap<(RegisterClass, u32), Allocator> = HashMap::new();
+
+    for res in resources.iter() {
+        if let Some(reg) = res.register {
+            allocators
+                .entry((reg.class, reg.space))
+                .or_default()
+                .reserve(reg.index, res.count);
+        }
+    }
+
+    // Bounded resources first, then unbounded arrays after everything else
+    for unbounded in [false, true] {
+        for res in resources.iter_mut() {
+            if res.register.is_some() || (res.count == DescriptorCount::Unbounded) != unbounded {
+                continue;
+            }
+
+            let class = res.register_class();
+            let Some(index) = allocators
+                .entry((class, space))
+                .or_default()
+                .allocate(res.count)
+            else {
+                continue;
+            };
+
+            res.register = Some(Register {
+                class,
+                index,
+                space,
+            });
+            res.assigned = true;
+        }
+    }
+}
+
+/// Occupied register ranges of a class in a space, as `[start, end)` intervals sorted by start
+#[derive(Debug, Default)]
+struct Allocator {
+    ranges: Vec<(u32, u64)>,
+}
+
+impl Allocator {
+    fn reserve(&mut self, index: u32, count: DescriptorCount) {
+        let end = match count {
+            DescriptorCount::Bounded(n) => u64::from(index) + u64::from(n),
+            DescriptorCount::Unbounded => u64::from(u32::MAX) + 1,
+        };
+
+        let at = self.ranges.partition_point(|&(start, _)| start < index);
+        self.ranges.insert(at, (index, end));
+    }
+
+    fn allocate(&mut self, count: DescriptorCount) -> Option<u32> {
+        let index = match count {
+            DescriptorCount::Bounded(n) => {
+                let mut next = 0u64;
+                for &(start, end) in &self.ranges {
+                    if u64::from(start) >= next + u64::from(n) {
+                        break;
+                    }
+
+                    next = next.max(end);
+                }
+
+                next
+            }
+            DescriptorCount::Unbounded => {
+                self.ranges.iter().map(|&(_, end)| end).max().unwrap_or(0)
+            }
+        };
+
+        let index = u32::try_from(index).ok()?;
+        self.reserve(index, count);
+        Some(index)
+    }
+}
+
+/// Object kind, element type and register count of a resource, or of an array of resources
+fn object(ty: &Type) -> Option<(ObjectKind, Option<String>, DescriptorCount)> {
+    match ty {
+        Type::Object(kind, element) => Some((
+            *kind,
+            element.as_ref().map(|element| element.to_string()),
+            DescriptorCount::Bounded(1),
+        )),
+        Type::Array(inner, size) => {
+            let (kind, element, count) = object(inner)?;
+            let count = match (count, size) {
+                (DescriptorCount::Bounded(n), Some(size)) => {
+                    DescriptorCount::Bounded(n.saturating_mul(*size))
+                }
+                _ => DescriptorCount::Unbounded,
+            };
+
+            Some((kind, element, count))
+        }
+        _ => None,
+    }
+}
+
+/// Register given by a `register(...)` annotation
+pub(crate) fn register(
+    binding: &ast::ResourceBinding,
+    symbols: &SymbolTable<'_>,
+) -> Option<Register> {
+    let class = binding
+        .register_type
+        .as_str()
+        .chars()
+        .next()
+        .and_then(RegisterClass::from_prefix)?;
+
+    let eval = |expr| {
+        consteval::eval(expr, symbols)?
+            .as_int()
+            .and_then(|i| u32::try_from(i).ok())
+    };
+
+    Some(Register {
+        class,
+        index: eval(&binding.index)?,
+        space: match &binding.space {
+            Some(space) => eval(space)?,
+            None => 0,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::Parsable, resolver};
+
+    fn bindings(src: &str, space: Option<u32>) -> Vec<ResourceBinding> {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        let mut resources = resources(&symbols);
+        if let Some(space) = space {
+            assign_registers(&mut resources, space);
+        }
+
+        resources
+    }
+
+    fn registers(resources: &[ResourceBinding]) -> Vec<(&str, String, bool)> {
+        resources
+            .iter()
+            .map(|res| {
+                (
+                    res.name.as_str(),
+                    res.register.map(|reg| reg.to_string()).unwrap_or_default(),
+                    res.assigned,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn enumerate_resources() {
+        let resources = bindings(
+            r#"
+            cbuffer Camera : register(b1) { float4x4 view; };
+            StructuredBuffer<float4> albedo : register(t2, space1);
+            RWStructuredBuffer<uint> counters : register(u0);
+            SamplerState point : register(s3);
+            Texture2D layers[4][2], all[];
+            static const float scale = 2.0;
+            float4 tint;
+            struct Light { float3 dir; };
+            StructuredBuffer<Light> lights;
+            void main() { Texture2D local; }
+            "#,
+            None,
+        );
+
+        let summary: Vec<_> = resources
+            .iter()
+            .map(|res| {
+                (
+                    res.name.as_str(),
+                    res.kind,
+                    res.element.as_deref(),
+                    res.count,
+                    res.register.map(|reg| reg.to_string()),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                (
+                    "Camera",
+                    ResourceKind::CBuffer,
+                    None,
+                    DescriptorCount::Bounded(1),
+                    Some("b1".to_owned())
+                ),
+                (
+                    "albedo",
+                    ResourceKind::Object(ObjectKind::StructuredBuffer),
+                    Some("float4"),
+                    DescriptorCount::Bounded(1),
+                    Some("t2, space1".to_owned())
+                ),
+                (
+                    "counters",
+                    ResourceKind::Object(ObjectKind::RWStructuredBuffer),
+                    Some("uint"),
+                    DescriptorCount::Bounded(1),
+                    Some("u0".to_owned())
+                ),
+                (
+                    "point",
+                    ResourceKind::Object(ObjectKind::SamplerState),
+                    None,
+                    DescriptorCount::Bounded(1),
+                    Some("s3".to_owned())
+                ),
+                (
+                    "layers",
+                    ResourceKind::Object(ObjectKind::Texture2D),
+                    Some("float4"),
+                    DescriptorCount::Bounded(8),
+                    None
+                ),
+                (
+                    "all",
+                    ResourceKind::Object(ObjectKind::Texture2D),
+                    Some("float4"),
+                    DescriptorCount::Unbounded,
+                    None
+                ),
+                (
+                    "lights",
+                    ResourceKind::Object(ObjectKind::StructuredBuffer),
+                    Some("Light"),
+                    DescriptorCount::Bounded(1),
+                    None
+                ),
+            ]
+        );
+
+        assert_eq!(
+            resources[2].register_class(),
+            RegisterClass::UnorderedAccess
+        );
+        assert_eq!(resources[3].register_class(), RegisterClass::Sampler);
+        assert!(resources.iter().all(|res| res.span.is_some()));
+    }
+
+    #[test]
+    fn register_spaces() {
+        let resources = bindings(
+            r#"
+            Texture2D a : register(t0, space2);
+            SamplerState b : register(x1);
+            "#,
+            None,
+        );
+
+        assert_eq!(
+            resources[0].register,
+            Some(Register {
+                class: RegisterClass::ShaderResource,
+                index: 0,
+                space: 2,
+            })
+        );
+        assert_eq!(resources[1].register, None);
+    }
+
+    #[test]
+    fn assign_lowest_free_range() {
+        let resources = bindings(
+            r#"
+            Texture2D a : register(t1);
+            Texture2D b[2];
+            Texture2D c;
+            Texture2D d : register(t4);
+            Texture2D e[2];
+            RWStructuredBuffer<float> f;
+            cbuffer G { float g; };
+            cbuffer H : register(b0) { float h; };
+            "#,
+            Some(0),
+        );
+
+        assert_eq!(
+            registers(&resources),
+            [
+                ("a", "t1".to_owned(), false),
+                ("b", "t2".to_owned(), true),
+                ("c", "t0".to_owned(), true),
+                ("d", "t4".to_owned(), false),
+                ("e", "t5".to_owned(), true),
+                ("f", "u0".to_owned(), true),
+                ("G", "b1".to_owned(), true),
+                ("H", "b0".to_owned(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn assign_spaces_and_unbounded() {
+        let resources = bindings(
+            r#"
+            Texture2D bindless[] : register(t0, space1);
+            Texture2D rest[];
+            Texture2D a;
+            Texture2D b : register(t0);
+            "#,
+            Some(1),
+        );
+
+        assert_eq!(
+            registers(&resources),
+            [
+                ("bindless", "t0, space1".to_owned(), false),
+                ("rest", "".to_owned(), false),
+                ("a", "".to_owned(), false),
+                ("b", "t0".to_owned(), false),
+            ]
+        );
+
+        let resources = bindings(
+            "Texture2D rest[]; Texture2D a; Texture2D b : register(t1);",
+            Some(0),
+        );
+
+        assert_eq!(
+            registers(&resources),
+            [
+                ("rest", "t2".to_owned(), true),
+                ("a", "t0".to_owned(), true),
+                ("b", "t1".to_owned(), false),
+            ]
+        );
+    }
+}
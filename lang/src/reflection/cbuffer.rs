@@ -0,0 +1,648 @@
+//! Constant buffer layout
+//!
+//! Constant buffers follow the legacy HLSL packing rules: members are packed into 16-byte
+//! registers, and scalars and vectors may share a register but never straddle two. Arrays,
+//! matrices and structs always start on a new register, and every array element but the last
+//! is padded to a whole number of registers. Matrices are stored as a sequence of column
+//! vectors, unless they are declared `row_major` or follow a `#pragma pack_matrix(row_major)`.
+//! Members with a `packoffset` are placed at the given register and component.
+//!
+//! [layouts] computes the layout of every `cbuffer` of a translation unit. The layout of a
+//! `ConstantBuffer<T>` is the layout of its element struct, given by [struct_layout].
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, parse::Parsable, reflection::cbuffer, resolver};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     "cbuffer Camera { float4x4 view; float3 position; float2 jitter; float fov; }",
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//! let layout = &cbuffer::layouts(&tu, &symbols)[0];
+//!
+//! // `jitter` would straddle two registers after `position`, so it starts a new one
+//! let offsets: Vec<_> = layout.members.iter().map(|m| (m.name.as_str(), m.offset)).collect();
+//! assert_eq!(offsets, [("view", 0), ("position", 64), ("jitter", 80), ("fov", 88)]);
+//! assert_eq!(layout.size, 96);
+//! ```
+
+use std::{cell::RefCell, collections::HashMap};
+
+#[cfg(feature = "serde")]
+use rserde::{Deserialize, Serialize};
+
+use crate::{
+    ast,
+    resolver::{Declarator, SymbolId, SymbolKind, SymbolTable},
+    semantic::{
+        check::symbol_type,
+        consteval,
+        types::{ScalarType, Type},
+    },
+};
+
+/// Size of a constant buffer register, in bytes
+pub const REGISTER_SIZE: u32 = 16;
+
+/// Storage order of a matrix
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum MatrixOrder {
+    /// Each column is stored in its own register. This is the default.
+    #[default]
+    ColumnMajor,
+    /// Each row is stored in its own register
+    RowMajor,
+}
+
+/// Layout of a constant buffer
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct CBufferLayout {
+    /// Name of the constant buffer
+    pub name: String,
+    /// Size of the constant buffer, in bytes. This is always a whole number of registers.
+    pub size: u32,
+    /// Members, in declaration order
+    pub members: Vec<MemberLayout>,
+}
+
+/// Layout of a constant buffer member or struct field
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct MemberLayout {
+    /// Name of the member
+    pub name: String,
+    /// HLSL type of the member, e.g. `float3x4` or `Light[4]`
+    pub ty: String,
+    /// Offset from the start of the constant buffer, or of the enclosing struct, in bytes
+    pub offset: u32,
+    /// Size of the member in bytes, excluding the padding of its last register
+    pub size: u32,
+    /// Number of elements and stride of an array member
+    pub array: Option<ArrayLayout>,
+    /// Storage order of a matrix member, or of the elements of an array of matrices
+    pub matrix_order: Option<MatrixOrder>,
+    /// Fields of a struct member, or of the elements of an array of structs, with offsets
+    /// relative to the start of the struct
+    pub members: Vec<MemberLayout>,
+}
+
+/// Layout of an array member
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct ArrayLayout {
+    /// Number of elements in the outermost dimension
+    pub len: u32,
+    /// Distance between two consecutive elements, in bytes
+    pub stride: u32,
+}
+
+/// Layouts of the constant buffers declared in a translation unit, in declaration order
+///
+/// `#pragma pack_matrix` directives are honoured if they were injected into the translation
+/// unit by the parser.
+pub fn layouts(tu: &ast::TranslationUnit, symbols: &SymbolTable<'_>) -> Vec<CBufferLayout> {
+    let mut layouter = Layouter::new(symbols);
+    let mut order = MatrixOrder::default();
+    let mut layouts = Vec::new();
+
+    for decl in &tu.0 {
+        match &**decl {
+            ast::ExternalDeclarationData::Preprocessor(pp) => {
+                if let ast::PreprocessorData::Pragma(pragma) = &**pp {
+                    order = pack_matrix(&pragma.command).unwrap_or(order);
+                }
+            }
+            ast::ExternalDeclarationData::Declaration(decl) => match &**decl {
+                ast::DeclarationData::CBuffer(cbuffer) => {
+                    layouts.push(layouter.cbuffer(cbuffer, order))
+                }
+                ast::DeclarationData::InitDeclaratorList(list) => {
+                    // Struct fields take the matrix order in effect where the struct is declared
+                    if let ast::TypeSpecifierNonArrayData::Struct(spec) = &*list.head.ty.ty.ty {
+                        if let Some(id) = symbols.struct_specifier(spec) {
+                            layouter.struct_orders.insert(id, order);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            ast::ExternalDeclarationData::FunctionDefinition(_)
+            | ast::ExternalDeclarationData::Template(_) => {}
+        }
+    }
+
+    layouts
+}
+
+/// Layout of a constant buffer, with matrices stored in `order` unless declared otherwise
+pub fn cbuffer_layout(
+    cbuffer: &ast::CBuffer,
+    symbols: &SymbolTable<'_>,
+    order: MatrixOrder,
+) -> CBufferLayout {
+    Layouter::new(symbols).cbuffer(cbuffer, order)
+}
+
+/// Layout of a struct used as the element of a `ConstantBuffer<T>`, with matrices stored in
+/// `order` unless declared otherwise
+pub fn struct_layout(id: SymbolId, symbols: &SymbolTable<'_>, order: MatrixOrder) -> CBufferLayout {
+    let layouter = Layouter::new(symbols);
+    let mut members = Vec::new();
+    let end = layouter
+        .struct_members(id, order, &mut members)
+        .unwrap_or_default();
+
+    CBufferLayout {
+        name: symbols.symbol(id).name.to_string(),
+        size: align(end),
+        members,
+    }
+}
+
+/// Matrix order set by a `#pragma pack_matrix(...)` directive
+fn pack_matrix(command: &str) -> Option<MatrixOrder> {
+    let command: String = command.chars().filter(|c| !c.is_whitespace()).collect();
+    match command.as_str() {
+        "pack_matrix(row_major)" => Some(MatrixOrder::RowMajor),
+        "pack_matrix(column_major)" => Some(MatrixOrder::ColumnMajor),
+        _ => None,
+    }
+}
+
+/// Matrix order given by the qualifiers of a declaration, if any
+pub(crate) fn qualified_order(qualifier: Option<&ast::TypeQualifier>) -> Option<MatrixOrder> {
+    qualifier
+        .into_iter()
+        .flat_map(|qualifier| &qualifier.qualifiers)
+        .find_map(|spec| match &**spec {
+            ast::TypeQualifierSpecData::Storage(storage) => match **storage {
+                ast::StorageQualifierData::RowMajor => Some(MatrixOrder::RowMajor),
+                ast::StorageQualifierData::ColumnMajor => Some(MatrixOrder::ColumnMajor),
+                _ => None,
+            },
+            _ => None,
+        })
+}
+
+pub(crate) fn is_static(qualifier: Option<&ast::TypeQualifier>) -> bool {
+    qualifier
+        .into_iter()
+        .flat_map(|qualifier| &qualifier.qualifiers)
+        .any(|spec| {
+            matches!(&**spec, ast::TypeQualifierSpecData::Storage(storage)
+                if **storage == ast::StorageQualifierData::Static)
+        })
+}
+
+/// Round a size up to a whole number of registers
+fn align(size: u32) -> u32 {
+    size.next_multiple_of(REGISTER_SIZE)
+}
+
+fn scalar_size(scalar: ScalarType) -> u32 {
+    match scalar {
+        ScalarType::Float16 | ScalarType::Int16 | ScalarType::UInt16 => 2,
+        ScalarType::Double | ScalarType::Int64 | ScalarType::UInt64 => 8,
+        // Minimum precision types are stored with 32 bits
+        _ => 4,
+    }
+}
+
+/// Layout of a type placed at offset zero
+struct TypeLayout {
+    size: u32,
+    /// Alignment within a register, for types which do not start a new one
+    alignment: u32,
+    /// Whether the type starts a new register
+    aligned: bool,
+    array: Option<ArrayLayout>,
+    matrix_order: Option<MatrixOrder>,
+    members: Vec<MemberLayout>,
+}
+
+impl TypeLayout {
+    fn vector(scalar: ScalarType, len: u8) -> Self {
+        let size = scalar_size(scalar) * u32::from(len);
+        Self {
+            size,
+            alignment: scalar_size(scalar),
+            aligned: size > REGISTER_SIZE,
+            array: None,
+            matrix_order: None,
+            members: Vec::new(),
+        }
+    }
+
+    /// Offset of this type when placed after `cursor`
+    fn place(&self, cursor: u32) -> u32 {
+        let offset = cursor.next_multiple_of(self.alignment);
+        if self.aligned || offset % REGISTER_SIZE + self.size > REGISTER_SIZE {
+            align(offset)
+        } else {
+            offset
+        }
+    }
+}
+
+struct Layouter<'s, 'a> {
+    symbols: &'s SymbolTable<'a>,
+    /// Matrix order in effect where each struct was declared
+    struct_orders: HashMap<SymbolId, MatrixOrder>,
+    /// Structs whose fields are being laid out, to reject structs which contain themselves
+    active: RefCell<Vec<SymbolId>>,
+}
+
+impl<'s, 'a> Layouter<'s, 'a> {
+    fn new(symbols: &'s SymbolTable<'a>) -> Self {
+        Self {
+            symbols,
+            struct_orders: HashMap::new(),
+            active: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn cbuffer(&self, cbuffer: &ast::CBuffer, order: MatrixOrder) -> CBufferLayout {
+        let mut members = Vec::new();
+        let mut end = 0;
+
+        for field in &cbuffer.fields {
+            if is_static(field.qualifier.as_ref()) {
+                continue;
+            }
+
+            let order = qualified_order(field.qualifier.as_ref()).unwrap_or(order);
+            for ident in &field.identifiers {
+                let ty = Type::from_specifier(&field.ty, ident.array_spec.as_ref(), self.symbols);
+                let offset = ident
+                    .packoffset
+                    .as_ref()
+                    .and_then(|packoffset| self.packoffset(packoffset));
+
+                if let Some(member) = self.member(ident.ident.as_str(), &ty, order, end, offset) {
+                    end = end.max(member.offset + member.size);
+                    members.push(member);
+                }
+            }
+        }
+
+        CBufferLayout {
+            name: cbuffer.name.to_string(),
+            size: align(end),
+            members,
+        }
+    }
+
+    /// Offset in bytes given by a `packoffset(c<n>.<component>)`
+    fn packoffset(&self, packoffset: &ast::PackOffset) -> Option<u32> {
+        let register = consteval::eval(&packoffset.index, self.symbols)?.as_int()?;
+        let component = match &packoffset.component {
+            Some(component) => match component.as_str() {
+                "x" | "r" => 0,
+                "y" | "g" => 1,
+                "z" | "b" => 2,
+                "w" | "a" => 3,
+                _ => return None,
+            },
+            None => 0,
+        };
+
+        u32::try_from(register)
+            .ok()?
+            .checked_mul(REGISTER_SIZE)?
+            .checked_add(component * 4)
+    }
+
+    /// Lay out a member after `cursor`, or at `offset` if given
+    fn member(
+        &self,
+        name: &str,
+        ty: &Type,
+        order: MatrixOrder,
+        cursor: u32,
+        offset: Option<u32>,
+    ) -> Option<MemberLayout> {
+        let layout = self.layout(ty, order)?;
+        Some(MemberLayout {
+            name: name.to_owned(),
+            ty: ty.to_string(),
+            offset: offset.unwrap_or_else(|| layout.place(cursor)),
+            size: layout.size,
+            array: layout.array,
+            matrix_order: layout.matrix_order,
+            members: layout.members,
+        })
+    }
+
+    /// Lay out the fields of a struct, including inherited ones, and return where they end
+    ///
+    /// Returns `None` if the struct is already being laid out, i.e. it contains itself.
+    fn struct_members(
+        &self,
+        id: SymbolId,
+        order: MatrixOrder,
+        members: &mut Vec<MemberLayout>,
+    ) -> Option<u32> {
+        if self.active.borrow().contains(&id) {
+            return None;
+        }
+
+        self.active.borrow_mut().push(id);
+        let end = self.struct_fields(id, order, members);
+        self.active.borrow_mut().pop();
+        Some(end)
+    }
+
+    fn struct_fields(
+        &self,
+        id: SymbolId,
+        order: MatrixOrder,
+        members: &mut Vec<MemberLayout>,
+    ) -> u32 {
+        let mut end = 0;
+        for base in self.symbols.bases(id) {
+            if let Some(base_end) = self.struct_members(*base, order, members) {
+                end = end.max(base_end);
+            }
+        }
+
+        for member in self.symbols.members(id) {
+            let symbol = self.symbols.symbol(*member);
+            let Declarator::Field { field, .. } = symbol.declarator else {
+                continue;
+            };
+
+            if symbol.kind != SymbolKind::StructField || is_static(field.qualifier.as_ref()) {
+                continue;
+            }
+
+            let ty = symbol_type(self.symbols, *member);
+            let order = qualified_order(field.qualifier.as_ref()).unwrap_or(order);
+            if let Some(member) = self.member(&symbol.name, &ty, order, end, None) {
+                end = member.offset + member.size;
+                members.push(member);
+            }
+        }
+
+        end
+    }
+
+    /// Layout of a type, or `None` if it cannot be placed in a constant buffer
+    fn layout(&self, ty: &Type, order: MatrixOrder) -> Option<TypeLayout> {
+        match ty {
+            Type::Scalar(scalar) => Some(TypeLayout::vector(*scalar, 1)),
+            Type::Vector(scalar, len) => Some(TypeLayout::vector(*scalar, *len)),
+            Type::Matrix(scalar, rows, cols) => {
+                let (count, len) = match order {
+                    MatrixOrder::ColumnMajor => (*cols, *rows),
+                    MatrixOrder::RowMajor => (*rows, *cols),
+                };
+
+                let vector = TypeLayout::vector(*scalar, len).size;
+                Some(TypeLayout {
+                    size: align(vector) * (u32::from(count) - 1) + vector,
+                    alignment: REGISTER_SIZE,
+                    aligned: true,
+                    array: None,
+                    matrix_order: Some(order),
+                    members: Vec::new(),
+                })
+            }
+            Type::Array(element, len) => {
+                // Unsized arrays cannot be placed in a constant buffer
+                let len = (*len)?;
+                let element = self.layout(element, order)?;
+                let stride = align(element.size);
+                let size = match len {
+                    0 => 0,
+                    len => stride * (len - 1) + element.size,
+                };
+
+                Some(TypeLayout {
+                    size,
+                    alignment: REGISTER_SIZE,
+                    aligned: true,
+                    array: Some(ArrayLayout { len, stride }),
+                    ..element
+                })
+            }
+            Type::Struct(id, _) => {
+                let order = self.struct_orders.get(id).copied().unwrap_or(order);
+                let mut members = Vec::new();
+                let size = self.struct_members(*id, order, &mut members)?;
+
+                Some(TypeLayout {
+                    size,
+                    alignment: REGISTER_SIZE,
+                    aligned: true,
+                    array: None,
+                    matrix_order: None,
+                    members,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::Parsable, resolver};
+
+    /// Name, offset and size of the members of the first constant buffer, and its size
+    fn offsets(src: &str) -> (Vec<(String, u32, u32)>, u32) {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        let layout = layouts(&tu, &symbols).remove(0);
+
+        (
+            layout
+                .members
+                .into_iter()
+                .map(|m| (m.name, m.offset, m.size))
+                .collect(),
+            layout.size,
+        )
+    }
+
+    fn members(list: &[(&str, u32, u32)]) -> Vec<(String, u32, u32)> {
+        list.iter()
+            .map(|(name, offset, size)| (name.to_string(), *offset, *size))
+            .collect()
+    }
+
+    #[test]
+    fn pack_scalars_and_vectors() {
+        assert_eq!(
+            offsets("cbuffer B { float a; float2 b; float c; float3 d; double e; int f; }"),
+            (
+                members(&[
+                    ("a", 0, 4),
+                    ("b", 4, 8),
+                    ("c", 12, 4),
+                    ("d", 16, 12),
+                    ("e", 32, 8),
+                    ("f", 40, 4),
+                ]),
+                48
+            )
+        );
+    }
+
+    #[test]
+    fn pack_arrays_and_matrices() {
+        assert_eq!(
+            offsets(
+                "cbuffer B {
+                     float a[4]; float b;
+                     float3x2 c; float d;
+                     row_major float3x2 e; float f;
+                     float2x2 g[2];
+                 }"
+            ),
+            (
+                members(&[
+                    ("a", 0, 52),
+                    ("b", 52, 4),
+                    ("c", 64, 28),
+                    ("d", 92, 4),
+                    ("e", 96, 40),
+                    ("f", 136, 4),
+                    ("g", 144, 56),
+                ]),
+                208
+            )
+        );
+
+        let tu = ast::TranslationUnit::parse("cbuffer B { float2x2 g[2][3]; }").unwrap();
+        let symbols = resolver::resolve(&tu);
+        let member = &layouts(&tu, &symbols)[0].members[0];
+        assert_eq!(member.ty, "float2x2[2][3]");
+        assert_eq!(member.array, Some(ArrayLayout { len: 2, stride: 96 }));
+        assert_eq!(member.matrix_order, Some(MatrixOrder::ColumnMajor));
+    }
+
+    #[test]
+    fn pack_single_row_and_column_matrices() {
+        assert_eq!(
+            offsets("cbuffer B { float1x1 a; float2x1 b; row_major float2x1 c; float1x3 d; }"),
+            (
+                members(&[("a", 0, 4), ("b", 16, 8), ("c", 32, 20), ("d", 64, 36)]),
+                112
+            )
+        );
+
+        let tu = ast::TranslationUnit::parse("cbuffer B { float2x1 m; }").unwrap();
+        let symbols = resolver::resolve(&tu);
+        assert_eq!(layouts(&tu, &symbols)[0].members[0].ty, "float2x1");
+    }
+
+    #[test]
+    fn pack_structs() {
+        let tu = ast::TranslationUnit::parse(
+            "struct Light { float3 dir; float intensity; float3 color; };
+             cbuffer Lights { float count; Light lights[2]; float ambient; }",
+        )
+        .unwrap();
+        let symbols = resolver::resolve(&tu);
+        let layout = layouts(&tu, &symbols).remove(0);
+
+        let offsets: Vec<_> = layout
+            .members
+            .iter()
+            .map(|m| (m.name.as_str(), m.offset, m.size))
+            .collect();
+        assert_eq!(
+            offsets,
+            [("count", 0, 4), ("lights", 16, 60), ("ambient", 76, 4)]
+        );
+        assert_eq!(layout.size, 80);
+
+        let light = &layout.members[1];
+        assert_eq!(light.array, Some(ArrayLayout { len: 2, stride: 32 }));
+        let fields: Vec<_> = light
+            .members
+            .iter()
+            .map(|m| (m.name.as_str(), m.offset))
+            .collect();
+        assert_eq!(fields, [("dir", 0), ("intensity", 12), ("color", 16)]);
+
+        // The same struct as the element of a ConstantBuffer<Light>
+        let ast::ExternalDeclarationData::Declaration(decl) = &*tu.0[0] else {
+            panic!();
+        };
+        let ast::DeclarationData::InitDeclaratorList(list) = &**decl else {
+            panic!();
+        };
+        let ast::TypeSpecifierNonArrayData::Struct(spec) = &*list.head.ty.ty.ty else {
+            panic!();
+        };
+        let id = symbols.struct_specifier(spec).unwrap();
+        let layout = struct_layout(id, &symbols, MatrixOrder::ColumnMajor);
+        assert_eq!(layout.size, 32);
+        assert_eq!(layout.members.len(), 3);
+    }
+
+    #[test]
+    fn pack_recursive_structs() {
+        // A struct cannot contain itself, so the recursive field is skipped
+        assert_eq!(
+            offsets("struct A { float x; A inner; }; cbuffer B { A a; float y; }"),
+            (members(&[("a", 0, 4), ("y", 4, 4)]), 16)
+        );
+    }
+
+    #[test]
+    fn pack_offsets() {
+        assert_eq!(
+            offsets("cbuffer B { float4 x : packoffset(c2); float y : packoffset(c0.z); }"),
+            (members(&[("x", 32, 16), ("y", 8, 4)]), 48)
+        );
+    }
+
+    #[test]
+    fn pack_matrix_pragma() {
+        use crate::parse::DefaultParse;
+
+        let (mut tu, _, iter) = <ast::TranslationUnit as DefaultParse>::parse_with_options(
+            "#pragma pack_matrix(row_major)
+             cbuffer B { float4x3 m; column_major float4x3 n; }
+             #pragma pack_matrix( column_major )
+             cbuffer C { float4x3 m; }",
+            &Default::default(),
+        )
+        .unwrap();
+        iter.into_directives().inject(&mut tu);
+
+        let symbols = resolver::resolve(&tu);
+        let layouts: Vec<_> = layouts(&tu, &symbols)
+            .into_iter()
+            .map(|layout| {
+                layout
+                    .members
+                    .into_iter()
+                    .map(|m| (m.name, m.offset, m.size, m.matrix_order.unwrap()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        assert_eq!(
+            layouts,
+            [
+                vec![
+                    ("m".to_owned(), 0, 60, MatrixOrder::RowMajor),
+                    ("n".to_owned(), 64, 48, MatrixOrder::ColumnMajor)
+                ],
+                vec![("m".to_owned(), 0, 48, MatrixOrder::ColumnMajor)],
+            ]
+        );
+    }
+}
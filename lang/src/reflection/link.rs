@@ -0,0 +1,315 @@
+//! Stage interface linking
+//!
+//! [link] checks that the output signature of a shader stage feeds the input signature of the
+//! next one, e.g. a vertex shader and a pixel shader, or a domain shader and a geometry shader.
+//! Elements are matched by semantic name, ignoring case, and index. Every input must be written
+//! by the previous stage with the same component type, at least as many components as are
+//! read, and the same interpolation mode if the previous stage declares one. Both stages must
+//! also pack the element into the same register, with the components read among those written.
+//! Inputs which are generated by the pipeline, such as `SV_IsFrontFace` or `SV_PrimitiveID`,
+//! need not be written.
+//!
+//! Errors carry the spans of both semantics, which may come from different source files.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, entry_point, parse::Parsable, reflection::{link, signature}, resolver};
+//!
+//! let signature = |src| {
+//!     let tu = ast::TranslationUnit::parse(src).unwrap();
+//!     let symbols = resolver::resolve(&tu);
+//!     let entry = &entry_point::entry_points(&tu, &symbols)[0];
+//!     signature::signature(entry, &symbols)
+//! };
+//!
+//! let vs = signature("float4 vs(inout float2 uv : TEXCOORD0) : SV_Position { return 0; }");
+//! let ps = signature("float4 ps(float3 uv : TEXCOORD0) : SV_Target { return 0; }");
+//!
+//! assert_eq!(
+//!     link::link(&vs, &ps)[0].to_string(),
+//!     "`TEXCOORD0` is written with 2 components but read with 3"
+//! );
+//! ```
+
+use crate::ast::{NodeSpan, SystemValue};
+
+use super::signature::{ComponentType, Interpolation, Signature, SignatureElement};
+
+/// Error raised when linking two stages
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("{kind}")]
+pub struct LinkError {
+    /// Kind of error
+    pub kind: LinkErrorKind,
+    /// Span of the semantic in the output signature of the previous stage, if it is written
+    pub output: Option<NodeSpan>,
+    /// Span of the semantic in the input signature of the next stage
+    pub input: Option<NodeSpan>,
+}
+
+/// Kind of linking error
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum LinkErrorKind {
+    /// An input is not written by the previous stage
+    #[error("`{0}` is read but not written by the previous stage")]
+    Missing(String),
+    /// An input is written with another component type
+    #[error("`{semantic}` is written as `{output}` but read as `{input}`")]
+    ComponentType {
+        /// Semantic name and index
+        semantic: String,
+        /// Component type of the output
+        output: ComponentType,
+        /// Component type of the input
+        input: ComponentType,
+    },
+    /// An input reads more components than are written
+    #[error("`{semantic}` is written with {output} components but read with {input}")]
+    ComponentCount {
+        /// Semantic name and index
+        semantic: String,
+        /// Number of components of the output
+        output: u8,
+        /// Number of components of the input
+        input: u8,
+    },
+    /// An input is interpolated differently than declared by the previous stage
+    #[error("`{semantic}` is written with `{output}` interpolation but read with `{input}`")]
+    Interpolation {
+        /// Semantic name and index
+        semantic: String,
+        /// Interpolation mode of the output
+        output: Interpolation,
+        /// Interpolation mode of the input
+        input: Interpolation,
+    },
+    /// An input is packed into other register components than the previous stage writes
+    #[error(
+        "`{semantic}` is written to register {} but read from register {}",
+        location(.output),
+        location(.input)
+    )]
+    Register {
+        /// Semantic name and index
+        semantic: String,
+        /// Register and component mask of the output
+        output: (u32, u8),
+        /// Register and component mask of the input
+        input: (u32, u8),
+    },
+}
+
+/// Register and components of a register and mask, e.g. `1.zw`
+fn location(&(register, mask): &(u32, u8)) -> String {
+    let components: String = "xyzw"
+        .chars()
+        .enumerate()
+        .filter(|(i, _)| mask & (1 << i) != 0)
+        .map(|(_, c)| c)
+        .collect();
+    format!("{}.{}", register, components)
+}
+
+/// Check that the outputs of a stage provide the inputs of the next one
+pub fn link(previous: &Signature, next: &Signature) -> Vec<LinkError> {
+    let mut errors = Vec::new();
+
+    for input in &next.inputs {
+        let error = |kind, output: Option<&SignatureElement>| LinkError {
+            kind,
+            output: output.and_then(|output| output.span),
+            input: input.span,
+        };
+
+        let Some(output) = previous.outputs.iter().find(|output| {
+            output.index == input.index && output.semantic.eq_ignore_ascii_case(&input.semantic)
+        }) else {
+            if !input.system_value.is_some_and(is_generated) {
+                errors.push(error(LinkErrorKind::Missing(input.full_name()), None));
+            }
+
+            continue;
+        };
+
+        if output.component_type != input.component_type {
+            errors.push(error(
+                LinkErrorKind::ComponentType {
+                    semantic: input.full_name(),
+                    output: output.component_type,
+                    input: input.component_type,
+                },
+                Some(output),
+            ));
+        } else if output.components < input.components {
+            errors.push(error(
+                LinkErrorKind::ComponentCount {
+                    semantic: input.full_name(),
+                    output: output.components,
+                    input: input.components,
+                },
+                Some(output),
+            ));
+        } else if let (Some(output_register), Some(input_register)) =
+            (output.register, input.register)
+        {
+            if output_register != input_register || input.mask & !output.mask != 0 {
+                errors.push(error(
+                    LinkErrorKind::Register {
+                        semantic: input.full_name(),
+                        output: (output_register, output.mask),
+                        input: (input_register, input.mask),
+                    },
+                    Some(output),
+                ));
+            }
+        }
+
+        // Outputs without interpolation qualifiers may be read with any mode
+        if output.interpolation_declared && output.interpolation != input.interpolation {
+            errors.push(error(
+                LinkErrorKind::Interpolation {
+                    semantic: input.full_name(),
+                    output: output.interpolation,
+                    input: input.interpolation,
+                },
+                Some(output),
+            ));
+        }
+    }
+
+    errors
+}
+
+/// `true` for system values which are generated by the pipeline rather than the previous stage
+fn is_generated(sv: SystemValue) -> bool {
+    matches!(
+        sv,
+        SystemValue::VertexId
+            | SystemValue::InstanceId
+            | SystemValue::PrimitiveId
+            | SystemValue::IsFrontFace
+            | SystemValue::SampleIndex
+            | SystemValue::Coverage
+            | SystemValue::InnerCoverage
+            | SystemValue::Barycentrics
+            | SystemValue::ShadingRate
+            | SystemValue::ViewId
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ast, entry_point, parse::Parsable, reflection::signature, resolver};
+
+    fn signature(src: &str) -> Signature {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        let entry = &entry_point::entry_points(&tu, &symbols)[0];
+        signature::signature(entry, &symbols)
+    }
+
+    #[test]
+    fn link_vertex_and_pixel_shaders() {
+        let vs = signature(
+            r#"
+            struct VSOut {
+                float4 pos : SV_Position;
+                float3 normal : NORMAL;
+                float2 uv : TEXCOORD0;
+                uint id : ID;
+                float4 color : COLOR;
+                nointerpolation float fade : FADE;
+            };
+            VSOut vs() { VSOut o; return o; }
+            "#,
+        );
+        let ps = signature(
+            r#"
+            struct PSIn {
+                float4 pos : SV_Position;
+                float2 normal : normal;
+                float3 uv : TEXCOORD0;
+                int id : ID;
+                float4 tangent : TANGENT;
+                float fade : FADE;
+            };
+            float4 ps(PSIn i, bool front : SV_IsFrontFace) : SV_Target { return 0; }
+            "#,
+        );
+
+        let errors = link(&vs, &ps);
+        let messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "`TEXCOORD0` is written with 2 components but read with 3",
+                "`ID0` is written as `uint` but read as `int`",
+                "`TANGENT0` is read but not written by the previous stage",
+                "`FADE0` is written with `nointerpolation` interpolation but read with `linear`",
+            ]
+        );
+
+        assert!(errors[0].output.is_some() && errors[0].input.is_some());
+        assert_eq!(errors[2].output, None);
+        assert!(errors[2].input.is_some());
+    }
+
+    #[test]
+    fn link_matching_stages() {
+        let vs = signature(
+            r#"
+            void vs(out float4 pos : SV_Position, out float2 uv : TEXCOORD0, out float w : TEXCOORD1) {}
+            "#,
+        );
+        let ps = signature(
+            r#"
+            float4 ps(float4 pos : SV_Position, float2 uv : texcoord0) : SV_Target { return 0; }
+            "#,
+        );
+
+        assert_eq!(link(&vs, &ps), []);
+    }
+
+    #[test]
+    fn link_default_interpolation() {
+        let vs = signature(
+            r#"
+            void vs(out float4 pos : SV_Position, out float4 uv : TEXCOORD0, out float4 w : FADE) {}
+            "#,
+        );
+        let ps = signature(
+            r#"
+            float4 ps(
+                float4 pos : SV_Position,
+                nointerpolation float2 uv : TEXCOORD0,
+                centroid float w : FADE
+            ) : SV_Target { return 0; }
+            "#,
+        );
+
+        assert_eq!(link(&vs, &ps), []);
+    }
+
+    #[test]
+    fn link_registers() {
+        let vs = signature(
+            r#"
+            void vs(out float4 pos : SV_Position, out float3 n : NORMAL, out float w : TEXCOORD1) {}
+            "#,
+        );
+        let ps = signature(
+            r#"
+            float4 ps(float4 pos : SV_Position, float2 n : NORMAL, float w : TEXCOORD1)
+                : SV_Target { return 0; }
+            "#,
+        );
+
+        let messages: Vec<_> = link(&vs, &ps).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            ["`TEXCOORD1` is written to register 1.w but read from register 1.z"]
+        );
+    }
+}
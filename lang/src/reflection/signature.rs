@@ -0,0 +1,831 @@
+//! Input and output signatures
+//!
+//! The signature of an entry point lists the values it exchanges with the fixed-function stages
+//! and the neighbouring shader stages. [signature] flattens the parameters and the return value
+//! of an entry point into signature elements: struct members are expanded in declaration order,
+//! and arrays and matrices yield one element per row with consecutive semantic indices. A
+//! semantic written on a struct applies to all its members, with increasing indices. Matrices
+//! contribute one row per column, unless declared `row_major`.
+//!
+//! Elements are packed into 4-component registers following the D3D signature packing rules:
+//! vertex shader inputs each take a register of their own, pixel shader `SV_Target` outputs go
+//! to the register given by their semantic index, and `SV_Depth`, `SV_Coverage` and
+//! `SV_StencilRef` outputs have no register. The rows of arrays and matrices also take a register
+//! of their own. Other elements are packed in declaration order, sharing the current register
+//! with the previous elements if there are enough free components left, their interpolation
+//! modes agree, and neither of them is a system value (clip and cull distances may share a
+//! register with each other).
+//!
+//! Compute shader thread and group identifiers, `SV_DomainLocation`, `SV_OutputControlPointID`
+//! and `SV_GSInstanceID` are not part of the signature.
+//!
+//! Values which cannot be part of a signature, such as arrays longer than the
+//! [register limit](MAX_REGISTERS) or semantic indices beyond `u32::MAX`, are reported in
+//! [Signature::errors] and left out.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, entry_point, parse::Parsable, reflection::signature, resolver};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     r#"
+//!     struct VSOut { float4 pos : SV_Position; float2 uv : TEXCOORD0; float fog : FOG; };
+//!     VSOut main(float3 pos : POSITION, float2 uv : TEXCOORD0) { VSOut o; return o; }
+//!     "#,
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//! let entry = &entry_point::entry_points(&tu, &symbols)[0];
+//! let sig = signature::signature(entry, &symbols);
+//!
+//! // `fog` is packed next to `uv`
+//! let outputs: Vec<_> = sig
+//!     .outputs
+//!     .iter()
+//!     .map(|e| (e.semantic.as_str(), e.register, e.mask))
+//!     .collect();
+//! assert_eq!(
+//!     outputs,
+//!     [("SV_Position", Some(0), 0b1111), ("TEXCOORD", Some(1), 0b0011), ("FOG", Some(1), 0b0100)]
+//! );
+//! ```
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use rserde::{Deserialize, Serialize};
+
+use crate::{
+    ast::{self, NodeSpan, SystemValue},
+    entry_point::{direction, EntryPoint},
+    parse::ShaderStage,
+    resolver::{Declarator, SymbolId, SymbolTable},
+    semantic::{
+        check::symbol_type,
+        types::{ScalarType, Type},
+    },
+};
+
+use super::cbuffer::{qualified_order, MatrixOrder};
+
+/// Number of registers of a signature
+pub const MAX_REGISTERS: u32 = 32;
+
+/// Input and output signatures of an entry point
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct Signature {
+    /// Input elements, in declaration order
+    pub inputs: Vec<SignatureElement>,
+    /// Output elements, in declaration order
+    pub outputs: Vec<SignatureElement>,
+    /// Values left out of the signature
+    pub errors: Vec<SignatureError>,
+}
+
+/// Error raised for a value which cannot be part of a signature
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+#[error("{kind}")]
+pub struct SignatureError {
+    /// Kind of error
+    pub kind: SignatureErrorKind,
+    /// Span of the semantic
+    pub span: Option<NodeSpan>,
+}
+
+/// Kind of signature error
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum SignatureErrorKind {
+    /// An array needs more registers than a signature has
+    #[error("`{0}` needs more than the {MAX_REGISTERS} registers of a signature")]
+    TooManyRegisters(String),
+    /// Semantic indices run past `u32::MAX`
+    #[error("semantic index of `{0}` overflows")]
+    IndexOverflow(String),
+}
+
+/// An element of a signature
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct SignatureElement {
+    /// Semantic name, as written in the source, without its index
+    pub semantic: String,
+    /// Semantic index
+    pub index: u32,
+    /// Type of the components
+    pub component_type: ComponentType,
+    /// Number of components, from 1 to 4
+    pub components: u8,
+    /// Interpolation mode
+    pub interpolation: Interpolation,
+    /// `true` if the interpolation mode is declared by qualifiers rather than the default for
+    /// the component type
+    pub interpolation_declared: bool,
+    /// System value, or `None` for arbitrary semantics
+    pub system_value: Option<SystemValue>,
+    /// Register the element is packed into, or `None` for outputs with a dedicated register
+    /// such as `SV_Depth`
+    pub register: Option<u32>,
+    /// Components of the register used by the element, with `x` as the lowest bit
+    pub mask: u8,
+    /// Span of the semantic
+    pub span: Option<NodeSpan>,
+}
+
+impl SignatureElement {
+    /// Semantic name followed by its index, e.g. `TEXCOORD1`
+    pub fn full_name(&self) -> String {
+        format!("{}{}", self.semantic, self.index)
+    }
+}
+
+/// Type of the components of a signature element
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum ComponentType {
+    /// 32-bit floating-point
+    Float32,
+    /// 32-bit signed integer
+    SInt32,
+    /// 32-bit unsigned integer, also used for booleans
+    UInt32,
+    /// 16-bit floating-point
+    Float16,
+    /// 16-bit signed integer
+    SInt16,
+    /// 16-bit unsigned integer
+    UInt16,
+    /// 64-bit floating-point
+    Float64,
+    /// 64-bit signed integer
+    SInt64,
+    /// 64-bit unsigned integer
+    UInt64,
+}
+
+impl ComponentType {
+    /// Component type of the given scalar type
+    pub fn from_scalar(scalar: ScalarType) -> Self {
+        match scalar {
+            // Minimum precision types are stored at full precision
+            ScalarType::Bool | ScalarType::UInt | ScalarType::Min16UInt => Self::UInt32,
+            ScalarType::Int
+            | ScalarType::LiteralInt
+            | ScalarType::Min12Int
+            | ScalarType::Min16Int => Self::SInt32,
+            ScalarType::Float
+            | ScalarType::LiteralFloat
+            | ScalarType::Half
+            | ScalarType::Min10Float
+            | ScalarType::Min16Float => Self::Float32,
+            ScalarType::Float16 => Self::Float16,
+            ScalarType::Int16 => Self::SInt16,
+            ScalarType::UInt16 => Self::UInt16,
+            ScalarType::Double => Self::Float64,
+            ScalarType::Int64 => Self::SInt64,
+            ScalarType::UInt64 => Self::UInt64,
+        }
+    }
+
+    /// `true` for floating-point types
+    pub fn is_float(self) -> bool {
+        matches!(self, Self::Float32 | Self::Float16 | Self::Float64)
+    }
+}
+
+impl fmt::Display for ComponentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Float32 => "float",
+            Self::SInt32 => "int",
+            Self::UInt32 => "uint",
+            Self::Float16 => "float16_t",
+            Self::SInt16 => "int16_t",
+            Self::UInt16 => "uint16_t",
+            Self::Float64 => "double",
+            Self::SInt64 => "int64_t",
+            Self::UInt64 => "uint64_t",
+        })
+    }
+}
+
+/// Interpolation mode of a signature element
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum Interpolation {
+    /// `nointerpolation`. This is the default for integer elements.
+    Constant,
+    /// Perspective-correct interpolation. This is the default for floating-point elements.
+    #[default]
+    Linear,
+    /// `centroid`
+    LinearCentroid,
+    /// `noperspective`
+    LinearNoPerspective,
+    /// `noperspective centroid`
+    LinearNoPerspectiveCentroid,
+    /// `sample`
+    LinearSample,
+    /// `noperspective sample`
+    LinearNoPerspectiveSample,
+}
+
+impl Interpolation {
+    /// Interpolation mode given by the qualifiers of a declaration, for components of type `ty`,
+    /// and whether any qualifier declares it
+    fn from_qualifier(qualifier: Option<&ast::TypeQualifier>, ty: ComponentType) -> (Self, bool) {
+        let (mut constant, mut perspective, mut centroid, mut sample) =
+            (!ty.is_float(), true, false, false);
+        let mut declared = false;
+        for spec in qualifier
+            .into_iter()
+            .flat_map(|qualifier| &qualifier.qualifiers)
+        {
+            match &**spec {
+                ast::TypeQualifierSpecData::Interpolation(interp) => {
+                    declared = true;
+                    match **interp {
+                        ast::InterpolationQualifierData::NoInterpolation
+                        | ast::InterpolationQualifierData::Flat => constant = true,
+                        ast::InterpolationQualifierData::NoPerspective => perspective = false,
+                        _ => {}
+                    }
+                }
+                ast::TypeQualifierSpecData::Storage(storage) => match **storage {
+                    ast::StorageQualifierData::Centroid => (centroid, declared) = (true, true),
+                    ast::StorageQualifierData::Sample => (sample, declared) = (true, true),
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+
+        let interpolation = match (constant, perspective, centroid, sample) {
+            (true, ..) => Self::Constant,
+            (_, true, _, true) => Self::LinearSample,
+            (_, false, _, true) => Self::LinearNoPerspectiveSample,
+            (_, true, true, _) => Self::LinearCentroid,
+            (_, false, true, _) => Self::LinearNoPerspectiveCentroid,
+            (_, true, false, _) => Self::Linear,
+            (_, false, false, _) => Self::LinearNoPerspective,
+        };
+
+        (interpolation, declared)
+    }
+}
+
+impl fmt::Display for Interpolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Constant => "nointerpolation",
+            Self::Linear => "linear",
+            Self::LinearCentroid => "centroid",
+            Self::LinearNoPerspective => "noperspective",
+            Self::LinearNoPerspectiveCentroid => "noperspective centroid",
+            Self::LinearSample => "sample",
+            Self::LinearNoPerspectiveSample => "noperspective sample",
+        })
+    }
+}
+
+/// Input and output signatures of an entry point. Entry points without a known stage are
+/// packed with the general rules.
+pub fn signature(entry: &EntryPoint<'_>, symbols: &SymbolTable<'_>) -> Signature {
+    let proto = &entry.definition.prototype;
+    let mut inputs = Flattener::new(symbols);
+    let mut outputs = Flattener::new(symbols);
+
+    for param in &proto.parameters {
+        let ast::FunctionParameterDeclarationData::Named(qualifier, declarator) = &**param else {
+            continue;
+        };
+
+        let mut ty = Type::from_specifier(
+            &declarator.ty,
+            declarator.ident.array_spec.as_ref(),
+            symbols,
+        );
+
+        // Geometry shader inputs are arrays of vertices, whose elements are the signature
+        if is_primitive(qualifier.as_ref()) {
+            if let Type::Array(inner, _) = ty {
+                ty = *inner;
+            }
+        }
+
+        let (input, output) = direction(qualifier.as_ref());
+        let semantic = declarator.ident.semantic.as_ref();
+        for (used, flattener) in [(input, &mut inputs), (output, &mut outputs)] {
+            if used {
+                flattener.value(&ty, qualifier.as_ref(), semantic, MatrixOrder::default());
+            }
+        }
+    }
+
+    let ret = Type::from_specifier(&proto.ty.ty, None, symbols);
+    outputs.value(
+        &ret,
+        proto.ty.qualifier.as_ref(),
+        proto.semantic.as_ref(),
+        MatrixOrder::default(),
+    );
+
+    match entry.stage {
+        Some(ShaderStage::Vertex) => pack_unpacked(&mut inputs.elements),
+        _ => inputs.pack(),
+    }
+    match entry.stage {
+        Some(ShaderStage::Pixel) => pack_pixel_outputs(&mut outputs.elements),
+        _ => outputs.pack(),
+    }
+
+    Signature {
+        inputs: inputs.elements,
+        outputs: outputs.elements,
+        errors: [inputs.errors, outputs.errors].concat(),
+    }
+}
+
+/// Builds the unpacked elements of one side of a signature
+struct Flattener<'s, 'a> {
+    symbols: &'s SymbolTable<'a>,
+    elements: Vec<SignatureElement>,
+    /// Whether each element is a row of an array or a matrix, which takes a whole register
+    whole_register: Vec<bool>,
+    /// Structs whose members are being added, to skip structs which contain themselves
+    structs: Vec<SymbolId>,
+    errors: Vec<SignatureError>,
+}
+
+impl<'s, 'a> Flattener<'s, 'a> {
+    fn new(symbols: &'s SymbolTable<'a>) -> Self {
+        Self {
+            symbols,
+            elements: Vec::new(),
+            whole_register: Vec::new(),
+            structs: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Add the elements of a value, which carries `semantic` or whose struct members do
+    fn value(
+        &mut self,
+        ty: &Type,
+        qualifier: Option<&ast::TypeQualifier>,
+        semantic: Option<&ast::Semantic>,
+        order: MatrixOrder,
+    ) {
+        let order = qualified_order(qualifier).unwrap_or(order);
+
+        match semantic {
+            Some(semantic) => {
+                if semantic
+                    .system_value()
+                    .is_some_and(|sv| !sv.is_signature_element())
+                {
+                    return;
+                }
+
+                let mut index = u64::from(semantic.index.unwrap_or(0));
+                self.rows(ty, qualifier, semantic, &mut index, order);
+            }
+            None => {
+                if let Type::Struct(id, _) = ty {
+                    self.fields(*id, None, order);
+                }
+            }
+        }
+    }
+
+    /// Add the members of a struct. With a semantic, members are given consecutive indices.
+    fn fields(
+        &mut self,
+        id: SymbolId,
+        semantic: Option<(&ast::Semantic, &mut u64)>,
+        order: MatrixOrder,
+    ) {
+        if self.structs.contains(&id) {
+            return;
+        }
+
+        self.structs.push(id);
+        self.members(id, semantic, order);
+        self.structs.pop();
+    }
+
+    fn members(
+        &mut self,
+        id: SymbolId,
+        semantic: Option<(&ast::Semantic, &mut u64)>,
+        order: MatrixOrder,
+    ) {
+        let mut semantic = semantic;
+        let symbols = self.symbols;
+
+        for base in symbols.bases(id) {
+            let semantic = semantic.as_mut().map(|(name, index)| (*name, &mut **index));
+            self.fields(*base, semantic, order);
+        }
+
+        for &member in symbols.members(id) {
+            let Declarator::Field { field, ident } = symbols.symbol(member).declarator else {
+                continue;
+            };
+
+            let ty = symbol_type(symbols, member);
+            let qualifier = field.qualifier.as_ref();
+            match semantic.as_mut() {
+                Some((name, index)) => {
+                    let order = qualified_order(qualifier).unwrap_or(order);
+                    self.rows(&ty, qualifier, name, index, order);
+                }
+                None => self.value(&ty, qualifier, ident.semantic.as_ref(), order),
+            }
+        }
+    }
+
+    /// Add one element per row of a value, with semantic indices starting at `index`
+    fn rows(
+        &mut self,
+        ty: &Type,
+        qualifier: Option<&ast::TypeQualifier>,
+        semantic: &ast::Semantic,
+        index: &mut u64,
+        order: MatrixOrder,
+    ) {
+        let (rows, components) = match ty {
+            Type::Scalar(_) => (1, 1),
+            Type::Vector(_, n) => (1, *n),
+            Type::Matrix(_, r, c) => match order {
+                MatrixOrder::ColumnMajor => (*c, *r),
+                MatrixOrder::RowMajor => (*r, *c),
+            },
+            Type::Array(inner, size) => {
+                let start = self.elements.len();
+                let errors = self.errors.len();
+                for _ in 0..size.unwrap_or(1) {
+                    self.rows(inner, qualifier, semantic, index, order);
+
+                    // Stop at the first error, or as soon as the array overflows the registers
+                    if self.errors.len() > errors {
+                        break;
+                    }
+                    if self.elements.len() - start > MAX_REGISTERS as usize {
+                        self.elements.truncate(start);
+                        self.whole_register.truncate(start);
+                        self.error(
+                            SignatureErrorKind::TooManyRegisters(semantic.name.to_string()),
+                            semantic,
+                        );
+                        return;
+                    }
+                }
+                self.whole_register[start..].fill(true);
+                return;
+            }
+            Type::Struct(id, _) => {
+                self.fields(*id, Some((semantic, index)), order);
+                return;
+            }
+            _ => return,
+        };
+
+        let Some(scalar) = ty.scalar() else {
+            return;
+        };
+
+        let component_type = ComponentType::from_scalar(scalar);
+        let (interpolation, interpolation_declared) =
+            Interpolation::from_qualifier(qualifier, component_type);
+        for _ in 0..rows {
+            let Ok(element_index) = u32::try_from(*index) else {
+                self.error(
+                    SignatureErrorKind::IndexOverflow(semantic.name.to_string()),
+                    semantic,
+                );
+                return;
+            };
+
+            self.elements.push(SignatureElement {
+                semantic: semantic.name.to_string(),
+                index: element_index,
+                component_type,
+                components,
+                interpolation,
+                interpolation_declared,
+                system_value: semantic.system_value(),
+                register: None,
+                mask: 0,
+                span: semantic.span,
+            });
+            self.whole_register.push(matches!(ty, Type::Matrix(..)));
+            *index += 1;
+        }
+    }
+
+    fn error(&mut self, kind: SignatureErrorKind, semantic: &ast::Semantic) {
+        self.errors.push(SignatureError {
+            kind,
+            span: semantic.span,
+        });
+    }
+
+    /// Pack the elements in declaration order, sharing the current register when possible
+    fn pack(&mut self) {
+        // Kind of elements which may share a register: arbitrary ones, or clip and cull
+        // distances
+        let class = |element: &SignatureElement| match element.system_value {
+            None => Some(false),
+            Some(SystemValue::ClipDistance | SystemValue::CullDistance) => Some(true),
+            Some(_) => None,
+        };
+
+        // Register being filled, number of components used and index of its last element
+        let mut current: Option<(u32, u8, usize)> = None;
+        let mut next = 0;
+
+        for i in 0..self.elements.len() {
+            let element = &self.elements[i];
+            let shared = current.and_then(|(register, used, previous)| {
+                let fits = used + element.components <= 4;
+                let whole = self.whole_register[i] || self.whole_register[previous];
+                let previous = &self.elements[previous];
+                let compatible = class(element).is_some()
+                    && class(element) == class(previous)
+                    && element.interpolation == previous.interpolation;
+                (fits && compatible && !whole).then_some((register, used))
+            });
+
+            let (register, start) = shared.unwrap_or_else(|| {
+                next += 1;
+                (next - 1, 0)
+            });
+
+            let element = &mut self.elements[i];
+            element.register = Some(register);
+            element.mask = mask(start, element.components);
+            current = Some((register, start + element.components, i));
+        }
+    }
+}
+
+/// Mask of `components` components starting at `start`
+fn mask(start: u8, components: u8) -> u8 {
+    ((1u8 << components) - 1) << start
+}
+
+/// Give each element a register of its own
+fn pack_unpacked(elements: &mut [SignatureElement]) {
+    for (register, element) in (0..).zip(elements.iter_mut()) {
+        element.register = Some(register);
+        element.mask = mask(0, element.components);
+    }
+}
+
+/// Pixel shader outputs go to the register of their render target, or to a dedicated one
+fn pack_pixel_outputs(elements: &mut [SignatureElement]) {
+    for element in elements.iter_mut() {
+        element.register = match element.system_value {
+            Some(sv) if sv.has_dedicated_register() => None,
+            _ => Some(element.index),
+        };
+        element.mask = mask(0, element.components);
+    }
+}
+
+/// `true` if the qualifier declares a geometry shader input primitive
+fn is_primitive(qualifier: Option<&ast::TypeQualifier>) -> bool {
+    qualifier
+        .into_iter()
+        .flat_map(|qualifier| &qualifier.qualifiers)
+        .any(|spec| {
+            matches!(&**spec, ast::TypeQualifierSpecData::Storage(storage)
+                if matches!(**storage,
+                    ast::StorageQualifierData::Point
+                    | ast::StorageQualifierData::Line
+                    | ast::StorageQualifierData::Triangle
+                    | ast::StorageQualifierData::LineAdj
+                    | ast::StorageQualifierData::TriangleAdj))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{entry_point, parse::Parsable, resolver};
+
+    fn signature_of(src: &str, entry: &str) -> Signature {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        let entry = entry_point::entry_points(&tu, &symbols)
+            .into_iter()
+            .find(|e| e.name == entry)
+            .unwrap();
+        signature(&entry, &symbols)
+    }
+
+    fn summary(elements: &[SignatureElement]) -> Vec<(String, Option<u32>, u8)> {
+        elements
+            .iter()
+            .map(|e| (e.full_name(), e.register, e.mask))
+            .collect()
+    }
+
+    fn names(elements: &[SignatureElement]) -> Vec<(String, u8)> {
+        elements
+            .iter()
+            .map(|e| (e.full_name(), e.components))
+            .collect()
+    }
+
+    #[test]
+    fn vertex_inputs_are_not_packed() {
+        let sig = signature_of(
+            r#"
+            float4 vs(float3 pos : POSITION, float2 uv : TEXCOORD0, uint id : SV_VertexID)
+                : SV_Position { return 0; }
+            "#,
+            "vs",
+        );
+
+        assert_eq!(
+            summary(&sig.inputs),
+            [
+                ("POSITION0".to_owned(), Some(0), 0b0111),
+                ("TEXCOORD0".to_owned(), Some(1), 0b0011),
+                ("SV_VertexID0".to_owned(), Some(2), 0b0001),
+            ]
+        );
+        assert_eq!(sig.inputs[2].system_value, Some(SystemValue::VertexId));
+        assert_eq!(sig.inputs[2].component_type, ComponentType::UInt32);
+        assert_eq!(
+            summary(&sig.outputs),
+            [("SV_Position0".to_owned(), Some(0), 0b1111)]
+        );
+    }
+
+    #[test]
+    fn pixel_signature() {
+        let sig = signature_of(
+            r#"
+            struct PSIn {
+                float4 pos : SV_Position;
+                float2 uv : TEXCOORD0;
+                noperspective float2 screen : TEXCOORD1;
+                nointerpolation uint material : MATERIAL;
+                float fade : TEXCOORD2;
+                uint layer : LAYER;
+            };
+            struct PSOut {
+                float4 color : SV_Target0;
+                float4 normal : SV_Target2;
+                float depth : SV_Depth;
+            };
+            PSOut ps(PSIn i, bool front : SV_IsFrontFace) { PSOut o; return o; }
+            "#,
+            "ps",
+        );
+
+        assert_eq!(
+            summary(&sig.inputs),
+            [
+                ("SV_Position0".to_owned(), Some(0), 0b1111),
+                ("TEXCOORD0".to_owned(), Some(1), 0b0011),
+                ("TEXCOORD1".to_owned(), Some(2), 0b0011),
+                ("MATERIAL0".to_owned(), Some(3), 0b0001),
+                ("TEXCOORD2".to_owned(), Some(4), 0b0001),
+                ("LAYER0".to_owned(), Some(5), 0b0001),
+                ("SV_IsFrontFace0".to_owned(), Some(6), 0b0001),
+            ]
+        );
+
+        let interpolation: Vec<_> = sig.inputs.iter().map(|e| e.interpolation).collect();
+        assert_eq!(
+            interpolation,
+            [
+                Interpolation::Linear,
+                Interpolation::Linear,
+                Interpolation::LinearNoPerspective,
+                Interpolation::Constant,
+                Interpolation::Linear,
+                Interpolation::Constant,
+                Interpolation::Constant,
+            ]
+        );
+
+        assert_eq!(
+            summary(&sig.outputs),
+            [
+                ("SV_Target0".to_owned(), Some(0), 0b1111),
+                ("SV_Target2".to_owned(), Some(2), 0b1111),
+                ("SV_Depth0".to_owned(), None, 0b0001),
+            ]
+        );
+    }
+
+    #[test]
+    fn flatten_matrices_arrays_and_structs() {
+        let sig = signature_of(
+            r#"
+            struct Pair { float2 a; float b; };
+            struct VSOut {
+                float4 pos : SV_Position;
+                float4x3 m : TEXCOORD2;
+                row_major float2x3 r : NORMAL;
+                float2 uv[2] : TEXCOORD5;
+                Pair p : COLOR0;
+            };
+            VSOut vs() { VSOut o; return o; }
+            "#,
+            "vs",
+        );
+
+        assert_eq!(
+            names(&sig.outputs),
+            [
+                ("SV_Position0".to_owned(), 4),
+                ("TEXCOORD2".to_owned(), 4),
+                ("TEXCOORD3".to_owned(), 4),
+                ("TEXCOORD4".to_owned(), 4),
+                ("NORMAL0".to_owned(), 3),
+                ("NORMAL1".to_owned(), 3),
+                ("TEXCOORD5".to_owned(), 2),
+                ("TEXCOORD6".to_owned(), 2),
+                ("COLOR0".to_owned(), 2),
+                ("COLOR1".to_owned(), 1),
+            ]
+        );
+
+        // Array elements take a register each rather than sharing one
+        let registers: Vec<_> = sig.outputs.iter().map(|e| e.register.unwrap()).collect();
+        assert_eq!(registers, [0, 1, 2, 3, 4, 5, 6, 7, 8, 8]);
+        assert_eq!(sig.outputs[6].mask, 0b0011);
+        assert_eq!(sig.outputs[7].mask, 0b0011);
+    }
+
+    #[test]
+    fn out_of_range_values() {
+        let sig = signature_of(
+            r#"
+            struct A { float4 pos : SV_Position; A inner; };
+            struct B { float x; B inner; };
+            float4 ps(
+                A a,
+                B b : TEXCOORD0,
+                float4 c[4] : COLOR4294967295,
+                float4 x[100000000] : TEXCOORD1,
+                float4 y[32][32] : TEXCOORD2
+            ) : SV_Target { return 0; }
+            "#,
+            "ps",
+        );
+
+        assert_eq!(
+            names(&sig.inputs),
+            [
+                ("SV_Position0".to_owned(), 4),
+                ("TEXCOORD0".to_owned(), 1),
+                ("COLOR4294967295".to_owned(), 4),
+            ]
+        );
+
+        let errors: Vec<_> = sig.errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            errors,
+            [
+                "semantic index of `COLOR` overflows",
+                "`TEXCOORD` needs more than the 32 registers of a signature",
+                "`TEXCOORD` needs more than the 32 registers of a signature",
+            ]
+        );
+    }
+
+    #[test]
+    fn excluded_semantics() {
+        let sig = signature_of(
+            r#"
+            struct GSIn { float4 pos : SV_Position; float2 uv : TEXCOORD0; };
+            [maxvertexcount(3)]
+            void gs(triangle GSIn input[3], uint instance : SV_GSInstanceID) {}
+            [numthreads(8, 8, 1)] void cs(uint3 id : SV_DispatchThreadID) {}
+            "#,
+            "gs",
+        );
+
+        assert_eq!(
+            names(&sig.inputs),
+            [("SV_Position0".to_owned(), 4), ("TEXCOORD0".to_owned(), 2)]
+        );
+        assert!(sig.outputs.is_empty());
+    }
+}
@@ -0,0 +1,1372 @@
+//! Symbol resolution
+//!
+//! The parser only knows about names as strings: an [ExprData::Variable](ast::ExprData::Variable)
+//! carries an identifier, not the declaration it refers to. This module walks a translation unit,
+//! builds its lexical scopes and produces a [SymbolTable], a side table which links every
+//! identifier use to the declaration it refers to: a local variable, a parameter, a global, a
+//! cbuffer member, a struct field, a function or an overload set.
+//!
+//! Names are resolved in source order with C++ scoping rules: a name is visible from its
+//! declarator to the end of the enclosing block, and inner declarations shadow outer ones. Struct
+//! fields are resolved through the type of the expression they are selected from, and are also
+//! visible by their bare name inside member functions.
+//!
+//! The table borrows the translation unit it was built from, and identifies AST nodes by their
+//! address: lookups must be given references into that same translation unit.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, parse::Parsable, resolver::{self, Resolution, SymbolKind}};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     "float x; float f(float x) { { float x = 1.0; } return x; }",
+//! )
+//! .unwrap();
+//! let table = resolver::resolve(&tu);
+//!
+//! // The returned `x` is the parameter, not the global or the block-scoped local
+//! let (ident, resolution) = table.uses().last().unwrap();
+//! assert_eq!(ident.as_str(), "x");
+//!
+//! let Resolution::Symbol(id) = resolution else { panic!() };
+//! assert_eq!(table.symbol(*id).kind, SymbolKind::Parameter);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::ast::{self, NodeSpan, SmolStr};
+
+/// Identifier of a symbol in a [SymbolTable]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(rserde::Serialize, rserde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct SymbolId(u32);
+
+impl SymbolId {
+    /// Index of this symbol in [SymbolTable::symbols]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Identifier of a scope in a [SymbolTable]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScopeId(u32);
+
+impl ScopeId {
+    /// Index of this scope in [SymbolTable::scopes]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Kind of declared symbol
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    /// Variable declared at global scope, including `static const` globals and sampler states
+    Global,
+    /// Variable declared in a function body
+    Local,
+    /// Function parameter
+    Parameter,
+    /// Constant buffer. Its name is not visible to expressions.
+    CBuffer,
+    /// Member of a constant buffer, visible at the scope the constant buffer is declared in
+    CBufferMember,
+    /// Struct, class or interface type
+    Struct,
+    /// Field of a struct
+    StructField,
+    /// Function, or member function of a struct
+    Function,
+}
+
+/// AST node declaring a symbol
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Declarator<'a> {
+    /// Variable declared by the first declarator of a declaration
+    Variable(&'a ast::SingleDeclaration),
+    /// Variable declared by a following declarator, whose type is given by `head`
+    Declarator {
+        /// First declarator of the declaration
+        head: &'a ast::SingleDeclaration,
+        /// Declarator of this variable
+        declarator: &'a ast::SingleDeclarationNoType,
+    },
+    /// Variable declared in the condition of a loop
+    Condition(&'a ast::Condition),
+    /// Function parameter
+    Parameter(&'a ast::FunctionParameterDeclarator),
+    /// Field of a struct or member of a constant buffer
+    Field {
+        /// Field declaration, which gives the type of the field
+        field: &'a ast::StructFieldSpecifier,
+        /// Declarator of this field
+        ident: &'a ast::ArrayedIdentifier,
+    },
+    /// Constant buffer
+    CBuffer(&'a ast::CBuffer),
+    /// Sampler state
+    SamplerState(&'a ast::SamplerState),
+    /// Struct type
+    Struct(&'a ast::StructSpecifier),
+    /// Function
+    Function {
+        /// First prototype declared for this function
+        prototype: &'a ast::FunctionPrototype,
+        /// Definition of this function, if any
+        definition: Option<&'a ast::FunctionDefinition>,
+    },
+    /// Member function of a struct
+    Method {
+        /// Declaration of the method in the struct body
+        method: &'a ast::Method,
+        /// Out-of-line definition of the method, if any
+        definition: Option<&'a ast::FunctionDefinition>,
+    },
+}
+
+impl<'a> Declarator<'a> {
+    /// Type of the declared variable, field or parameter, along with the array specifier
+    /// attached to its name, if any. For functions, this is the return type.
+    pub fn ty(&self) -> Option<(&'a ast::TypeSpecifier, Option<&'a ast::ArraySpecifier>)> {
+        match *self {
+            Self::Variable(decl) => Some((&decl.ty.ty, decl.array_specifier.as_ref())),
+            Self::Declarator { head, declarator } => {
+                Some((&head.ty.ty, declarator.ident.array_spec.as_ref()))
+            }
+            Self::Condition(cond) => match &**cond {
+                ast::ConditionData::Assignment(ty, _, _) => Some((&ty.ty, None)),
+                ast::ConditionData::Expr(_) => None,
+            },
+            Self::Parameter(param) => Some((&param.ty, param.ident.array_spec.as_ref())),
+            Self::Field { field, ident } => Some((&field.ty, ident.array_spec.as_ref())),
+            Self::Function { prototype, .. } => Some((&prototype.ty.ty, None)),
+            Self::Method { method, .. } => Some((&method.prototype.ty.ty, None)),
+            Self::CBuffer(_) | Self::SamplerState(_) | Self::Struct(_) => None,
+        }
+    }
+
+    /// Prototype of the declared function or method
+    pub fn prototype(&self) -> Option<&'a ast::FunctionPrototype> {
+        match *self {
+            Self::Function { prototype, .. } => Some(prototype),
+            Self::Method { method, .. } => Some(&method.prototype),
+            _ => None,
+        }
+    }
+}
+
+/// A declared symbol
+#[derive(Clone, Debug, PartialEq)]
+pub struct Symbol<'a> {
+    /// Declared name. Empty for anonymous structs.
+    pub name: SmolStr,
+    /// Kind of symbol
+    pub kind: SymbolKind,
+    /// Declaring node
+    pub declarator: Declarator<'a>,
+    /// Span of the declared name
+    pub span: Option<NodeSpan>,
+    /// Scope this symbol is declared in
+    pub scope: ScopeId,
+    /// Struct or constant buffer this symbol is a member of
+    pub parent: Option<SymbolId>,
+    /// Variable of an enclosing scope hidden by this declaration
+    pub shadows: Option<SymbolId>,
+}
+
+/// Kind of scope
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScopeKind {
+    /// Translation unit scope
+    Global,
+    /// Parameters and outermost block of a function
+    Function,
+    /// Statement block, or the implicit block of a control flow statement
+    Block,
+    /// Members of a struct
+    Struct,
+}
+
+/// A lexical scope
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scope {
+    /// Kind of scope
+    pub kind: ScopeKind,
+    /// Enclosing scope
+    pub parent: Option<ScopeId>,
+    /// Symbols declared in this scope, in declaration order
+    pub symbols: Vec<SymbolId>,
+    values: HashMap<SmolStr, Binding>,
+    types: HashMap<SmolStr, SymbolId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Binding {
+    Symbol(SymbolId),
+    Functions(Vec<SymbolId>),
+}
+
+impl From<&Binding> for Resolution {
+    fn from(binding: &Binding) -> Self {
+        match binding {
+            Binding::Symbol(id) => Self::Symbol(*id),
+            Binding::Functions(ids) if ids.len() == 1 => Self::Symbol(ids[0]),
+            Binding::Functions(ids) => Self::Overloads(ids.clone()),
+        }
+    }
+}
+
+/// Declaration an identifier use refers to
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    /// A single symbol
+    Symbol(SymbolId),
+    /// Several overloads of a function, to be disambiguated by the call arguments
+    Overloads(Vec<SymbolId>),
+    /// No declaration is visible. This is the case of calls to intrinsic functions.
+    Unresolved,
+}
+
+impl Resolution {
+    /// Symbols this use may refer to
+    pub fn symbols(&self) -> &[SymbolId] {
+        match self {
+            Self::Symbol(id) => std::slice::from_ref(id),
+            Self::Overloads(ids) => ids,
+            Self::Unresolved => &[],
+        }
+    }
+}
+
+/// Result of symbol resolution over a translation unit
+#[derive(Clone, Debug, Default)]
+pub struct SymbolTable<'a> {
+    symbols: Vec<Symbol<'a>>,
+    scopes: Vec<Scope>,
+    uses: Vec<(&'a ast::Identifier, Resolution)>,
+    use_index: HashMap<usize, usize>,
+    declarations: HashMap<usize, SymbolId>,
+    types: HashMap<usize, SymbolId>,
+    structs: HashMap<usize, SymbolId>,
+    members: HashMap<SymbolId, (ScopeId, Vec<SymbolId>)>,
+}
+
+/// Address of a node, used to identify it in side tables
+fn addr<T>(node: &T) -> usize {
+    node as *const T as usize
+}
+
+impl<'a> SymbolTable<'a> {
+    /// All the declared symbols, in declaration order
+    pub fn symbols(&self) -> &[Symbol<'a>] {
+        &self.symbols
+    }
+
+    /// Get a symbol by its identifier
+    pub fn symbol(&self, id: SymbolId) -> &Symbol<'a> {
+        &self.symbols[id.index()]
+    }
+
+    /// All the scopes. The first one is the global scope.
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    /// Get a scope by its identifier
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.index()]
+    }
+
+    /// All the identifier uses, in source order, with the declaration they refer to
+    pub fn uses(&self) -> impl Iterator<Item = (&'a ast::Identifier, &Resolution)> + '_ {
+        self.uses.iter().map(|(ident, res)| (*ident, res))
+    }
+
+    /// Identifier uses which may refer to the given symbol
+    pub fn uses_of(&self, id: SymbolId) -> impl Iterator<Item = &'a ast::Identifier> + '_ {
+        self.uses
+            .iter()
+            .filter(move |(_, res)| res.symbols().contains(&id))
+            .map(|(ident, _)| *ident)
+    }
+
+    /// Declaration the given identifier use refers to
+    ///
+    /// Returns `None` if `ident` is not a use of a name, such as a swizzle or the name in a
+    /// declaration.
+    pub fn resolution(&self, ident: &ast::Identifier) -> Option<&Resolution> {
+        self.use_index
+            .get(&addr(ident))
+            .map(|index| &self.uses[*index].1)
+    }
+
+    /// Symbol declared by the given identifier
+    pub fn declared_by(&self, ident: &ast::Identifier) -> Option<SymbolId> {
+        self.declarations.get(&addr(ident)).copied()
+    }
+
+    /// Struct the given type name refers to
+    pub fn type_name(&self, name: &ast::TypeName) -> Option<SymbolId> {
+        self.types.get(&addr(name)).copied()
+    }
+
+    /// Struct declared by the given struct specifier
+    pub fn struct_specifier(&self, spec: &ast::StructSpecifier) -> Option<SymbolId> {
+        self.structs.get(&addr(spec)).copied()
+    }
+
+    /// Struct the given type specifier refers to, if it names a struct
+    pub fn struct_type(&self, ty: &ast::TypeSpecifierNonArray) -> Option<SymbolId> {
+        match &**ty {
+            ast::TypeSpecifierNonArrayData::TypeName(name) => self.type_name(name),
+            ast::TypeSpecifierNonArrayData::Struct(spec) => self.struct_specifier(spec),
+            _ => None,
+        }
+    }
+
+    /// Fields and member functions of a struct, in declaration order, excluding inherited ones
+    pub fn members(&self, id: SymbolId) -> &[SymbolId] {
+        match self.members.get(&id) {
+            Some((scope, _)) => &self.scope(*scope).symbols,
+            None => &[],
+        }
+    }
+
+    /// Base types of a struct
+    pub fn bases(&self, id: SymbolId) -> &[SymbolId] {
+        match self.members.get(&id) {
+            Some((_, bases)) => bases,
+            None => &[],
+        }
+    }
+
+    /// Look up a field or member function of a struct by name, including inherited members
+    pub fn member(&self, id: SymbolId, name: &str) -> Option<Resolution> {
+        let (scope, bases) = self.members.get(&id)?;
+
+        self.scope(*scope)
+            .values
+            .get(name)
+            .map(Resolution::from)
+            .or_else(|| bases.iter().find_map(|base| self.member(*base, name)))
+    }
+
+    /// Look up a name visible from the given scope
+    pub fn lookup(&self, scope: ScopeId, name: &str) -> Option<Resolution> {
+        self.lookup_binding(scope, name).map(|(_, b)| b.into())
+    }
+
+    fn lookup_binding(&self, scope: ScopeId, name: &str) -> Option<(ScopeId, &Binding)> {
+        let mut current = Some(scope);
+        while let Some(id) = current {
+            let scope = self.scope(id);
+            if let Some(binding) = scope.values.get(name) {
+                return Some((id, binding));
+            }
+
+            current = scope.parent;
+        }
+
+        None
+    }
+
+    fn lookup_type(&self, scope: ScopeId, name: &str) -> Option<SymbolId> {
+        let mut current = Some(scope);
+        while let Some(id) = current {
+            let scope = self.scope(id);
+            if let Some(ty) = scope.types.get(name) {
+                return Some(*ty);
+            }
+
+            current = scope.parent;
+        }
+
+        None
+    }
+}
+
+/// Resolve all the names in a translation unit
+pub fn resolve(tu: &ast::TranslationUnit) -> SymbolTable<'_> {
+    let mut resolver = Resolver {
+        table: SymbolTable::default(),
+        current: ScopeId(0),
+    };
+
+    resolver.table.scopes.push(Scope {
+        kind: ScopeKind::Global,
+        parent: None,
+        symbols: Vec::new(),
+        values: HashMap::new(),
+        types: HashMap::new(),
+    });
+
+    for decl in &tu.0 {
+        resolver.external_declaration(decl);
+    }
+
+    resolver.table
+}
+
+/// Shape of the value of an expression, as far as struct member lookup is concerned
+enum Shape {
+    Struct(SymbolId),
+    Buffer(SymbolId),
+    Array(Box<Shape>),
+}
+
+struct Resolver<'a> {
+    table: SymbolTable<'a>,
+    current: ScopeId,
+}
+
+impl<'a> Resolver<'a> {
+    fn external_declaration(&mut self, decl: &'a ast::ExternalDeclaration) {
+        match &**decl {
+            ast::ExternalDeclarationData::Preprocessor(_) => {}
+            ast::ExternalDeclarationData::FunctionDefinition(def) => self.function_definition(def),
+            ast::ExternalDeclarationData::Declaration(decl) => self.declaration(decl),
+            // Type parameters are left unresolved
+            ast::ExternalDeclarationData::Template(template) => {
+                self.external_declaration(&template.declaration)
+            }
+        }
+    }
+
+    /// Enter a new scope, returning the scope to restore with [Self::leave]
+    fn enter(&mut self, kind: ScopeKind, parent: ScopeId) -> ScopeId {
+        let id = ScopeId(self.table.scopes.len() as u32);
+        self.table.scopes.push(Scope {
+            kind,
+            parent: Some(parent),
+            symbols: Vec::new(),
+            values: HashMap::new(),
+            types: HashMap::new(),
+        });
+
+        std::mem::replace(&mut self.current, id)
+    }
+
+    fn leave(&mut self, previous: ScopeId) {
+        self.current = previous;
+    }
+
+    fn add_symbol(
+        &mut self,
+        name: &SmolStr,
+        kind: SymbolKind,
+        declarator: Declarator<'a>,
+        span: Option<NodeSpan>,
+        parent: Option<SymbolId>,
+    ) -> SymbolId {
+        let id = SymbolId(self.table.symbols.len() as u32);
+        self.table.symbols.push(Symbol {
+            name: name.clone(),
+            kind,
+            declarator,
+            span,
+            scope: self.current,
+            parent,
+            shadows: None,
+        });
+
+        self.table.scopes[self.current.index()].symbols.push(id);
+        id
+    }
+
+    /// Declare a variable, field or parameter in the current scope
+    fn declare_value(
+        &mut self,
+        ident: &'a ast::Identifier,
+        kind: SymbolKind,
+        declarator: Declarator<'a>,
+        parent: Option<SymbolId>,
+    ) -> SymbolId {
+        let id = self.add_symbol(&ident.0, kind, declarator, ident.span, parent);
+
+        let scope = self.table.scope(self.current);
+        let shadows = scope
+            .parent
+            .and_then(|parent| self.table.lookup_binding(parent, ident.as_str()))
+            .and_then(|(_, binding)| match binding {
+                Binding::Symbol(id) => Some(*id),
+                Binding::Functions(_) => None,
+            });
+
+        self.table.symbols[id.index()].shadows = shadows;
+        self.table.scopes[self.current.index()]
+            .values
+            .insert(ident.0.clone(), Binding::Symbol(id));
+        self.table.declarations.insert(addr(ident), id);
+        id
+    }
+
+    /// Declare a function in the current scope, or add a definition to an existing prototype
+    fn declare_function(
+        &mut self,
+        prototype: &'a ast::FunctionPrototype,
+        declarator: Declarator<'a>,
+        definition: Option<&'a ast::FunctionDefinition>,
+    ) -> SymbolId {
+        let name = &prototype.name;
+        let existing = match self.table.scope(self.current).values.get(name.as_str()) {
+            Some(Binding::Functions(ids)) => ids.clone(),
+            _ => Vec::new(),
+        };
+
+        // A definition completes an earlier prototype with the same signature
+        if let Some(def) = definition {
+            if let Some(id) = existing.iter().copied().find(|id| {
+                let symbol = self.table.symbol(*id);
+                symbol
+                    .declarator
+                    .prototype()
+                    .is_some_and(|other| same_signature(other, prototype))
+            }) {
+                match &mut self.table.symbols[id.index()].declarator {
+                    Declarator::Function { definition, .. }
+                    | Declarator::Method { definition, .. }
+                        if definition.is_none() =>
+                    {
+                        *definition = Some(def);
+                        self.table.declarations.insert(addr(name), id);
+                        return id;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let id = self.add_symbol(&name.0, SymbolKind::Function, declarator, name.span, None);
+        let mut ids = existing;
+        ids.push(id);
+        self.table.scopes[self.current.index()]
+            .values
+            .insert(name.0.clone(), Binding::Functions(ids));
+        self.table.declarations.insert(addr(name), id);
+        id
+    }
+
+    fn record_use(&mut self, ident: &'a ast::Identifier, resolution: Resolution) {
+        self.table
+            .use_index
+            .insert(addr(ident), self.table.uses.len());
+        self.table.uses.push((ident, resolution));
+    }
+
+    fn declaration(&mut self, decl: &'a ast::Declaration) {
+        let kind = if self.current == ScopeId(0) {
+            SymbolKind::Global
+        } else {
+            SymbolKind::Local
+        };
+
+        match &**decl {
+            ast::DeclarationData::FunctionPrototype(proto) => {
+                self.prototype_types(proto);
+                self.declare_function(
+                    proto,
+                    Declarator::Function {
+                        prototype: proto,
+                        definition: None,
+                    },
+                    None,
+                );
+            }
+            ast::DeclarationData::InitDeclaratorList(list) => {
+                let head = &list.head;
+                self.attributes(head.attributes.as_deref());
+                self.type_specifier(&head.ty.ty);
+
+                if let Some(name) = &head.name {
+                    self.array_specifier(head.array_specifier.as_ref());
+                    self.declare_value(name, kind, Declarator::Variable(head), None);
+                    self.initializer(head.initializer.as_ref());
+                }
+
+                for declarator in &list.tail {
+                    self.array_specifier(declarator.ident.array_spec.as_ref());
+                    self.declare_value(
+                        &declarator.ident.ident,
+                        kind,
+                        Declarator::Declarator { head, declarator },
+                        None,
+                    );
+                    self.initializer(declarator.initializer.as_ref());
+                }
+            }
+            ast::DeclarationData::CBuffer(cbuffer) => {
+                self.attributes(cbuffer.attributes.as_deref());
+                let parent = self.add_symbol(
+                    &cbuffer.name.0,
+                    SymbolKind::CBuffer,
+                    Declarator::CBuffer(cbuffer),
+                    cbuffer.name.span,
+                    None,
+                );
+                self.table.declarations.insert(addr(&cbuffer.name), parent);
+
+                for field in &cbuffer.fields {
+                    self.fields(field, SymbolKind::CBufferMember, parent);
+                }
+            }
+            ast::DeclarationData::SamplerState(sampler) => {
+                self.declare_value(&sampler.name, kind, Declarator::SamplerState(sampler), None);
+            }
+            ast::DeclarationData::Precision(_, _)
+            | ast::DeclarationData::Block(_)
+            | ast::DeclarationData::Invariant(_)
+            | ast::DeclarationData::TypeOnly(_) => {}
+        }
+    }
+
+    fn fields(&mut self, field: &'a ast::StructFieldSpecifier, kind: SymbolKind, parent: SymbolId) {
+        self.attributes(field.attributes.as_deref());
+        self.type_specifier(&field.ty);
+
+        for ident in &field.identifiers {
+            self.array_specifier(ident.array_spec.as_ref());
+            if let Some(width) = &ident.bit_width {
+                self.expr(width);
+            }
+
+            self.declare_value(
+                &ident.ident,
+                kind,
+                Declarator::Field { field, ident },
+                Some(parent),
+            );
+        }
+    }
+
+    fn struct_specifier(&mut self, spec: &'a ast::StructSpecifier) {
+        let name = spec.name.as_ref().map(|n| n.0.clone()).unwrap_or_default();
+        let id = self.add_symbol(
+            &name,
+            SymbolKind::Struct,
+            Declarator::Struct(spec),
+            spec.name.as_ref().and_then(|n| n.span),
+            None,
+        );
+        self.table.structs.insert(addr(spec), id);
+
+        // Bases are resolved before the struct name is visible, so a struct cannot derive from
+        // itself and the inheritance graph has no cycles
+        let bases = spec
+            .bases
+            .iter()
+            .filter_map(|base| self.type_name(base))
+            .collect();
+
+        // The struct name is visible in its own body
+        if let Some(name) = &spec.name {
+            self.table.scopes[self.current.index()]
+                .types
+                .insert(name.0.clone(), id);
+        }
+
+        let previous = self.enter(ScopeKind::Struct, self.current);
+        self.table.members.insert(id, (self.current, bases));
+
+        for field in &spec.fields {
+            self.fields(field, SymbolKind::StructField, id);
+        }
+
+        // All the member functions are visible in the bodies of inline methods
+        let mut methods = Vec::with_capacity(spec.methods.len());
+        for method in &spec.methods {
+            self.attributes(method.attributes.as_deref());
+            self.prototype_types(&method.prototype);
+            let method_id = self.declare_function(
+                &method.prototype,
+                Declarator::Method {
+                    method,
+                    definition: None,
+                },
+                None,
+            );
+            self.table.symbols[method_id.index()].parent = Some(id);
+            methods.push(method);
+        }
+
+        for method in methods {
+            if let Some(body) = &method.statement {
+                self.function_body(&method.prototype, body, self.current);
+            }
+        }
+
+        self.leave(previous);
+    }
+
+    fn function_definition(&mut self, def: &'a ast::FunctionDefinition) {
+        let proto = &def.prototype;
+        self.attributes(def.attributes.as_deref());
+        self.prototype_types(proto);
+
+        // Out-of-line method definitions are declared in the scope of their type
+        let member_scope = proto
+            .scope
+            .as_ref()
+            .and_then(|ty| self.type_name(ty))
+            .and_then(|id| Some((id, self.table.members.get(&id)?.0)));
+
+        let parent = match member_scope {
+            Some((struct_id, scope)) => {
+                let previous = std::mem::replace(&mut self.current, scope);
+                let id = self.declare_function(
+                    proto,
+                    Declarator::Function {
+                        prototype: proto,
+                        definition: Some(def),
+                    },
+                    Some(def),
+                );
+                self.table.symbols[id.index()]
+                    .parent
+                    .get_or_insert(struct_id);
+                self.current = previous;
+                scope
+            }
+            None => {
+                self.declare_function(
+                    proto,
+                    Declarator::Function {
+                        prototype: proto,
+                        definition: Some(def),
+                    },
+                    Some(def),
+                );
+                self.current
+            }
+        };
+
+        self.function_body(proto, &def.statement, parent);
+    }
+
+    /// Resolve the types and default values in a function prototype
+    fn prototype_types(&mut self, proto: &'a ast::FunctionPrototype) {
+        if let Some(scope) = &proto.scope {
+            self.type_name(scope);
+        }
+
+        self.type_specifier(&proto.ty.ty);
+
+        for param in &proto.parameters {
+            match &**param {
+                ast::FunctionParameterDeclarationData::Named(_, declarator) => {
+                    self.attributes(declarator.attributes.as_deref());
+                    self.type_specifier(&declarator.ty);
+                    self.array_specifier(declarator.ident.array_spec.as_ref());
+                    self.initializer(declarator.default_value.as_ref());
+                }
+                ast::FunctionParameterDeclarationData::Unnamed(_, ty) => self.type_specifier(ty),
+            }
+        }
+    }
+
+    /// Declare the parameters of a function and resolve the names in its body
+    fn function_body(
+        &mut self,
+        proto: &'a ast::FunctionPrototype,
+        body: &'a ast::CompoundStatement,
+        parent: ScopeId,
+    ) {
+        let previous = self.enter(ScopeKind::Function, parent);
+
+        for param in &proto.parameters {
+            if let ast::FunctionParameterDeclarationData::Named(_, declarator) = &**param {
+                self.declare_value(
+                    &declarator.ident.ident,
+                    SymbolKind::Parameter,
+                    Declarator::Parameter(declarator),
+                    None,
+                );
+            }
+        }
+
+        // Parameters and the outermost block of the body share the same scope
+        for statement in &body.statement_list {
+            self.statement(statement);
+        }
+
+        self.leave(previous);
+    }
+
+    fn statement(&mut self, statement: &'a ast::Statement) {
+        match &**statement {
+            ast::StatementData::Declaration(decl) => self.declaration(decl),
+            ast::StatementData::Expression(expr) => {
+                if let Some(expr) = &expr.0 {
+                    self.expr(expr);
+                }
+            }
+            ast::StatementData::Selection(selection) => {
+                self.expr(&selection.cond);
+                match &*selection.rest {
+                    ast::SelectionRestStatementData::Statement(then) => self.sub_statement(then),
+                    ast::SelectionRestStatementData::Else(then, otherwise) => {
+                        self.sub_statement(then);
+                        self.sub_statement(otherwise);
+                    }
+                }
+            }
+            ast::StatementData::Switch(switch) => {
+                self.expr(&switch.head);
+
+                let previous = self.enter(ScopeKind::Block, self.current);
+                for statement in &switch.body {
+                    self.statement(statement);
+                }
+                self.leave(previous);
+            }
+            ast::StatementData::CaseLabel(label) => {
+                if let ast::CaseLabelData::Case(expr) = &**label {
+                    self.expr(expr);
+                }
+            }
+            ast::StatementData::Iteration(iteration) => {
+                let previous = self.enter(ScopeKind::Block, self.current);
+
+                match &**iteration {
+                    ast::IterationStatementData::While(cond, body) => {
+                        self.condition(cond);
+                        self.sub_statement(body);
+                    }
+                    ast::IterationStatementData::DoWhile(body, cond) => {
+                        self.sub_statement(body);
+                        self.expr(cond);
+                    }
+                    ast::IterationStatementData::For(init, rest, body) => {
+                        match &**init {
+                            ast::ForInitStatementData::Expression(expr) => {
+                                if let Some(expr) = expr {
+                                    self.expr(expr);
+                                }
+                            }
+                            ast::ForInitStatementData::Declaration(decl) => self.declaration(decl),
+                        }
+
+                        if let Some(cond) = &rest.condition {
+                            self.condition(cond);
+                        }
+
+                        if let Some(post) = &rest.post_expr {
+                            self.expr(post);
+                        }
+
+                        self.sub_statement(body);
+                    }
+                }
+
+                self.leave(previous);
+            }
+            ast::StatementData::Jump(jump) => {
+                if let ast::JumpStatementData::Return(Some(expr)) = &**jump {
+                    self.expr(expr);
+                }
+            }
+            ast::StatementData::Compound(compound) => {
+                let previous = self.enter(ScopeKind::Block, self.current);
+                for statement in &compound.statement_list {
+                    self.statement(statement);
+                }
+                self.leave(previous);
+            }
+            ast::StatementData::Attributed(attributes, statement) => {
+                self.attributes(Some(attributes));
+                self.statement(statement);
+            }
+        }
+    }
+
+    /// Body of a control flow statement, which has its own scope even if it is not a block
+    fn sub_statement(&mut self, statement: &'a ast::Statement) {
+        if let ast::StatementData::Compound(_) = &**statement {
+            self.statement(statement);
+        } else {
+            let previous = self.enter(ScopeKind::Block, self.current);
+            self.statement(statement);
+            self.leave(previous);
+        }
+    }
+
+    fn condition(&mut self, cond: &'a ast::Condition) {
+        match &**cond {
+            ast::ConditionData::Expr(expr) => self.expr(expr),
+            ast::ConditionData::Assignment(ty, name, init) => {
+                self.type_specifier(&ty.ty);
+                self.declare_value(name, SymbolKind::Local, Declarator::Condition(cond), None);
+                self.initializer(Some(init));
+            }
+        }
+    }
+
+    fn attributes(&mut self, attributes: Option<&'a [ast::Attribute]>) {
+        for attr in attributes.into_iter().flatten() {
+            for param in attr.spec.params.iter().flatten() {
+                self.expr(param);
+            }
+        }
+    }
+
+    fn initializer(&mut self, init: Option<&'a ast::Initializer>) {
+        match init.map(|init| &**init) {
+            Some(ast::InitializerData::Simple(expr)) => self.expr(expr),
+            Some(ast::InitializerData::List(list)) => {
+                for init in list {
+                    self.initializer(Some(init));
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn array_specifier(&mut self, array: Option<&'a ast::ArraySpecifier>) {
+        for dim in array.into_iter().flat_map(|array| &array.dimensions) {
+            if let ast::ArraySpecifierDimensionData::ExplicitlySized(size) = &**dim {
+                self.expr(size);
+            }
+        }
+    }
+
+    fn type_name(&mut self, name: &'a ast::TypeName) -> Option<SymbolId> {
+        let id = self.table.lookup_type(self.current, name.as_str())?;
+        self.table.types.insert(addr(name), id);
+        Some(id)
+    }
+
+    fn type_specifier(&mut self, ty: &'a ast::TypeSpecifier) {
+        match &*ty.ty {
+            ast::TypeSpecifierNonArrayData::TypeName(name) => {
+                self.type_name(name);
+            }
+            ast::TypeSpecifierNonArrayData::Struct(spec) => self.struct_specifier(spec),
+            ast::TypeSpecifierNonArrayData::StructuredBufferTemplate(inner)
+            | ast::TypeSpecifierNonArrayData::RWStructuredBufferTemplate(inner) => {
+                self.type_specifier(inner)
+            }
+            _ => {}
+        }
+
+        self.array_specifier(ty.array_specifier.as_ref());
+    }
+
+    fn expr(&mut self, expr: &'a ast::Expr) {
+        match &**expr {
+            ast::ExprData::Variable(ident) => {
+                let resolution = self
+                    .table
+                    .lookup(self.current, ident.as_str())
+                    .unwrap_or(Resolution::Unresolved);
+                self.record_use(ident, resolution);
+            }
+            ast::ExprData::IntConst(_)
+            | ast::ExprData::UIntConst(_)
+            | ast::ExprData::BoolConst(_)
+            | ast::ExprData::FloatConst(_)
+            | ast::ExprData::DoubleConst(_)
+            | ast::ExprData::StringConst(_)
+            | ast::ExprData::StringConcat(_)
+            | ast::ExprData::CharConst(_) => {}
+            ast::ExprData::Unary(_, e) | ast::ExprData::PostInc(e) | ast::ExprData::PostDec(e) => {
+                self.expr(e)
+            }
+            ast::ExprData::Cast(ty, e) => {
+                self.type_specifier(ty);
+                self.expr(e);
+            }
+            ast::ExprData::Binary(_, l, r)
+            | ast::ExprData::Assignment(l, _, r)
+            | ast::ExprData::Bracket(l, r)
+            | ast::ExprData::Comma(l, r) => {
+                self.expr(l);
+                self.expr(r);
+            }
+            ast::ExprData::Ternary(c, t, f) => {
+                self.expr(c);
+                self.expr(t);
+                self.expr(f);
+            }
+            ast::ExprData::FunCall(fun, args) => {
+                match &**fun {
+                    ast::FunIdentifierData::TypeSpecifier(ty) => self.type_specifier(ty),
+                    ast::FunIdentifierData::Expr(e) => self.expr(e),
+                }
+
+                for arg in args {
+                    self.expr(arg);
+                }
+            }
+            ast::ExprData::Dot(base, field) => {
+                self.expr(base);
+
+                // Only member selections on structs are names: other selections are swizzles
+                if let Some(Shape::Struct(id)) = self.shape(base) {
+                    let resolution = self
+                        .table
+                        .member(id, field.as_str())
+                        .unwrap_or(Resolution::Unresolved);
+                    self.record_use(field, resolution);
+                }
+            }
+        }
+    }
+
+    /// Shape of the value of an already resolved expression
+    fn shape(&self, expr: &ast::Expr) -> Option<Shape> {
+        match &**expr {
+            ast::ExprData::Variable(ident) | ast::ExprData::Dot(_, ident) => {
+                match self.table.resolution(ident)? {
+                    Resolution::Symbol(id) => self.symbol_shape(*id),
+                    _ => None,
+                }
+            }
+            ast::ExprData::Bracket(base, _) => match self.shape(base)? {
+                Shape::Array(inner) => Some(*inner),
+                Shape::Buffer(id) => Some(Shape::Struct(id)),
+                Shape::Struct(_) => None,
+            },
+            ast::ExprData::FunCall(fun, _) => match &**fun {
+                ast::FunIdentifierData::TypeSpecifier(ty) => self.type_shape(ty, None),
+                ast::FunIdentifierData::Expr(callee) => match &***callee {
+                    ast::ExprData::Variable(ident) | ast::ExprData::Dot(_, ident) => {
+                        // Overloads usually share their return type
+                        let id = *self.table.resolution(ident)?.symbols().first()?;
+                        self.symbol_shape(id)
+                    }
+                    _ => None,
+                },
+            },
+            ast::ExprData::Cast(ty, _) => self.type_shape(ty, None),
+            ast::ExprData::Ternary(_, e, _)
+            | ast::ExprData::Assignment(e, _, _)
+            | ast::ExprData::Comma(_, e) => self.shape(e),
+            _ => None,
+        }
+    }
+
+    fn symbol_shape(&self, id: SymbolId) -> Option<Shape> {
+        let (ty, array) = self.table.symbol(id).declarator.ty()?;
+        self.type_shape(ty, array)
+    }
+
+    fn type_shape(
+        &self,
+        ty: &ast::TypeSpecifier,
+        array: Option<&ast::ArraySpecifier>,
+    ) -> Option<Shape> {
+        let mut shape = match &*ty.ty {
+            ast::TypeSpecifierNonArrayData::StructuredBufferTemplate(inner)
+            | ast::TypeSpecifierNonArrayData::RWStructuredBufferTemplate(inner) => {
+                match self.type_shape(inner, None)? {
+                    Shape::Struct(id) => Shape::Buffer(id),
+                    _ => return None,
+                }
+            }
+            _ => Shape::Struct(self.table.struct_type(&ty.ty)?),
+        };
+
+        let dimensions = ty
+            .array_specifier
+            .iter()
+            .chain(array)
+            .map(|array| array.dimensions.len())
+            .sum::<usize>();
+        for _ in 0..dimensions {
+            shape = Shape::Array(Box::new(shape));
+        }
+
+        Some(shape)
+    }
+}
+
+/// `true` if two prototypes declare the same overload
+fn same_signature(a: &ast::FunctionPrototype, b: &ast::FunctionPrototype) -> bool {
+    fn param_types(
+        proto: &ast::FunctionPrototype,
+    ) -> impl Iterator<Item = (&ast::TypeSpecifier, Option<&ast::ArraySpecifier>)> {
+        proto.parameters.iter().map(|param| match &**param {
+            ast::FunctionParameterDeclarationData::Named(_, declarator) => {
+                (&declarator.ty, declarator.ident.array_spec.as_ref())
+            }
+            ast::FunctionParameterDeclarationData::Unnamed(_, ty) => (ty, None),
+        })
+    }
+
+    a.parameters.len() == b.parameters.len() && param_types(a).eq(param_types(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Parsable;
+
+    /// Kinds and declaration order of the symbols referred to by the uses of `name`
+    fn resolve_uses(src: &str, name: &str) -> Vec<Option<(SymbolKind, usize)>> {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let table = resolve(&tu);
+
+        table
+            .uses()
+            .filter(|(ident, _)| ident.as_str() == name)
+            .map(|(_, res)| match res {
+                Resolution::Symbol(id) => {
+                    let symbol = table.symbol(*id);
+                    let nth = table
+                        .symbols()
+                        .iter()
+                        .filter(|s| s.name == symbol.name)
+                        .position(|s| s == symbol)
+                        .unwrap();
+                    Some((symbol.kind, nth))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resolve_scopes_and_shadowing() {
+        assert_eq!(
+            resolve_uses(
+                "float x;
+                float f(float x) {
+                    float a = x;
+                    {
+                        float x = 2.0;
+                        a += x;
+                    }
+                    for (int x = 0; x < 4; x++) { a += x; }
+                    return a * x;
+                }
+                float g() { return x; }",
+                "x"
+            ),
+            vec![
+                Some((SymbolKind::Parameter, 1)),
+                Some((SymbolKind::Local, 2)),
+                Some((SymbolKind::Local, 3)),
+                Some((SymbolKind::Local, 3)),
+                Some((SymbolKind::Local, 3)),
+                Some((SymbolKind::Parameter, 1)),
+                Some((SymbolKind::Global, 0)),
+            ]
+        );
+
+        // Names are only visible after their declaration
+        assert_eq!(
+            resolve_uses("float f() { return y; } float y;", "y"),
+            vec![None]
+        );
+    }
+
+    #[test]
+    fn resolve_shadowed_symbols() {
+        let tu =
+            ast::TranslationUnit::parse("float x; void f() { float x = 1.0; { float x = x; } }")
+                .unwrap();
+        let table = resolve(&tu);
+
+        let xs: Vec<_> = table.symbols().iter().filter(|s| s.name == "x").collect();
+        assert_eq!(xs[0].shadows, None);
+        assert_eq!(xs[1].shadows, Some(SymbolId(0)));
+        assert_eq!(xs[2].shadows, Some(SymbolId(2)));
+        assert_eq!(table.scope(xs[2].scope).kind, ScopeKind::Block);
+    }
+
+    #[test]
+    fn resolve_cbuffer_members() {
+        let tu = ast::TranslationUnit::parse(
+            "cbuffer Globals : register(b0) { float4x4 mvp; float4 tint; };
+            float4 main(float4 pos : POSITION) : SV_Position { return mul(mvp, pos) * tint; }",
+        )
+        .unwrap();
+        let table = resolve(&tu);
+
+        let uses: Vec<_> = table
+            .uses()
+            .map(|(ident, res)| {
+                (
+                    ident.as_str(),
+                    res.symbols()
+                        .first()
+                        .map(|id| (table.symbol(*id).kind, table.symbol(*id).parent)),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            uses,
+            vec![
+                ("mul", None),
+                ("mvp", Some((SymbolKind::CBufferMember, Some(SymbolId(0))))),
+                ("pos", Some((SymbolKind::Parameter, None))),
+                ("tint", Some((SymbolKind::CBufferMember, Some(SymbolId(0))))),
+            ]
+        );
+        assert_eq!(table.symbol(SymbolId(0)).kind, SymbolKind::CBuffer);
+    }
+
+    #[test]
+    fn resolve_struct_fields() {
+        let tu = ast::TranslationUnit::parse(
+            "struct Light { float3 dir; float3 color; };
+            struct Scene { Light lights[4]; };
+            StructuredBuffer<Light> extra;
+            float3 f(Scene s) {
+                return s.lights[1].color + extra[0].dir.xyz + s.missing;
+            }",
+        )
+        .unwrap();
+        let table = resolve(&tu);
+
+        let uses: Vec<_> = table
+            .uses()
+            .map(|(ident, res)| {
+                let target = res.symbols().first().map(|id| {
+                    let symbol = table.symbol(*id);
+                    let parent = symbol.parent.map(|p| table.symbol(p).name.to_string());
+                    (symbol.kind, parent)
+                });
+                (ident.as_str(), target)
+            })
+            .collect();
+
+        let field = |parent: &str| Some((SymbolKind::StructField, Some(parent.to_owned())));
+        assert_eq!(
+            uses,
+            vec![
+                ("s", Some((SymbolKind::Parameter, None))),
+                ("lights", field("Scene")),
+                ("color", field("Light")),
+                ("extra", Some((SymbolKind::Global, None))),
+                ("dir", field("Light")),
+                ("s", Some((SymbolKind::Parameter, None))),
+                ("missing", None),
+            ]
+        );
+
+        // The swizzle is not a name
+        assert!(table.uses().all(|(ident, _)| ident.as_str() != "xyz"));
+    }
+
+    #[test]
+    fn resolve_methods() {
+        let tu = ast::TranslationUnit::parse(
+            "struct Counter {
+                int value;
+                int get() { return value; }
+                void add(int n);
+            };
+            void Counter::add(int n) { value += n; }
+            int f(Counter c) { c.add(1); return c.get(); }",
+        )
+        .unwrap();
+        let table = resolve(&tu);
+
+        let targets: Vec<_> = table
+            .uses()
+            .map(|(ident, res)| {
+                let symbol = table.symbol(res.symbols()[0]);
+                (ident.as_str(), symbol.kind, symbol.name.as_str())
+            })
+            .collect();
+
+        assert_eq!(
+            targets,
+            vec![
+                ("value", SymbolKind::StructField, "value"),
+                ("value", SymbolKind::StructField, "value"),
+                ("n", SymbolKind::Parameter, "n"),
+                ("c", SymbolKind::Parameter, "c"),
+                ("add", SymbolKind::Function, "add"),
+                ("c", SymbolKind::Parameter, "c"),
+                ("get", SymbolKind::Function, "get"),
+            ]
+        );
+
+        // The out-of-line definition completes the method declared in the struct
+        let add = table.symbols().iter().find(|s| s.name == "add").unwrap();
+        assert!(matches!(
+            add.declarator,
+            Declarator::Method {
+                definition: Some(_),
+                ..
+            }
+        ));
+        assert_eq!(
+            table.symbols().iter().filter(|s| s.name == "add").count(),
+            1
+        );
+    }
+
+    #[test]
+    fn resolve_inherited_members() {
+        let tu = ast::TranslationUnit::parse(
+            "struct Base { float x; };
+            struct Derived : Base { float y; };
+            float f(Derived d) { return d.x + d.y; }",
+        )
+        .unwrap();
+        let table = resolve(&tu);
+
+        let fields: Vec<_> = table
+            .uses()
+            .filter(|(ident, _)| ident.as_str() != "d")
+            .map(|(ident, res)| {
+                let symbol = table.symbol(res.symbols()[0]);
+                (
+                    ident.as_str(),
+                    table.symbol(symbol.parent.unwrap()).name.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(fields, vec![("x", "Base"), ("y", "Derived")]);
+
+        // A struct cannot be its own base
+        let tu =
+            ast::TranslationUnit::parse("struct A : A { float x; }; float f(A a) { return a.y; }")
+                .unwrap();
+        let table = resolve(&tu);
+        assert!(table.bases(SymbolId(0)).is_empty());
+        assert_eq!(table.member(SymbolId(0), "y"), None);
+    }
+
+    #[test]
+    fn resolve_overloads() {
+        let tu = ast::TranslationUnit::parse(
+            "float scale(float x);
+            float scale(float x) { return x * 2.0; }
+            float f() { return scale(1.0); }
+            float2 scale(float2 x) { return x * 2.0; }
+            float2 g() { return scale(float2(1.0, 2.0)); }",
+        )
+        .unwrap();
+        let table = resolve(&tu);
+
+        let calls: Vec<_> = table
+            .uses()
+            .filter(|(ident, _)| ident.as_str() == "scale")
+            .map(|(_, res)| res.clone())
+            .collect();
+
+        // The definition completes the earlier prototype, and only the overloads declared before
+        // a call are candidates
+        assert_eq!(
+            calls,
+            vec![
+                Resolution::Symbol(SymbolId(0)),
+                Resolution::Overloads(vec![SymbolId(0), SymbolId(3)]),
+            ]
+        );
+        assert!(matches!(
+            table.symbol(SymbolId(0)).declarator,
+            Declarator::Function {
+                definition: Some(_),
+                ..
+            }
+        ));
+    }
+}
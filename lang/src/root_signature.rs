@@ -0,0 +1,1571 @@
+//! Root signature parsing and validation
+//!
+//! Root signatures are attached to entry points through a `[RootSignature("...")]` attribute,
+//! whose argument is written in the root signature description language, e.g.
+//! `"RootFlags(ALLOW_INPUT_ASSEMBLER_INPUT_LAYOUT), CBV(b0), DescriptorTable(SRV(t0, numDescriptors=4))"`.
+//! This module parses that string into a [RootSignature], and checks it against the
+//! `register(...)` bindings declared in a translation unit.
+//!
+//! As with DXC, keywords such as element names, parameter names and flags are case-insensitive.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, parse::Parsable, root_signature::RootSignature};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     r#"
+//!     cbuffer Globals : register(b0) { float4x4 mvp; };
+//!     Texture2D albedo : register(t1);
+//!
+//!     [RootSignature("CBV(b0), DescriptorTable(SRV(t0))")]
+//!     float4 main() : SV_Target { return 0; }
+//!     "#,
+//! )
+//! .unwrap();
+//!
+//! let def = tu.0.iter().find_map(|decl| match &**decl {
+//!     ast::ExternalDeclarationData::FunctionDefinition(def) => Some(def),
+//!     _ => None,
+//! }).unwrap();
+//!
+//! let rs = RootSignature::of_function(def).unwrap().unwrap();
+//! let errors = rs.check_bindings(&tu);
+//! assert_eq!(errors[0].to_string(), "`albedo` (t1) is not bound by the root signature");
+//! ```
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use rserde::{Deserialize, Serialize};
+
+use crate::{
+    ast::{self, NodeSpan, SmolStr, TextRange, TextSize},
+    attributes::eval_int,
+};
+
+/// A parsed root signature
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RootSignature {
+    /// Root signature flags
+    pub flags: Vec<RootFlag>,
+    /// Root parameters, in declaration order
+    pub parameters: Vec<RootParameter>,
+    /// Static samplers, in declaration order
+    pub static_samplers: Vec<StaticSampler>,
+    /// Span of the string literal this root signature was parsed from
+    pub span: Option<NodeSpan>,
+    /// Range of each string literal piece in the root signature string, with the span of its
+    /// contents if ranges in the piece map directly onto the source (i.e. the literal has no
+    /// escapes and was not macro-expanded)
+    pieces: Vec<(TextRange, Option<NodeSpan>)>,
+}
+
+/// Root signature flag, as given to `RootFlags(...)`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RootFlag {
+    /// `ALLOW_INPUT_ASSEMBLER_INPUT_LAYOUT`
+    AllowInputAssemblerInputLayout,
+    /// `DENY_VERTEX_SHADER_ROOT_ACCESS`
+    DenyVertexShaderRootAccess,
+    /// `DENY_HULL_SHADER_ROOT_ACCESS`
+    DenyHullShaderRootAccess,
+    /// `DENY_DOMAIN_SHADER_ROOT_ACCESS`
+    DenyDomainShaderRootAccess,
+    /// `DENY_GEOMETRY_SHADER_ROOT_ACCESS`
+    DenyGeometryShaderRootAccess,
+    /// `DENY_PIXEL_SHADER_ROOT_ACCESS`
+    DenyPixelShaderRootAccess,
+    /// `DENY_AMPLIFICATION_SHADER_ROOT_ACCESS`
+    DenyAmplificationShaderRootAccess,
+    /// `DENY_MESH_SHADER_ROOT_ACCESS`
+    DenyMeshShaderRootAccess,
+    /// `ALLOW_STREAM_OUTPUT`
+    AllowStreamOutput,
+    /// `LOCAL_ROOT_SIGNATURE`
+    LocalRootSignature,
+    /// `CBV_SRV_UAV_HEAP_DIRECTLY_INDEXED`
+    CbvSrvUavHeapDirectlyIndexed,
+    /// `SAMPLER_HEAP_DIRECTLY_INDEXED`
+    SamplerHeapDirectlyIndexed,
+}
+
+impl RootFlag {
+    /// All the root signature flags
+    pub const ALL: [Self; 12] = [
+        Self::AllowInputAssemblerInputLayout,
+        Self::DenyVertexShaderRootAccess,
+        Self::DenyHullShaderRootAccess,
+        Self::DenyDomainShaderRootAccess,
+        Self::DenyGeometryShaderRootAccess,
+        Self::DenyPixelShaderRootAccess,
+        Self::DenyAmplificationShaderRootAccess,
+        Self::DenyMeshShaderRootAccess,
+        Self::AllowStreamOutput,
+        Self::LocalRootSignature,
+        Self::CbvSrvUavHeapDirectlyIndexed,
+        Self::SamplerHeapDirectlyIndexed,
+    ];
+
+    /// Name of this flag in the root signature language
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::AllowInputAssemblerInputLayout => "ALLOW_INPUT_ASSEMBLER_INPUT_LAYOUT",
+            Self::DenyVertexShaderRootAccess => "DENY_VERTEX_SHADER_ROOT_ACCESS",
+            Self::DenyHullShaderRootAccess => "DENY_HULL_SHADER_ROOT_ACCESS",
+            Self::DenyDomainShaderRootAccess => "DENY_DOMAIN_SHADER_ROOT_ACCESS",
+            Self::DenyGeometryShaderRootAccess => "DENY_GEOMETRY_SHADER_ROOT_ACCESS",
+            Self::DenyPixelShaderRootAccess => "DENY_PIXEL_SHADER_ROOT_ACCESS",
+            Self::DenyAmplificationShaderRootAccess => "DENY_AMPLIFICATION_SHADER_ROOT_ACCESS",
+            Self::DenyMeshShaderRootAccess => "DENY_MESH_SHADER_ROOT_ACCESS",
+            Self::AllowStreamOutput => "ALLOW_STREAM_OUTPUT",
+            Self::LocalRootSignature => "LOCAL_ROOT_SIGNATURE",
+            Self::CbvSrvUavHeapDirectlyIndexed => "CBV_SRV_UAV_HEAP_DIRECTLY_INDEXED",
+            Self::SamplerHeapDirectlyIndexed => "SAMPLER_HEAP_DIRECTLY_INDEXED",
+        }
+    }
+}
+
+/// Shader stages a root parameter is visible to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ShaderVisibility {
+    /// `SHADER_VISIBILITY_ALL`
+    #[default]
+    All,
+    /// `SHADER_VISIBILITY_VERTEX`
+    Vertex,
+    /// `SHADER_VISIBILITY_HULL`
+    Hull,
+    /// `SHADER_VISIBILITY_DOMAIN`
+    Domain,
+    /// `SHADER_VISIBILITY_GEOMETRY`
+    Geometry,
+    /// `SHADER_VISIBILITY_PIXEL`
+    Pixel,
+    /// `SHADER_VISIBILITY_AMPLIFICATION`
+    Amplification,
+    /// `SHADER_VISIBILITY_MESH`
+    Mesh,
+}
+
+impl ShaderVisibility {
+    /// All the shader visibilities
+    pub const ALL: [Self; 8] = [
+        Self::All,
+        Self::Vertex,
+        Self::Hull,
+        Self::Domain,
+        Self::Geometry,
+        Self::Pixel,
+        Self::Amplification,
+        Self::Mesh,
+    ];
+
+    /// Name of this visibility in the root signature language
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::All => "SHADER_VISIBILITY_ALL",
+            Self::Vertex => "SHADER_VISIBILITY_VERTEX",
+            Self::Hull => "SHADER_VISIBILITY_HULL",
+            Self::Domain => "SHADER_VISIBILITY_DOMAIN",
+            Self::Geometry => "SHADER_VISIBILITY_GEOMETRY",
+            Self::Pixel => "SHADER_VISIBILITY_PIXEL",
+            Self::Amplification => "SHADER_VISIBILITY_AMPLIFICATION",
+            Self::Mesh => "SHADER_VISIBILITY_MESH",
+        }
+    }
+
+    /// `true` if a stage can see parameters with both visibilities
+    pub fn overlaps(self, other: Self) -> bool {
+        self == Self::All || other == Self::All || self == other
+    }
+}
+
+/// Class of a shader register
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum RegisterClass {
+    /// Constant buffer views (`b` registers)
+    ConstantBuffer,
+    /// Shader resource views (`t` registers)
+    ShaderResource,
+    /// Unordered access views (`u` registers)
+    UnorderedAccess,
+    /// Samplers (`s` registers)
+    Sampler,
+}
+
+impl RegisterClass {
+    /// Register prefix of this class, e.g. `'t'`
+    pub fn prefix(self) -> char {
+        match self {
+            Self::ConstantBuffer => 'b',
+            Self::ShaderResource => 't',
+            Self::UnorderedAccess => 'u',
+            Self::Sampler => 's',
+        }
+    }
+
+    /// Register class for the given prefix
+    pub fn from_prefix(prefix: char) -> Option<Self> {
+        match prefix.to_ascii_lowercase() {
+            'b' => Some(Self::ConstantBuffer),
+            't' => Some(Self::ShaderResource),
+            'u' => Some(Self::UnorderedAccess),
+            's' => Some(Self::Sampler),
+            _ => None,
+        }
+    }
+}
+
+/// A shader register, e.g. `t3` in `space1`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct Register {
+    /// Register class
+    pub class: RegisterClass,
+    /// Register index
+    pub index: u32,
+    /// Register space
+    pub space: u32,
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.class.prefix(), self.index)?;
+
+        if self.space != 0 {
+            write!(f, ", space{}", self.space)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A root parameter
+#[derive(Clone, Debug, PartialEq)]
+pub struct RootParameter {
+    /// Kind of parameter
+    pub kind: RootParameterKind,
+    /// Shader stages this parameter is visible to
+    pub visibility: ShaderVisibility,
+    /// Range of this parameter in the root signature string
+    pub range: TextRange,
+}
+
+/// Kind of root parameter
+#[derive(Clone, Debug, PartialEq)]
+pub enum RootParameterKind {
+    /// `RootConstants(num32BitConstants=N, bN)`: inline root constants
+    Constants {
+        /// Number of 32-bit values
+        num_32bit_values: u32,
+        /// Constant buffer register
+        register: Register,
+    },
+    /// `CBV(bN)`, `SRV(tN)` or `UAV(uN)`: root descriptor
+    Descriptor {
+        /// Descriptor register, whose class gives the descriptor type
+        register: Register,
+        /// Descriptor flags (e.g. `DATA_STATIC`)
+        flags: Vec<SmolStr>,
+    },
+    /// `DescriptorTable(...)`: descriptor table
+    DescriptorTable(Vec<DescriptorRange>),
+}
+
+/// Number of descriptors in a descriptor range
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum DescriptorCount {
+    /// A fixed number of descriptors
+    Bounded(u32),
+    /// `numDescriptors=unbounded`
+    Unbounded,
+}
+
+/// A range of descriptors in a descriptor table
+#[derive(Clone, Debug, PartialEq)]
+pub struct DescriptorRange {
+    /// First register of the range, whose class gives the descriptor type
+    pub base: Register,
+    /// Number of descriptors
+    pub count: DescriptorCount,
+    /// Offset from the start of the table, or `None` for `DESCRIPTOR_RANGE_OFFSET_APPEND`
+    pub offset: Option<u32>,
+    /// Descriptor range flags (e.g. `DESCRIPTORS_VOLATILE`)
+    pub flags: Vec<SmolStr>,
+    /// Range of this clause in the root signature string
+    pub range: TextRange,
+}
+
+/// A static sampler
+#[derive(Clone, Debug, PartialEq)]
+pub struct StaticSampler {
+    /// Sampler register
+    pub register: Register,
+    /// Shader stages this sampler is visible to
+    pub visibility: ShaderVisibility,
+    /// Sampler state (`filter`, `addressU`, `maxAnisotropy`, ...), in declaration order
+    pub properties: Vec<(SmolStr, SmolStr)>,
+    /// Range of this sampler in the root signature string
+    pub range: TextRange,
+}
+
+/// Error raised while parsing or validating a root signature
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("{kind}")]
+pub struct RootSignatureError {
+    /// Kind of error
+    pub kind: RootSignatureErrorKind,
+    /// Range in the root signature string this error refers to
+    pub range: Option<TextRange>,
+    /// Span in the source this error refers to
+    pub span: Option<NodeSpan>,
+}
+
+/// Kind of root signature error
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum RootSignatureErrorKind {
+    /// The `RootSignature` attribute argument is not a string
+    #[error("root signature must be a string literal")]
+    NotAString,
+    /// Unexpected character or token
+    #[error("unexpected {found}, expected {expected}")]
+    Syntax {
+        /// What was found instead
+        found: String,
+        /// What was expected
+        expected: &'static str,
+    },
+    /// Unknown root signature element or descriptor table clause
+    #[error("unknown root signature element `{0}`")]
+    UnknownElement(String),
+    /// Unknown parameter for an element
+    #[error("unknown parameter `{parameter}` for `{element}`")]
+    UnknownParameter {
+        /// Element name
+        element: &'static str,
+        /// Parameter name
+        parameter: String,
+    },
+    /// Invalid parameter value
+    #[error("invalid value `{value}` for `{parameter}`")]
+    InvalidValue {
+        /// Parameter name
+        parameter: &'static str,
+        /// Value that was given
+        value: String,
+    },
+    /// Required parameter is missing
+    #[error("`{element}` requires {parameter}")]
+    Missing {
+        /// Element name
+        element: &'static str,
+        /// Missing parameter
+        parameter: &'static str,
+    },
+    /// Parameter or element given more than once
+    #[error("duplicate `{0}`")]
+    Duplicate(String),
+    /// A descriptor table mixes sampler and CBV/SRV/UAV ranges
+    #[error("descriptor tables cannot mix samplers with other descriptors")]
+    MixedSamplerTable,
+    /// Two ranges of the root signature bind the same register for the same stages
+    #[error("register {register} is bound more than once")]
+    Overlap {
+        /// First register bound by both ranges
+        register: Register,
+    },
+    /// A resource declared in the translation unit is not bound by the root signature
+    #[error("`{name}` ({register}) is not bound by the root signature")]
+    Unbound {
+        /// Resource name
+        name: SmolStr,
+        /// Resource register
+        register: Register,
+    },
+}
+
+impl RootSignature {
+    /// Parse a root signature string
+    pub fn parse(source: &str) -> Result<Self, RootSignatureError> {
+        let calls = Parser::new(source).parse_list()?;
+
+        let mut rs = Self::default();
+        for call in calls {
+            rs.add_element(call)?;
+        }
+
+        Ok(rs)
+    }
+
+    /// Parse a root signature from a string literal expression, such as the argument of a
+    /// `[RootSignature(...)]` attribute. Adjacent string literals are concatenated. Error spans
+    /// point into the string literal they occur in.
+    pub fn from_expr(expr: &ast::Expr) -> Result<Self, RootSignatureError> {
+        let pieces = match &**expr {
+            ast::ExprData::StringConst(_) => std::slice::from_ref(expr),
+            ast::ExprData::StringConcat(pieces) => pieces.as_slice(),
+            _ => {
+                return Err(RootSignatureError {
+                    kind: RootSignatureErrorKind::NotAString,
+                    range: None,
+                    span: expr.span,
+                })
+            }
+        };
+
+        let mut rs = Self {
+            span: expr.span,
+            ..Default::default()
+        };
+
+        let mut source = String::new();
+        for piece in pieces {
+            let ast::ExprData::StringConst(value) = &**piece else {
+                continue;
+            };
+
+            // Ranges can only be mapped back into a literal written as-is in the source
+            let contents = piece
+                .span
+                .filter(|span| usize::from(span.len()) == value.len() + 2)
+                .map(|span| {
+                    let start = span.range().start() + TextSize::from(1);
+                    NodeSpan::new(span.source_id(), TextRange::at(start, TextSize::of(value)))
+                });
+
+            let start = TextSize::of(source.as_str());
+            source.push_str(value);
+            rs.pieces.push((
+                TextRange::new(start, TextSize::of(source.as_str())),
+                contents,
+            ));
+        }
+
+        let result = Parser::new(&source)
+            .parse_list()
+            .and_then(|calls| calls.into_iter().try_for_each(|call| rs.add_element(call)));
+
+        match result {
+            Ok(()) => Ok(rs),
+            Err(mut error) => {
+                error.span = error.range.and_then(|range| rs.node_span(range));
+                Err(error)
+            }
+        }
+    }
+
+    /// Parse the root signature attached to a function through a `[RootSignature(...)]`
+    /// attribute, if any
+    pub fn of_function(def: &ast::FunctionDefinition) -> Option<Result<Self, RootSignatureError>> {
+        def.attributes
+            .iter()
+            .flatten()
+            .find(|attr| {
+                attr.spec.namespace.is_none()
+                    && attr
+                        .spec
+                        .name
+                        .as_str()
+                        .eq_ignore_ascii_case("RootSignature")
+            })
+            .map(|attr| match attr.spec.params.as_deref() {
+                Some([expr]) => Self::from_expr(expr),
+                _ => Err(RootSignatureError {
+                    kind: RootSignatureErrorKind::NotAString,
+                    range: None,
+                    span: attr.spec.span,
+                }),
+            })
+    }
+
+    /// Source span of the given range of the root signature string. Falls back to the span of
+    /// the whole string literal if the range cannot be mapped exactly.
+    pub fn node_span(&self, range: TextRange) -> Option<NodeSpan> {
+        let span = self.span?;
+
+        // Ranges which span several pieces are clipped to the piece they start in
+        let piece = self
+            .pieces
+            .iter()
+            .find(|(piece, _)| piece.contains(range.start()) || piece.start() == range.start());
+        Some(match piece {
+            Some((piece, Some(contents))) => {
+                let range = range.intersect(*piece).unwrap_or(range) - piece.start();
+                NodeSpan::new(contents.source_id(), range + contents.range().start())
+            }
+            _ => span,
+        })
+    }
+
+    /// Register ranges bound by this root signature, with their visibility and the range in the
+    /// root signature string which declares them
+    pub fn bindings(&self) -> Vec<(Register, DescriptorCount, ShaderVisibility, TextRange)> {
+        let mut bindings = Vec::new();
+
+        for param in &self.parameters {
+            match &param.kind {
+                RootParameterKind::Constants { register, .. }
+                | RootParameterKind::Descriptor { register, .. } => bindings.push((
+                    *register,
+                    DescriptorCount::Bounded(1),
+                    param.visibility,
+                    param.range,
+                )),
+                RootParameterKind::DescriptorTable(ranges) => {
+                    bindings.extend(
+                        ranges
+                            .iter()
+                            .map(|r| (r.base, r.count, param.visibility, r.range)),
+                    );
+                }
+            }
+        }
+
+        bindings.extend(self.static_samplers.iter().map(|s| {
+            (
+                s.register,
+                DescriptorCount::Bounded(1),
+                s.visibility,
+                s.range,
+            )
+        }));
+
+        bindings
+    }
+
+    /// Check this root signature against the `register(...)` bindings declared in a translation
+    /// unit. This reports resources which are not bound by any range of the root signature, and
+    /// ranges of the root signature which bind the same registers.
+    pub fn check_bindings(&self, tu: &ast::TranslationUnit) -> Vec<RootSignatureError> {
+        let mut errors = Vec::new();
+        let bindings = self.bindings();
+
+        // Overlapping ranges in the root signature itself
+        for (i, (reg, count, visibility, range)) in bindings.iter().enumerate() {
+            if let Some((other, _, _, _)) =
+                bindings[..i]
+                    .iter()
+                    .find(|(other, other_count, other_vis, _)| {
+                        other.class == reg.class
+                            && other.space == reg.space
+                            && other_vis.overlaps(*visibility)
+                            && ranges_overlap(other.index, *other_count, reg.index, *count)
+                    })
+            {
+                errors.push(RootSignatureError {
+                    kind: RootSignatureErrorKind::Overlap {
+                        register: Register {
+                            index: reg.index.max(other.index),
+                            ..*reg
+                        },
+                    },
+                    range: Some(*range),
+                    span: self.node_span(*range),
+                });
+            }
+        }
+
+        // Resources declared in the translation unit
+        for (name, reg, count, span) in declared_bindings(tu) {
+            let bound = bindings.iter().any(|(rs_reg, rs_count, _, _)| {
+                rs_reg.class == reg.class
+                    && rs_reg.space == reg.space
+                    && range_contains(rs_reg.index, *rs_count, reg.index, count)
+            });
+
+            if !bound {
+                errors.push(RootSignatureError {
+                    kind: RootSignatureErrorKind::Unbound {
+                        name,
+                        register: reg,
+                    },
+                    range: None,
+                    span,
+                });
+            }
+        }
+
+        errors
+    }
+
+    fn add_element(&mut self, call: Call<'_>) -> Result<(), RootSignatureError> {
+        let Some(element) = keyword(call.name, ELEMENTS) else {
+            return Err(call.error(RootSignatureErrorKind::UnknownElement(call.name.to_owned())));
+        };
+
+        match element {
+            "RootFlags" => {
+                if !self.flags.is_empty() {
+                    return Err(
+                        call.error(RootSignatureErrorKind::Duplicate("RootFlags".to_owned()))
+                    );
+                }
+
+                let [Arg::Value(words)] = &call.args[..] else {
+                    return Err(call.error(RootSignatureErrorKind::Missing {
+                        element: "RootFlags",
+                        parameter: "a list of flags",
+                    }));
+                };
+
+                for word in words {
+                    if word.text == "0" {
+                        continue;
+                    }
+
+                    self.flags.push(
+                        RootFlag::ALL
+                            .into_iter()
+                            .find(|flag| flag.name().eq_ignore_ascii_case(word.text))
+                            .ok_or_else(|| word.invalid("RootFlags"))?,
+                    );
+                }
+            }
+            "RootConstants" | "CBV" | "SRV" | "UAV" => {
+                let class = match element {
+                    "SRV" => RegisterClass::ShaderResource,
+                    "UAV" => RegisterClass::UnorderedAccess,
+                    _ => RegisterClass::ConstantBuffer,
+                };
+
+                let mut args = Args::new(element, &call)?;
+                let register = args.register(class)?;
+                let visibility = args.visibility()?;
+                let kind = if element == "RootConstants" {
+                    RootParameterKind::Constants {
+                        num_32bit_values: args.required_uint("num32BitConstants")?,
+                        register,
+                    }
+                } else {
+                    RootParameterKind::Descriptor {
+                        register,
+                        flags: args.flags()?,
+                    }
+                };
+                args.finish()?;
+
+                self.parameters.push(RootParameter {
+                    kind,
+                    visibility,
+                    range: call.range,
+                });
+            }
+            "DescriptorTable" => {
+                let mut ranges = Vec::new();
+                let mut visibility = None;
+
+                for arg in &call.args {
+                    match arg {
+                        Arg::Call(clause) => ranges.push(Self::descriptor_range(clause)?),
+                        Arg::Assign { key, value }
+                            if key.text.eq_ignore_ascii_case("visibility") =>
+                        {
+                            if visibility.is_some() {
+                                return Err(key.duplicate());
+                            }
+
+                            visibility = Some(parse_visibility(value)?);
+                        }
+                        Arg::Assign { key, .. } => {
+                            return Err(key.unknown("DescriptorTable"));
+                        }
+                        Arg::Value(words) => {
+                            return Err(words[0].unknown("DescriptorTable"));
+                        }
+                    }
+                }
+
+                let samplers = ranges
+                    .iter()
+                    .filter(|r| r.base.class == RegisterClass::Sampler)
+                    .count();
+                if samplers != 0 && samplers != ranges.len() {
+                    return Err(call.error(RootSignatureErrorKind::MixedSamplerTable));
+                }
+
+                self.parameters.push(RootParameter {
+                    kind: RootParameterKind::DescriptorTable(ranges),
+                    visibility: visibility.unwrap_or_default(),
+                    range: call.range,
+                });
+            }
+            // StaticSampler
+            _ => {
+                let mut args = Args::new("StaticSampler", &call)?;
+                let register = args.register(RegisterClass::Sampler)?;
+                let visibility = args.visibility()?;
+
+                let mut properties = Vec::new();
+                for key in STATIC_SAMPLER_PROPERTIES {
+                    if let Some(value) = args.take(key) {
+                        properties.push((SmolStr::from(*key), single_word(key, value)?.into()));
+                    }
+                }
+                args.finish()?;
+
+                self.static_samplers.push(StaticSampler {
+                    register,
+                    visibility,
+                    properties,
+                    range: call.range,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn descriptor_range(clause: &Call<'_>) -> Result<DescriptorRange, RootSignatureError> {
+        let (element, class) = match keyword(clause.name, DESCRIPTOR_RANGES) {
+            Some("CBV") => ("CBV", RegisterClass::ConstantBuffer),
+            Some("SRV") => ("SRV", RegisterClass::ShaderResource),
+            Some("UAV") => ("UAV", RegisterClass::UnorderedAccess),
+            Some("Sampler") => ("Sampler", RegisterClass::Sampler),
+            _ => {
+                return Err(clause.error(RootSignatureErrorKind::UnknownElement(
+                    clause.name.to_owned(),
+                )))
+            }
+        };
+
+        let mut args = Args::new(element, clause)?;
+        let base = args.register(class)?;
+
+        let count = match args.take("numDescriptors") {
+            Some(value) => {
+                if single_word("numDescriptors", value)?.eq_ignore_ascii_case("unbounded") {
+                    DescriptorCount::Unbounded
+                } else {
+                    DescriptorCount::Bounded(parse_uint("numDescriptors", value)?)
+                }
+            }
+            None => DescriptorCount::Bounded(1),
+        };
+
+        let offset = match args.take("offset") {
+            Some(value) => {
+                if single_word("offset", value)?
+                    .eq_ignore_ascii_case("DESCRIPTOR_RANGE_OFFSET_APPEND")
+                {
+                    None
+                } else {
+                    Some(parse_uint("offset", value)?)
+                }
+            }
+            None => None,
+        };
+
+        let flags = args.flags()?;
+        args.finish()?;
+
+        Ok(DescriptorRange {
+            base,
+            count,
+            offset,
+            flags,
+            range: clause.range,
+        })
+    }
+}
+
+/// Elements of a root signature
+const ELEMENTS: &[&str] = &[
+    "RootFlags",
+    "RootConstants",
+    "CBV",
+    "SRV",
+    "UAV",
+    "DescriptorTable",
+    "StaticSampler",
+];
+
+/// Clauses of a descriptor table
+const DESCRIPTOR_RANGES: &[&str] = &["CBV", "SRV", "UAV", "Sampler"];
+
+/// Keyword spelled like `text`, ignoring case as DXC does
+fn keyword(text: &str, keywords: &[&'static str]) -> Option<&'static str> {
+    keywords
+        .iter()
+        .copied()
+        .find(|keyword| keyword.eq_ignore_ascii_case(text))
+}
+
+/// Sampler state parameters accepted by `StaticSampler`
+const STATIC_SAMPLER_PROPERTIES: &[&str] = &[
+    "filter",
+    "addressU",
+    "addressV",
+    "addressW",
+    "mipLODBias",
+    "maxAnisotropy",
+    "comparisonFunc",
+    "borderColor",
+    "minLOD",
+    "maxLOD",
+];
+
+fn ranges_overlap(a: u32, a_count: DescriptorCount, b: u32, b_count: DescriptorCount) -> bool {
+    let end = |start: u32, count| match count {
+        DescriptorCount::Bounded(n) => u64::from(start) + u64::from(n),
+        DescriptorCount::Unbounded => u64::MAX,
+    };
+
+    u64::from(a) < end(b, b_count) && u64::from(b) < end(a, a_count)
+}
+
+fn range_contains(
+    outer: u32,
+    outer_count: DescriptorCount,
+    inner: u32,
+    inner_count: DescriptorCount,
+) -> bool {
+    match (outer_count, inner_count) {
+        (DescriptorCount::Unbounded, _) => inner >= outer,
+        (DescriptorCount::Bounded(_), DescriptorCount::Unbounded) => false,
+        (DescriptorCount::Bounded(n), DescriptorCount::Bounded(m)) => {
+            inner >= outer && u64::from(inner) + u64::from(m) <= u64::from(outer) + u64::from(n)
+        }
+    }
+}
+
+/// Register bindings declared in a translation unit, as (name, first register, count, span)
+fn declared_bindings(
+    tu: &ast::TranslationUnit,
+) -> Vec<(SmolStr, Register, DescriptorCount, Option<NodeSpan>)> {
+    let mut bindings = Vec::new();
+
+    for decl in &tu.0 {
+        let ast::ExternalDeclarationData::Declaration(decl) = &**decl else {
+            continue;
+        };
+
+        let (name, binding, array) = match &**decl {
+            ast::DeclarationData::InitDeclaratorList(list) => match &list.head.name {
+                Some(name) => (
+                    name,
+                    &list.head.resource_binding,
+                    list.head.array_specifier.as_ref(),
+                ),
+                None => continue,
+            },
+            ast::DeclarationData::CBuffer(cbuffer) => {
+                (&cbuffer.name, &cbuffer.resource_binding, None)
+            }
+            ast::DeclarationData::SamplerState(sampler) => {
+                (&sampler.name, &sampler.resource_binding, None)
+            }
+            _ => continue,
+        };
+
+        let Some(binding) = binding else {
+            continue;
+        };
+
+        let Some(class) = binding
+            .register_type
+            .as_str()
+            .chars()
+            .next()
+            .and_then(RegisterClass::from_prefix)
+        else {
+            continue;
+        };
+
+        let index = eval_int(&binding.index).and_then(|i| u32::try_from(i).ok());
+        let space = match &binding.space {
+            Some(space) => eval_int(space).and_then(|i| u32::try_from(i).ok()),
+            None => Some(0),
+        };
+        let (Some(index), Some(space)) = (index, space) else {
+            continue;
+        };
+
+        let count = match array.and_then(|array| array.dimensions.first()) {
+            Some(dim) => match &**dim {
+                ast::ArraySpecifierDimensionData::Unsized => DescriptorCount::Unbounded,
+                ast::ArraySpecifierDimensionData::ExplicitlySized(size) => {
+                    DescriptorCount::Bounded(
+                        eval_int(size)
+                            .and_then(|i| u32::try_from(i).ok())
+                            .unwrap_or(1),
+                    )
+                }
+            },
+            None => DescriptorCount::Bounded(1),
+        };
+
+        bindings.push((
+            name.0.clone(),
+            Register {
+                class,
+                index,
+                space,
+            },
+            count,
+            binding.span,
+        ));
+    }
+
+    bindings
+}
+
+fn parse_visibility(value: &[Word<'_>]) -> Result<ShaderVisibility, RootSignatureError> {
+    let word = single_word("visibility", value)?;
+    ShaderVisibility::ALL
+        .into_iter()
+        .find(|v| v.name().eq_ignore_ascii_case(word))
+        .ok_or_else(|| value[0].invalid("visibility"))
+}
+
+fn parse_uint(parameter: &'static str, value: &[Word<'_>]) -> Result<u32, RootSignatureError> {
+    single_word(parameter, value)?
+        .parse()
+        .map_err(|_| value[0].invalid(parameter))
+}
+
+fn single_word<'s>(
+    parameter: &'static str,
+    value: &[Word<'s>],
+) -> Result<&'s str, RootSignatureError> {
+    match value {
+        [word] => Ok(word.text),
+        _ => Err(value[1].invalid(parameter)),
+    }
+}
+
+/// Named and positional arguments of an element
+struct Args<'c, 's> {
+    element: &'static str,
+    call: &'c Call<'s>,
+    positional: Vec<&'c [Word<'s>]>,
+    named: Vec<(&'c Word<'s>, &'c [Word<'s>])>,
+}
+
+impl<'c, 's> Args<'c, 's> {
+    fn new(element: &'static str, call: &'c Call<'s>) -> Result<Self, RootSignatureError> {
+        let mut positional = Vec::new();
+        let mut named: Vec<(&Word, &[Word])> = Vec::new();
+
+        for arg in &call.args {
+            match arg {
+                Arg::Value(words) => positional.push(&words[..]),
+                Arg::Assign { key, value } => {
+                    if named
+                        .iter()
+                        .any(|(k, _)| k.text.eq_ignore_ascii_case(key.text))
+                    {
+                        return Err(key.duplicate());
+                    }
+
+                    named.push((key, &value[..]));
+                }
+                Arg::Call(nested) => {
+                    return Err(nested.error(RootSignatureErrorKind::UnknownParameter {
+                        element,
+                        parameter: nested.name.to_owned(),
+                    }));
+                }
+            }
+        }
+
+        Ok(Self {
+            element,
+            call,
+            positional,
+            named,
+        })
+    }
+
+    fn take(&mut self, key: &str) -> Option<&'c [Word<'s>]> {
+        let pos = self
+            .named
+            .iter()
+            .position(|(k, _)| k.text.eq_ignore_ascii_case(key))?;
+        Some(self.named.remove(pos).1)
+    }
+
+    fn register(&mut self, class: RegisterClass) -> Result<Register, RootSignatureError> {
+        if self.positional.is_empty() {
+            return Err(self.call.error(RootSignatureErrorKind::Missing {
+                element: self.element,
+                parameter: "a register",
+            }));
+        }
+
+        let value = self.positional.remove(0);
+        let word = single_word("register", value)?;
+        let index = word
+            .strip_prefix(class.prefix())
+            .or_else(|| word.strip_prefix(class.prefix().to_ascii_uppercase()))
+            .and_then(|index| index.parse().ok())
+            .ok_or_else(|| value[0].invalid("register"))?;
+
+        let space = match self.take("space") {
+            Some(value) => parse_uint("space", value)?,
+            None => 0,
+        };
+
+        Ok(Register {
+            class,
+            index,
+            space,
+        })
+    }
+
+    fn required_uint(&mut self, key: &'static str) -> Result<u32, RootSignatureError> {
+        match self.take(key) {
+            Some(value) => parse_uint(key, value),
+            None => Err(self.call.error(RootSignatureErrorKind::Missing {
+                element: self.element,
+                parameter: key,
+            })),
+        }
+    }
+
+    fn visibility(&mut self) -> Result<ShaderVisibility, RootSignatureError> {
+        self.take("visibility")
+            .map(parse_visibility)
+            .unwrap_or(Ok(ShaderVisibility::All))
+    }
+
+    fn flags(&mut self) -> Result<Vec<SmolStr>, RootSignatureError> {
+        Ok(self
+            .take("flags")
+            .map(|value| {
+                value
+                    .iter()
+                    .filter(|word| word.text != "0")
+                    .map(|word| word.text.to_ascii_uppercase().into())
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn finish(self) -> Result<(), RootSignatureError> {
+        if let Some(value) = self.positional.first() {
+            return Err(value[0].unknown(self.element));
+        }
+
+        if let Some((key, _)) = self.named.first() {
+            return Err(key.unknown(self.element));
+        }
+
+        Ok(())
+    }
+}
+
+/// A word of the root signature language: an identifier or a number
+#[derive(Debug)]
+struct Word<'s> {
+    text: &'s str,
+    range: TextRange,
+}
+
+impl Word<'_> {
+    fn error(&self, kind: RootSignatureErrorKind) -> RootSignatureError {
+        RootSignatureError {
+            kind,
+            range: Some(self.range),
+            span: None,
+        }
+    }
+
+    fn invalid(&self, parameter: &'static str) -> RootSignatureError {
+        self.error(RootSignatureErrorKind::InvalidValue {
+            parameter,
+            value: self.text.to_owned(),
+        })
+    }
+
+    fn unknown(&self, element: &'static str) -> RootSignatureError {
+        self.error(RootSignatureErrorKind::UnknownParameter {
+            element,
+            parameter: self.text.to_owned(),
+        })
+    }
+
+    fn duplicate(&self) -> RootSignatureError {
+        self.error(RootSignatureErrorKind::Duplicate(self.text.to_owned()))
+    }
+}
+
+/// An element or clause: `Name(args...)`
+#[derive(Debug)]
+struct Call<'s> {
+    name: &'s str,
+    args: Vec<Arg<'s>>,
+    range: TextRange,
+}
+
+impl Call<'_> {
+    fn error(&self, kind: RootSignatureErrorKind) -> RootSignatureError {
+        RootSignatureError {
+            kind,
+            range: Some(self.range),
+            span: None,
+        }
+    }
+}
+
+/// An argument of an element
+#[derive(Debug)]
+enum Arg<'s> {
+    /// Nested clause, e.g. `SRV(t0)` in a descriptor table
+    Call(Call<'s>),
+    /// Named parameter, e.g. `space = 1` or `flags = DATA_STATIC | DATA_VOLATILE`
+    Assign { key: Word<'s>, value: Vec<Word<'s>> },
+    /// Positional value, e.g. `b0`, or `|`-separated list of words
+    Value(Vec<Word<'s>>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tok<'s> {
+    Word(&'s str),
+    LParen,
+    RParen,
+    Comma,
+    Equals,
+    Pipe,
+    Eof,
+}
+
+impl fmt::Display for Tok<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Word(word) => write!(f, "`{}`", word),
+            Self::LParen => f.write_str("`(`"),
+            Self::RParen => f.write_str("`)`"),
+            Self::Comma => f.write_str("`,`"),
+            Self::Equals => f.write_str("`=`"),
+            Self::Pipe => f.write_str("`|`"),
+            Self::Eof => f.write_str("end of string"),
+        }
+    }
+}
+
+/// Recursive descent parser for the root signature language
+struct Parser<'s> {
+    source: &'s str,
+    pos: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn new(source: &'s str) -> Self {
+        Self { source, pos: 0 }
+    }
+
+    fn range(start: usize, end: usize) -> TextRange {
+        TextRange::new(
+            TextSize::try_from(start).unwrap(),
+            TextSize::try_from(end).unwrap(),
+        )
+    }
+
+    /// Return the next token and its range, without consuming it
+    fn peek(&self) -> Result<(Tok<'s>, TextRange), RootSignatureError> {
+        let rest = &self.source[self.pos..];
+        let start = self.pos + (rest.len() - rest.trim_start().len());
+        let rest = &self.source[start..];
+
+        let Some(c) = rest.chars().next() else {
+            return Ok((Tok::Eof, Self::range(start, start)));
+        };
+
+        let (tok, len) = match c {
+            '(' => (Tok::LParen, 1),
+            ')' => (Tok::RParen, 1),
+            ',' => (Tok::Comma, 1),
+            '=' => (Tok::Equals, 1),
+            '|' => (Tok::Pipe, 1),
+            c if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '+') => {
+                // Numbers may have a signed exponent, e.g. `1e-5`
+                let numeric = !(c.is_ascii_alphabetic() || c == '_');
+                let len = rest
+                    .char_indices()
+                    .skip(1)
+                    .zip(rest.chars())
+                    .find(|((_, c), prev)| {
+                        !(c.is_ascii_alphanumeric()
+                            || matches!(c, '_' | '.')
+                            || (numeric && matches!(c, '-' | '+') && matches!(prev, 'e' | 'E')))
+                    })
+                    .map(|((i, _), _)| i)
+                    .unwrap_or(rest.len());
+                (Tok::Word(&rest[..len]), len)
+            }
+            other => {
+                return Err(RootSignatureError {
+                    kind: RootSignatureErrorKind::Syntax {
+                        found: format!("`{}`", other),
+                        expected: "a root signature element",
+                    },
+                    range: Some(Self::range(start, start + other.len_utf8())),
+                    span: None,
+                });
+            }
+        };
+
+        Ok((tok, Self::range(start, start + len)))
+    }
+
+    fn next(&mut self) -> Result<(Tok<'s>, TextRange), RootSignatureError> {
+        let (tok, range) = self.peek()?;
+        self.pos = usize::from(range.end());
+        Ok((tok, range))
+    }
+
+    fn expect(&mut self, expected: Tok<'s>) -> Result<TextRange, RootSignatureError> {
+        let (tok, range) = self.next()?;
+        if tok == expected {
+            Ok(range)
+        } else {
+            Err(Self::unexpected(tok, range, "a `(`, `)`, `,` or `=`"))
+        }
+    }
+
+    fn unexpected(tok: Tok<'_>, range: TextRange, expected: &'static str) -> RootSignatureError {
+        RootSignatureError {
+            kind: RootSignatureErrorKind::Syntax {
+                found: tok.to_string(),
+                expected,
+            },
+            range: Some(range),
+            span: None,
+        }
+    }
+
+    fn word(&mut self, expected: &'static str) -> Result<Word<'s>, RootSignatureError> {
+        match self.next()? {
+            (Tok::Word(text), range) => Ok(Word { text, range }),
+            (tok, range) => Err(Self::unexpected(tok, range, expected)),
+        }
+    }
+
+    /// Parse a comma-separated list of elements, up to the end of the string
+    fn parse_list(mut self) -> Result<Vec<Call<'s>>, RootSignatureError> {
+        let mut calls = Vec::new();
+
+        if self.peek()?.0 == Tok::Eof {
+            return Ok(calls);
+        }
+
+        loop {
+            let name = self.word("a root signature element")?;
+            calls.push(self.parse_call(name)?);
+
+            match self.next()? {
+                (Tok::Comma, _) => continue,
+                (Tok::Eof, _) => break,
+                (tok, range) => return Err(Self::unexpected(tok, range, "a `,`")),
+            }
+        }
+
+        Ok(calls)
+    }
+
+    /// Parse the parenthesized arguments of an element whose name was just read
+    fn parse_call(&mut self, name: Word<'s>) -> Result<Call<'s>, RootSignatureError> {
+        self.expect(Tok::LParen)?;
+
+        let mut args = Vec::new();
+        if self.peek()?.0 != Tok::RParen {
+            loop {
+                args.push(self.parse_arg()?);
+
+                if self.peek()?.0 == Tok::Comma {
+                    self.next()?;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        let end = self.expect(Tok::RParen)?;
+        Ok(Call {
+            name: name.text,
+            args,
+            range: TextRange::new(name.range.start(), end.end()),
+        })
+    }
+
+    fn parse_arg(&mut self) -> Result<Arg<'s>, RootSignatureError> {
+        let first = self.word("a parameter")?;
+
+        match self.peek()?.0 {
+            Tok::LParen => Ok(Arg::Call(self.parse_call(first)?)),
+            Tok::Equals => {
+                self.next()?;
+                let value = self.word("a value")?;
+                let value = self.parse_words(value)?;
+                Ok(Arg::Assign { key: first, value })
+            }
+            _ => Ok(Arg::Value(self.parse_words(first)?)),
+        }
+    }
+
+    /// Parse a `|`-separated list of words starting with `first`
+    fn parse_words(&mut self, first: Word<'s>) -> Result<Vec<Word<'s>>, RootSignatureError> {
+        let mut words = vec![first];
+
+        while self.peek()?.0 == Tok::Pipe {
+            self.next()?;
+            words.push(self.word("a flag")?);
+        }
+
+        Ok(words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Parsable;
+
+    #[test]
+    fn parse_root_signature() {
+        let rs = RootSignature::parse(
+            "RootFlags(ALLOW_INPUT_ASSEMBLER_INPUT_LAYOUT | DENY_HULL_SHADER_ROOT_ACCESS), \
+             RootConstants(num32BitConstants=4, b1, visibility=SHADER_VISIBILITY_PIXEL), \
+             CBV(b0, space = 2, flags = DATA_STATIC), \
+             DescriptorTable(SRV(t0, numDescriptors=4), UAV(u0, numDescriptors=unbounded, offset=8)), \
+             StaticSampler(s0, filter=FILTER_MIN_MAG_MIP_POINT, maxAnisotropy=8)",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rs.flags,
+            vec![
+                RootFlag::AllowInputAssemblerInputLayout,
+                RootFlag::DenyHullShaderRootAccess
+            ]
+        );
+
+        let kinds: Vec<_> = rs.parameters.iter().map(|p| p.kind.clone()).collect();
+        let reg = |class, index, space| Register {
+            class,
+            index,
+            space,
+        };
+        assert_eq!(
+            kinds[0],
+            RootParameterKind::Constants {
+                num_32bit_values: 4,
+                register: reg(RegisterClass::ConstantBuffer, 1, 0),
+            }
+        );
+        assert_eq!(rs.parameters[0].visibility, ShaderVisibility::Pixel);
+        assert_eq!(
+            kinds[1],
+            RootParameterKind::Descriptor {
+                register: reg(RegisterClass::ConstantBuffer, 0, 2),
+                flags: vec!["DATA_STATIC".into()],
+            }
+        );
+
+        let RootParameterKind::DescriptorTable(ranges) = &kinds[2] else {
+            panic!("expected a descriptor table");
+        };
+        assert_eq!(ranges[0].base, reg(RegisterClass::ShaderResource, 0, 0));
+        assert_eq!(ranges[0].count, DescriptorCount::Bounded(4));
+        assert_eq!(ranges[0].offset, None);
+        assert_eq!(ranges[1].count, DescriptorCount::Unbounded);
+        assert_eq!(ranges[1].offset, Some(8));
+
+        assert_eq!(
+            rs.static_samplers[0].register,
+            reg(RegisterClass::Sampler, 0, 0)
+        );
+        assert_eq!(
+            rs.static_samplers[0].properties,
+            vec![
+                ("filter".into(), "FILTER_MIN_MAG_MIP_POINT".into()),
+                ("maxAnisotropy".into(), "8".into()),
+            ]
+        );
+
+        assert_eq!(RootSignature::parse(""), Ok(RootSignature::default()));
+    }
+
+    #[test]
+    fn parse_root_signature_errors() {
+        let error = |src| {
+            let e = RootSignature::parse(src).unwrap_err();
+            let range = e.range.unwrap();
+            (
+                e.kind.to_string(),
+                &src[usize::from(range.start())..usize::from(range.end())],
+            )
+        };
+
+        assert_eq!(
+            error("CBV(b0), SRV(b1)"),
+            ("invalid value `b1` for `register`".to_owned(), "b1")
+        );
+        assert_eq!(
+            error("RootConstants(b0)"),
+            (
+                "`RootConstants` requires num32BitConstants".to_owned(),
+                "RootConstants(b0)"
+            )
+        );
+        assert_eq!(
+            error("DescriptorTable(CBV(b0), Sampler(s0))"),
+            (
+                "descriptor tables cannot mix samplers with other descriptors".to_owned(),
+                "DescriptorTable(CBV(b0), Sampler(s0))"
+            )
+        );
+        assert_eq!(
+            error("CBV(b0, spac=1)"),
+            ("unknown parameter `spac` for `CBV`".to_owned(), "spac")
+        );
+        assert_eq!(
+            error("CBV(b0) SRV(t0)"),
+            ("unexpected `SRV`, expected a `,`".to_owned(), "SRV")
+        );
+    }
+
+    #[test]
+    fn parse_root_signature_keywords_and_exponents() {
+        let rs = RootSignature::parse(
+            "rootflags(allow_input_assembler_input_layout), \
+             cbv(B0, Space = 1, FLAGS = data_static, visibility = shader_visibility_vertex), \
+             descriptortable(sampler(s0, numdescriptors = UNBOUNDED, offset = descriptor_range_offset_append)), \
+             staticsampler(s1, MinLOD = 1e-5, maxLOD = 1.5E+3, mipLODBias = -2e-1)",
+        )
+        .unwrap();
+
+        assert_eq!(rs.flags, vec![RootFlag::AllowInputAssemblerInputLayout]);
+        assert_eq!(
+            rs.parameters[0].kind,
+            RootParameterKind::Descriptor {
+                register: Register {
+                    class: RegisterClass::ConstantBuffer,
+                    index: 0,
+                    space: 1,
+                },
+                flags: vec!["DATA_STATIC".into()],
+            }
+        );
+        assert_eq!(rs.parameters[0].visibility, ShaderVisibility::Vertex);
+
+        let RootParameterKind::DescriptorTable(ranges) = &rs.parameters[1].kind else {
+            panic!("expected a descriptor table");
+        };
+        assert_eq!(ranges[0].count, DescriptorCount::Unbounded);
+        assert_eq!(ranges[0].offset, None);
+
+        assert_eq!(
+            rs.static_samplers[0].properties,
+            vec![
+                ("mipLODBias".into(), "-2e-1".into()),
+                ("minLOD".into(), "1e-5".into()),
+                ("maxLOD".into(), "1.5E+3".into()),
+            ]
+        );
+
+        // Only exponents may be signed
+        assert_eq!(
+            RootSignature::parse("StaticSampler(s0, filter = a-b)")
+                .unwrap_err()
+                .kind
+                .to_string(),
+            "unexpected `-b`, expected a `(`, `)`, `,` or `=`"
+        );
+    }
+
+    #[test]
+    fn root_signature_error_spans() {
+        let def =
+            ast::FunctionDefinition::parse("[RootSignature(\"CBV(b0), UAV(x1)\")] void main() {}")
+                .unwrap();
+
+        let error = RootSignature::of_function(&def).unwrap().unwrap_err();
+        let range = error.span.unwrap().range();
+        assert_eq!((u32::from(range.start()), u32::from(range.end())), (29, 31));
+    }
+
+    #[test]
+    fn concatenated_root_signature() {
+        // Error spans point into the piece the error occurs in
+        let def = ast::FunctionDefinition::parse(
+            "[RootSignature(\"CBV(b0), \" \"UAV(x1)\")] void main() {}",
+        )
+        .unwrap();
+
+        let error = RootSignature::of_function(&def).unwrap().unwrap_err();
+        let range = error.span.unwrap().range();
+        assert_eq!((u32::from(range.start()), u32::from(range.end())), (32, 34));
+
+        let tu = ast::TranslationUnit::parse(
+            "#define RS \"CBV(b0), \" \"SRV(t0)\"\n[RootSignature(RS)] void main() {}",
+        )
+        .unwrap();
+        let def =
+            tu.0.iter()
+                .find_map(|decl| match &**decl {
+                    ast::ExternalDeclarationData::FunctionDefinition(def) => Some(def),
+                    _ => None,
+                })
+                .unwrap();
+
+        let rs = RootSignature::of_function(def).unwrap().unwrap();
+        assert_eq!(rs.parameters.len(), 2);
+    }
+
+    #[test]
+    fn check_root_signature_bindings() {
+        let tu = ast::TranslationUnit::parse(
+            "cbuffer Globals : register(b0) { float4 a; };
+            Texture2D textures[4] : register(t0, space1);
+            RWStructuredBuffer<float4> output : register(u0);
+            SamplerState linear_sampler : register(s0);",
+        )
+        .unwrap();
+
+        let check = |src| {
+            RootSignature::parse(src)
+                .unwrap()
+                .check_bindings(&tu)
+                .into_iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            check(
+                "CBV(b0), DescriptorTable(SRV(t0, space=1, numDescriptors=4), UAV(u0)), \
+                 StaticSampler(s0)"
+            ),
+            Vec::<String>::new()
+        );
+
+        assert_eq!(
+            check(
+                "CBV(b0), RootConstants(num32BitConstants=1, b0), \
+                 DescriptorTable(SRV(t0, space=1, numDescriptors=2)), \
+                 DescriptorTable(UAV(u0, numDescriptors=unbounded), visibility=SHADER_VISIBILITY_PIXEL)"
+            ),
+            vec![
+                "register b0 is bound more than once".to_owned(),
+                "`textures` (t0, space1) is not bound by the root signature".to_owned(),
+                "`linear_sampler` (s0) is not bound by the root signature".to_owned(),
+            ]
+        );
+
+        // Overlapping ranges are allowed when visible to different stages
+        assert_eq!(
+            check(
+                "CBV(b0, visibility=SHADER_VISIBILITY_VERTEX), CBV(b0, visibility=SHADER_VISIBILITY_PIXEL), \
+                 DescriptorTable(SRV(t0, space=1, numDescriptors=unbounded), UAV(u0)), StaticSampler(s0)"
+            ),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! Semantic analysis
+//!
+//! Analyses in this module run on top of the [resolver](crate::resolver), which links names to
+//! their declarations. They report their findings as [Diagnostic]s which point back at the
+//! source through node spans.
+
+use std::fmt;
+
+use crate::{
+    ast::{NodeSpan, SmolStr},
+    reflection::bindings::ResourceKind,
+    root_signature::{Register, RegisterClass},
+};
+
+pub mod call_graph;
+pub mod cfg;
+pub mod check;
+pub mod consteval;
+pub mod flow;
+pub mod intrinsics;
+pub mod overload;
+pub mod registers;
+pub mod types;
+
+use consteval::Constant;
+use types::Type;
+
+/// Severity of a diagnostic
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The code is valid, but likely not doing what was intended
+    Warning,
+    /// The code is invalid
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => f.write_str("warning"),
+            Self::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A diagnostic raised by semantic analysis
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+#[error("{kind}")]
+pub struct Diagnostic {
+    /// Kind of diagnostic
+    pub kind: DiagnosticKind,
+    /// Span of the offending node
+    pub span: Option<NodeSpan>,
+}
+
+impl Diagnostic {
+    /// Create a new diagnostic
+    pub fn new(kind: DiagnosticKind, span: Option<NodeSpan>) -> Self {
+        Self { kind, span }
+    }
+
+    /// Severity of this diagnostic
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+}
+
+/// Kind of semantic diagnostic
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum DiagnosticKind {
+    /// A name does not refer to any visible declaration
+    #[error("use of undeclared identifier `{0}`")]
+    UndeclaredIdentifier(SmolStr),
+    /// A vector or matrix is implicitly converted to a type with fewer components
+    #[error("implicit truncation of `{from}` to `{to}`")]
+    ImplicitTruncation {
+        /// Type of the value
+        from: Type,
+        /// Type the value is converted to
+        to: Type,
+    },
+    /// A value cannot be implicitly converted to the expected type
+    #[error("cannot convert from `{found}` to `{expected}`")]
+    TypeMismatch {
+        /// Expected type
+        expected: Type,
+        /// Type of the value
+        found: Type,
+    },
+    /// A binary operator is applied to operands it does not support
+    #[error("invalid operands to `{op}`: `{left}` and `{right}`")]
+    InvalidOperands {
+        /// Operator
+        op: &'static str,
+        /// Type of the left operand
+        left: Type,
+        /// Type of the right operand
+        right: Type,
+    },
+    /// A unary operator is applied to an operand it does not support
+    #[error("invalid operand to `{op}`: `{ty}`")]
+    InvalidOperand {
+        /// Operator
+        op: &'static str,
+        /// Type of the operand
+        ty: Type,
+    },
+    /// A short-circuiting logical operator is applied to a vector or matrix
+    #[error("operands of `{op}` must be scalar, found `{ty}`; use `{intrinsic}` for component-wise operations")]
+    NonScalarLogicalOperand {
+        /// Operator
+        op: &'static str,
+        /// Type of the offending operand
+        ty: Type,
+        /// Intrinsic which operates component-wise
+        intrinsic: &'static str,
+    },
+    /// An explicit cast between incompatible types
+    #[error("cannot cast from `{from}` to `{to}`")]
+    InvalidCast {
+        /// Type of the value
+        from: Type,
+        /// Target type of the cast
+        to: Type,
+    },
+    /// A swizzle which is not valid for the type it is applied to
+    #[error("invalid swizzle `{swizzle}` on `{ty}`")]
+    InvalidSwizzle {
+        /// Swizzle
+        swizzle: SmolStr,
+        /// Type the swizzle is applied to
+        ty: Type,
+    },
+    /// Selection of a member which does not exist
+    #[error("`{ty}` has no member named `{member}`")]
+    UnknownMember {
+        /// Member name
+        member: SmolStr,
+        /// Type the member is selected from
+        ty: Type,
+    },
+    /// Indexing of a type which is not an array, vector, matrix or buffer
+    #[error("`{0}` cannot be indexed")]
+    NotIndexable(Type),
+    /// A vector or matrix constructor with the wrong number of components
+    #[error("`{ty}` constructor expects {expected} components, found {found}")]
+    ConstructorArity {
+        /// Constructed type
+        ty: Type,
+        /// Number of components of the constructed type
+        expected: usize,
+        /// Number of components given
+        found: usize,
+    },
+    /// A `mul` call whose operand shapes do not match
+    #[error("incompatible operands to `mul`: `{left}` and `{right}`")]
+    IncompatibleMul {
+        /// Type of the left operand
+        left: Type,
+        /// Type of the right operand
+        right: Type,
+    },
+    /// No overload of a function accepts the arguments of a call
+    #[error("no matching overload for call to `{name}({})`{}", list_types(.args), list_candidates(.candidates))]
+    NoMatchingOverload {
+        /// Function name
+        name: SmolStr,
+        /// Types of the arguments
+        args: Vec<Type>,
+        /// Signatures of the overloads
+        candidates: Vec<String>,
+    },
+    /// Several overloads of a function match the arguments of a call equally well
+    #[error("call to `{name}({})` is ambiguous{}", list_types(.args), list_candidates(.candidates))]
+    AmbiguousCall {
+        /// Function name
+        name: SmolStr,
+        /// Types of the arguments
+        args: Vec<Type>,
+        /// Signatures of the best matching overloads
+        candidates: Vec<String>,
+    },
+    /// Signed integer arithmetic in a constant expression does not fit its type
+    #[error("overflow in constant expression of type `{}`; result is `{}`", .0.ty().concrete(), .0)]
+    ConstantOverflow(Constant),
+    /// Integer division or remainder by zero in a constant expression
+    #[error("division by zero in constant expression")]
+    DivisionByZero,
+    /// An expression which must be an integer constant, such as a case label, is not one
+    #[error("{0} is not an integer constant expression")]
+    NotIntegerConstant(&'static str),
+    /// A bitfield is declared with a type other than an integer scalar
+    #[error("bitfield `{name}` must have an integer scalar type, found `{ty}`")]
+    InvalidBitfieldType {
+        /// Field name
+        name: SmolStr,
+        /// Type of the field
+        ty: Type,
+    },
+    /// A resource is bound to a register of the wrong class
+    #[error("`{name}` of type `{kind}` cannot be bound to register `{register}`; only `{}` registers are allowed", .expected.prefix())]
+    RegisterClassMismatch {
+        /// Resource name
+        name: SmolStr,
+        /// Kind of resource
+        kind: ResourceKind,
+        /// Register given by the annotation
+        register: SmolStr,
+        /// Class of the registers the resource can be bound to
+        expected: RegisterClass,
+    },
+    /// Two resources are bound to overlapping register ranges
+    #[error("`{name}` overlaps `{other}` at register `{register}`")]
+    RegisterOverlap {
+        /// Resource name
+        name: SmolStr,
+        /// Previously declared resource
+        other: SmolStr,
+        /// First register claimed by both resources
+        register: Register,
+    },
+    /// A resource is bound past the last register of its class
+    #[error("`{name}` at register `{register}` extends past the last `{}` register, `{}{last}`", .register.class.prefix(), .register.class.prefix())]
+    RegisterOutOfRange {
+        /// Resource name
+        name: SmolStr,
+        /// Base register of the resource
+        register: Register,
+        /// Last register of the class
+        last: u32,
+    },
+    /// A function calls itself, directly or through other functions
+    #[error("recursion is not allowed: {}", list_chain(.0))]
+    Recursion(Vec<SmolStr>),
+    /// The end of a function which returns a value can be reached without a `return`
+    #[error("not all control paths of `{0}` return a value")]
+    MissingReturn(SmolStr),
+    /// A statement can never be executed
+    #[error("unreachable code")]
+    UnreachableCode,
+    /// A local variable is read before it is definitely assigned
+    #[error("`{0}` is read before it is assigned")]
+    UninitializedRead(SmolStr),
+}
+
+fn list_types(args: &[Type]) -> String {
+    let args: Vec<_> = args.iter().map(Type::to_string).collect();
+    args.join(", ")
+}
+
+fn list_chain(names: &[SmolStr]) -> String {
+    let names: Vec<_> = names.iter().map(|name| format!("`{}`", name)).collect();
+    names.join(" -> ")
+}
+
+fn list_candidates(candidates: &[String]) -> String {
+    if candidates.is_empty() {
+        return String::new();
+    }
+
+    let candidates: Vec<_> = candidates.iter().map(|c| format!("`{}`", c)).collect();
+    format!("; candidates are: {}", candidates.join(", "))
+}
+
+impl DiagnosticKind {
+    /// Severity of this kind of diagnostic
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::ImplicitTruncation { .. }
+            | Self::ConstantOverflow(_)
+            | Self::UnreachableCode
+            | Self::UninitializedRead(_) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
@@ -0,0 +1,452 @@
+//! Call graph
+//!
+//! [build] links every function and struct method of a translation unit to the functions it
+//! calls. Calls are resolved to the overload selected by the [type checker](super::check); when
+//! the type checker could not select one, e.g. because the call is ambiguous, the call is linked
+//! to every overload of the called name instead and marked as unresolved. Calls to intrinsics
+//! and constructors are not part of the graph.
+//!
+//! HLSL does not allow recursion, so [check] reports every strongly connected set of functions
+//! of the graph as a diagnostic, along with a chain of calls which leads from one of these
+//! functions back to itself.
+//!
+//! With the `serde` feature, a [CallGraph] can be serialized to export it to other tools.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, parse::Parsable, resolver, semantic::{call_graph, check}};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     r#"
+//!     float b(float x);
+//!     float a(float x) { return b(x - 1); }
+//!     float b(float x) { return x > 0 ? a(x) : x; }
+//!     "#,
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//! let types = check::check(&tu, &symbols);
+//! let diagnostics = call_graph::check(&call_graph::build(&symbols, &types));
+//!
+//! assert_eq!(
+//!     diagnostics[0].to_string(),
+//!     "recursion is not allowed: `b` -> `a` -> `b`"
+//! );
+//! ```
+
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+
+#[cfg(feature = "serde")]
+use rserde::{Deserialize, Serialize};
+
+use crate::{
+    ast::{self, NodeSpan, SmolStr},
+    resolver::{Declarator, SymbolId, SymbolKind, SymbolTable},
+    visitor::{Host, Visit, Visitor},
+};
+
+use super::{
+    check::{signature, TypeTable},
+    overload::Callee,
+    Diagnostic, DiagnosticKind,
+};
+
+/// Functions of a translation unit, and the calls between them
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct CallGraph {
+    /// Functions and methods, in declaration order
+    pub functions: Vec<Function>,
+    /// Calls, grouped by caller and in source order within each caller
+    pub calls: Vec<CallSite>,
+}
+
+/// A function or struct method
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct Function {
+    /// Symbol of the function
+    pub symbol: SymbolId,
+    /// Name of the function, qualified by its struct for methods, e.g. `Light::Attenuation`
+    pub name: SmolStr,
+    /// Signature of the function, e.g. `float3 shade(float3 n)`
+    pub signature: String,
+    /// `true` if the function has a body, `false` if it is only declared
+    pub defined: bool,
+    /// Span of the name of the function
+    pub span: Option<NodeSpan>,
+}
+
+/// A call from one function to another
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct CallSite {
+    /// Index of the calling function in [CallGraph::functions]
+    pub caller: usize,
+    /// Index of the called function in [CallGraph::functions]
+    pub callee: usize,
+    /// `false` if no overload could be selected, in which case the call is linked to all of them
+    pub resolved: bool,
+    /// Span of the call expression
+    pub span: Option<NodeSpan>,
+}
+
+impl CallGraph {
+    /// Index of the function declared by the given symbol
+    pub fn function(&self, id: SymbolId) -> Option<usize> {
+        self.functions
+            .iter()
+            .position(|function| function.symbol == id)
+    }
+
+    /// Calls made by a function, in source order
+    pub fn calls_from(&self, function: usize) -> impl Iterator<Item = &CallSite> + '_ {
+        self.calls
+            .iter()
+            .filter(move |call| call.caller == function)
+    }
+
+    /// Calls made to a function
+    pub fn calls_to(&self, function: usize) -> impl Iterator<Item = &CallSite> + '_ {
+        self.calls
+            .iter()
+            .filter(move |call| call.callee == function)
+    }
+
+    /// Recursive cycles of the graph: one for every set of functions which call each other,
+    /// directly or not. Each cycle is a chain of indices into [CallGraph::calls], leading from
+    /// the first declared function of the set back to itself through as few calls as possible.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let component = components(self);
+
+        let mut cycles = Vec::new();
+        let mut seen = vec![false; self.functions.len()];
+        for start in 0..self.functions.len() {
+            if seen[component[start]] {
+                continue;
+            }
+            seen[component[start]] = true;
+
+            // Breadth-first search for the shortest way back to the start, staying in the
+            // component since no other path can lead back
+            let mut via: HashMap<usize, usize> = HashMap::new();
+            let mut queue = VecDeque::from([start]);
+            'search: while let Some(function) = queue.pop_front() {
+                for (index, call) in self.calls.iter().enumerate() {
+                    if call.caller != function || component[call.callee] != component[start] {
+                        continue;
+                    }
+
+                    if call.callee == start {
+                        let mut chain = vec![index];
+                        let mut function = function;
+                        while function != start {
+                            let index = via[&function];
+                            chain.push(index);
+                            function = self.calls[index].caller;
+                        }
+
+                        chain.reverse();
+                        cycles.push(chain);
+                        break 'search;
+                    }
+
+                    if let Entry::Vacant(entry) = via.entry(call.callee) {
+                        entry.insert(index);
+                        queue.push_back(call.callee);
+                    }
+                }
+            }
+        }
+
+        cycles
+    }
+}
+
+/// Build the call graph of the functions declared in a symbol table
+pub fn build(symbols: &SymbolTable<'_>, types: &TypeTable<'_>) -> CallGraph {
+    let mut ids: Vec<_> = symbols
+        .scopes()
+        .iter()
+        .flat_map(|scope| scope.symbols.iter().copied())
+        .filter(|id| symbols.symbol(*id).kind == SymbolKind::Function)
+        .collect();
+    ids.sort();
+
+    let index: HashMap<_, _> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+    let mut graph = CallGraph::default();
+    for (caller, &id) in ids.iter().enumerate() {
+        let symbol = symbols.symbol(id);
+        let body = match symbol.declarator {
+            Declarator::Function { definition, .. } => definition.map(|def| &def.statement),
+            Declarator::Method { method, definition } => definition
+                .map(|def| &def.statement)
+                .or(method.statement.as_ref()),
+            _ => None,
+        };
+
+        let name = match symbol.parent {
+            Some(parent) => format!("{}::{}", symbols.symbol(parent).name, symbol.name).into(),
+            None => symbol.name.clone(),
+        };
+
+        graph.functions.push(Function {
+            symbol: id,
+            name,
+            signature: signature(symbols, id),
+            defined: body.is_some(),
+            span: symbol.span,
+        });
+
+        if let Some(body) = body {
+            let mut calls = Calls {
+                symbols,
+                types,
+                index: &index,
+                caller,
+                calls: &mut graph.calls,
+            };
+            body.visit(&mut calls);
+        }
+    }
+
+    graph
+}
+
+/// Report the recursive cycles of a call graph
+pub fn check(graph: &CallGraph) -> Vec<Diagnostic> {
+    graph
+        .cycles()
+        .into_iter()
+        .map(|chain| {
+            let first = &graph.calls[chain[0]];
+            let names = std::iter::once(first.caller)
+                .chain(chain.iter().map(|index| graph.calls[*index].callee))
+                .map(|function| graph.functions[function].name.clone())
+                .collect();
+
+            Diagnostic::new(DiagnosticKind::Recursion(names), first.span)
+        })
+        .collect()
+}
+
+/// Strongly connected component of every function, computed with Tarjan's algorithm. Components
+/// are identified by one of their functions.
+fn components(graph: &CallGraph) -> Vec<usize> {
+    struct Tarjan<'g> {
+        graph: &'g CallGraph,
+        next: usize,
+        order: Vec<Option<usize>>,
+        low: Vec<usize>,
+        stack: Vec<usize>,
+        on_stack: Vec<bool>,
+        component: Vec<usize>,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, function: usize) {
+            self.order[function] = Some(self.next);
+            self.low[function] = self.next;
+            self.next += 1;
+            self.stack.push(function);
+            self.on_stack[function] = true;
+
+            for call in self.graph.calls_from(function) {
+                match self.order[call.callee] {
+                    None => {
+                        self.visit(call.callee);
+                        self.low[function] = self.low[function].min(self.low[call.callee]);
+                    }
+                    Some(order) if self.on_stack[call.callee] => {
+                        self.low[function] = self.low[function].min(order);
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            if Some(self.low[function]) == self.order[function] {
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    self.component[member] = function;
+                    if member == function {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    let len = graph.functions.len();
+    let mut tarjan = Tarjan {
+        graph,
+        next: 0,
+        order: vec![None; len],
+        low: vec![0; len],
+        stack: Vec::new(),
+        on_stack: vec![false; len],
+        component: vec![0; len],
+    };
+
+    for function in 0..len {
+        if tarjan.order[function].is_none() {
+            tarjan.visit(function);
+        }
+    }
+
+    tarjan.component
+}
+
+/// Collects the calls made by a function body
+struct Calls<'s, 'a, 'g> {
+    symbols: &'s SymbolTable<'a>,
+    types: &'s TypeTable<'a>,
+    index: &'s HashMap<SymbolId, usize>,
+    caller: usize,
+    calls: &'g mut Vec<CallSite>,
+}
+
+impl Calls<'_, '_, '_> {
+    fn push(&mut self, callee: SymbolId, resolved: bool, span: Option<NodeSpan>) {
+        if let Some(&callee) = self.index.get(&callee) {
+            self.calls.push(CallSite {
+                caller: self.caller,
+                callee,
+                resolved,
+                span,
+            });
+        }
+    }
+}
+
+impl Visitor for Calls<'_, '_, '_> {
+    fn visit_expr(&mut self, expr: &ast::Expr) -> Visit {
+        let ast::ExprData::FunCall(fun, _) = &**expr else {
+            return Visit::Children;
+        };
+
+        match self.types.call(expr).map(|call| &call.callee) {
+            Some(Callee::Function(id)) => self.push(*id, true, expr.span),
+            Some(Callee::Intrinsic(..)) => {}
+            None => {
+                if let ast::FunIdentifierData::Expr(callee) = &**fun {
+                    if let ast::ExprData::Variable(name) = &***callee {
+                        if let Some(resolution) = self.symbols.resolution(name) {
+                            for id in resolution.symbols() {
+                                self.push(*id, false, expr.span);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Visit::Children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::Parsable, resolver, semantic::check};
+
+    fn graph(src: &str) -> (CallGraph, Vec<String>) {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        let types = check::check(&tu, &symbols);
+        let graph = build(&symbols, &types);
+        let diagnostics = check(&graph);
+        assert!(diagnostics.iter().all(|diag| diag.span.is_some()));
+        let diagnostics = diagnostics.iter().map(ToString::to_string).collect();
+        (graph, diagnostics)
+    }
+
+    fn edges(graph: &CallGraph) -> Vec<(String, String, bool)> {
+        graph
+            .calls
+            .iter()
+            .map(|call| {
+                (
+                    graph.functions[call.caller].signature.clone(),
+                    graph.functions[call.callee].signature.clone(),
+                    call.resolved,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resolve_overloads() {
+        let (graph, diagnostics) = graph(
+            r#"
+            float f(float x) { return x; }
+            int f(int x) { return x; }
+            float g(float2 v);
+            struct S {
+                float m() { return f(1); }
+            };
+            float g(float2 v) { S s; return f(v.x) + s.m() + sin(v.y); }
+            float h(double x) { return f(x, x) + f(true); }
+            "#,
+        );
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(
+            graph
+                .functions
+                .iter()
+                .map(|function| (function.name.as_str(), function.defined))
+                .collect::<Vec<_>>(),
+            [
+                ("f", true),
+                ("f", true),
+                ("g", true),
+                ("S::m", true),
+                ("h", true)
+            ]
+        );
+
+        let calls = |caller: &str, callee: &str, resolved: bool| {
+            (caller.to_owned(), callee.to_owned(), resolved)
+        };
+        assert_eq!(
+            edges(&graph),
+            [
+                calls("float g(float2 v)", "float f(float x)", true),
+                calls("float g(float2 v)", "float m()", true),
+                calls("float m()", "int f(int x)", true),
+                calls("float h(double x)", "float f(float x)", false),
+                calls("float h(double x)", "int f(int x)", false),
+                calls("float h(double x)", "float f(float x)", false),
+                calls("float h(double x)", "int f(int x)", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn detect_recursion() {
+        let (graph, diagnostics) = graph(
+            r#"
+            float a(float x) { return x > 0 ? a(x - 1) : x; }
+            float c(float x);
+            float b(float x) { return c(x) + a(x); }
+            float d(float x) { return b(x); }
+            float c(float x) { return d(x) + x; }
+            float e(float x) { return b(x); }
+            "#,
+        );
+
+        assert_eq!(
+            diagnostics,
+            [
+                "recursion is not allowed: `a` -> `a`",
+                "recursion is not allowed: `c` -> `d` -> `b` -> `c`",
+            ]
+        );
+        assert_eq!(graph.cycles().len(), 2);
+    }
+}
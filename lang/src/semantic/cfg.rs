@@ -0,0 +1,411 @@
+//! Control-flow graphs
+//!
+//! [Cfg::build] lowers the body of a function to a graph of basic blocks. Each block holds the
+//! declarations, conditions and expressions it evaluates in order, and ends with a
+//! [Terminator] which says how control leaves it. `if`, loops and `switch` statements branch
+//! between blocks, `break` and `continue` jump to the end or the next iteration of the enclosing
+//! loop, and a `case` label which is not preceded by a `break` is reached both from the `switch`
+//! and by falling through from the previous case. `return`, `discard` and the end of the body
+//! lead to the exit block.
+//!
+//! Loops whose condition is missing or a true constant, such as `for (;;)` or `while (true)`,
+//! only exit through `break`. Other conditions are assumed to go both ways, and the operands of
+//! `&&`, `||` and `?:` are not split into separate blocks.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, parse::Parsable, resolver, semantic::cfg::{Cfg, Terminator}};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     "float f(float x) { if (x > 0) { return x; } else { discard; } }",
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//! let ast::ExternalDeclarationData::FunctionDefinition(def) = &*tu.0[0] else {
+//!     unreachable!()
+//! };
+//!
+//! let cfg = Cfg::build(&def.statement, &symbols);
+//! let reachable = cfg.reachable();
+//!
+//! // The end of the body cannot be reached, since both branches leave the function
+//! assert!(cfg
+//!     .blocks
+//!     .iter()
+//!     .zip(reachable)
+//!     .all(|(block, reachable)| !reachable || block.terminator != Terminator::FallOff));
+//! ```
+
+use crate::{
+    ast::{self, NodeSpan},
+    resolver::SymbolTable,
+};
+
+use super::consteval;
+
+/// Item evaluated by a basic block
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Element<'a> {
+    /// Local variable declaration, with its initialisers
+    Declaration(&'a ast::Declaration),
+    /// Condition of a loop, which may declare a variable
+    Condition(&'a ast::Condition),
+    /// Expression statement, `if` or `switch` condition, `for` update or returned value
+    Expr(&'a ast::Expr),
+}
+
+impl Element<'_> {
+    /// Span of the element
+    pub fn span(&self) -> Option<NodeSpan> {
+        match self {
+            Self::Declaration(decl) => decl.span,
+            Self::Condition(cond) => cond.span,
+            Self::Expr(expr) => expr.span,
+        }
+    }
+}
+
+/// How control leaves a basic block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Terminator {
+    /// Jump to the only successor
+    Goto,
+    /// Branch to one of the successors, depending on a condition evaluated by the block
+    Branch,
+    /// `return` statement
+    Return(Option<NodeSpan>),
+    /// `discard` statement
+    Discard(Option<NodeSpan>),
+    /// End of the function body, without a `return` statement
+    FallOff,
+}
+
+/// Sequence of elements evaluated without branching
+#[derive(Clone, Debug, PartialEq)]
+pub struct BasicBlock<'a> {
+    /// Elements, in evaluation order
+    pub elements: Vec<Element<'a>>,
+    /// How control leaves the block
+    pub terminator: Terminator,
+    /// Indices of the blocks control may go to next
+    pub successors: Vec<usize>,
+}
+
+/// Control-flow graph of a function body
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cfg<'a> {
+    /// Basic blocks, starting with [Cfg::ENTRY] and [Cfg::EXIT]
+    pub blocks: Vec<BasicBlock<'a>>,
+    /// Statements of the body, in source order, with the index of the block they start in.
+    /// `case` labels are not included.
+    pub statements: Vec<(&'a ast::Statement, usize)>,
+}
+
+impl<'a> Cfg<'a> {
+    /// Index of the block control enters the function body through
+    pub const ENTRY: usize = 0;
+    /// Index of the block control leaves the function through. It has no elements nor
+    /// successors.
+    pub const EXIT: usize = 1;
+
+    /// Build the control-flow graph of a function body
+    pub fn build(body: &'a ast::CompoundStatement, symbols: &SymbolTable<'_>) -> Self {
+        let mut builder = Builder {
+            symbols,
+            cfg: Cfg {
+                blocks: Vec::new(),
+                statements: Vec::new(),
+            },
+            current: 0,
+            breaks: Vec::new(),
+            continues: Vec::new(),
+        };
+
+        builder.new_block();
+        builder.new_block();
+        for statement in &body.statement_list {
+            builder.statement(statement);
+        }
+        builder.end(Terminator::FallOff, vec![Self::EXIT]);
+
+        builder.cfg
+    }
+
+    /// `true` for every block which can be reached from the given one, including itself
+    pub fn reachable_from(&self, start: usize) -> Vec<bool> {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut stack = vec![start];
+        while let Some(block) = stack.pop() {
+            if !std::mem::replace(&mut reachable[block], true) {
+                stack.extend(&self.blocks[block].successors);
+            }
+        }
+
+        reachable
+    }
+
+    /// `true` for every block which can be reached from the entry
+    pub fn reachable(&self) -> Vec<bool> {
+        self.reachable_from(Self::ENTRY)
+    }
+
+    /// Indices of the predecessors of every block
+    pub fn predecessors(&self) -> Vec<Vec<usize>> {
+        let mut predecessors = vec![Vec::new(); self.blocks.len()];
+        for (index, block) in self.blocks.iter().enumerate() {
+            for &successor in &block.successors {
+                predecessors[successor].push(index);
+            }
+        }
+
+        predecessors
+    }
+}
+
+struct Builder<'s, 'a, 'b> {
+    symbols: &'s SymbolTable<'b>,
+    cfg: Cfg<'a>,
+    /// Block being filled
+    current: usize,
+    /// Blocks `break` jumps to, innermost last
+    breaks: Vec<usize>,
+    /// Blocks `continue` jumps to, innermost last
+    continues: Vec<usize>,
+}
+
+impl<'a> Builder<'_, 'a, '_> {
+    fn new_block(&mut self) -> usize {
+        self.cfg.blocks.push(BasicBlock {
+            elements: Vec::new(),
+            terminator: Terminator::Goto,
+            successors: Vec::new(),
+        });
+        self.cfg.blocks.len() - 1
+    }
+
+    fn push(&mut self, element: Element<'a>) {
+        self.cfg.blocks[self.current].elements.push(element);
+    }
+
+    /// End the current block, and continue in a new one with no predecessors
+    fn end(&mut self, terminator: Terminator, successors: Vec<usize>) {
+        let block = &mut self.cfg.blocks[self.current];
+        block.terminator = terminator;
+        block.successors = successors;
+        self.current = self.new_block();
+    }
+
+    /// End the current block with a jump, and continue in the target
+    fn goto(&mut self, target: usize) {
+        let block = &mut self.cfg.blocks[self.current];
+        block.terminator = Terminator::Goto;
+        block.successors = vec![target];
+        self.current = target;
+    }
+
+    /// End the current block with a branch
+    fn branch(&mut self, successors: Vec<usize>) {
+        let block = &mut self.cfg.blocks[self.current];
+        block.terminator = if successors.len() > 1 {
+            Terminator::Branch
+        } else {
+            Terminator::Goto
+        };
+        block.successors = successors;
+    }
+
+    /// `true` if an expression is a true constant
+    fn is_true(&self, expr: &ast::Expr) -> bool {
+        consteval::eval(expr, self.symbols).and_then(|value| value.as_bool()) == Some(true)
+    }
+
+    /// Blocks the header of a `while` or `for` loop branches to
+    fn loop_branch(&self, cond: Option<&ast::Condition>, body: usize, exit: usize) -> Vec<usize> {
+        let always = match cond.map(|cond| &**cond) {
+            None => true,
+            Some(ast::ConditionData::Expr(expr)) => self.is_true(expr),
+            Some(ast::ConditionData::Assignment(..)) => false,
+        };
+
+        if always {
+            vec![body]
+        } else {
+            vec![body, exit]
+        }
+    }
+
+    fn statement(&mut self, statement: &'a ast::Statement) {
+        if !matches!(**statement, ast::StatementData::CaseLabel(_)) {
+            self.cfg.statements.push((statement, self.current));
+        }
+
+        match &**statement {
+            ast::StatementData::Declaration(decl) => self.push(Element::Declaration(decl)),
+            ast::StatementData::Expression(expr) => {
+                if let Some(expr) = &expr.0 {
+                    self.push(Element::Expr(expr));
+                }
+            }
+            ast::StatementData::Selection(selection) => {
+                self.push(Element::Expr(&selection.cond));
+                let then = self.new_block();
+                let join = self.new_block();
+
+                match &*selection.rest {
+                    ast::SelectionRestStatementData::Statement(body) => {
+                        self.branch(vec![then, join]);
+                        self.current = then;
+                        self.statement(body);
+                        self.goto(join);
+                    }
+                    ast::SelectionRestStatementData::Else(body, otherwise) => {
+                        let other = self.new_block();
+                        self.branch(vec![then, other]);
+                        self.current = then;
+                        self.statement(body);
+                        self.goto(join);
+                        self.current = other;
+                        self.statement(otherwise);
+                        self.goto(join);
+                    }
+                }
+            }
+            ast::StatementData::Switch(switch) => {
+                self.push(Element::Expr(&switch.head));
+                let head = self.current;
+                let exit = self.new_block();
+
+                // Statements before the first label are never executed
+                self.current = self.new_block();
+                self.breaks.push(exit);
+
+                let mut targets = Vec::new();
+                let mut default = false;
+                for statement in &switch.body {
+                    if let ast::StatementData::CaseLabel(label) = &**statement {
+                        default |= matches!(**label, ast::CaseLabelData::Def);
+                        let case = self.new_block();
+                        self.goto(case);
+                        targets.push(case);
+                    } else {
+                        self.statement(statement);
+                    }
+                }
+
+                self.breaks.pop();
+                self.goto(exit);
+
+                if !default {
+                    targets.push(exit);
+                }
+
+                self.current = head;
+                self.branch(targets);
+                self.current = exit;
+            }
+            ast::StatementData::CaseLabel(_) => {}
+            ast::StatementData::Iteration(iteration) => match &**iteration {
+                ast::IterationStatementData::While(cond, body) => {
+                    let header = self.new_block();
+                    let start = self.new_block();
+                    let exit = self.new_block();
+
+                    self.goto(header);
+                    self.push(Element::Condition(cond));
+                    let successors = self.loop_branch(Some(cond), start, exit);
+                    self.branch(successors);
+
+                    self.current = start;
+                    self.loop_body(body, exit, header);
+                    self.goto(header);
+                    self.current = exit;
+                }
+                ast::IterationStatementData::DoWhile(body, cond) => {
+                    let start = self.new_block();
+                    let latch = self.new_block();
+                    let exit = self.new_block();
+
+                    self.goto(start);
+                    self.loop_body(body, exit, latch);
+                    self.goto(latch);
+                    self.push(Element::Expr(cond));
+                    let always = self.is_true(cond);
+                    let successors = if always {
+                        vec![start]
+                    } else {
+                        vec![start, exit]
+                    };
+                    self.branch(successors);
+                    self.current = exit;
+                }
+                ast::IterationStatementData::For(init, rest, body) => {
+                    match &**init {
+                        ast::ForInitStatementData::Expression(expr) => {
+                            if let Some(expr) = expr {
+                                self.push(Element::Expr(expr));
+                            }
+                        }
+                        ast::ForInitStatementData::Declaration(decl) => {
+                            self.push(Element::Declaration(decl))
+                        }
+                    }
+
+                    let header = self.new_block();
+                    let start = self.new_block();
+                    let latch = self.new_block();
+                    let exit = self.new_block();
+
+                    self.goto(header);
+                    if let Some(cond) = &rest.condition {
+                        self.push(Element::Condition(cond));
+                    }
+                    let successors = self.loop_branch(rest.condition.as_ref(), start, exit);
+                    self.branch(successors);
+
+                    self.current = start;
+                    self.loop_body(body, exit, latch);
+                    self.goto(latch);
+                    if let Some(post) = &rest.post_expr {
+                        self.push(Element::Expr(post));
+                    }
+                    self.goto(header);
+                    self.current = exit;
+                }
+            },
+            ast::StatementData::Jump(jump) => match &**jump {
+                ast::JumpStatementData::Continue => match self.continues.last() {
+                    Some(&target) => self.end(Terminator::Goto, vec![target]),
+                    None => self.end(Terminator::Goto, Vec::new()),
+                },
+                ast::JumpStatementData::Break => match self.breaks.last() {
+                    Some(&target) => self.end(Terminator::Goto, vec![target]),
+                    None => self.end(Terminator::Goto, Vec::new()),
+                },
+                ast::JumpStatementData::Return(expr) => {
+                    if let Some(expr) = expr {
+                        self.push(Element::Expr(expr));
+                    }
+                    self.end(Terminator::Return(jump.span), vec![Cfg::EXIT]);
+                }
+                ast::JumpStatementData::Discard => {
+                    self.end(Terminator::Discard(jump.span), vec![Cfg::EXIT]);
+                }
+            },
+            ast::StatementData::Compound(compound) => {
+                for statement in &compound.statement_list {
+                    self.statement(statement);
+                }
+            }
+            ast::StatementData::Attributed(_, statement) => self.statement(statement),
+        }
+    }
+
+    fn loop_body(&mut self, body: &'a ast::Statement, exit: usize, next: usize) {
+        self.breaks.push(exit);
+        self.continues.push(next);
+        self.statement(body);
+        self.breaks.pop();
+        self.continues.pop();
+    }
+}
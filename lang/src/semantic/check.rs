@@ -0,0 +1,1550 @@
+//! Expression type checking
+//!
+//! [check] computes the type of every expression of a translation unit, following the HLSL
+//! rules: scalars are splatted to the shape of the other operand, vectors and matrices are
+//! truncated to the smallest operand (with a warning), integers are promoted to floating-point,
+//! and unsuffixed literals adopt the type of the other operand. Calls are resolved to a single
+//! overload, following the [ranking rules](super::overload) of DXC. The result is a [TypeTable],
+//! which also records the implicit conversions a transpiler needs to make explicit.
+//!
+//! Array sizes, case labels and `[unroll(n)]` counts must be
+//! [integer constants](super::consteval), and the initialisers of constants are evaluated to report
+//! overflows and divisions by zero. Bitfields must have an integer scalar type.
+//!
+//! Since HLSL 2021, `&&` and `||` short-circuit and only accept scalar operands. [check] follows
+//! the default language version, use [check_version] for sources written for an earlier one.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, parse::Parsable, resolver, semantic::check};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     "float3 f(float4 v, float4x4 m) { return mul(m, v) * 2; }",
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//! let table = check::check(&tu, &symbols);
+//!
+//! assert_eq!(
+//!     table.diagnostics()[0].to_string(),
+//!     "implicit truncation of `float4` to `float3`"
+//! );
+//! ```
+
+use std::{collections::HashMap, marker::PhantomData};
+
+use crate::{
+    ast::{self, SmolStr},
+    parse::HlslVersion,
+    resolver::{Declarator, Resolution, SymbolId, SymbolKind, SymbolTable},
+};
+
+use super::{
+    consteval::Evaluator,
+    intrinsics::{self, Intrinsic},
+    overload::{self, Call, Callee, Selection},
+    types::{conversion, Conversion, ScalarType, Shape, Type},
+    Diagnostic, DiagnosticKind, Severity,
+};
+
+/// Types computed for the expressions of a translation unit. Expressions are identified by
+/// their address, so the table borrows the translation unit it was computed for.
+#[derive(Clone, Debug, Default)]
+pub struct TypeTable<'a> {
+    types: HashMap<usize, Type>,
+    conversions: HashMap<usize, Type>,
+    calls: HashMap<usize, Call>,
+    diagnostics: Vec<Diagnostic>,
+    tu: PhantomData<&'a ast::TranslationUnit>,
+}
+
+/// Address of a node, used to identify it in side tables
+fn addr<T>(node: &T) -> usize {
+    node as *const T as usize
+}
+
+impl TypeTable<'_> {
+    /// Type of an expression of the checked translation unit
+    pub fn type_of(&self, expr: &ast::Expr) -> Option<&Type> {
+        self.types.get(&addr(expr))
+    }
+
+    /// Type an expression is implicitly converted to, if it differs from its own type
+    pub fn converted_to(&self, expr: &ast::Expr) -> Option<&Type> {
+        self.conversions.get(&addr(expr))
+    }
+
+    /// Overload selected by a call expression, if it calls a function
+    pub fn call(&self, expr: &ast::Expr) -> Option<&Call> {
+        self.calls.get(&addr(expr))
+    }
+
+    /// Diagnostics raised while type checking, in source order
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+/// Type of a declared variable, parameter or field, or return type of a function
+pub fn symbol_type(symbols: &SymbolTable<'_>, id: SymbolId) -> Type {
+    let symbol = symbols.symbol(id);
+    match symbol.declarator {
+        Declarator::SamplerState(_) => Type::from_name("SamplerState").unwrap_or(Type::Error),
+        declarator => match declarator.ty() {
+            Some((ty, array)) => Type::from_specifier(ty, array, symbols),
+            None => Type::Error,
+        },
+    }
+}
+
+/// Type of a function parameter
+fn parameter_type(symbols: &SymbolTable<'_>, param: &ast::FunctionParameterDeclaration) -> Type {
+    match &**param {
+        ast::FunctionParameterDeclarationData::Named(_, declarator) => Type::from_specifier(
+            &declarator.ty,
+            declarator.ident.array_spec.as_ref(),
+            symbols,
+        ),
+        ast::FunctionParameterDeclarationData::Unnamed(_, ty) => {
+            Type::from_specifier(ty, None, symbols)
+        }
+    }
+}
+
+/// Human-readable signature of a user-defined function, for diagnostics
+pub(crate) fn signature(symbols: &SymbolTable<'_>, id: SymbolId) -> String {
+    let symbol = symbols.symbol(id);
+    let Some(proto) = symbol.declarator.prototype() else {
+        return symbol.name.to_string();
+    };
+
+    let params: Vec<_> = proto
+        .parameters
+        .iter()
+        .map(|param| match &**param {
+            ast::FunctionParameterDeclarationData::Named(_, declarator) => format!(
+                "{} {}",
+                parameter_type(symbols, param),
+                declarator.ident.ident.as_str()
+            ),
+            ast::FunctionParameterDeclarationData::Unnamed(_, _) => {
+                parameter_type(symbols, param).to_string()
+            }
+        })
+        .collect();
+
+    format!(
+        "{} {}({})",
+        symbol_type(symbols, id),
+        symbol.name,
+        params.join(", ")
+    )
+}
+
+/// Type check all the expressions in a translation unit
+pub fn check<'a>(tu: &'a ast::TranslationUnit, symbols: &SymbolTable<'a>) -> TypeTable<'a> {
+    check_version(tu, symbols, HlslVersion::default())
+}
+
+/// Type check all the expressions in a translation unit written for the given HLSL version
+pub fn check_version<'a>(
+    tu: &'a ast::TranslationUnit,
+    symbols: &SymbolTable<'a>,
+    version: HlslVersion,
+) -> TypeTable<'a> {
+    let mut checker = Checker {
+        symbols,
+        version,
+        table: TypeTable::default(),
+        return_type: None,
+        constants: Evaluator::new(symbols),
+    };
+
+    for decl in &tu.0 {
+        match &**decl {
+            ast::ExternalDeclarationData::Preprocessor(_) => {}
+            ast::ExternalDeclarationData::FunctionDefinition(def) => {
+                checker.function(&def.prototype, &def.statement)
+            }
+            ast::ExternalDeclarationData::Declaration(decl) => checker.declaration(decl),
+            // Templates are only checked once instantiated
+            ast::ExternalDeclarationData::Template(_) => {}
+        }
+    }
+
+    checker.table
+}
+
+struct Checker<'s, 'a> {
+    symbols: &'s SymbolTable<'a>,
+    version: HlslVersion,
+    table: TypeTable<'a>,
+    return_type: Option<Type>,
+    constants: Evaluator<'s, 'a>,
+}
+
+impl<'s, 'a> Checker<'s, 'a> {
+    fn error(&mut self, kind: DiagnosticKind, expr: &ast::Expr) {
+        self.table
+            .diagnostics
+            .push(Diagnostic::new(kind, expr.span));
+    }
+
+    fn function(&mut self, proto: &ast::FunctionPrototype, body: &ast::CompoundStatement) {
+        self.prototype(proto);
+
+        let return_type = Type::from_specifier(&proto.ty.ty, None, self.symbols);
+        let previous = self.return_type.replace(return_type);
+        for statement in &body.statement_list {
+            self.statement(statement);
+        }
+        self.return_type = previous;
+    }
+
+    fn prototype(&mut self, proto: &ast::FunctionPrototype) {
+        for param in &proto.parameters {
+            if let ast::FunctionParameterDeclarationData::Named(_, declarator) = &**param {
+                if let Some(init) = &declarator.default_value {
+                    let ty = Type::from_specifier(
+                        &declarator.ty,
+                        declarator.ident.array_spec.as_ref(),
+                        self.symbols,
+                    );
+                    self.initializer(init, &ty);
+                }
+            }
+        }
+    }
+
+    fn declaration(&mut self, decl: &ast::Declaration) {
+        match &**decl {
+            ast::DeclarationData::InitDeclaratorList(list) => {
+                let head = &list.head;
+                if let ast::TypeSpecifierNonArrayData::Struct(spec) = &*head.ty.ty.ty {
+                    self.bitfields(spec);
+                    self.struct_methods(spec);
+                }
+
+                self.array_sizes(head.ty.ty.array_specifier.as_ref());
+                self.array_sizes(head.array_specifier.as_ref());
+                if let Some(name) = &head.name {
+                    self.declarator(name, head.initializer.as_ref());
+                }
+
+                for declarator in &list.tail {
+                    self.array_sizes(declarator.ident.array_spec.as_ref());
+                    self.declarator(&declarator.ident.ident, declarator.initializer.as_ref());
+                }
+            }
+            ast::DeclarationData::FunctionPrototype(proto) => self.prototype(proto),
+            _ => {}
+        }
+    }
+
+    fn bitfields(&mut self, spec: &ast::StructSpecifier) {
+        for field in &spec.fields {
+            for ident in &field.identifiers {
+                if ident.bit_width.is_none() {
+                    continue;
+                }
+
+                let ty = Type::from_specifier(&field.ty, None, self.symbols);
+                if !ty.is_error() && !matches!(ty, Type::Scalar(scalar) if scalar.is_integer()) {
+                    self.table.diagnostics.push(Diagnostic::new(
+                        DiagnosticKind::InvalidBitfieldType {
+                            name: ident.ident.0.clone(),
+                            ty,
+                        },
+                        ident.ident.span,
+                    ));
+                }
+            }
+        }
+    }
+
+    fn struct_methods(&mut self, spec: &ast::StructSpecifier) {
+        for method in &spec.methods {
+            match &method.statement {
+                Some(body) => self.function(&method.prototype, body),
+                None => self.prototype(&method.prototype),
+            }
+        }
+    }
+
+    fn declarator(&mut self, name: &ast::Identifier, init: Option<&ast::Initializer>) {
+        if let Some(init) = init {
+            let id = self.symbols.declared_by(name);
+            let ty = match id {
+                Some(id) => symbol_type(self.symbols, id),
+                None => Type::Error,
+            };
+
+            self.initializer(init, &ty);
+
+            // Report overflows in the initialisers of constants
+            if let Some(id) = id {
+                self.constants.symbol(id);
+                let diagnostics = self.constants.take_diagnostics();
+                self.table.diagnostics.extend(diagnostics);
+            }
+        }
+    }
+
+    fn array_sizes(&mut self, array: Option<&ast::ArraySpecifier>) {
+        for dim in array.into_iter().flat_map(|array| &array.dimensions) {
+            if let ast::ArraySpecifierDimensionData::ExplicitlySized(size) = &**dim {
+                let ty = self.expr(size);
+                self.integer_constant(size, &ty, "array size");
+            }
+        }
+    }
+
+    /// Check that an expression is an integer constant
+    fn integer_constant(&mut self, expr: &ast::Expr, ty: &Type, what: &'static str) {
+        if ty.is_error() {
+            return;
+        }
+
+        let value = self.constants.eval(expr);
+        let diagnostics = self.constants.take_diagnostics();
+        let failed = diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity() == Severity::Error);
+        self.table.diagnostics.extend(diagnostics);
+
+        if !failed && value.and_then(|value| value.as_int()).is_none() {
+            self.error(DiagnosticKind::NotIntegerConstant(what), expr);
+        }
+    }
+
+    fn initializer(&mut self, init: &ast::Initializer, target: &Type) {
+        match &**init {
+            ast::InitializerData::Simple(expr) => {
+                let ty = self.expr(expr);
+                self.convert(expr, &ty, target);
+            }
+            ast::InitializerData::List(items) => {
+                // Initializer lists are flattened for anything but arrays
+                let element = match target {
+                    Type::Array(element, _) => (**element).clone(),
+                    _ => Type::Error,
+                };
+
+                for item in items {
+                    self.initializer(item, &element);
+                }
+            }
+        }
+    }
+
+    /// Check that a value of type `from` implicitly converts to `to`
+    fn convert(&mut self, expr: &ast::Expr, from: &Type, to: &Type) {
+        match conversion(from, to) {
+            Conversion::Identity => {}
+            Conversion::Implicit => {
+                if !from.is_error() && !to.is_error() {
+                    self.table.conversions.insert(addr(expr), to.clone());
+                }
+            }
+            Conversion::Truncation => {
+                self.table.conversions.insert(addr(expr), to.clone());
+                self.error(
+                    DiagnosticKind::ImplicitTruncation {
+                        from: from.concrete(),
+                        to: to.clone(),
+                    },
+                    expr,
+                );
+            }
+            Conversion::Invalid => self.error(
+                DiagnosticKind::TypeMismatch {
+                    expected: to.clone(),
+                    found: from.concrete(),
+                },
+                expr,
+            ),
+        }
+    }
+
+    fn statement(&mut self, statement: &ast::Statement) {
+        match &**statement {
+            ast::StatementData::Declaration(decl) => self.declaration(decl),
+            ast::StatementData::Expression(expr) => {
+                if let Some(expr) = &expr.0 {
+                    self.expr(expr);
+                }
+            }
+            ast::StatementData::Selection(selection) => {
+                self.expr(&selection.cond);
+                match &*selection.rest {
+                    ast::SelectionRestStatementData::Statement(then) => self.statement(then),
+                    ast::SelectionRestStatementData::Else(then, otherwise) => {
+                        self.statement(then);
+                        self.statement(otherwise);
+                    }
+                }
+            }
+            ast::StatementData::Switch(switch) => {
+                self.expr(&switch.head);
+                for statement in &switch.body {
+                    self.statement(statement);
+                }
+            }
+            ast::StatementData::CaseLabel(label) => {
+                if let ast::CaseLabelData::Case(expr) = &**label {
+                    let ty = self.expr(expr);
+                    self.integer_constant(expr, &ty, "case label");
+                }
+            }
+            ast::StatementData::Iteration(iteration) => match &**iteration {
+                ast::IterationStatementData::While(cond, body) => {
+                    self.condition(cond);
+                    self.statement(body);
+                }
+                ast::IterationStatementData::DoWhile(body, cond) => {
+                    self.statement(body);
+                    self.expr(cond);
+                }
+                ast::IterationStatementData::For(init, rest, body) => {
+                    match &**init {
+                        ast::ForInitStatementData::Expression(Some(expr)) => {
+                            self.expr(expr);
+                        }
+                        ast::ForInitStatementData::Expression(None) => {}
+                        ast::ForInitStatementData::Declaration(decl) => self.declaration(decl),
+                    }
+
+                    if let Some(cond) = &rest.condition {
+                        self.condition(cond);
+                    }
+
+                    if let Some(post) = &rest.post_expr {
+                        self.expr(post);
+                    }
+
+                    self.statement(body);
+                }
+            },
+            ast::StatementData::Jump(jump) => {
+                if let ast::JumpStatementData::Return(Some(expr)) = &**jump {
+                    let ty = self.expr(expr);
+                    if let Some(target) = self.return_type.clone() {
+                        self.convert(expr, &ty, &target);
+                    }
+                }
+            }
+            ast::StatementData::Compound(compound) => {
+                for statement in &compound.statement_list {
+                    self.statement(statement);
+                }
+            }
+            ast::StatementData::Attributed(attributes, statement) => {
+                for attr in attributes {
+                    self.statement_attribute(attr);
+                }
+                self.statement(statement);
+            }
+        }
+    }
+
+    /// Check the arguments of a statement attribute, such as the count of `[unroll(4)]`
+    fn statement_attribute(&mut self, attr: &ast::Attribute) {
+        let unroll =
+            attr.spec.namespace.is_none() && attr.spec.name.as_str().eq_ignore_ascii_case("unroll");
+        for param in attr.spec.params.iter().flatten() {
+            let ty = self.expr(param);
+            if unroll {
+                self.integer_constant(param, &ty, "unroll count");
+            }
+        }
+    }
+
+    fn condition(&mut self, cond: &ast::Condition) {
+        match &**cond {
+            ast::ConditionData::Expr(expr) => {
+                self.expr(expr);
+            }
+            ast::ConditionData::Assignment(_, name, init) => self.declarator(name, Some(init)),
+        }
+    }
+
+    /// Compute, record and return the type of an expression
+    fn expr(&mut self, expr: &ast::Expr) -> Type {
+        let ty = self.expr_type(expr);
+        self.table.types.insert(addr(expr), ty.clone());
+        ty
+    }
+
+    fn expr_type(&mut self, expr: &ast::Expr) -> Type {
+        match &**expr {
+            ast::ExprData::Variable(ident) => match self.symbols.resolution(ident) {
+                Some(Resolution::Symbol(id)) => match self.symbols.symbol(*id).kind {
+                    SymbolKind::Function | SymbolKind::Struct | SymbolKind::CBuffer => Type::Error,
+                    _ => symbol_type(self.symbols, *id),
+                },
+                Some(Resolution::Overloads(_)) => Type::Error,
+                Some(Resolution::Unresolved) | None => {
+                    self.error(DiagnosticKind::UndeclaredIdentifier(ident.0.clone()), expr);
+                    Type::Error
+                }
+            },
+            ast::ExprData::IntConst(_) | ast::ExprData::CharConst(_) => {
+                Type::Scalar(ScalarType::LiteralInt)
+            }
+            ast::ExprData::UIntConst(_) => Type::UINT,
+            ast::ExprData::BoolConst(_) => Type::BOOL,
+            ast::ExprData::FloatConst(_) => Type::Scalar(ScalarType::LiteralFloat),
+            ast::ExprData::DoubleConst(_) => Type::Scalar(ScalarType::Double),
+            ast::ExprData::StringConst(_) | ast::ExprData::StringConcat(_) => Type::String,
+            ast::ExprData::Unary(op, e) => {
+                let ty = self.expr(e);
+                self.unary(expr, op, ty)
+            }
+            ast::ExprData::PostInc(e) | ast::ExprData::PostDec(e) => self.expr(e),
+            ast::ExprData::Cast(ty, e) => {
+                let to = Type::from_specifier(ty, None, self.symbols);
+                let from = self.expr(e);
+                self.cast(expr, from, to)
+            }
+            ast::ExprData::Binary(op, l, r) => {
+                let left = self.expr(l);
+                let right = self.expr(r);
+                self.binary(expr, op, left, right)
+            }
+            ast::ExprData::Ternary(c, t, f) => {
+                let cond = self.expr(c);
+                let then = self.expr(t);
+                let otherwise = self.expr(f);
+                self.ternary(expr, cond, then, otherwise)
+            }
+            ast::ExprData::Assignment(l, op, r) => {
+                let left = self.expr(l);
+                let right = self.expr(r);
+
+                let value = match binary_op_of_assignment(op) {
+                    Some(op) => self.binary(expr, &op.into(), left.clone(), right),
+                    None => right,
+                };
+                self.convert(r, &value, &left);
+                left
+            }
+            ast::ExprData::Bracket(base, index) => {
+                let ty = self.expr(base);
+                self.expr(index);
+
+                if ty.is_error() {
+                    return Type::Error;
+                }
+
+                ty.index().unwrap_or_else(|| {
+                    self.error(DiagnosticKind::NotIndexable(ty), expr);
+                    Type::Error
+                })
+            }
+            ast::ExprData::Dot(base, field) => {
+                let ty = self.expr(base);
+                self.member(expr, &ty, field)
+            }
+            ast::ExprData::FunCall(fun, args) => self.call(expr, fun, args),
+            ast::ExprData::Comma(l, r) => {
+                self.expr(l);
+                self.expr(r)
+            }
+        }
+    }
+
+    /// Return type of a user-defined operator on a struct
+    fn operator(&self, ty: &Type, op: &str) -> Option<Type> {
+        let Type::Struct(id, _) = ty else {
+            return None;
+        };
+
+        let resolution = self.symbols.member(*id, &format!("operator{}", op))?;
+        resolution
+            .symbols()
+            .first()
+            .map(|id| symbol_type(self.symbols, *id))
+    }
+
+    fn unary(&mut self, expr: &ast::Expr, op: &ast::UnaryOp, ty: Type) -> Type {
+        let symbol = ast::OverloadedOperatorData::Unary(op.clone()).symbol();
+        if let Some(result) = self.operator(&ty, symbol) {
+            return result;
+        }
+
+        let Some(scalar) = ty.scalar() else {
+            if !ty.is_error() {
+                self.error(DiagnosticKind::InvalidOperand { op: symbol, ty }, expr);
+            }
+
+            return Type::Error;
+        };
+
+        match **op {
+            ast::UnaryOpData::Not => ty.with_scalar(ScalarType::Bool),
+            ast::UnaryOpData::Complement if scalar.is_float() => {
+                self.error(DiagnosticKind::InvalidOperand { op: symbol, ty }, expr);
+                Type::Error
+            }
+            ast::UnaryOpData::Inc | ast::UnaryOpData::Dec => ty,
+            _ if scalar == ScalarType::Bool => ty.with_scalar(ScalarType::Int),
+            _ => ty,
+        }
+    }
+
+    fn binary(&mut self, expr: &ast::Expr, op: &ast::BinaryOp, left: Type, right: Type) -> Type {
+        let symbol = ast::OverloadedOperatorData::Binary(op.clone()).symbol();
+        if let Some(result) = self.operator(&left, symbol) {
+            return result;
+        }
+
+        if left.is_error() || right.is_error() {
+            return Type::Error;
+        }
+
+        let invalid = |left: Type, right: Type| DiagnosticKind::InvalidOperands {
+            op: symbol,
+            left: left.concrete(),
+            right: right.concrete(),
+        };
+
+        let (Some(ls), Some(rs)) = (left.scalar(), right.scalar()) else {
+            self.error(invalid(left, right), expr);
+            return Type::Error;
+        };
+
+        let Some(shape) = self.common_shape(expr, &left, &right) else {
+            self.error(invalid(left, right), expr);
+            return Type::Error;
+        };
+
+        use ast::BinaryOpData as B;
+        if matches!(**op, B::And | B::Or)
+            && self.version.short_circuits_logical_ops()
+            && shape != Shape::Scalar
+        {
+            let ty = if left.shape() == Some(Shape::Scalar) {
+                right
+            } else {
+                left
+            };
+
+            let intrinsic = if **op == B::And { "and" } else { "or" };
+            self.error(
+                DiagnosticKind::NonScalarLogicalOperand {
+                    op: symbol,
+                    ty: ty.concrete(),
+                    intrinsic,
+                },
+                expr,
+            );
+            return Type::Error;
+        }
+
+        let scalar = match **op {
+            B::Add | B::Sub | B::Mult | B::Div | B::Mod => ls.common(rs),
+            B::BitAnd | B::BitOr | B::BitXor | B::LShift | B::RShift => {
+                if ls.is_float() || rs.is_float() {
+                    self.error(invalid(left, right), expr);
+                    return Type::Error;
+                }
+
+                match **op {
+                    B::LShift | B::RShift => ls.common(ls),
+                    _ => ls.common(rs),
+                }
+            }
+            B::Equal | B::NonEqual | B::Lt | B::Gt | B::Lte | B::Gte | B::And | B::Or | B::Xor => {
+                ScalarType::Bool
+            }
+        };
+
+        Type::numeric(scalar, shape)
+    }
+
+    /// Shape of the result of a component-wise operation, warning about truncations
+    fn common_shape(&mut self, expr: &ast::Expr, left: &Type, right: &Type) -> Option<Shape> {
+        let (l, r) = (left.shape()?, right.shape()?);
+        let shape = l.common(r)?;
+
+        for (operand, operand_shape) in [(left, l), (right, r)] {
+            if operand_shape != shape && operand_shape.len() > 1 {
+                self.error(
+                    DiagnosticKind::ImplicitTruncation {
+                        from: operand.concrete(),
+                        to: Type::numeric(operand.scalar()?.concrete(), shape),
+                    },
+                    expr,
+                );
+            }
+        }
+
+        Some(shape)
+    }
+
+    fn ternary(&mut self, expr: &ast::Expr, cond: Type, then: Type, otherwise: Type) -> Type {
+        if then.is_error() || otherwise.is_error() {
+            return Type::Error;
+        }
+
+        let (Some(ts), Some(fs)) = (then.scalar(), otherwise.scalar()) else {
+            if then == otherwise {
+                return then;
+            }
+
+            self.error(
+                DiagnosticKind::TypeMismatch {
+                    expected: then,
+                    found: otherwise,
+                },
+                expr,
+            );
+            return Type::Error;
+        };
+
+        let Some(shape) = self.common_shape(expr, &then, &otherwise) else {
+            self.error(
+                DiagnosticKind::TypeMismatch {
+                    expected: then,
+                    found: otherwise,
+                },
+                expr,
+            );
+            return Type::Error;
+        };
+
+        // A vector condition selects component-wise
+        let shape = match cond.shape() {
+            Some(Shape::Vector(n)) if n > 1 && shape == Shape::Scalar => Shape::Vector(n),
+            _ => shape,
+        };
+
+        Type::numeric(ts.common(fs), shape)
+    }
+
+    fn cast(&mut self, expr: &ast::Expr, from: Type, to: Type) -> Type {
+        let valid = match (from.component_count(), to.component_count()) {
+            // Explicit casts may truncate, but not extend
+            (Some(n), Some(m)) => n == 1 || n >= m,
+            // Scalars can initialize any struct
+            (Some(1), None) => matches!(to, Type::Struct(_, _)),
+            _ => from.is_error() || to.is_error() || from == to,
+        };
+
+        if !valid {
+            self.error(
+                DiagnosticKind::InvalidCast {
+                    from: from.concrete(),
+                    to: to.clone(),
+                },
+                expr,
+            );
+        }
+
+        to
+    }
+
+    fn member(&mut self, expr: &ast::Expr, ty: &Type, field: &ast::Identifier) -> Type {
+        match ty {
+            Type::Struct(id, _) => match self.symbols.member(*id, field.as_str()) {
+                Some(Resolution::Symbol(member))
+                    if self.symbols.symbol(member).kind == SymbolKind::StructField =>
+                {
+                    symbol_type(self.symbols, member)
+                }
+                // Member functions are typed by the call expression
+                Some(Resolution::Symbol(_)) | Some(Resolution::Overloads(_)) => Type::Error,
+                Some(Resolution::Unresolved) | None => {
+                    self.error(
+                        DiagnosticKind::UnknownMember {
+                            member: field.0.clone(),
+                            ty: ty.clone(),
+                        },
+                        expr,
+                    );
+                    Type::Error
+                }
+            },
+            Type::Scalar(_) | Type::Vector(_, _) | Type::Matrix(_, _, _) => {
+                match swizzle(ty, field.as_str()) {
+                    Some(ty) => ty,
+                    None => {
+                        self.error(
+                            DiagnosticKind::InvalidSwizzle {
+                                swizzle: field.0.clone(),
+                                ty: ty.concrete(),
+                            },
+                            expr,
+                        );
+                        Type::Error
+                    }
+                }
+            }
+            _ => Type::Error,
+        }
+    }
+
+    fn call(&mut self, expr: &ast::Expr, fun: &ast::FunIdentifier, args: &[ast::Expr]) -> Type {
+        let arg_types: Vec<_> = args.iter().map(|arg| self.expr(arg)).collect();
+
+        match &**fun {
+            ast::FunIdentifierData::TypeSpecifier(ty) => {
+                let ty = Type::from_specifier(ty, None, self.symbols);
+                self.constructor(expr, ty, &arg_types)
+            }
+            ast::FunIdentifierData::Expr(callee) => match &***callee {
+                ast::ExprData::Variable(name) => match self.symbols.resolution(name) {
+                    Some(resolution @ (Resolution::Symbol(_) | Resolution::Overloads(_))) => {
+                        self.user_call(expr, name, resolution.symbols(), args, &arg_types)
+                    }
+                    _ => match intrinsics::intrinsic(name.as_str()) {
+                        Some(intrinsic) => {
+                            self.intrinsic_call(expr, intrinsic, None, args, &arg_types)
+                        }
+                        None => {
+                            self.error(
+                                DiagnosticKind::UndeclaredIdentifier(name.0.clone()),
+                                callee,
+                            );
+                            Type::Error
+                        }
+                    },
+                },
+                ast::ExprData::Dot(object, method) => {
+                    let ty = self.expr(object);
+                    match &ty {
+                        Type::Struct(id, _) => {
+                            if let Some(
+                                resolution @ (Resolution::Symbol(_) | Resolution::Overloads(_)),
+                            ) = self.symbols.member(*id, method.as_str())
+                            {
+                                return self.user_call(
+                                    expr,
+                                    method,
+                                    resolution.symbols(),
+                                    args,
+                                    &arg_types,
+                                );
+                            }
+                        }
+                        Type::Object(kind, _) => {
+                            if let Some(intrinsic) = intrinsics::method(*kind, method.as_str()) {
+                                return self.intrinsic_call(
+                                    expr,
+                                    intrinsic,
+                                    Some(&ty),
+                                    args,
+                                    &arg_types,
+                                );
+                            }
+                        }
+                        _ => return Type::Error,
+                    }
+
+                    self.error(
+                        DiagnosticKind::UnknownMember {
+                            member: method.0.clone(),
+                            ty,
+                        },
+                        callee,
+                    );
+                    Type::Error
+                }
+                _ => {
+                    self.expr(callee);
+                    Type::Error
+                }
+            },
+        }
+    }
+
+    /// Resolve a call to a user-defined function or method among its overloads
+    fn user_call(
+        &mut self,
+        expr: &ast::Expr,
+        name: &ast::Identifier,
+        candidates: &[SymbolId],
+        args: &[ast::Expr],
+        arg_types: &[Type],
+    ) -> Type {
+        let viable = candidates
+            .iter()
+            .filter_map(|id| {
+                let proto = self.symbols.symbol(*id).declarator.prototype()?;
+                proto.omitted_arguments(args.len())?;
+
+                let params: Vec<_> = proto
+                    .parameters
+                    .iter()
+                    .take(args.len())
+                    .map(|param| parameter_type(self.symbols, param))
+                    .collect();
+                let ranks = overload::rank_arguments(arg_types, &params)?;
+                Some(((*id, params), ranks))
+            })
+            .collect();
+
+        match overload::select(viable) {
+            Selection::Unique((id, params)) => {
+                let ret = symbol_type(self.symbols, id);
+                self.record_call(expr, Callee::Function(id), args, arg_types, params, &ret);
+                ret
+            }
+            Selection::Ambiguous(ties) => {
+                let candidates = ties
+                    .iter()
+                    .map(|(id, _)| signature(self.symbols, *id))
+                    .collect();
+                self.overload_error(expr, name.0.clone(), arg_types, candidates, true)
+            }
+            Selection::NoMatch => {
+                let candidates = candidates
+                    .iter()
+                    .map(|id| signature(self.symbols, *id))
+                    .collect();
+                self.overload_error(expr, name.0.clone(), arg_types, candidates, false)
+            }
+        }
+    }
+
+    /// Resolve a call to an intrinsic function or object method among its overloads
+    fn intrinsic_call(
+        &mut self,
+        expr: &ast::Expr,
+        intrinsic: &'static Intrinsic,
+        object: Option<&Type>,
+        args: &[ast::Expr],
+        arg_types: &[Type],
+    ) -> Type {
+        let viable = intrinsic
+            .overloads
+            .iter()
+            .filter_map(|overload| {
+                let instance = overload::instantiate(overload, object, arg_types)?;
+                let ranks = overload::rank_arguments(arg_types, &instance.params)?;
+                Some(((overload, instance), ranks))
+            })
+            .collect();
+
+        match overload::select(viable) {
+            Selection::Unique((overload, instance)) => {
+                let callee = Callee::Intrinsic(intrinsic, overload);
+                self.record_call(
+                    expr,
+                    callee,
+                    args,
+                    arg_types,
+                    instance.params,
+                    &instance.ret,
+                );
+                instance.ret
+            }
+            Selection::Ambiguous(ties) => {
+                let candidates = ties
+                    .iter()
+                    .map(|(overload, _)| intrinsic.signature(overload))
+                    .collect();
+                self.overload_error(expr, intrinsic.name.into(), arg_types, candidates, true)
+            }
+            Selection::NoMatch => match arg_types {
+                // Give a more helpful message for the most common mistake
+                [left, right]
+                    if intrinsic.name == "mul" && left.is_numeric() && right.is_numeric() =>
+                {
+                    self.error(
+                        DiagnosticKind::IncompatibleMul {
+                            left: left.concrete(),
+                            right: right.concrete(),
+                        },
+                        expr,
+                    );
+                    Type::Error
+                }
+                _ => {
+                    let candidates = intrinsic.signatures().collect();
+                    self.overload_error(expr, intrinsic.name.into(), arg_types, candidates, false)
+                }
+            },
+        }
+    }
+
+    fn record_call(
+        &mut self,
+        expr: &ast::Expr,
+        callee: Callee,
+        args: &[ast::Expr],
+        arg_types: &[Type],
+        params: Vec<Type>,
+        ret: &Type,
+    ) {
+        for ((arg, ty), param) in args.iter().zip(arg_types).zip(&params) {
+            self.convert(arg, ty, param);
+        }
+
+        self.table.calls.insert(
+            addr(expr),
+            Call {
+                callee,
+                params,
+                ret: ret.clone(),
+            },
+        );
+    }
+
+    fn overload_error(
+        &mut self,
+        expr: &ast::Expr,
+        name: SmolStr,
+        arg_types: &[Type],
+        candidates: Vec<String>,
+        ambiguous: bool,
+    ) -> Type {
+        // Errors in the arguments have already been reported
+        if arg_types.iter().any(Type::is_error) {
+            return Type::Error;
+        }
+
+        let args = arg_types.iter().map(Type::concrete).collect();
+        let kind = if ambiguous {
+            DiagnosticKind::AmbiguousCall {
+                name,
+                args,
+                candidates,
+            }
+        } else {
+            DiagnosticKind::NoMatchingOverload {
+                name,
+                args,
+                candidates,
+            }
+        };
+
+        self.error(kind, expr);
+        Type::Error
+    }
+
+    fn constructor(&mut self, expr: &ast::Expr, ty: Type, args: &[Type]) -> Type {
+        let Some(expected) = ty.component_count() else {
+            return ty;
+        };
+
+        if args.iter().any(Type::is_error) {
+            return ty;
+        }
+
+        // A single scalar is splatted
+        if let [arg] = args {
+            if arg.component_count() == Some(1) {
+                return ty;
+            }
+        }
+
+        let found = args
+            .iter()
+            .map(|arg| arg.component_count().unwrap_or(1))
+            .sum::<usize>();
+
+        if found != expected {
+            self.error(
+                DiagnosticKind::ConstructorArity {
+                    ty: ty.clone(),
+                    expected,
+                    found,
+                },
+                expr,
+            );
+        }
+
+        ty
+    }
+}
+
+/// Binary operator applied by a compound assignment
+fn binary_op_of_assignment(op: &ast::AssignmentOp) -> Option<ast::BinaryOpData> {
+    use ast::{AssignmentOpData as A, BinaryOpData as B};
+
+    Some(match **op {
+        A::Equal => return None,
+        A::Mult => B::Mult,
+        A::Div => B::Div,
+        A::Mod => B::Mod,
+        A::Add => B::Add,
+        A::Sub => B::Sub,
+        A::LShift => B::LShift,
+        A::RShift => B::RShift,
+        A::And => B::BitAnd,
+        A::Xor => B::BitXor,
+        A::Or => B::BitOr,
+    })
+}
+
+/// Result type of a swizzle, e.g. `.xyz` on a vector or `._m00_m11` on a matrix
+pub fn swizzle(ty: &Type, swizzle: &str) -> Option<Type> {
+    let scalar = ty.scalar()?;
+
+    match swizzle_indices(ty.shape()?, swizzle)?.len() {
+        1 => Some(Type::Scalar(scalar)),
+        count @ 2..=4 => Some(Type::Vector(scalar, count as u8)),
+        _ => None,
+    }
+}
+
+/// Components selected by a swizzle on a value of the given shape, as row-major indices
+pub(crate) fn swizzle_indices(shape: Shape, swizzle: &str) -> Option<Vec<usize>> {
+    match shape {
+        Shape::Matrix(rows, cols) => {
+            let mut indices = Vec::new();
+            let mut rest = swizzle;
+            while !rest.is_empty() {
+                // `_m<row><col>` is zero-based, `_<row><col>` is one-based
+                let (digits, base) = match rest.strip_prefix("_m") {
+                    Some(digits) => (digits, 0),
+                    None => (rest.strip_prefix('_')?, 1),
+                };
+
+                let mut chars = digits.chars();
+                let row = chars.next()?.to_digit(10)?.checked_sub(base)?;
+                let col = chars.next()?.to_digit(10)?.checked_sub(base)?;
+                if row >= rows as u32 || col >= cols as u32 {
+                    return None;
+                }
+
+                rest = chars.as_str();
+                indices.push(row as usize * cols as usize + col as usize);
+            }
+
+            Some(indices)
+        }
+        shape => {
+            let size = shape.len();
+            let index = |c: char, set: &str| set.find(c).filter(|i| *i < size);
+
+            ["xyzw", "rgba"].iter().find_map(|set| {
+                swizzle
+                    .chars()
+                    .map(|c| index(c, set))
+                    .collect::<Option<Vec<_>>>()
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::Parsable, resolver};
+
+    /// Type of the expression returned by the last function, and all the diagnostics
+    fn check_return(src: &str) -> (String, Vec<String>) {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        let table = check(&tu, &symbols);
+
+        let def =
+            tu.0.iter()
+                .rev()
+                .find_map(|decl| match &**decl {
+                    ast::ExternalDeclarationData::FunctionDefinition(def) => Some(def),
+                    _ => None,
+                })
+                .unwrap();
+
+        let ty = def
+            .statement
+            .statement_list
+            .iter()
+            .find_map(|stmt| match &**stmt {
+                ast::StatementData::Jump(jump) => match &**jump {
+                    ast::JumpStatementData::Return(Some(expr)) => table.type_of(expr),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .unwrap();
+
+        (
+            ty.to_string(),
+            table
+                .diagnostics()
+                .iter()
+                .map(|d| format!("{}: {}", d.severity(), d))
+                .collect(),
+        )
+    }
+
+    fn ty(src: &str) -> String {
+        let (ty, diagnostics) = check_return(src);
+        assert_eq!(diagnostics, Vec::<String>::new());
+        ty
+    }
+
+    #[test]
+    fn check_promotion() {
+        assert_eq!(ty("float3 f(float3 v) { return v * 2; }"), "float3");
+        assert_eq!(ty("float f(int i, float x) { return i + x; }"), "float");
+        assert_eq!(ty("uint f(int i, uint u) { return i + u; }"), "uint");
+        assert_eq!(ty("int f(bool a, bool b) { return a + b; }"), "int");
+        assert_eq!(ty("float f() { return 1 + 2.0; }"), "literal float");
+        assert_eq!(ty("bool4 f(float4 a) { return a < 0.5; }"), "bool4");
+        assert_eq!(ty("int2 f(int2 a) { return a << 1; }"), "int2");
+        assert_eq!(ty("float f(float2 v) { return -v.x; }"), "float");
+    }
+
+    #[test]
+    fn check_truncation() {
+        let (ty, diagnostics) = check_return("float3 f(float4 a, float3 b) { return a + b; }");
+        assert_eq!(ty, "float3");
+        assert_eq!(
+            diagnostics,
+            vec!["warning: implicit truncation of `float4` to `float3`"]
+        );
+
+        let (_, diagnostics) = check_return("float f(float2 a) { float3 v = a; return 0; }");
+        assert_eq!(
+            diagnostics,
+            vec!["error: cannot convert from `float2` to `float3`"]
+        );
+
+        let (_, diagnostics) =
+            check_return("float2 f(float4x4 m) { float3x3 n = m; return n[0].xy; }");
+        assert_eq!(
+            diagnostics,
+            vec!["warning: implicit truncation of `float4x4` to `float3x3`"]
+        );
+    }
+
+    #[test]
+    fn check_mul() {
+        assert_eq!(
+            ty("float4 f(float4 v, float4x4 m) { return mul(v, m); }"),
+            "float4"
+        );
+        assert_eq!(
+            ty("float3 f(float4 v, float3x4 m) { return mul(m, v); }"),
+            "float3"
+        );
+        assert_eq!(
+            ty("float2x4 f(float2x3 a, float3x4 b) { return mul(a, b); }"),
+            "float2x4"
+        );
+        assert_eq!(
+            ty("float f(float3 a, float3 b) { return mul(a, b); }"),
+            "float"
+        );
+        assert_eq!(ty("float3 f(float3 a) { return mul(2.0, a); }"), "float3");
+
+        let (_, diagnostics) = check_return("float4 f(float3 v, float4x4 m) { return mul(m, v); }");
+        assert_eq!(
+            diagnostics,
+            vec!["error: incompatible operands to `mul`: `float4x4` and `float3`"]
+        );
+    }
+
+    #[test]
+    fn check_swizzles_and_members() {
+        assert_eq!(ty("float2 f(float4 v) { return v.zx; }"), "float2");
+        assert_eq!(ty("float3 f(float v) { return v.xxx; }"), "float3");
+        assert_eq!(ty("float4 f(float4 c) { return c.bgra; }"), "float4");
+        assert_eq!(ty("float2 f(float4x4 m) { return m._m00_m33; }"), "float2");
+        assert_eq!(ty("float f(float3x3 m) { return m._33; }"), "float");
+        assert_eq!(ty("float3 f(float4x3 m) { return m[1]; }"), "float3");
+
+        assert_eq!(
+            ty("struct Light { float3 color; float intensity; };
+                float3 f(Light lights[2]) { return lights[1].color * lights[0].intensity; }"),
+            "float3"
+        );
+
+        let (_, diagnostics) =
+            check_return("struct S { float a; }; float f(float2 v, S s) { return v.xz + s.b; }");
+        assert_eq!(
+            diagnostics,
+            vec![
+                "error: invalid swizzle `xz` on `float2`",
+                "error: `S` has no member named `b`"
+            ]
+        );
+    }
+
+    #[test]
+    fn check_ternary_and_constructors() {
+        assert_eq!(
+            ty("float3 f(bool3 c) { return c ? 1.0 : 0.0; }"),
+            "literal float3"
+        );
+        assert_eq!(
+            ty("float4 f(bool b, float4 x, float y) { return b ? x : y; }"),
+            "float4"
+        );
+        assert_eq!(
+            ty("float4 f(float2 a) { return float4(a, 0, 1); }"),
+            "float4"
+        );
+        assert_eq!(ty("float3 f(float4 a) { return (float3)a; }"), "float3");
+
+        let (_, diagnostics) = check_return("float4 f(float2 a) { return float4(a, 1); }");
+        assert_eq!(
+            diagnostics,
+            vec!["error: `float4` constructor expects 4 components, found 3"]
+        );
+    }
+
+    #[test]
+    fn check_logical_operators() {
+        let src = "bool2 f(bool2 a, bool b) { return a && b; }";
+        let (_, diagnostics) = check_return(src);
+        assert_eq!(
+            diagnostics,
+            vec![
+                "error: operands of `&&` must be scalar, found `bool2`; use `and` for \
+                 component-wise operations"
+            ]
+        );
+
+        // Earlier versions operate component-wise
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        assert!(check_version(&tu, &symbols, HlslVersion::V2018)
+            .diagnostics()
+            .is_empty());
+
+        assert_eq!(ty("bool f(bool a, int b) { return a || b; }"), "bool");
+    }
+
+    #[test]
+    fn check_constant_expressions() {
+        let (ty, diagnostics) = check_return(
+            "static const int KERNEL = 2;
+             float g(float w[5]) { return w[0]; }
+             float f(uint i) {
+                 float w[KERNEL * 2 + 1];
+                 switch (i) { case KERNEL: break; case 1 << 2: break; }
+                 [unroll(KERNEL + 1)] for (uint j = 0; j < i; j++) {}
+                 return g(w);
+             }",
+        );
+        assert_eq!(ty, "float");
+        assert_eq!(diagnostics, Vec::<String>::new());
+
+        let (_, diagnostics) = check_return(
+            "static const int BIG = (int)0x7fffffff * 2;
+             int n;
+             float f(uint i) {
+                 float w[n];
+                 float v[1 / (KERNEL - KERNEL)];
+                 switch (i) { case 1.5: break; }
+                 [unroll(n)] while (i < 4) { i++; }
+                 return 0;
+             }",
+        );
+        assert_eq!(
+            diagnostics,
+            vec![
+                "warning: overflow in constant expression of type `int`; result is `-2`",
+                "error: array size is not an integer constant expression",
+                "error: use of undeclared identifier `KERNEL`",
+                "error: use of undeclared identifier `KERNEL`",
+                "error: case label is not an integer constant expression",
+                "error: unroll count is not an integer constant expression",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_templates() {
+        // Template bodies are not checked, and calls to templates have no known type
+        let (ty, diagnostics) = check_return(
+            "template<typename T> T twice(T x) { return x * undeclared; }
+             float f() { return twice(1.0); }",
+        );
+        assert_eq!(ty, "<error>");
+        assert_eq!(diagnostics, Vec::<String>::new());
+    }
+
+    #[test]
+    fn check_bitfields() {
+        let (_, diagnostics) = check_return(
+            "struct Flags { uint a : 3; int b : 4; float x : 3; bool2 y : 1; };
+             float f() { return 0; }",
+        );
+        assert_eq!(
+            diagnostics,
+            vec![
+                "error: bitfield `x` must have an integer scalar type, found `float`",
+                "error: bitfield `y` must have an integer scalar type, found `bool2`",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_functions() {
+        assert_eq!(
+            ty("float2 g(float2 v, float w) { return v * w; }
+                float g(float v) { return v; }
+                float f() { return g(1.0); }"),
+            "float"
+        );
+
+        let (_, diagnostics) = check_return("float f() { return missing + 1; }");
+        assert_eq!(
+            diagnostics,
+            vec!["error: use of undeclared identifier `missing`"]
+        );
+    }
+
+    #[test]
+    fn check_overloads() {
+        let overloads = "float g(float x) { return x; }
+                         int g(int x) { return x; }
+                         double g(double x) { return x; }";
+
+        assert_eq!(
+            ty(&format!("{} float f() {{ return g(1.0); }}", overloads)),
+            "float"
+        );
+        assert_eq!(
+            ty(&format!("{} int f() {{ return g(1); }}", overloads)),
+            "int"
+        );
+        assert_eq!(
+            ty("double h(double x) { return x; }
+                uint h(uint x) { return x; }
+                double f(float v) { return h(v); }"),
+            "double"
+        );
+        assert_eq!(
+            ty(&format!(
+                "{} double f(double d) {{ return g(d) + g(1.0); }}",
+                overloads
+            )),
+            "double"
+        );
+
+        let (_, diagnostics) =
+            check_return(&format!("{} float f(uint u) {{ return g(u); }}", overloads));
+        assert_eq!(
+            diagnostics,
+            vec!["error: call to `g(uint)` is ambiguous; candidates are: `float g(float x)`, `int g(int x)`, `double g(double x)`"]
+        );
+
+        let (_, diagnostics) = check_return(&format!(
+            "{} float f(float2 v) {{ return g(v, v); }}",
+            overloads
+        ));
+        assert_eq!(
+            diagnostics,
+            vec!["error: no matching overload for call to `g(float2, float2)`; candidates are: `float g(float x)`, `int g(int x)`, `double g(double x)`"]
+        );
+
+        assert_eq!(
+            ty("float g(float3 v) { return v.x; }
+                float g(float v, float w = 1.0) { return v; }
+                float f() { return g(1.0); }"),
+            "float"
+        );
+    }
+
+    #[test]
+    fn check_intrinsics() {
+        assert_eq!(
+            ty("float3 f(float3 a, float3 b) { return lerp(a, b, 0.5); }"),
+            "float3"
+        );
+        assert_eq!(
+            ty("float f(float3 a) { return dot(a, a) + length(a); }"),
+            "float"
+        );
+        assert_eq!(ty("float3 f(int3 i) { return saturate(i); }"), "float3");
+        assert_eq!(ty("bool2 f(float2 v) { return isnan(v); }"), "bool2");
+        assert_eq!(ty("uint f(uint u) { return WaveActiveSum(u); }"), "uint");
+        assert_eq!(
+            ty("Texture2D tex; SamplerState s : register(s0);
+                float4 f(float2 uv) { return tex.SampleLevel(s, uv, 0); }"),
+            "float4"
+        );
+        assert_eq!(
+            ty("RWStructuredBuffer<float4> buf[2];
+                float4 f(uint i) { return buf[0].Load(i) + buf[1][i]; }"),
+            "float4"
+        );
+
+        let (_, diagnostics) = check_return("float f(float2 v) { return frac(v, v); }");
+        assert_eq!(
+            diagnostics,
+            vec!["error: no matching overload for call to `frac(float2, float2)`; candidates are: `float_like<> frac(float_like<> x)`"]
+        );
+
+        let (_, diagnostics) =
+            check_return("Texture2D tex; float4 f(float2 uv) { return tex.Sample(uv); }");
+        assert_eq!(
+            diagnostics,
+            vec![
+                "error: no matching overload for call to `Sample(float2)`; candidates are: \
+                 `$element Sample(SamplerState s, $location location)`, \
+                 `$element Sample(SamplerState s, $location location, $offset offset)`, \
+                 `$element Sample(SamplerState s, $location location, $offset offset, float clamp)`, \
+                 `$element Sample(SamplerState s, $location location, $offset offset, float clamp, out uint status)`"
+            ]
+        );
+
+        let (_, diagnostics) = check_return("float f(float x) { return undefined_fn(x); }");
+        assert_eq!(
+            diagnostics,
+            vec!["error: use of undeclared identifier `undefined_fn`"]
+        );
+    }
+
+    #[test]
+    fn record_calls() {
+        let tu =
+            ast::TranslationUnit::parse("float f(float3 v) { return max(length(v), 1); }").unwrap();
+        let symbols = resolver::resolve(&tu);
+        let table = check(&tu, &symbols);
+
+        let ast::ExternalDeclarationData::FunctionDefinition(def) = &*tu.0[0] else {
+            panic!();
+        };
+        let ast::StatementData::Jump(jump) = &*def.statement.statement_list[0] else {
+            panic!();
+        };
+        let ast::JumpStatementData::Return(Some(expr)) = &**jump else {
+            panic!();
+        };
+        let ast::ExprData::FunCall(_, args) = &***expr else {
+            panic!();
+        };
+
+        let call = table.call(expr).unwrap();
+        let Callee::Intrinsic(intrinsic, _) = call.callee else {
+            panic!();
+        };
+        assert_eq!(intrinsic.name, "max");
+        assert_eq!(call.params, [Type::FLOAT, Type::FLOAT]);
+        assert_eq!(table.converted_to(&args[1]), Some(&Type::FLOAT));
+        assert_eq!(table.call(&args[0]).unwrap().ret, Type::FLOAT);
+    }
+
+    #[test]
+    fn record_implicit_conversions() {
+        let tu = ast::TranslationUnit::parse("float4 f(int i) { return i; }").unwrap();
+        let symbols = resolver::resolve(&tu);
+        let table = check(&tu, &symbols);
+
+        let ast::ExternalDeclarationData::FunctionDefinition(def) = &*tu.0[0] else {
+            panic!();
+        };
+        let ast::StatementData::Jump(jump) = &*def.statement.statement_list[0] else {
+            panic!();
+        };
+        let ast::JumpStatementData::Return(Some(expr)) = &**jump else {
+            panic!();
+        };
+
+        assert_eq!(table.type_of(expr), Some(&Type::INT));
+        assert_eq!(
+            table.converted_to(expr),
+            Some(&Type::Vector(ScalarType::Float, 4))
+        );
+    }
+}
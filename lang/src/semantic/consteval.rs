@@ -0,0 +1,948 @@
+//! Constant expression evaluation
+//!
+//! Array sizes, attribute arguments, case labels and the initialisers of `static const` globals
+//! are constant expressions. [Evaluator] folds them into typed [Constant] values, following the
+//! same promotion and splatting rules as the [type checker](super::check): integer arithmetic
+//! wraps to the width of its type, and floating-point arithmetic is rounded to the precision of
+//! its type. Signed overflow is reported as a warning, integer division by zero as an error.
+//!
+//! Expressions which are not constant, such as reads of uniforms or calls to user functions,
+//! evaluate to `None` without raising diagnostics.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, parse::Parsable, resolver, semantic::consteval::Evaluator};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     "static const int KERNEL = 3; float w[max(KERNEL, 2) * 2 + 1];",
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//!
+//! let ast::ExternalDeclarationData::Declaration(decl) = &*tu.0[1] else { panic!() };
+//! let ast::DeclarationData::InitDeclaratorList(list) = &**decl else { panic!() };
+//! let array = list.head.array_specifier.as_ref().unwrap();
+//! let ast::ArraySpecifierDimensionData::ExplicitlySized(size) = &*array.dimensions[0] else {
+//!     panic!()
+//! };
+//!
+//! let value = Evaluator::new(&symbols).eval(size).unwrap();
+//! assert_eq!(value.as_int(), Some(7));
+//! ```
+
+use std::{cmp::Ordering, collections::HashMap, fmt};
+
+use crate::{
+    ast,
+    resolver::{Declarator, Resolution, SymbolId, SymbolKind, SymbolTable},
+};
+
+use super::{
+    check::swizzle_indices,
+    types::{ScalarType, Shape, Type},
+    Diagnostic, DiagnosticKind,
+};
+
+/// Value of a scalar component
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value {
+    /// Boolean
+    Bool(bool),
+    /// Signed integer, sign-extended from the width of its type
+    Int(i64),
+    /// Unsigned integer
+    UInt(u64),
+    /// Floating-point number, rounded to the precision of its type
+    Float(f64),
+}
+
+impl Value {
+    fn to_bool(self) -> bool {
+        match self {
+            Self::Bool(b) => b,
+            Self::Int(i) => i != 0,
+            Self::UInt(u) => u != 0,
+            Self::Float(f) => f != 0.0,
+        }
+    }
+
+    fn to_i128(self) -> i128 {
+        match self {
+            Self::Bool(b) => i128::from(b),
+            Self::Int(i) => i128::from(i),
+            Self::UInt(u) => i128::from(u),
+            // Truncates toward zero, and saturates
+            Self::Float(f) => f as i128,
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        match self {
+            Self::Bool(b) => f64::from(u8::from(b)),
+            Self::Int(i) => i as f64,
+            Self::UInt(u) => u as f64,
+            Self::Float(f) => f,
+        }
+    }
+
+    /// Convert this value to the given component type
+    pub fn convert(self, to: ScalarType) -> Self {
+        if to == ScalarType::Bool {
+            Self::Bool(self.to_bool())
+        } else if to.is_float() {
+            float(self.to_f64(), to)
+        } else {
+            wrap(self.to_i128(), to).0
+        }
+    }
+
+    fn compare(self, other: Self, scalar: ScalarType) -> Option<Ordering> {
+        if scalar.is_float() {
+            self.to_f64().partial_cmp(&other.to_f64())
+        } else {
+            Some(self.to_i128().cmp(&other.to_i128()))
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::Int(i) => write!(f, "{}", i),
+            Self::UInt(u) => write!(f, "{}", u),
+            Self::Float(x) if x.is_finite() && x.fract() == 0.0 => write!(f, "{:.1}", x),
+            Self::Float(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+/// Width in bits of an integer type. Minimum precision types are evaluated at 32 bits, and
+/// unsuffixed literals at 64 bits, like DXC does.
+fn int_bits(scalar: ScalarType) -> u32 {
+    match scalar {
+        ScalarType::Int16 | ScalarType::UInt16 => 16,
+        ScalarType::Int64 | ScalarType::UInt64 | ScalarType::LiteralInt => 64,
+        _ => 32,
+    }
+}
+
+/// Wrap an integer to the width of `scalar`, along with whether it did not fit
+fn wrap(value: i128, scalar: ScalarType) -> (Value, bool) {
+    let bits = int_bits(scalar);
+    let raw = value & ((1 << bits) - 1);
+
+    if scalar.is_unsigned() {
+        (Value::UInt(raw as u64), raw != value)
+    } else {
+        let signed = if raw >> (bits - 1) == 1 {
+            raw - (1 << bits)
+        } else {
+            raw
+        };
+        (Value::Int(signed as i64), signed != value)
+    }
+}
+
+/// Round a floating-point number to the precision of `scalar`
+fn float(value: f64, scalar: ScalarType) -> Value {
+    match scalar {
+        ScalarType::Double | ScalarType::LiteralFloat => Value::Float(value),
+        _ => Value::Float(value as f32 as f64),
+    }
+}
+
+/// A constant scalar, vector or matrix
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constant {
+    scalar: ScalarType,
+    shape: Shape,
+    values: Vec<Value>,
+}
+
+impl Constant {
+    fn scalar(scalar: ScalarType, value: Value) -> Self {
+        Self {
+            scalar,
+            shape: Shape::Scalar,
+            values: vec![value],
+        }
+    }
+
+    /// Type of this constant. Unsuffixed literals keep their literal type.
+    pub fn ty(&self) -> Type {
+        Type::numeric(self.scalar, self.shape)
+    }
+
+    /// Components of this constant, in row-major order
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    fn single(&self) -> Option<Value> {
+        match self.values[..] {
+            [value] => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Value of a single component integer or boolean constant
+    pub fn as_int(&self) -> Option<i64> {
+        match self.single()? {
+            Value::Bool(b) => Some(i64::from(b)),
+            Value::Int(i) => Some(i),
+            Value::UInt(u) => i64::try_from(u).ok(),
+            Value::Float(_) => None,
+        }
+    }
+
+    /// Value of a single component constant, as a floating-point number
+    pub fn as_float(&self) -> Option<f64> {
+        self.single().map(Value::to_f64)
+    }
+
+    /// Value of a single component constant, as a condition
+    pub fn as_bool(&self) -> Option<bool> {
+        self.single().map(Value::to_bool)
+    }
+
+    fn convert(&self, scalar: ScalarType) -> Self {
+        Self {
+            scalar,
+            shape: self.shape,
+            values: self.values.iter().map(|v| v.convert(scalar)).collect(),
+        }
+    }
+
+    /// Splat or truncate this constant to the given shape
+    fn reshape(&self, shape: Shape) -> Option<Self> {
+        let values = match (self.shape, shape) {
+            _ if self.values.len() == 1 => vec![self.values[0]; shape.len()],
+            (Shape::Matrix(_, cols), Shape::Matrix(r, c)) => (0..r as usize)
+                .flat_map(|row| (0..c as usize).map(move |col| row * cols as usize + col))
+                .map(|index| self.values.get(index).copied())
+                .collect::<Option<_>>()?,
+            (_, shape) if shape.len() <= self.values.len() => self.values[..shape.len()].to_vec(),
+            _ => return None,
+        };
+
+        Some(Self {
+            scalar: self.scalar,
+            shape,
+            values,
+        })
+    }
+
+    /// Convert this constant to a numeric type, as done by casts and initialisers
+    fn cast(&self, ty: &Type) -> Option<Self> {
+        self.convert(ty.scalar()?).reshape(ty.shape()?)
+    }
+}
+
+impl fmt::Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.shape == Shape::Scalar {
+            return write!(f, "{}", self.values[0]);
+        }
+
+        write!(f, "{}(", Type::numeric(self.scalar.concrete(), self.shape))?;
+        for (i, value) in self.values.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// Evaluate an expression, discarding diagnostics
+pub fn eval(expr: &ast::Expr, symbols: &SymbolTable<'_>) -> Option<Constant> {
+    Evaluator::new(symbols).eval(expr)
+}
+
+/// Constant expression evaluator
+///
+/// The values of constant symbols are cached, so that the diagnostics raised by their
+/// initialisers are only reported once per evaluator.
+pub struct Evaluator<'s, 'a> {
+    symbols: &'s SymbolTable<'a>,
+    values: HashMap<SymbolId, Option<Constant>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'s, 'a> Evaluator<'s, 'a> {
+    /// Create an evaluator which resolves names through the given symbol table
+    pub fn new(symbols: &'s SymbolTable<'a>) -> Self {
+        Self {
+            symbols,
+            values: HashMap::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Diagnostics raised so far
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Take the diagnostics raised so far
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    fn error(&mut self, kind: DiagnosticKind, expr: &ast::Expr) {
+        self.diagnostics.push(Diagnostic::new(kind, expr.span));
+    }
+
+    /// Value of a constant variable: a `static const` global, or a `const` local
+    pub fn symbol(&mut self, id: SymbolId) -> Option<Constant> {
+        if let Some(value) = self.values.get(&id) {
+            return value.clone();
+        }
+
+        // Guards against initialisers which refer to their own symbol
+        self.values.insert(id, None);
+        let value = self.symbol_value(id);
+        self.values.insert(id, value.clone());
+        value
+    }
+
+    fn symbol_value(&mut self, id: SymbolId) -> Option<Constant> {
+        let symbol = self.symbols.symbol(id);
+        let (head, init, array) = match symbol.declarator {
+            Declarator::Variable(decl) => (
+                decl,
+                decl.initializer.as_ref(),
+                decl.array_specifier.as_ref(),
+            ),
+            Declarator::Declarator { head, declarator } => (
+                head,
+                declarator.initializer.as_ref(),
+                declarator.ident.array_spec.as_ref(),
+            ),
+            _ => return None,
+        };
+
+        let qualifiers: Vec<_> = head
+            .ty
+            .qualifier
+            .iter()
+            .flat_map(|qualifier| &qualifier.qualifiers)
+            .filter_map(|spec| match &**spec {
+                ast::TypeQualifierSpecData::Storage(storage) => Some(&**storage),
+                _ => None,
+            })
+            .collect();
+        let is_const = qualifiers.contains(&&ast::StorageQualifierData::Const);
+        let is_static = qualifiers.contains(&&ast::StorageQualifierData::Static);
+
+        // Non-static globals are uniforms, whose initialiser is only a default value
+        let constant = match symbol.kind {
+            SymbolKind::Global => is_const && is_static,
+            SymbolKind::Local => is_const,
+            _ => false,
+        };
+
+        if !constant || array.is_some() || head.ty.ty.array_specifier.is_some() {
+            return None;
+        }
+
+        let ty = Type::from_specifier(&head.ty.ty, None, self.symbols);
+        let (scalar, shape) = (ty.scalar()?, ty.shape()?);
+
+        let init = init?;
+        match &**init {
+            ast::InitializerData::Simple(expr) => self.eval(expr)?.cast(&ty),
+            ast::InitializerData::List(_) => {
+                let mut values = Vec::new();
+                self.flatten(init, scalar, &mut values)?;
+
+                (values.len() == shape.len()).then_some(Constant {
+                    scalar,
+                    shape,
+                    values,
+                })
+            }
+        }
+    }
+
+    /// Components of an initialiser list, converted to `scalar`
+    fn flatten(
+        &mut self,
+        init: &ast::Initializer,
+        scalar: ScalarType,
+        values: &mut Vec<Value>,
+    ) -> Option<()> {
+        match &**init {
+            ast::InitializerData::Simple(expr) => {
+                let value = self.eval(expr)?.convert(scalar);
+                values.extend(value.values);
+            }
+            ast::InitializerData::List(items) => {
+                for item in items {
+                    self.flatten(item, scalar, values)?;
+                }
+            }
+        }
+
+        Some(())
+    }
+
+    /// Evaluate a constant expression
+    pub fn eval(&mut self, expr: &ast::Expr) -> Option<Constant> {
+        use ScalarType::*;
+
+        match &**expr {
+            ast::ExprData::IntConst(value) => {
+                Some(Constant::scalar(LiteralInt, Value::Int(i64::from(*value))))
+            }
+            ast::ExprData::UIntConst(value) => {
+                Some(Constant::scalar(UInt, Value::UInt(u64::from(*value))))
+            }
+            ast::ExprData::BoolConst(value) => Some(Constant::scalar(Bool, Value::Bool(*value))),
+            ast::ExprData::FloatConst(value) => Some(Constant::scalar(
+                LiteralFloat,
+                Value::Float(f64::from(*value)),
+            )),
+            ast::ExprData::DoubleConst(value) => {
+                Some(Constant::scalar(Double, Value::Float(*value)))
+            }
+            ast::ExprData::Variable(ident) => match self.symbols.resolution(ident)? {
+                Resolution::Symbol(id) => self.symbol(*id),
+                _ => None,
+            },
+            ast::ExprData::Unary(op, e) => {
+                let value = self.eval(e)?;
+                self.unary(expr, op, value)
+            }
+            ast::ExprData::Cast(ty, e) => {
+                self.eval(e)?
+                    .cast(&Type::from_specifier(ty, None, self.symbols))
+            }
+            ast::ExprData::Binary(op, l, r) => {
+                let left = self.eval(l)?;
+                let right = self.eval(r)?;
+                self.binary(expr, op, left, right)
+            }
+            ast::ExprData::Ternary(c, t, f) => {
+                let cond = self.eval(c)?;
+                let then = self.eval(t)?;
+                let otherwise = self.eval(f)?;
+                ternary(cond, then, otherwise)
+            }
+            ast::ExprData::Bracket(base, index) => {
+                let base = self.eval(base)?;
+                let index = usize::try_from(self.eval(index)?.as_int()?).ok()?;
+
+                match base.shape {
+                    Shape::Vector(n) if index < n as usize => {
+                        Some(Constant::scalar(base.scalar, base.values[index]))
+                    }
+                    Shape::Matrix(rows, cols) if index < rows as usize => {
+                        let cols = cols as usize;
+                        Some(Constant {
+                            scalar: base.scalar,
+                            shape: Shape::Vector(cols as u8),
+                            values: base.values[index * cols..(index + 1) * cols].to_vec(),
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            ast::ExprData::Dot(base, field) => {
+                let base = self.eval(base)?;
+                let indices = swizzle_indices(base.shape, field.as_str())?;
+                let shape = match indices.len() {
+                    1 => Shape::Scalar,
+                    n @ 2..=4 => Shape::Vector(n as u8),
+                    _ => return None,
+                };
+
+                Some(Constant {
+                    scalar: base.scalar,
+                    shape,
+                    values: indices.into_iter().map(|i| base.values[i]).collect(),
+                })
+            }
+            ast::ExprData::FunCall(fun, args) => match &**fun {
+                ast::FunIdentifierData::TypeSpecifier(ty) => {
+                    let ty = Type::from_specifier(ty, None, self.symbols);
+                    let args = self.eval_all(args)?;
+                    constructor(&ty, &args)
+                }
+                ast::FunIdentifierData::Expr(callee) => match &***callee {
+                    // Intrinsics are the functions which do not resolve to a declaration
+                    ast::ExprData::Variable(ident)
+                        if matches!(
+                            self.symbols.resolution(ident),
+                            Some(Resolution::Unresolved) | None
+                        ) =>
+                    {
+                        let args = self.eval_all(args)?;
+                        intrinsic(ident.as_str(), &args)
+                    }
+                    _ => None,
+                },
+            },
+            _ => None,
+        }
+    }
+
+    fn eval_all(&mut self, exprs: &[ast::Expr]) -> Option<Vec<Constant>> {
+        exprs.iter().map(|expr| self.eval(expr)).collect()
+    }
+
+    fn unary(&mut self, expr: &ast::Expr, op: &ast::UnaryOp, value: Constant) -> Option<Constant> {
+        // Arithmetic on booleans promotes them to `int`
+        let promoted = |value: Constant| match value.scalar {
+            ScalarType::Bool => value.convert(ScalarType::Int),
+            _ => value,
+        };
+
+        match **op {
+            ast::UnaryOpData::Add => Some(promoted(value)),
+            ast::UnaryOpData::Minus => {
+                let value = promoted(value);
+                let zero = Constant::scalar(value.scalar, Value::Int(0).convert(value.scalar));
+                self.arithmetic(expr, &ast::BinaryOpData::Sub, zero, value)
+            }
+            ast::UnaryOpData::Not => Some(map(value.convert(ScalarType::Bool), |v| {
+                Value::Bool(!v.to_bool())
+            })),
+            ast::UnaryOpData::Complement if !value.scalar.is_float() => {
+                let value = promoted(value);
+                let scalar = value.scalar;
+                Some(map(value, |v| wrap(!v.to_i128(), scalar).0))
+            }
+            _ => None,
+        }
+    }
+
+    fn binary(
+        &mut self,
+        expr: &ast::Expr,
+        op: &ast::BinaryOp,
+        left: Constant,
+        right: Constant,
+    ) -> Option<Constant> {
+        use ast::BinaryOpData as B;
+
+        match **op {
+            B::Or | B::Xor | B::And => {
+                let (left, right) = operands(left, right, ScalarType::Bool)?;
+                let values = zip(&left, &right, |l, r| {
+                    let (l, r) = (l.to_bool(), r.to_bool());
+                    Value::Bool(match **op {
+                        B::Or => l || r,
+                        B::Xor => l != r,
+                        _ => l && r,
+                    })
+                });
+
+                Some(Constant { values, ..left })
+            }
+            B::Equal | B::NonEqual | B::Lt | B::Gt | B::Lte | B::Gte => {
+                let scalar = left.scalar.common(right.scalar);
+                let (left, right) = operands(left, right, scalar)?;
+                let values = zip(&left, &right, |l, r| {
+                    let ordering = l.compare(r, scalar);
+                    Value::Bool(match **op {
+                        B::Equal => ordering == Some(Ordering::Equal),
+                        B::NonEqual => ordering != Some(Ordering::Equal),
+                        B::Lt => ordering == Some(Ordering::Less),
+                        B::Gt => ordering == Some(Ordering::Greater),
+                        B::Lte => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                        _ => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+                    })
+                });
+
+                Some(Constant {
+                    scalar: ScalarType::Bool,
+                    values,
+                    ..left
+                })
+            }
+            ref op => self.arithmetic(expr, op, left, right),
+        }
+    }
+
+    fn arithmetic(
+        &mut self,
+        expr: &ast::Expr,
+        op: &ast::BinaryOpData,
+        left: Constant,
+        right: Constant,
+    ) -> Option<Constant> {
+        use ast::BinaryOpData as B;
+
+        let scalar = left.scalar.common(right.scalar);
+        let (left, right) = operands(left, right, scalar)?;
+
+        if scalar.is_float() {
+            let values = left
+                .values
+                .iter()
+                .zip(&right.values)
+                .map(|(l, r)| {
+                    let (l, r) = (l.to_f64(), r.to_f64());
+                    Some(float(
+                        match op {
+                            B::Add => l + r,
+                            B::Sub => l - r,
+                            B::Mult => l * r,
+                            B::Div => l / r,
+                            B::Mod => l % r,
+                            _ => return None,
+                        },
+                        scalar,
+                    ))
+                })
+                .collect::<Option<_>>()?;
+
+            return Some(Constant { values, ..left });
+        }
+
+        let bits = int_bits(scalar);
+        let mut overflow = false;
+        let mut values = Vec::with_capacity(left.values.len());
+        for (l, r) in left.values.iter().zip(&right.values) {
+            let (l, r) = (l.to_i128(), r.to_i128());
+            let (value, overflowed) = match op {
+                B::Add => l.overflowing_add(r),
+                B::Sub => l.overflowing_sub(r),
+                B::Mult => l.overflowing_mul(r),
+                B::Div | B::Mod if r == 0 => {
+                    self.error(DiagnosticKind::DivisionByZero, expr);
+                    return None;
+                }
+                B::Div => (l / r, false),
+                B::Mod => (l % r, false),
+                // Shift amounts are masked to the width of the type
+                B::LShift => (l.wrapping_shl(r as u32 & (bits - 1)), false),
+                B::RShift => (l >> (r as u32 & (bits - 1)), false),
+                B::BitAnd => (l & r, false),
+                B::BitOr => (l | r, false),
+                B::BitXor => (l ^ r, false),
+                _ => return None,
+            };
+
+            let (value, wrapped) = wrap(value, scalar);
+            let arithmetic = matches!(op, B::Add | B::Sub | B::Mult | B::Div | B::Mod);
+            overflow |= overflowed || (wrapped && arithmetic);
+            values.push(value);
+        }
+
+        let result = Constant { values, ..left };
+
+        // Unsigned arithmetic wraps by definition
+        if overflow && !scalar.is_unsigned() {
+            self.error(DiagnosticKind::ConstantOverflow(result.clone()), expr);
+        }
+
+        Some(result)
+    }
+}
+
+/// Convert both operands of a binary operation to `scalar` and to their common shape
+fn operands(left: Constant, right: Constant, scalar: ScalarType) -> Option<(Constant, Constant)> {
+    let shape = left.shape.common(right.shape)?;
+    Some((
+        left.convert(scalar).reshape(shape)?,
+        right.convert(scalar).reshape(shape)?,
+    ))
+}
+
+fn map(value: Constant, f: impl Fn(Value) -> Value) -> Constant {
+    Constant {
+        values: value.values.iter().copied().map(f).collect(),
+        ..value
+    }
+}
+
+fn zip(left: &Constant, right: &Constant, f: impl Fn(Value, Value) -> Value) -> Vec<Value> {
+    left.values
+        .iter()
+        .zip(&right.values)
+        .map(|(l, r)| f(*l, *r))
+        .collect()
+}
+
+fn ternary(cond: Constant, then: Constant, otherwise: Constant) -> Option<Constant> {
+    let scalar = then.scalar.common(otherwise.scalar);
+    let mut shape = then.shape.common(otherwise.shape)?;
+
+    // A vector condition selects component-wise
+    if shape.len() == 1 && cond.values.len() > 1 {
+        shape = cond.shape;
+    }
+
+    let cond = cond.reshape(shape)?;
+    let then = then.convert(scalar).reshape(shape)?;
+    let otherwise = otherwise.convert(scalar).reshape(shape)?;
+
+    let values = cond
+        .values
+        .iter()
+        .zip(then.values.iter().zip(&otherwise.values))
+        .map(|(c, (t, f))| if c.to_bool() { *t } else { *f })
+        .collect();
+
+    Some(Constant { values, ..then })
+}
+
+fn constructor(ty: &Type, args: &[Constant]) -> Option<Constant> {
+    let (scalar, shape) = (ty.scalar()?, ty.shape()?);
+
+    // A single scalar is splatted
+    if let [arg] = args {
+        if arg.values.len() == 1 {
+            return arg.cast(ty);
+        }
+    }
+
+    let values: Vec<_> = args
+        .iter()
+        .flat_map(|arg| arg.values.iter().map(|v| v.convert(scalar)))
+        .collect();
+
+    (values.len() == shape.len()).then_some(Constant {
+        scalar,
+        shape,
+        values,
+    })
+}
+
+/// Apply an intrinsic component-wise, after converting the arguments to `scalar` and to their
+/// common shape
+fn apply(
+    args: &[Constant],
+    scalar: ScalarType,
+    result: ScalarType,
+    f: impl Fn(&[Value]) -> Value,
+) -> Option<Constant> {
+    let shape = args
+        .iter()
+        .try_fold(Shape::Scalar, |shape, arg| shape.common(arg.shape))?;
+    let args = args
+        .iter()
+        .map(|arg| arg.convert(scalar).reshape(shape))
+        .collect::<Option<Vec<_>>>()?;
+
+    let values = (0..shape.len())
+        .map(|i| {
+            let components: Vec<_> = args.iter().map(|arg| arg.values[i]).collect();
+            f(&components).convert(result)
+        })
+        .collect();
+
+    Some(Constant {
+        scalar: result,
+        shape,
+        values,
+    })
+}
+
+/// Evaluate a call to an intrinsic function which can be folded
+fn intrinsic(name: &str, args: &[Constant]) -> Option<Constant> {
+    let scalar = args
+        .iter()
+        .map(|arg| arg.scalar)
+        .reduce(ScalarType::common)?;
+    let float = if scalar.is_float() {
+        scalar
+    } else {
+        ScalarType::Float
+    };
+
+    let pick = |keep: Ordering| {
+        move |v: &[Value]| match v[0].compare(v[1], scalar) {
+            Some(ordering) if ordering != keep => v[1],
+            _ => v[0],
+        }
+    };
+    let unary = |f: fn(f64) -> f64| move |v: &[Value]| Value::Float(f(v[0].to_f64()));
+
+    match (name, args.len()) {
+        ("abs", 1) => apply(args, scalar, scalar, |v| match v[0] {
+            Value::Float(x) => Value::Float(x.abs()),
+            value => wrap(value.to_i128().abs(), scalar).0,
+        }),
+        ("min", 2) => apply(args, scalar, scalar, pick(Ordering::Less)),
+        ("max", 2) => apply(args, scalar, scalar, pick(Ordering::Greater)),
+        ("clamp", 3) => apply(args, scalar, scalar, |v| {
+            let low = pick(Ordering::Greater)(&[v[0], v[1]]);
+            pick(Ordering::Less)(&[low, v[2]])
+        }),
+        ("sign", 1) => apply(args, scalar, ScalarType::Int, |v| {
+            Value::Int(match v[0].compare(Value::Int(0), scalar) {
+                Some(Ordering::Less) => -1,
+                Some(Ordering::Greater) => 1,
+                _ => 0,
+            })
+        }),
+        ("saturate", 1) => apply(args, float, float, unary(|x| x.clamp(0.0, 1.0))),
+        ("floor", 1) => apply(args, float, float, unary(f64::floor)),
+        ("ceil", 1) => apply(args, float, float, unary(f64::ceil)),
+        ("round", 1) => apply(args, float, float, unary(f64::round_ties_even)),
+        ("trunc", 1) => apply(args, float, float, unary(f64::trunc)),
+        ("frac", 1) => apply(args, float, float, unary(|x| x - x.floor())),
+        ("sqrt", 1) => apply(args, float, float, unary(f64::sqrt)),
+        ("pow", 2) => apply(args, float, float, |v| {
+            Value::Float(v[0].to_f64().powf(v[1].to_f64()))
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::Parsable, resolver};
+
+    /// Value of the expression returned by the last function, and the diagnostics raised
+    fn eval_return(src: &str) -> (Option<String>, Vec<String>) {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+
+        let def =
+            tu.0.iter()
+                .rev()
+                .find_map(|decl| match &**decl {
+                    ast::ExternalDeclarationData::FunctionDefinition(def) => Some(def),
+                    _ => None,
+                })
+                .unwrap();
+
+        let expr = def
+            .statement
+            .statement_list
+            .iter()
+            .find_map(|stmt| match &**stmt {
+                ast::StatementData::Jump(jump) => match &**jump {
+                    ast::JumpStatementData::Return(Some(expr)) => Some(&**expr),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .unwrap();
+
+        let mut evaluator = Evaluator::new(&symbols);
+        let value = evaluator.eval(expr);
+        (
+            value.map(|value| format!("{}: {}", value.ty(), value)),
+            evaluator
+                .diagnostics()
+                .iter()
+                .map(|d| format!("{}: {}", d.severity(), d))
+                .collect(),
+        )
+    }
+
+    fn value(src: &str) -> String {
+        let (value, diagnostics) = eval_return(src);
+        assert_eq!(diagnostics, Vec::<String>::new());
+        value.unwrap()
+    }
+
+    #[test]
+    fn eval_scalars() {
+        assert_eq!(value("int f() { return 2 * 3 + 1; }"), "literal int: 7");
+        assert_eq!(value("int f() { return 7 / 2 - 7 % 2; }"), "literal int: 2");
+        assert_eq!(value("uint f() { return 1u << 33; }"), "uint: 2");
+        assert_eq!(value("uint f() { return 0u - 1; }"), "uint: 4294967295");
+        assert_eq!(value("int f() { return -8 >> 1; }"), "literal int: -4");
+        assert_eq!(value("float f() { return 1 / 2.0; }"), "literal float: 0.5");
+        assert_eq!(
+            value("float f() { return (float)0.1; }"),
+            "float: 0.10000000149011612"
+        );
+        assert_eq!(value("int f() { return (int)-2.7; }"), "int: -2");
+        assert_eq!(value("bool f() { return 1 < 2 && !false; }"), "bool: true");
+        assert_eq!(
+            value("int f() { return true ? 1 : 2.0; }"),
+            "literal float: 1.0"
+        );
+    }
+
+    #[test]
+    fn eval_vectors_and_intrinsics() {
+        assert_eq!(
+            value("float3 f() { return float3(1, 2, 3) * 2; }"),
+            "float3: float3(2.0, 4.0, 6.0)"
+        );
+        assert_eq!(
+            value("float2 f() { return float4(1, 2, 3, 4).wz; }"),
+            "float2: float2(4.0, 3.0)"
+        );
+        assert_eq!(
+            value("float f() { return float2x2(1, 2, 3, 4)[1].y; }"),
+            "float: 4.0"
+        );
+        assert_eq!(
+            value("int3 f() { return int3(1, 2, 3) > 1 ? 10 : -10; }"),
+            "literal int3: int3(-10, 10, 10)"
+        );
+        assert_eq!(
+            value("int f() { return max(abs(-3), min(5, 4)); }"),
+            "literal int: 4"
+        );
+        assert_eq!(
+            value("uint2 f() { return clamp(uint2(1, 9), 2u, 8u); }"),
+            "uint2: uint2(2, 8)"
+        );
+        assert_eq!(
+            value("float f() { return floor(2.5) + round(2.5); }"),
+            "literal float: 4.0"
+        );
+        assert_eq!(value("int f() { return sign(-2.0); }"), "int: -1");
+    }
+
+    #[test]
+    fn eval_constant_symbols() {
+        assert_eq!(
+            value(
+                "static const int KERNEL = 3;
+                 static const float2 SCALE = { 0.5, 2 };
+                 float f() { const int n = KERNEL * 2 + 1; return n * SCALE.y; }"
+            ),
+            "float: 14.0"
+        );
+
+        // Uniforms, non-const locals and user functions are not constant
+        for src in [
+            "const int N = 3; int f() { return N; }",
+            "int f() { int n = 3; return n; }",
+            "int g() { return 1; } int f() { return g(); }",
+            "static const int N = N + 1; int f() { return N; }",
+        ] {
+            assert_eq!(eval_return(src), (None, vec![]), "{}", src);
+        }
+    }
+
+    #[test]
+    fn eval_diagnostics() {
+        assert_eq!(
+            eval_return("int f() { return (int)2147483647 + 1; }"),
+            (
+                Some("int: -2147483648".to_owned()),
+                vec![
+                    "warning: overflow in constant expression of type `int`; result is `-2147483648`"
+                        .to_owned()
+                ]
+            )
+        );
+
+        assert_eq!(
+            eval_return("int f() { return 1 / (2 - 2); }"),
+            (
+                None,
+                vec!["error: division by zero in constant expression".to_owned()]
+            )
+        );
+
+        // Floating-point division by zero is well-defined
+        assert_eq!(value("float f() { return 1.0 / 0; }"), "literal float: inf");
+    }
+}
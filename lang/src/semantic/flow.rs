@@ -0,0 +1,603 @@
+//! Control-flow checks
+//!
+//! [check] builds the [control-flow graph](super::cfg) of every function and method body and
+//! reports:
+//!
+//! - non-`void` functions whose end can be reached without a `return` statement;
+//! - statements which can never be executed, such as code after a `return`, `discard`, `break`
+//!   or `continue`. Only the first statement of each unreachable region is reported;
+//! - local variables which are read before they are definitely assigned. Only the first such
+//!   read of each variable is reported.
+//!
+//! A variable is assigned by an initialiser, by an assignment to it or to any of its components,
+//! or by passing it as an `out` argument. It is not assigned yet within its own initialiser.
+//! Calling a method on a variable does not read it. `static` locals are zero-initialised, and
+//! never reported.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, parse::Parsable, resolver, semantic::{check, flow}};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     "float f(bool b) { float x; if (b) { x = 1; } return x; }",
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//! let types = check::check(&tu, &symbols);
+//! let diagnostics = flow::check(&symbols, &types);
+//!
+//! assert_eq!(
+//!     diagnostics[0].to_string(),
+//!     "`x` is read before it is assigned"
+//! );
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    ast,
+    entry_point::direction,
+    reflection::cbuffer::is_static,
+    resolver::{Declarator, Resolution, SymbolId, SymbolTable},
+};
+
+use super::{
+    cfg::{Cfg, Element, Terminator},
+    check::TypeTable,
+    intrinsics::Direction,
+    overload::Callee,
+    Diagnostic, DiagnosticKind,
+};
+
+/// Check the control flow of every function and method body
+pub fn check(symbols: &SymbolTable<'_>, types: &TypeTable<'_>) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for symbol in symbols.symbols() {
+        let (proto, body) = match symbol.declarator {
+            Declarator::Function {
+                definition: Some(def),
+                ..
+            }
+            | Declarator::Method {
+                definition: Some(def),
+                ..
+            } => (&def.prototype, &def.statement),
+            Declarator::Method { method, .. } => match &method.statement {
+                Some(body) => (&method.prototype, body),
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        let cfg = Cfg::build(body, symbols);
+        let reachable = cfg.reachable();
+
+        unreachable_code(&cfg, &reachable, &mut diagnostics);
+        Assignments::new(symbols, types, &cfg, locals(symbols, &cfg))
+            .check(&reachable, &mut diagnostics);
+
+        let returns_value = !matches!(*proto.ty.ty.ty, ast::TypeSpecifierNonArrayData::Void);
+        let falls_off = cfg
+            .blocks
+            .iter()
+            .zip(&reachable)
+            .any(|(block, reachable)| *reachable && block.terminator == Terminator::FallOff);
+
+        if returns_value && falls_off {
+            diagnostics.push(Diagnostic::new(
+                DiagnosticKind::MissingReturn(symbol.name.clone()),
+                symbol.span,
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// Report the first statement of every unreachable region
+fn unreachable_code(cfg: &Cfg<'_>, reachable: &[bool], diagnostics: &mut Vec<Diagnostic>) {
+    let mut covered = reachable.to_vec();
+    for (statement, block) in &cfg.statements {
+        if covered[*block] {
+            continue;
+        }
+
+        diagnostics.push(Diagnostic::new(
+            DiagnosticKind::UnreachableCode,
+            statement.span,
+        ));
+
+        // Statements which can only be reached from this one are part of the same region
+        for (covered, reached) in covered.iter_mut().zip(cfg.reachable_from(*block)) {
+            *covered |= reached;
+        }
+    }
+}
+
+/// Index of the local variables of a function body which are not `static`
+fn locals(symbols: &SymbolTable<'_>, cfg: &Cfg<'_>) -> HashMap<SymbolId, usize> {
+    let mut tracked = HashMap::new();
+    for element in cfg.blocks.iter().flat_map(|block| &block.elements) {
+        let Element::Declaration(decl) = element else {
+            continue;
+        };
+
+        let ast::DeclarationData::InitDeclaratorList(list) = &***decl else {
+            continue;
+        };
+
+        if is_static(list.head.ty.qualifier.as_ref()) {
+            continue;
+        }
+
+        let head = list.head.name.as_ref();
+        let tail = list.tail.iter().map(|decl| &decl.ident.ident);
+
+        for name in head.into_iter().chain(tail) {
+            if let Some(id) = symbols.declared_by(name) {
+                let index = tracked.len();
+                tracked.entry(id).or_insert(index);
+            }
+        }
+    }
+
+    tracked
+}
+
+/// Definite assignment analysis of a set of variables of a function, which are unassigned when
+/// the function is entered
+pub(crate) struct Assignments<'s, 'a, 'c> {
+    symbols: &'s SymbolTable<'a>,
+    types: &'s TypeTable<'a>,
+    cfg: &'c Cfg<'c>,
+    /// Index of the tracked variables
+    tracked: HashMap<SymbolId, usize>,
+}
+
+/// Variables assigned on every path to a point, or `None` for points which are not reached
+pub(crate) type State = Option<Vec<bool>>;
+
+impl<'s, 'a, 'c> Assignments<'s, 'a, 'c> {
+    pub(crate) fn new(
+        symbols: &'s SymbolTable<'a>,
+        types: &'s TypeTable<'a>,
+        cfg: &'c Cfg<'c>,
+        tracked: HashMap<SymbolId, usize>,
+    ) -> Self {
+        Self {
+            symbols,
+            types,
+            cfg,
+            tracked,
+        }
+    }
+
+    /// Variables assigned at the end of every block, indexed like the tracked variables
+    pub(crate) fn outputs(&self, reachable: &[bool]) -> Vec<State> {
+        // Forward data flow to a fixed point: a variable is assigned at the start of a block if
+        // it is assigned at the end of all its predecessors
+        let predecessors = self.cfg.predecessors();
+        let mut outputs: Vec<State> = vec![None; self.cfg.blocks.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, block) in self.cfg.blocks.iter().enumerate() {
+                if !reachable[index] {
+                    continue;
+                }
+
+                let mut state = self.input(index, &predecessors, &outputs);
+                for element in &block.elements {
+                    self.element(element, &mut state, &mut |_, _| {});
+                }
+
+                let state = Some(state);
+                if outputs[index] != state {
+                    outputs[index] = state;
+                    changed = true;
+                }
+            }
+        }
+
+        outputs
+    }
+
+    fn check(&self, reachable: &[bool], diagnostics: &mut Vec<Diagnostic>) {
+        if self.tracked.is_empty() {
+            return;
+        }
+
+        let predecessors = self.cfg.predecessors();
+        let outputs = self.outputs(reachable);
+
+        // Report the first read of each variable which is not definitely assigned
+        let mut reported = vec![false; self.tracked.len()];
+        for (index, block) in self.cfg.blocks.iter().enumerate() {
+            if !reachable[index] {
+                continue;
+            }
+
+            let mut state = self.input(index, &predecessors, &outputs);
+            for element in &block.elements {
+                self.element(element, &mut state, &mut |variable, ident| {
+                    if !std::mem::replace(&mut reported[variable], true) {
+                        diagnostics.push(Diagnostic::new(
+                            DiagnosticKind::UninitializedRead(ident.0.clone()),
+                            ident.span,
+                        ));
+                    }
+                });
+            }
+        }
+    }
+
+    /// Variables assigned at the start of a block
+    fn input(&self, block: usize, predecessors: &[Vec<usize>], outputs: &[State]) -> Vec<bool> {
+        if block == Cfg::ENTRY {
+            return vec![false; self.tracked.len()];
+        }
+
+        let mut input: Option<Vec<bool>> = None;
+        for output in predecessors[block]
+            .iter()
+            .filter_map(|p| outputs[*p].as_ref())
+        {
+            match &mut input {
+                Some(input) => {
+                    for (input, output) in input.iter_mut().zip(output) {
+                        *input &= *output;
+                    }
+                }
+                None => input = Some(output.clone()),
+            }
+        }
+
+        // Predecessors which have not been visited yet do not constrain the state
+        input.unwrap_or_else(|| vec![true; self.tracked.len()])
+    }
+
+    /// Index of the tracked variable an identifier refers to
+    fn variable(&self, ident: &ast::Identifier) -> Option<usize> {
+        match self.symbols.resolution(ident) {
+            Some(Resolution::Symbol(id)) => self.tracked.get(id).copied(),
+            _ => None,
+        }
+    }
+
+    fn element(
+        &self,
+        element: &Element<'_>,
+        state: &mut [bool],
+        read: &mut dyn FnMut(usize, &ast::Identifier),
+    ) {
+        match element {
+            Element::Declaration(decl) => {
+                if let ast::DeclarationData::InitDeclaratorList(list) = &***decl {
+                    let head = list
+                        .head
+                        .name
+                        .as_ref()
+                        .map(|name| (name, &list.head.initializer));
+                    let tail = list
+                        .tail
+                        .iter()
+                        .map(|decl| (&decl.ident.ident, &decl.initializer));
+
+                    for (name, initializer) in head.into_iter().chain(tail) {
+                        let variable = self
+                            .symbols
+                            .declared_by(name)
+                            .and_then(|id| self.tracked.get(&id).copied());
+
+                        // Declarations in loops start a new variable on every iteration, which
+                        // is only assigned once its initialiser has been evaluated
+                        if let Some(variable) = variable {
+                            state[variable] = false;
+                        }
+
+                        if let Some(initializer) = initializer {
+                            self.initializer(initializer, state, read);
+                            if let Some(variable) = variable {
+                                state[variable] = true;
+                            }
+                        }
+                    }
+                }
+            }
+            Element::Condition(cond) => match &***cond {
+                ast::ConditionData::Expr(expr) => self.expr(expr, state, read),
+                ast::ConditionData::Assignment(_, _, initializer) => {
+                    self.initializer(initializer, state, read)
+                }
+            },
+            Element::Expr(expr) => self.expr(expr, state, read),
+        }
+    }
+
+    fn initializer(
+        &self,
+        initializer: &ast::Initializer,
+        state: &mut [bool],
+        read: &mut dyn FnMut(usize, &ast::Identifier),
+    ) {
+        match &**initializer {
+            ast::InitializerData::Simple(expr) => self.expr(expr, state, read),
+            ast::InitializerData::List(list) => {
+                for initializer in list {
+                    self.initializer(initializer, state, read);
+                }
+            }
+        }
+    }
+
+    /// Evaluate an expression, reporting reads of unassigned variables and recording writes
+    fn expr(
+        &self,
+        expr: &ast::Expr,
+        state: &mut [bool],
+        read: &mut dyn FnMut(usize, &ast::Identifier),
+    ) {
+        match &**expr {
+            ast::ExprData::Variable(ident) => {
+                if let Some(variable) = self.variable(ident) {
+                    if !state[variable] {
+                        read(variable, ident);
+                    }
+                }
+            }
+            ast::ExprData::Cast(_, operand) => self.expr(operand, state, read),
+            ast::ExprData::Unary(op, operand) => {
+                self.expr(operand, state, read);
+                if matches!(**op, ast::UnaryOpData::Inc | ast::UnaryOpData::Dec) {
+                    self.write(operand, state);
+                }
+            }
+            ast::ExprData::Binary(_, left, right) => {
+                self.expr(left, state, read);
+                self.expr(right, state, read);
+            }
+            ast::ExprData::Ternary(cond, then, otherwise) => {
+                self.expr(cond, state, read);
+                self.expr(then, state, read);
+                self.expr(otherwise, state, read);
+            }
+            ast::ExprData::Assignment(target, op, value) => {
+                self.expr(value, state, read);
+                if matches!(**op, ast::AssignmentOpData::Equal) {
+                    self.place(target, state, read);
+                } else {
+                    self.expr(target, state, read);
+                }
+                self.write(target, state);
+            }
+            ast::ExprData::Bracket(array, index) => {
+                self.expr(array, state, read);
+                self.expr(index, state, read);
+            }
+            ast::ExprData::FunCall(fun, args) => {
+                // Calling a method does not read the whole object
+                if let ast::FunIdentifierData::Expr(callee) = &**fun {
+                    self.place(callee, state, read);
+                }
+
+                let outputs: Vec<_> = (0..args.len())
+                    .map(|index| self.is_output(expr, index))
+                    .collect();
+                for (arg, output) in args.iter().zip(&outputs) {
+                    if *output {
+                        self.place(arg, state, read);
+                    } else {
+                        self.expr(arg, state, read);
+                    }
+                }
+
+                for (arg, output) in args.iter().zip(outputs) {
+                    if output {
+                        self.write(arg, state);
+                    }
+                }
+            }
+            ast::ExprData::Dot(object, _) => self.expr(object, state, read),
+            ast::ExprData::PostInc(operand) | ast::ExprData::PostDec(operand) => {
+                self.expr(operand, state, read);
+                self.write(operand, state);
+            }
+            ast::ExprData::Comma(left, right) => {
+                self.expr(left, state, read);
+                self.expr(right, state, read);
+            }
+            _ => {}
+        }
+    }
+
+    /// Evaluate the indices of an expression which is written to
+    fn place(
+        &self,
+        expr: &ast::Expr,
+        state: &mut [bool],
+        read: &mut dyn FnMut(usize, &ast::Identifier),
+    ) {
+        match &**expr {
+            ast::ExprData::Dot(object, _) => self.place(object, state, read),
+            ast::ExprData::Bracket(array, index) => {
+                self.place(array, state, read);
+                self.expr(index, state, read);
+            }
+            ast::ExprData::Variable(_) => {}
+            _ => self.expr(expr, state, read),
+        }
+    }
+
+    /// Record a write to an expression, which assigns the variable it designates
+    fn write(&self, expr: &ast::Expr, state: &mut [bool]) {
+        match &**expr {
+            ast::ExprData::Variable(ident) => {
+                if let Some(variable) = self.variable(ident) {
+                    state[variable] = true;
+                }
+            }
+            ast::ExprData::Dot(object, _) | ast::ExprData::Bracket(object, _) => {
+                self.write(object, state)
+            }
+            _ => {}
+        }
+    }
+
+    /// `true` if an argument of a call is an `out` argument
+    fn is_output(&self, call: &ast::Expr, index: usize) -> bool {
+        match self.types.call(call).map(|call| &call.callee) {
+            Some(Callee::Function(id)) => self
+                .symbols
+                .symbol(*id)
+                .declarator
+                .prototype()
+                .and_then(|proto| proto.parameters.get(index))
+                .is_some_and(|param| {
+                    let qualifier = match &**param {
+                        ast::FunctionParameterDeclarationData::Named(qualifier, _)
+                        | ast::FunctionParameterDeclarationData::Unnamed(qualifier, _) => qualifier,
+                    };
+                    direction(qualifier.as_ref()) == (false, true)
+                }),
+            Some(Callee::Intrinsic(_, overload)) => overload
+                .params
+                .get(index)
+                .is_some_and(|param| param.direction == Direction::Out),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::Parsable, resolver, semantic::check as types};
+
+    fn check_src(src: &str) -> Vec<String> {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        let types = types::check(&tu, &symbols);
+        let diagnostics = check(&symbols, &types);
+        assert!(diagnostics.iter().all(|diag| diag.span.is_some()));
+        diagnostics.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn missing_returns() {
+        let diagnostics = check_src(
+            r#"
+            float a(bool b) { if (b) return 1; }
+            float b(bool b) { if (b) return 1; else return 2; }
+            float c(int k) { switch (k) { case 0: return 1; default: return 2; } }
+            float d(int k) { switch (k) { case 0: return 1; case 1: return 2; } }
+            float e(bool b) { while (true) { if (b) return 1; } }
+            float f(bool b) { for (;;) { if (b) break; } }
+            float g(bool b) { if (b) return 1; discard; }
+            void h() {}
+            struct S { float m(bool b) { if (b) return 1; } };
+            "#,
+        );
+
+        assert_eq!(
+            diagnostics,
+            [
+                "not all control paths of `a` return a value",
+                "not all control paths of `d` return a value",
+                "not all control paths of `f` return a value",
+                "not all control paths of `m` return a value",
+            ]
+        );
+    }
+
+    #[test]
+    fn unreachable_code() {
+        let diagnostics = check_src(
+            r#"
+            float a(float x) {
+                return x;
+                x = 2;
+                if (x > 0) { x = 3; }
+                return x;
+            }
+            void b(int k) {
+                for (int i = 0; i < k; i++) {
+                    if (i == 2) { continue; k--; }
+                    break;
+                }
+                switch (k) {
+                    k++;
+                    case 0: k = 1;
+                    case 1: k = 2; break; k = 3;
+                    default: break;
+                }
+                discard;
+                k = 4;
+            }
+            "#,
+        );
+
+        assert_eq!(diagnostics, ["unreachable code"; 5]);
+    }
+
+    #[test]
+    fn uninitialized_reads() {
+        let src = r#"
+            void set(out float x) { x = 1; }
+            float f(bool b, int k) {
+                float a;
+                float c, d = 1, e;
+                float4 v;
+                float s, t;
+                static float z;
+                float y;
+                if (b) { a = 1; } else { a = 2; }
+                if (b) { c = 1; }
+                v.xy = c;
+                set(e);
+                sincos(1.0, s, t);
+                for (int i = 0; i < k; i++) { y = i; }
+                float w;
+                do { w = 1; } while (w < 0);
+                int u;
+                switch (k) { case 0: u = 1; break; default: u = 2; }
+                return a + c + d + e + v.x + s + t + z + y + w + u;
+            }
+        "#;
+
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        let types = types::check(&tu, &symbols);
+        let diagnostics = check(&symbols, &types);
+
+        let reads: Vec<_> = diagnostics
+            .iter()
+            .map(|diag| {
+                let span = diag.span.unwrap();
+                (diag.to_string(), &src[span.range()])
+            })
+            .collect();
+        assert_eq!(
+            reads,
+            [
+                ("`c` is read before it is assigned".to_owned(), "c"),
+                ("`y` is read before it is assigned".to_owned(), "y"),
+            ]
+        );
+    }
+
+    #[test]
+    fn methods_and_initializers() {
+        let diagnostics = check_src(
+            r#"
+            struct S { float v; float get() { return 1; } };
+            float f() { S s; return s.get(); }
+            float g() { float y = y + 1; return y; }
+            float h() { float a = 1, b = a; return b; }
+            "#,
+        );
+
+        assert_eq!(diagnostics, ["`y` is read before it is assigned"]);
+    }
+}
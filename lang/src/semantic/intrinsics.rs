@@ -0,0 +1,1160 @@
+//! Intrinsic functions and object methods
+//!
+//! This module describes the built-in functions of HLSL, as declared by DXC, in a table which
+//! can be queried by name with [intrinsic] and, for methods of resource objects, with [method].
+//! Each [Intrinsic] lists its overloads, the minimum shader model it requires and the stages it
+//! can be called from.
+//!
+//! Overloads are generic: a [ParamType::Generic] parameter accepts a family of types, e.g.
+//! `float_like<>` accepts any floating-point scalar, vector or matrix. All the generic
+//! parameters of an overload which share the same index must be given the same type, which
+//! other parameters and the return type can then refer to.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::semantic::{intrinsics, types::ObjectKind};
+//!
+//! let lerp = intrinsics::intrinsic("lerp").unwrap();
+//! assert_eq!(
+//!     lerp.signatures().collect::<Vec<_>>(),
+//!     ["float_like<> lerp(float_like<> x, float_like<> y, float_like<> s)"]
+//! );
+//!
+//! let load = intrinsics::method(ObjectKind::RWByteAddressBuffer, "Load4").unwrap();
+//! assert_eq!(load.overloads[0].ret.to_string(), "uint4");
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+use once_cell::sync::Lazy;
+
+use crate::parse::{ShaderModel, ShaderStage};
+
+use super::types::{ObjectKind, ScalarType, Shape, Type};
+
+/// Component types accepted by a generic parameter
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Components {
+    /// Any numeric or boolean type (`any`)
+    Any,
+    /// Any numeric type (`numeric`)
+    Numeric,
+    /// Floating-point types (`float_like`)
+    Float,
+    /// `double` (`double_only`)
+    Double,
+    /// Signed or unsigned integer types (`int_like`)
+    Int,
+    /// Unsigned integer types (`uint_only`)
+    UInt,
+    /// `bool`
+    Bool,
+}
+
+impl Components {
+    /// Name of this component class, as used by DXC's intrinsic declarations
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Any => "any",
+            Self::Numeric => "numeric",
+            Self::Float => "float_like",
+            Self::Double => "double_only",
+            Self::Int => "int_like",
+            Self::UInt => "uint_only",
+            Self::Bool => "bool",
+        }
+    }
+
+    /// `true` if `scalar` belongs to this class. Literals belong to the classes they can be
+    /// converted to without changing kind.
+    pub fn accepts(self, scalar: ScalarType) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Numeric => scalar != ScalarType::Bool,
+            Self::Float => scalar.is_float(),
+            Self::Double => matches!(scalar, ScalarType::Double | ScalarType::LiteralFloat),
+            Self::Int => scalar.is_integer(),
+            Self::UInt => scalar.is_unsigned() || scalar == ScalarType::LiteralInt,
+            Self::Bool => scalar == ScalarType::Bool,
+        }
+    }
+}
+
+/// Shapes accepted by a generic parameter
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// Scalars, vectors and matrices (`<>`)
+    Any,
+    /// Scalars and vectors (`<c>`)
+    ScalarOrVector,
+    /// Scalars only
+    Scalar,
+    /// Vectors with the given number of components (`<n>`)
+    Vector(u8),
+    /// Matrices (`<r,c>`)
+    Matrix,
+}
+
+impl Layout {
+    /// `true` if `shape` is accepted by this layout
+    pub fn accepts(self, shape: Shape) -> bool {
+        match (self, shape) {
+            (Self::Any, _) => true,
+            (Self::ScalarOrVector, Shape::Scalar | Shape::Vector(_)) => true,
+            (Self::Scalar, Shape::Scalar) => true,
+            (Self::Vector(n), Shape::Vector(m)) => n == m,
+            (Self::Matrix, Shape::Matrix(_, _)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => f.write_str("<>"),
+            Self::ScalarOrVector => f.write_str("<c>"),
+            Self::Scalar => Ok(()),
+            Self::Vector(n) => write!(f, "<{}>", n),
+            Self::Matrix => f.write_str("<r,c>"),
+        }
+    }
+}
+
+/// Texel coordinates of a texture method, sized after the texture
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Coordinates {
+    /// Floating-point sampling location, including the array index
+    Location,
+    /// Integer texel offset, which cube textures do not support
+    Offset,
+    /// Floating-point gradient, without the array index
+    Gradient,
+    /// Integer texel location, including the array index and the mip level
+    Load,
+}
+
+impl Coordinates {
+    /// Component type and count of these coordinates for `object`, or `None` if they do not
+    /// apply to it
+    pub fn of(self, object: ObjectKind) -> Option<(ScalarType, u8)> {
+        let dimension = object.dimension()?;
+        let array = object.is_array() as u8;
+
+        match self {
+            Self::Location => Some((ScalarType::Float, dimension + array)),
+            Self::Offset if object.is_cube() => None,
+            Self::Offset => Some((ScalarType::Int, dimension)),
+            Self::Gradient => Some((ScalarType::Float, dimension)),
+            Self::Load => Some((ScalarType::Int, dimension + array + object.has_mips() as u8)),
+        }
+    }
+}
+
+impl fmt::Display for Coordinates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Location => f.write_str("$location"),
+            Self::Offset => f.write_str("$offset"),
+            Self::Gradient => f.write_str("$gradient"),
+            Self::Load => f.write_str("$load"),
+        }
+    }
+}
+
+/// Type of an intrinsic parameter or return value
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParamType {
+    /// `void`
+    Void,
+    /// Generic numeric type. All the generic parameters with the same index bind to the same
+    /// type.
+    Generic(u8, Components, Layout),
+    /// Same shape as the generic type with the given index, with other components, e.g. the
+    /// result of `isnan`
+    ShapeOf(u8, ScalarType),
+    /// Component type of the generic type with the given index, e.g. the result of `dot`
+    ComponentOf(u8),
+    /// Transpose of the generic matrix type with the given index
+    Transposed(u8),
+    /// Result of `mul`, which depends on the shapes of both operands
+    Product,
+    /// Fixed numeric type
+    Fixed(ScalarType, Shape),
+    /// Element type of the object a method is called on
+    Element,
+    /// Four components of the element type, as returned by `Gather`
+    Gathered,
+    /// Texture coordinates, sized after the object a method is called on
+    Coordinates(Coordinates),
+    /// Resource or sampler object
+    Object(ObjectKind),
+    /// User-defined structure, e.g. a ray payload
+    UserDefined,
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Void => f.write_str("void"),
+            Self::Generic(_, components, layout) => write!(f, "{}{}", components.name(), layout),
+            Self::ShapeOf(index, scalar) => write!(f, "{}<$type{}>", scalar.name(), index),
+            Self::ComponentOf(index) => write!(f, "$scalar{}", index),
+            Self::Transposed(index) => write!(f, "$transpose{}", index),
+            Self::Product => f.write_str("$product"),
+            Self::Fixed(scalar, shape) => write!(f, "{}", Type::numeric(*scalar, *shape)),
+            Self::Element => f.write_str("$element"),
+            Self::Gathered => f.write_str("$element4"),
+            Self::Coordinates(coordinates) => write!(f, "{}", coordinates),
+            Self::Object(kind) => f.write_str(kind.name()),
+            Self::UserDefined => f.write_str("$struct"),
+        }
+    }
+}
+
+/// Direction of an intrinsic parameter
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// `in`
+    In,
+    /// `out`
+    Out,
+    /// `inout`
+    InOut,
+}
+
+/// Parameter of an intrinsic overload
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Param {
+    /// Parameter name
+    pub name: &'static str,
+    /// Parameter type
+    pub ty: ParamType,
+    /// Parameter direction
+    pub direction: Direction,
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.direction {
+            Direction::In => {}
+            Direction::Out => f.write_str("out ")?,
+            Direction::InOut => f.write_str("inout ")?,
+        }
+
+        write!(f, "{} {}", self.ty, self.name)
+    }
+}
+
+/// Overload of an intrinsic
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Overload {
+    /// Return type
+    pub ret: ParamType,
+    /// Parameters
+    pub params: &'static [Param],
+}
+
+/// Set of shader stages
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Stages(u16);
+
+impl Stages {
+    /// All the stages
+    pub const ALL: Self = Self((1 << ShaderStage::ALL.len()) - 1);
+    /// Pixel shaders
+    pub const PIXEL: Self = Self::of(ShaderStage::Pixel);
+    /// Vertex shaders
+    pub const VERTEX: Self = Self::of(ShaderStage::Vertex);
+    /// Geometry shaders
+    pub const GEOMETRY: Self = Self::of(ShaderStage::Geometry);
+    /// Hull shaders
+    pub const HULL: Self = Self::of(ShaderStage::Hull);
+    /// Domain shaders
+    pub const DOMAIN: Self = Self::of(ShaderStage::Domain);
+    /// Compute shaders
+    pub const COMPUTE: Self = Self::of(ShaderStage::Compute);
+    /// Shader libraries
+    pub const LIBRARY: Self = Self::of(ShaderStage::Library);
+    /// Mesh shaders
+    pub const MESH: Self = Self::of(ShaderStage::Mesh);
+    /// Amplification shaders
+    pub const AMPLIFICATION: Self = Self::of(ShaderStage::Amplification);
+
+    /// Set containing a single stage
+    pub const fn of(stage: ShaderStage) -> Self {
+        Self(1 << stage as u16)
+    }
+
+    /// Union of two sets of stages
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// `true` if `stage` belongs to this set
+    pub fn contains(self, stage: ShaderStage) -> bool {
+        self.0 & Self::of(stage).0 != 0
+    }
+
+    /// Stages in this set
+    pub fn iter(self) -> impl Iterator<Item = ShaderStage> {
+        ShaderStage::ALL
+            .into_iter()
+            .filter(move |stage| self.contains(*stage))
+    }
+}
+
+/// Intrinsic function or object method
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Intrinsic {
+    /// Function name
+    pub name: &'static str,
+    /// Overloads, in declaration order
+    pub overloads: &'static [Overload],
+    /// Minimum shader model, as a major and minor version
+    pub shader_model: (u8, u8),
+    /// Stages this intrinsic can be called from
+    pub stages: Stages,
+}
+
+impl Intrinsic {
+    /// `true` if this intrinsic can be called when compiling for `model`
+    pub fn is_available(&self, model: &ShaderModel) -> bool {
+        model.at_least(self.shader_model.0, self.shader_model.1)
+            && self.stages.contains(model.stage)
+    }
+
+    /// Human-readable signatures of the overloads, e.g. for diagnostics and completion
+    pub fn signatures(&self) -> impl Iterator<Item = String> + '_ {
+        self.overloads
+            .iter()
+            .map(move |overload| self.signature(overload))
+    }
+
+    /// Human-readable signature of one of the overloads
+    pub fn signature(&self, overload: &Overload) -> String {
+        let params: Vec<_> = overload.params.iter().map(Param::to_string).collect();
+        format!("{} {}({})", overload.ret, self.name, params.join(", "))
+    }
+
+    const fn since(self, major: u8, minor: u8) -> Self {
+        Self {
+            shader_model: (major, minor),
+            ..self
+        }
+    }
+
+    const fn only(self, stages: Stages) -> Self {
+        Self { stages, ..self }
+    }
+}
+
+/// Look up an intrinsic function by name
+pub fn intrinsic(name: &str) -> Option<&'static Intrinsic> {
+    static BY_NAME: Lazy<HashMap<&'static str, &'static Intrinsic>> =
+        Lazy::new(|| INTRINSICS.iter().map(|f| (f.name, f)).collect());
+
+    BY_NAME.get(name).copied()
+}
+
+/// All the intrinsic functions
+pub fn intrinsics() -> impl Iterator<Item = &'static Intrinsic> {
+    INTRINSICS.iter()
+}
+
+/// Look up a method of an object type by name
+pub fn method(object: ObjectKind, name: &str) -> Option<&'static Intrinsic> {
+    methods(object).find(|method| method.name == name)
+}
+
+/// All the methods of an object type
+pub fn methods(object: ObjectKind) -> impl Iterator<Item = &'static Intrinsic> {
+    METHODS
+        .iter()
+        .filter(move |method| method.objects.contains(&object))
+        .map(|method| &method.intrinsic)
+}
+
+/// Intrinsic available as a method of some object types
+struct Method {
+    objects: &'static [ObjectKind],
+    intrinsic: Intrinsic,
+}
+
+const fn def(name: &'static str, overloads: &'static [Overload]) -> Intrinsic {
+    Intrinsic {
+        name,
+        overloads,
+        shader_model: (4, 0),
+        stages: Stages::ALL,
+    }
+}
+
+const fn on(objects: &'static [ObjectKind], intrinsic: Intrinsic) -> Method {
+    Method { objects, intrinsic }
+}
+
+const fn sig(ret: ParamType, params: &'static [Param]) -> Overload {
+    Overload { ret, params }
+}
+
+const fn arg(name: &'static str, ty: ParamType) -> Param {
+    Param {
+        name,
+        ty,
+        direction: Direction::In,
+    }
+}
+
+const fn out(name: &'static str, ty: ParamType) -> Param {
+    Param {
+        name,
+        ty,
+        direction: Direction::Out,
+    }
+}
+
+const fn inout(name: &'static str, ty: ParamType) -> Param {
+    Param {
+        name,
+        ty,
+        direction: Direction::InOut,
+    }
+}
+
+use Components as C;
+use Layout as L;
+use ParamType as P;
+
+// Generic types
+const ANY: ParamType = P::Generic(0, C::Any, L::Any);
+const NUMERIC: ParamType = P::Generic(0, C::Numeric, L::Any);
+const NUMERIC_VECTOR: ParamType = P::Generic(0, C::Numeric, L::ScalarOrVector);
+const FLOAT_LIKE: ParamType = P::Generic(0, C::Float, L::Any);
+const FLOAT_VECTOR: ParamType = P::Generic(0, C::Float, L::ScalarOrVector);
+const DOUBLE_LIKE: ParamType = P::Generic(0, C::Double, L::Any);
+const INT_LIKE: ParamType = P::Generic(0, C::Int, L::Any);
+const INT_SCALAR: ParamType = P::Generic(0, C::Int, L::Scalar);
+const UINT_LIKE: ParamType = P::Generic(0, C::UInt, L::Any);
+const UINT_VECTOR: ParamType = P::Generic(0, C::UInt, L::ScalarOrVector);
+const SCALAR: ParamType = P::ComponentOf(0);
+
+// Fixed types
+const VOID: ParamType = P::Void;
+const BOOL: ParamType = P::Fixed(ScalarType::Bool, Shape::Scalar);
+const INT: ParamType = P::Fixed(ScalarType::Int, Shape::Scalar);
+const INT2: ParamType = P::Fixed(ScalarType::Int, Shape::Vector(2));
+const INT4: ParamType = P::Fixed(ScalarType::Int, Shape::Vector(4));
+const UINT: ParamType = P::Fixed(ScalarType::UInt, Shape::Scalar);
+const UINT2: ParamType = P::Fixed(ScalarType::UInt, Shape::Vector(2));
+const UINT3: ParamType = P::Fixed(ScalarType::UInt, Shape::Vector(3));
+const UINT4: ParamType = P::Fixed(ScalarType::UInt, Shape::Vector(4));
+const UINT64: ParamType = P::Fixed(ScalarType::UInt64, Shape::Scalar);
+const INT16_4: ParamType = P::Fixed(ScalarType::Int16, Shape::Vector(4));
+const UINT16_4: ParamType = P::Fixed(ScalarType::UInt16, Shape::Vector(4));
+const HALF2: ParamType = P::Fixed(ScalarType::Half, Shape::Vector(2));
+const FLOAT: ParamType = P::Fixed(ScalarType::Float, Shape::Scalar);
+const FLOAT2: ParamType = P::Fixed(ScalarType::Float, Shape::Vector(2));
+const FLOAT3: ParamType = P::Fixed(ScalarType::Float, Shape::Vector(3));
+const FLOAT4: ParamType = P::Fixed(ScalarType::Float, Shape::Vector(4));
+const DOUBLE: ParamType = P::Fixed(ScalarType::Double, Shape::Scalar);
+const FLOAT3X4: ParamType = P::Fixed(ScalarType::Float, Shape::Matrix(3, 4));
+const FLOAT4X3: ParamType = P::Fixed(ScalarType::Float, Shape::Matrix(4, 3));
+
+// Object types
+const SAMPLER: ParamType = P::Object(ObjectKind::SamplerState);
+const CMP_SAMPLER: ParamType = P::Object(ObjectKind::SamplerComparisonState);
+const ACCELERATION_STRUCTURE: ParamType = P::Object(ObjectKind::RaytracingAccelerationStructure);
+const LOCATION: ParamType = P::Coordinates(Coordinates::Location);
+const OFFSET: ParamType = P::Coordinates(Coordinates::Offset);
+const GRADIENT: ParamType = P::Coordinates(Coordinates::Gradient);
+const LOAD: ParamType = P::Coordinates(Coordinates::Load);
+
+// Stage restrictions. Derivatives in compute, mesh and amplification shaders (shader model 6.6)
+// are not modelled.
+const DERIVATIVES: Stages = Stages::PIXEL.union(Stages::LIBRARY);
+const GROUP_SYNC: Stages = Stages::COMPUTE
+    .union(Stages::MESH)
+    .union(Stages::AMPLIFICATION)
+    .union(Stages::LIBRARY);
+const TESSELLATION: Stages = Stages::HULL.union(Stages::LIBRARY);
+const RAY_TRACING: Stages = Stages::LIBRARY;
+
+// Overload sets shared by several intrinsics
+const UNARY_FLOAT: &[Overload] = &[sig(FLOAT_LIKE, &[arg("x", FLOAT_LIKE)])];
+const BINARY_FLOAT: &[Overload] = &[sig(
+    FLOAT_LIKE,
+    &[arg("x", FLOAT_LIKE), arg("y", FLOAT_LIKE)],
+)];
+const BINARY_NUMERIC: &[Overload] = &[sig(NUMERIC, &[arg("a", NUMERIC), arg("b", NUMERIC)])];
+const FLOAT_CLASS: &[Overload] = &[sig(
+    P::ShapeOf(0, ScalarType::Bool),
+    &[arg("x", FLOAT_LIKE)],
+)];
+const NO_ARGS_VOID: &[Overload] = &[sig(VOID, &[])];
+const NO_ARGS_UINT: &[Overload] = &[sig(UINT, &[])];
+const NO_ARGS_UINT3: &[Overload] = &[sig(UINT3, &[])];
+const NO_ARGS_FLOAT: &[Overload] = &[sig(FLOAT, &[])];
+const NO_ARGS_FLOAT3: &[Overload] = &[sig(FLOAT3, &[])];
+const NO_ARGS_FLOAT3X4: &[Overload] = &[sig(FLOAT3X4, &[])];
+const NO_ARGS_FLOAT4X3: &[Overload] = &[sig(FLOAT4X3, &[])];
+const INTERLOCKED: &[Overload] = &[
+    sig(VOID, &[inout("dest", INT_SCALAR), arg("value", INT_SCALAR)]),
+    sig(
+        VOID,
+        &[
+            inout("dest", INT_SCALAR),
+            arg("value", INT_SCALAR),
+            out("original_value", INT_SCALAR),
+        ],
+    ),
+];
+const WAVE_NUMERIC: &[Overload] = &[sig(NUMERIC, &[arg("value", NUMERIC)])];
+const WAVE_BITWISE: &[Overload] = &[sig(UINT_LIKE, &[arg("value", UINT_LIKE)])];
+const WAVE_BOOL: &[Overload] = &[sig(BOOL, &[arg("expr", BOOL)])];
+const WAVE_MULTI_NUMERIC: &[Overload] =
+    &[sig(NUMERIC, &[arg("value", NUMERIC), arg("mask", UINT4)])];
+const WAVE_MULTI_BITWISE: &[Overload] = &[sig(
+    UINT_LIKE,
+    &[arg("value", UINT_LIKE), arg("mask", UINT4)],
+)];
+const QUAD_READ: &[Overload] = &[sig(ANY, &[arg("value", ANY)])];
+const TRI_TESS_FACTORS: &[Overload] = &[sig(
+    VOID,
+    &[
+        arg("raw_edge_factors", FLOAT3),
+        arg("inside_scale", FLOAT),
+        out("rounded_edge_factors", FLOAT3),
+        out("rounded_inside_factor", FLOAT),
+        out("unrounded_inside_factor", FLOAT),
+    ],
+)];
+const QUAD_TESS_FACTORS: &[Overload] = &[sig(
+    VOID,
+    &[
+        arg("raw_edge_factors", FLOAT4),
+        arg("inside_scale", FLOAT),
+        out("rounded_edge_factors", FLOAT4),
+        out("rounded_inside_factors", FLOAT2),
+        out("unrounded_inside_factors", FLOAT2),
+    ],
+)];
+const QUAD_2D_TESS_FACTORS: &[Overload] = &[sig(
+    VOID,
+    &[
+        arg("raw_edge_factors", FLOAT4),
+        arg("inside_scale", FLOAT2),
+        out("rounded_edge_factors", FLOAT4),
+        out("rounded_inside_factors", FLOAT2),
+        out("unrounded_inside_factors", FLOAT2),
+    ],
+)];
+const PACK_UNSIGNED: &[Overload] = &[
+    sig(UINT, &[arg("unpacked", UINT4)]),
+    sig(UINT, &[arg("unpacked", UINT16_4)]),
+];
+const PACK_SIGNED: &[Overload] = &[
+    sig(UINT, &[arg("unpacked", INT4)]),
+    sig(UINT, &[arg("unpacked", INT16_4)]),
+];
+
+#[rustfmt::skip]
+static INTRINSICS: &[Intrinsic] = &[
+    // Math
+    def("abs", &[sig(NUMERIC, &[arg("x", NUMERIC)])]),
+    def("acos", UNARY_FLOAT),
+    def("all", &[sig(BOOL, &[arg("x", ANY)])]),
+    def("any", &[sig(BOOL, &[arg("x", ANY)])]),
+    def("asin", UNARY_FLOAT),
+    def("atan", UNARY_FLOAT),
+    def("atan2", &[sig(FLOAT_LIKE, &[arg("y", FLOAT_LIKE), arg("x", FLOAT_LIKE)])]),
+    def("ceil", UNARY_FLOAT),
+    def("clamp", &[sig(NUMERIC, &[arg("x", NUMERIC), arg("min", NUMERIC), arg("max", NUMERIC)])]),
+    def("cos", UNARY_FLOAT),
+    def("cosh", UNARY_FLOAT),
+    def("cross", &[sig(P::Generic(0, C::Float, L::Vector(3)), &[
+        arg("x", P::Generic(0, C::Float, L::Vector(3))), arg("y", P::Generic(0, C::Float, L::Vector(3))),
+    ])]),
+    def("degrees", UNARY_FLOAT),
+    def("determinant", &[sig(SCALAR, &[arg("m", P::Generic(0, C::Float, L::Matrix))])]),
+    def("distance", &[sig(SCALAR, &[arg("x", FLOAT_VECTOR), arg("y", FLOAT_VECTOR)])]),
+    def("dot", &[sig(SCALAR, &[arg("x", NUMERIC_VECTOR), arg("y", NUMERIC_VECTOR)])]),
+    def("dst", &[sig(P::Generic(0, C::Float, L::Vector(4)), &[
+        arg("a", P::Generic(0, C::Float, L::Vector(4))), arg("b", P::Generic(0, C::Float, L::Vector(4))),
+    ])]),
+    def("exp", UNARY_FLOAT),
+    def("exp2", UNARY_FLOAT),
+    def("faceforward", &[sig(FLOAT_VECTOR, &[arg("n", FLOAT_VECTOR), arg("i", FLOAT_VECTOR), arg("ng", FLOAT_VECTOR)])]),
+    def("floor", UNARY_FLOAT),
+    def("fma", &[sig(DOUBLE_LIKE, &[arg("a", DOUBLE_LIKE), arg("b", DOUBLE_LIKE), arg("c", DOUBLE_LIKE)])]).since(5, 0),
+    def("fmod", BINARY_FLOAT),
+    def("frac", UNARY_FLOAT),
+    def("frexp", &[sig(FLOAT_LIKE, &[arg("x", FLOAT_LIKE), out("exp", FLOAT_LIKE)])]),
+    def("isfinite", FLOAT_CLASS),
+    def("isinf", FLOAT_CLASS),
+    def("isnan", FLOAT_CLASS),
+    def("ldexp", &[sig(FLOAT_LIKE, &[arg("x", FLOAT_LIKE), arg("exp", FLOAT_LIKE)])]),
+    def("length", &[sig(SCALAR, &[arg("x", FLOAT_VECTOR)])]),
+    def("lerp", &[sig(FLOAT_LIKE, &[arg("x", FLOAT_LIKE), arg("y", FLOAT_LIKE), arg("s", FLOAT_LIKE)])]),
+    def("lit", &[sig(FLOAT4, &[arg("n_dot_l", FLOAT), arg("n_dot_h", FLOAT), arg("m", FLOAT)])]),
+    def("log", UNARY_FLOAT),
+    def("log10", UNARY_FLOAT),
+    def("log2", UNARY_FLOAT),
+    def("mad", &[sig(NUMERIC, &[arg("m", NUMERIC), arg("a", NUMERIC), arg("b", NUMERIC)])]),
+    def("max", BINARY_NUMERIC),
+    def("min", BINARY_NUMERIC),
+    def("modf", &[sig(FLOAT_LIKE, &[arg("x", FLOAT_LIKE), out("ip", FLOAT_LIKE)])]),
+    def("mul", &[sig(P::Product, &[arg("a", P::Generic(0, C::Numeric, L::Any)), arg("b", P::Generic(1, C::Numeric, L::Any))])]),
+    def("normalize", &[sig(FLOAT_VECTOR, &[arg("x", FLOAT_VECTOR)])]),
+    def("pow", BINARY_FLOAT),
+    def("radians", UNARY_FLOAT),
+    def("rcp", UNARY_FLOAT).since(5, 0),
+    def("reflect", &[sig(FLOAT_VECTOR, &[arg("i", FLOAT_VECTOR), arg("n", FLOAT_VECTOR)])]),
+    def("refract", &[sig(FLOAT_VECTOR, &[arg("i", FLOAT_VECTOR), arg("n", FLOAT_VECTOR), arg("eta", SCALAR)])]),
+    def("round", UNARY_FLOAT),
+    def("rsqrt", UNARY_FLOAT),
+    def("saturate", UNARY_FLOAT),
+    def("select", &[sig(P::Generic(1, C::Any, L::Any), &[
+        arg("cond", P::Generic(0, C::Bool, L::Any)), arg("t", P::Generic(1, C::Any, L::Any)), arg("f", P::Generic(1, C::Any, L::Any)),
+    ])]),
+    def("sign", &[sig(P::ShapeOf(0, ScalarType::Int), &[arg("x", NUMERIC)])]),
+    def("sin", UNARY_FLOAT),
+    def("sincos", &[sig(VOID, &[arg("x", FLOAT_LIKE), out("s", FLOAT_LIKE), out("c", FLOAT_LIKE)])]),
+    def("sinh", UNARY_FLOAT),
+    def("smoothstep", &[sig(FLOAT_LIKE, &[arg("min", FLOAT_LIKE), arg("max", FLOAT_LIKE), arg("x", FLOAT_LIKE)])]),
+    def("sqrt", UNARY_FLOAT),
+    def("step", &[sig(FLOAT_LIKE, &[arg("y", FLOAT_LIKE), arg("x", FLOAT_LIKE)])]),
+    def("tan", UNARY_FLOAT),
+    def("tanh", UNARY_FLOAT),
+    def("transpose", &[sig(P::Transposed(0), &[arg("m", P::Generic(0, C::Any, L::Matrix))])]),
+    def("trunc", UNARY_FLOAT),
+
+    // Bit manipulation and conversions
+    def("asdouble", &[sig(P::ShapeOf(0, ScalarType::Double), &[arg("lowbits", UINT_VECTOR), arg("highbits", UINT_VECTOR)])]).since(5, 0),
+    def("asfloat", &[sig(P::ShapeOf(0, ScalarType::Float), &[arg("x", NUMERIC)])]),
+    def("asfloat16", &[sig(P::ShapeOf(0, ScalarType::Float16), &[arg("x", NUMERIC)])]).since(6, 2),
+    def("asint", &[sig(P::ShapeOf(0, ScalarType::Int), &[arg("x", NUMERIC)])]),
+    def("asint16", &[sig(P::ShapeOf(0, ScalarType::Int16), &[arg("x", NUMERIC)])]).since(6, 2),
+    def("asuint", &[
+        sig(P::ShapeOf(0, ScalarType::UInt), &[arg("x", NUMERIC)]),
+        sig(VOID, &[arg("value", DOUBLE), out("lowbits", UINT), out("highbits", UINT)]),
+    ]),
+    def("asuint16", &[sig(P::ShapeOf(0, ScalarType::UInt16), &[arg("x", NUMERIC)])]).since(6, 2),
+    def("countbits", &[sig(UINT_LIKE, &[arg("value", UINT_LIKE)])]).since(5, 0),
+    def("D3DCOLORtoUBYTE4", &[sig(INT4, &[arg("x", FLOAT4)])]),
+    def("dot2add", &[sig(FLOAT, &[arg("a", HALF2), arg("b", HALF2), arg("acc", FLOAT)])]).since(6, 4),
+    def("dot4add_i8packed", &[sig(INT, &[arg("a", UINT), arg("b", UINT), arg("acc", INT)])]).since(6, 4),
+    def("dot4add_u8packed", &[sig(UINT, &[arg("a", UINT), arg("b", UINT), arg("acc", UINT)])]).since(6, 4),
+    def("f16tof32", &[sig(P::ShapeOf(0, ScalarType::Float), &[arg("value", UINT_LIKE)])]).since(5, 0),
+    def("f32tof16", &[sig(P::ShapeOf(0, ScalarType::UInt), &[arg("value", FLOAT_LIKE)])]).since(5, 0),
+    def("firstbithigh", &[sig(INT_LIKE, &[arg("value", INT_LIKE)])]).since(5, 0),
+    def("firstbitlow", &[sig(INT_LIKE, &[arg("value", INT_LIKE)])]).since(5, 0),
+    def("msad4", &[sig(UINT4, &[arg("reference", UINT), arg("source", UINT2), arg("accum", UINT4)])]).since(5, 0),
+    def("pack_clamp_s8", PACK_SIGNED).since(6, 6),
+    def("pack_clamp_u8", PACK_SIGNED).since(6, 6),
+    def("pack_s8", PACK_SIGNED).since(6, 6),
+    def("pack_u8", PACK_UNSIGNED).since(6, 6),
+    def("reversebits", &[sig(UINT_LIKE, &[arg("value", UINT_LIKE)])]).since(5, 0),
+    def("unpack_s8s16", &[sig(INT16_4, &[arg("packed", UINT)])]).since(6, 6),
+    def("unpack_s8s32", &[sig(INT4, &[arg("packed", UINT)])]).since(6, 6),
+    def("unpack_u8u16", &[sig(UINT16_4, &[arg("packed", UINT)])]).since(6, 6),
+    def("unpack_u8u32", &[sig(UINT4, &[arg("packed", UINT)])]).since(6, 6),
+
+    // Pixel shading
+    def("clip", &[sig(VOID, &[arg("x", FLOAT_LIKE)])]).only(DERIVATIVES),
+    def("ddx", UNARY_FLOAT).only(DERIVATIVES),
+    def("ddx_coarse", UNARY_FLOAT).since(5, 0).only(DERIVATIVES),
+    def("ddx_fine", UNARY_FLOAT).since(5, 0).only(DERIVATIVES),
+    def("ddy", UNARY_FLOAT).only(DERIVATIVES),
+    def("ddy_coarse", UNARY_FLOAT).since(5, 0).only(DERIVATIVES),
+    def("ddy_fine", UNARY_FLOAT).since(5, 0).only(DERIVATIVES),
+    def("EvaluateAttributeAtCentroid", &[sig(NUMERIC, &[arg("value", NUMERIC)])]).since(5, 0).only(DERIVATIVES),
+    def("EvaluateAttributeAtSample", &[sig(NUMERIC, &[arg("value", NUMERIC), arg("index", UINT)])]).since(5, 0).only(DERIVATIVES),
+    def("EvaluateAttributeSnapped", &[sig(NUMERIC, &[arg("value", NUMERIC), arg("offset", INT2)])]).since(5, 0).only(DERIVATIVES),
+    def("fwidth", UNARY_FLOAT).only(DERIVATIVES),
+    def("GetAttributeAtVertex", &[sig(ANY, &[arg("value", ANY), arg("vertex_index", UINT)])]).since(6, 1).only(DERIVATIVES),
+    def("GetRenderTargetSampleCount", NO_ARGS_UINT).only(DERIVATIVES),
+    def("GetRenderTargetSamplePosition", &[sig(FLOAT2, &[arg("index", INT)])]).only(DERIVATIVES),
+    def("IsHelperLane", &[sig(BOOL, &[])]).since(6, 6),
+
+    // Resources and synchronization
+    def("AllMemoryBarrier", NO_ARGS_VOID).since(5, 0),
+    def("AllMemoryBarrierWithGroupSync", NO_ARGS_VOID).since(5, 0).only(GROUP_SYNC),
+    def("CheckAccessFullyMapped", &[sig(BOOL, &[arg("status", UINT)])]).since(5, 0),
+    def("DeviceMemoryBarrier", NO_ARGS_VOID).since(5, 0),
+    def("DeviceMemoryBarrierWithGroupSync", NO_ARGS_VOID).since(5, 0).only(GROUP_SYNC),
+    def("GroupMemoryBarrier", NO_ARGS_VOID).since(5, 0).only(GROUP_SYNC),
+    def("GroupMemoryBarrierWithGroupSync", NO_ARGS_VOID).since(5, 0).only(GROUP_SYNC),
+    def("InterlockedAdd", INTERLOCKED).since(5, 0),
+    def("InterlockedAnd", INTERLOCKED).since(5, 0),
+    def("InterlockedCompareExchange", &[sig(VOID, &[
+        inout("dest", INT_SCALAR), arg("compare_value", INT_SCALAR), arg("value", INT_SCALAR), out("original_value", INT_SCALAR),
+    ])]).since(5, 0),
+    def("InterlockedCompareExchangeFloatBitwise", &[sig(VOID, &[
+        inout("dest", FLOAT), arg("compare_value", FLOAT), arg("value", FLOAT), out("original_value", FLOAT),
+    ])]).since(6, 6),
+    def("InterlockedCompareStore", &[sig(VOID, &[
+        inout("dest", INT_SCALAR), arg("compare_value", INT_SCALAR), arg("value", INT_SCALAR),
+    ])]).since(5, 0),
+    def("InterlockedCompareStoreFloatBitwise", &[sig(VOID, &[
+        inout("dest", FLOAT), arg("compare_value", FLOAT), arg("value", FLOAT),
+    ])]).since(6, 6),
+    def("InterlockedExchange", &[sig(VOID, &[
+        inout("dest", INT_SCALAR), arg("value", INT_SCALAR), out("original_value", INT_SCALAR),
+    ])]).since(5, 0),
+    def("InterlockedMax", INTERLOCKED).since(5, 0),
+    def("InterlockedMin", INTERLOCKED).since(5, 0),
+    def("InterlockedOr", INTERLOCKED).since(5, 0),
+    def("InterlockedXor", INTERLOCKED).since(5, 0),
+    def("NonUniformResourceIndex", &[sig(UINT, &[arg("index", UINT)])]).since(5, 1),
+
+    // Tessellation
+    def("Process2DQuadTessFactorsAvg", QUAD_2D_TESS_FACTORS).since(5, 0).only(TESSELLATION),
+    def("Process2DQuadTessFactorsMax", QUAD_2D_TESS_FACTORS).since(5, 0).only(TESSELLATION),
+    def("Process2DQuadTessFactorsMin", QUAD_2D_TESS_FACTORS).since(5, 0).only(TESSELLATION),
+    def("ProcessIsolineTessFactors", &[sig(VOID, &[
+        arg("raw_detail_factor", FLOAT), arg("raw_density_factor", FLOAT),
+        out("rounded_detail_factor", FLOAT), out("rounded_density_factor", FLOAT),
+    ])]).since(5, 0).only(TESSELLATION),
+    def("ProcessQuadTessFactorsAvg", QUAD_TESS_FACTORS).since(5, 0).only(TESSELLATION),
+    def("ProcessQuadTessFactorsMax", QUAD_TESS_FACTORS).since(5, 0).only(TESSELLATION),
+    def("ProcessQuadTessFactorsMin", QUAD_TESS_FACTORS).since(5, 0).only(TESSELLATION),
+    def("ProcessTriTessFactorsAvg", TRI_TESS_FACTORS).since(5, 0).only(TESSELLATION),
+    def("ProcessTriTessFactorsMax", TRI_TESS_FACTORS).since(5, 0).only(TESSELLATION),
+    def("ProcessTriTessFactorsMin", TRI_TESS_FACTORS).since(5, 0).only(TESSELLATION),
+
+    // Wave and quad operations
+    def("QuadAll", &[sig(BOOL, &[arg("cond", BOOL)])]).since(6, 7),
+    def("QuadAny", &[sig(BOOL, &[arg("cond", BOOL)])]).since(6, 7),
+    def("QuadReadAcrossDiagonal", QUAD_READ).since(6, 0),
+    def("QuadReadAcrossX", QUAD_READ).since(6, 0),
+    def("QuadReadAcrossY", QUAD_READ).since(6, 0),
+    def("QuadReadLaneAt", &[sig(ANY, &[arg("value", ANY), arg("quad_lane_id", UINT)])]).since(6, 0),
+    def("WaveActiveAllEqual", &[sig(P::ShapeOf(0, ScalarType::Bool), &[arg("value", ANY)])]).since(6, 0),
+    def("WaveActiveAllTrue", WAVE_BOOL).since(6, 0),
+    def("WaveActiveAnyTrue", WAVE_BOOL).since(6, 0),
+    def("WaveActiveBallot", &[sig(UINT4, &[arg("expr", BOOL)])]).since(6, 0),
+    def("WaveActiveBitAnd", WAVE_BITWISE).since(6, 0),
+    def("WaveActiveBitOr", WAVE_BITWISE).since(6, 0),
+    def("WaveActiveBitXor", WAVE_BITWISE).since(6, 0),
+    def("WaveActiveCountBits", &[sig(UINT, &[arg("expr", BOOL)])]).since(6, 0),
+    def("WaveActiveMax", WAVE_NUMERIC).since(6, 0),
+    def("WaveActiveMin", WAVE_NUMERIC).since(6, 0),
+    def("WaveActiveProduct", WAVE_NUMERIC).since(6, 0),
+    def("WaveActiveSum", WAVE_NUMERIC).since(6, 0),
+    def("WaveGetLaneCount", NO_ARGS_UINT).since(6, 0),
+    def("WaveGetLaneIndex", NO_ARGS_UINT).since(6, 0),
+    def("WaveIsFirstLane", &[sig(BOOL, &[])]).since(6, 0),
+    def("WaveMatch", &[sig(UINT4, &[arg("value", ANY)])]).since(6, 5),
+    def("WaveMultiPrefixBitAnd", WAVE_MULTI_BITWISE).since(6, 5),
+    def("WaveMultiPrefixBitOr", WAVE_MULTI_BITWISE).since(6, 5),
+    def("WaveMultiPrefixBitXor", WAVE_MULTI_BITWISE).since(6, 5),
+    def("WaveMultiPrefixCountBits", &[sig(UINT, &[arg("value", BOOL), arg("mask", UINT4)])]).since(6, 5),
+    def("WaveMultiPrefixProduct", WAVE_MULTI_NUMERIC).since(6, 5),
+    def("WaveMultiPrefixSum", WAVE_MULTI_NUMERIC).since(6, 5),
+    def("WavePrefixCountBits", &[sig(UINT, &[arg("value", BOOL)])]).since(6, 0),
+    def("WavePrefixProduct", WAVE_NUMERIC).since(6, 0),
+    def("WavePrefixSum", WAVE_NUMERIC).since(6, 0),
+    def("WaveReadLaneAt", &[sig(ANY, &[arg("value", ANY), arg("lane_index", UINT)])]).since(6, 0),
+    def("WaveReadLaneFirst", &[sig(ANY, &[arg("value", ANY)])]).since(6, 0),
+
+    // Ray tracing
+    def("AcceptHitAndEndSearch", NO_ARGS_VOID).since(6, 3).only(RAY_TRACING),
+    def("CallShader", &[sig(VOID, &[arg("shader_index", UINT), inout("parameter", P::UserDefined)])]).since(6, 3).only(RAY_TRACING),
+    def("DispatchRaysDimensions", NO_ARGS_UINT3).since(6, 3).only(RAY_TRACING),
+    def("DispatchRaysIndex", NO_ARGS_UINT3).since(6, 3).only(RAY_TRACING),
+    def("GeometryIndex", NO_ARGS_UINT).since(6, 5).only(RAY_TRACING),
+    def("HitKind", NO_ARGS_UINT).since(6, 3).only(RAY_TRACING),
+    def("IgnoreHit", NO_ARGS_VOID).since(6, 3).only(RAY_TRACING),
+    def("InstanceID", NO_ARGS_UINT).since(6, 3).only(RAY_TRACING),
+    def("InstanceIndex", NO_ARGS_UINT).since(6, 3).only(RAY_TRACING),
+    def("ObjectRayDirection", NO_ARGS_FLOAT3).since(6, 3).only(RAY_TRACING),
+    def("ObjectRayOrigin", NO_ARGS_FLOAT3).since(6, 3).only(RAY_TRACING),
+    def("ObjectToWorld", NO_ARGS_FLOAT3X4).since(6, 3).only(RAY_TRACING),
+    def("ObjectToWorld3x4", NO_ARGS_FLOAT3X4).since(6, 3).only(RAY_TRACING),
+    def("ObjectToWorld4x3", NO_ARGS_FLOAT4X3).since(6, 3).only(RAY_TRACING),
+    def("PrimitiveIndex", NO_ARGS_UINT).since(6, 3).only(RAY_TRACING),
+    def("RayFlags", NO_ARGS_UINT).since(6, 3).only(RAY_TRACING),
+    def("RayTCurrent", NO_ARGS_FLOAT).since(6, 3).only(RAY_TRACING),
+    def("RayTMin", NO_ARGS_FLOAT).since(6, 3).only(RAY_TRACING),
+    def("ReportHit", &[sig(BOOL, &[arg("t_hit", FLOAT), arg("hit_kind", UINT), arg("attributes", P::UserDefined)])]).since(6, 3).only(RAY_TRACING),
+    def("TraceRay", &[sig(VOID, &[
+        arg("acceleration_structure", ACCELERATION_STRUCTURE), arg("ray_flags", UINT), arg("instance_inclusion_mask", UINT),
+        arg("ray_contribution_to_hit_group_index", UINT), arg("multiplier_for_geometry_contribution_to_hit_group_index", UINT),
+        arg("miss_shader_index", UINT), arg("ray", P::UserDefined), inout("payload", P::UserDefined),
+    ])]).since(6, 3).only(RAY_TRACING),
+    def("WorldRayDirection", NO_ARGS_FLOAT3).since(6, 3).only(RAY_TRACING),
+    def("WorldRayOrigin", NO_ARGS_FLOAT3).since(6, 3).only(RAY_TRACING),
+    def("WorldToObject", NO_ARGS_FLOAT3X4).since(6, 3).only(RAY_TRACING),
+    def("WorldToObject3x4", NO_ARGS_FLOAT3X4).since(6, 3).only(RAY_TRACING),
+    def("WorldToObject4x3", NO_ARGS_FLOAT4X3).since(6, 3).only(RAY_TRACING),
+
+    // Mesh and amplification shaders
+    def("DispatchMesh", &[sig(VOID, &[
+        arg("thread_group_count_x", UINT), arg("thread_group_count_y", UINT), arg("thread_group_count_z", UINT),
+        arg("payload", P::UserDefined),
+    ])]).since(6, 5).only(Stages::AMPLIFICATION.union(Stages::LIBRARY)),
+    def("SetMeshOutputCounts", &[sig(VOID, &[arg("vertex_count", UINT), arg("primitive_count", UINT)])])
+        .since(6, 5).only(Stages::MESH.union(Stages::LIBRARY)),
+];
+
+// Object groups sharing methods
+const SAMPLED: &[ObjectKind] = &[
+    ObjectKind::Texture1D,
+    ObjectKind::Texture1DArray,
+    ObjectKind::Texture2D,
+    ObjectKind::Texture2DArray,
+    ObjectKind::Texture3D,
+    ObjectKind::TextureCube,
+    ObjectKind::TextureCubeArray,
+];
+const GATHERED: &[ObjectKind] = &[
+    ObjectKind::Texture2D,
+    ObjectKind::Texture2DArray,
+    ObjectKind::TextureCube,
+    ObjectKind::TextureCubeArray,
+];
+const MIPPED: &[ObjectKind] = &[
+    ObjectKind::Texture1D,
+    ObjectKind::Texture1DArray,
+    ObjectKind::Texture2D,
+    ObjectKind::Texture2DArray,
+    ObjectKind::Texture3D,
+];
+const MULTISAMPLED: &[ObjectKind] = &[ObjectKind::Texture2DMS, ObjectKind::Texture2DMSArray];
+const TYPED_UAVS: &[ObjectKind] = &[
+    ObjectKind::RWTexture1D,
+    ObjectKind::RWTexture1DArray,
+    ObjectKind::RWTexture2D,
+    ObjectKind::RWTexture2DArray,
+    ObjectKind::RWTexture3D,
+    ObjectKind::Buffer,
+    ObjectKind::RWBuffer,
+];
+const BYTE_ADDRESS: &[ObjectKind] = &[
+    ObjectKind::ByteAddressBuffer,
+    ObjectKind::RWByteAddressBuffer,
+];
+const RW_BYTE_ADDRESS: &[ObjectKind] = &[ObjectKind::RWByteAddressBuffer];
+const STRUCTURED: &[ObjectKind] = &[
+    ObjectKind::StructuredBuffer,
+    ObjectKind::RWStructuredBuffer,
+    ObjectKind::AppendStructuredBuffer,
+    ObjectKind::ConsumeStructuredBuffer,
+];
+
+// Overload sets shared by several methods
+const BYTE_ADDRESS_INTERLOCKED: &[Overload] = &[
+    sig(VOID, &[arg("dest", UINT), arg("value", UINT)]),
+    sig(
+        VOID,
+        &[
+            arg("dest", UINT),
+            arg("value", UINT),
+            out("original_value", UINT),
+        ],
+    ),
+];
+const BYTE_ADDRESS_INTERLOCKED64: &[Overload] = &[
+    sig(VOID, &[arg("dest", UINT), arg("value", UINT64)]),
+    sig(
+        VOID,
+        &[
+            arg("dest", UINT),
+            arg("value", UINT64),
+            out("original_value", UINT64),
+        ],
+    ),
+];
+const GATHER: &[Overload] = &[
+    sig(P::Gathered, &[arg("s", SAMPLER), arg("location", LOCATION)]),
+    sig(
+        P::Gathered,
+        &[
+            arg("s", SAMPLER),
+            arg("location", LOCATION),
+            arg("offset", OFFSET),
+        ],
+    ),
+];
+const GATHER_CMP: &[Overload] = &[
+    sig(
+        FLOAT4,
+        &[
+            arg("s", CMP_SAMPLER),
+            arg("location", LOCATION),
+            arg("compare_value", FLOAT),
+        ],
+    ),
+    sig(
+        FLOAT4,
+        &[
+            arg("s", CMP_SAMPLER),
+            arg("location", LOCATION),
+            arg("compare_value", FLOAT),
+            arg("offset", OFFSET),
+        ],
+    ),
+];
+const SAMPLE_CMP: &[Overload] = &[
+    sig(
+        FLOAT,
+        &[
+            arg("s", CMP_SAMPLER),
+            arg("location", LOCATION),
+            arg("compare_value", FLOAT),
+        ],
+    ),
+    sig(
+        FLOAT,
+        &[
+            arg("s", CMP_SAMPLER),
+            arg("location", LOCATION),
+            arg("compare_value", FLOAT),
+            arg("offset", OFFSET),
+        ],
+    ),
+];
+const LEVEL_OF_DETAIL: &[Overload] = &[sig(FLOAT, &[arg("s", SAMPLER), arg("location", GRADIENT)])];
+
+/// `GetDimensions` overloads for textures with mip levels
+macro_rules! mip_dimensions {
+    ($($dim:literal),*) => {
+        &[
+            sig(VOID, &[arg("mip_level", UINT), $(out($dim, UINT),)* out("number_of_levels", UINT)]),
+            sig(VOID, &[$(out($dim, UINT)),*]),
+        ]
+    };
+}
+
+#[rustfmt::skip]
+static METHODS: &[Method] = &[
+    // Sampling
+    on(SAMPLED, def("Sample", &[
+        sig(P::Element, &[arg("s", SAMPLER), arg("location", LOCATION)]),
+        sig(P::Element, &[arg("s", SAMPLER), arg("location", LOCATION), arg("offset", OFFSET)]),
+        sig(P::Element, &[arg("s", SAMPLER), arg("location", LOCATION), arg("offset", OFFSET), arg("clamp", FLOAT)]),
+        sig(P::Element, &[arg("s", SAMPLER), arg("location", LOCATION), arg("offset", OFFSET), arg("clamp", FLOAT), out("status", UINT)]),
+    ]).only(DERIVATIVES)),
+    on(SAMPLED, def("SampleBias", &[
+        sig(P::Element, &[arg("s", SAMPLER), arg("location", LOCATION), arg("bias", FLOAT)]),
+        sig(P::Element, &[arg("s", SAMPLER), arg("location", LOCATION), arg("bias", FLOAT), arg("offset", OFFSET)]),
+    ]).only(DERIVATIVES)),
+    on(SAMPLED, def("SampleCmp", SAMPLE_CMP).only(DERIVATIVES)),
+    on(SAMPLED, def("SampleCmpLevelZero", SAMPLE_CMP)),
+    on(SAMPLED, def("SampleGrad", &[
+        sig(P::Element, &[arg("s", SAMPLER), arg("location", LOCATION), arg("ddx", GRADIENT), arg("ddy", GRADIENT)]),
+        sig(P::Element, &[arg("s", SAMPLER), arg("location", LOCATION), arg("ddx", GRADIENT), arg("ddy", GRADIENT), arg("offset", OFFSET)]),
+    ])),
+    on(SAMPLED, def("SampleLevel", &[
+        sig(P::Element, &[arg("s", SAMPLER), arg("location", LOCATION), arg("lod", FLOAT)]),
+        sig(P::Element, &[arg("s", SAMPLER), arg("location", LOCATION), arg("lod", FLOAT), arg("offset", OFFSET)]),
+    ])),
+    on(SAMPLED, def("CalculateLevelOfDetail", LEVEL_OF_DETAIL).since(4, 1).only(DERIVATIVES)),
+    on(SAMPLED, def("CalculateLevelOfDetailUnclamped", LEVEL_OF_DETAIL).since(4, 1).only(DERIVATIVES)),
+    on(GATHERED, def("Gather", GATHER).since(4, 1)),
+    on(GATHERED, def("GatherAlpha", GATHER).since(5, 0)),
+    on(GATHERED, def("GatherBlue", GATHER).since(5, 0)),
+    on(GATHERED, def("GatherCmp", GATHER_CMP).since(5, 0)),
+    on(GATHERED, def("GatherCmpAlpha", GATHER_CMP).since(5, 0)),
+    on(GATHERED, def("GatherCmpBlue", GATHER_CMP).since(5, 0)),
+    on(GATHERED, def("GatherCmpGreen", GATHER_CMP).since(5, 0)),
+    on(GATHERED, def("GatherCmpRed", GATHER_CMP).since(5, 0)),
+    on(GATHERED, def("GatherGreen", GATHER).since(5, 0)),
+    on(GATHERED, def("GatherRed", GATHER).since(5, 0)),
+
+    // Loads and stores
+    on(MIPPED, def("Load", &[
+        sig(P::Element, &[arg("location", LOAD)]),
+        sig(P::Element, &[arg("location", LOAD), arg("offset", OFFSET)]),
+        sig(P::Element, &[arg("location", LOAD), arg("offset", OFFSET), out("status", UINT)]),
+    ])),
+    on(MULTISAMPLED, def("Load", &[
+        sig(P::Element, &[arg("location", LOAD), arg("sample_index", INT)]),
+        sig(P::Element, &[arg("location", LOAD), arg("sample_index", INT), arg("offset", OFFSET)]),
+    ])),
+    on(TYPED_UAVS, def("Load", &[
+        sig(P::Element, &[arg("location", LOAD)]),
+        sig(P::Element, &[arg("location", LOAD), out("status", UINT)]),
+    ])),
+    on(STRUCTURED, def("Load", &[
+        sig(P::Element, &[arg("location", UINT)]),
+        sig(P::Element, &[arg("location", UINT), out("status", UINT)]),
+    ])),
+    on(BYTE_ADDRESS, def("Load", &[sig(UINT, &[arg("address", UINT)]), sig(UINT, &[arg("address", UINT), out("status", UINT)])])),
+    on(BYTE_ADDRESS, def("Load2", &[sig(UINT2, &[arg("address", UINT)]), sig(UINT2, &[arg("address", UINT), out("status", UINT)])])),
+    on(BYTE_ADDRESS, def("Load3", &[sig(UINT3, &[arg("address", UINT)]), sig(UINT3, &[arg("address", UINT), out("status", UINT)])])),
+    on(BYTE_ADDRESS, def("Load4", &[sig(UINT4, &[arg("address", UINT)]), sig(UINT4, &[arg("address", UINT), out("status", UINT)])])),
+    on(RW_BYTE_ADDRESS, def("Store", &[sig(VOID, &[arg("address", UINT), arg("value", UINT)])])),
+    on(RW_BYTE_ADDRESS, def("Store2", &[sig(VOID, &[arg("address", UINT), arg("value", UINT2)])])),
+    on(RW_BYTE_ADDRESS, def("Store3", &[sig(VOID, &[arg("address", UINT), arg("value", UINT3)])])),
+    on(RW_BYTE_ADDRESS, def("Store4", &[sig(VOID, &[arg("address", UINT), arg("value", UINT4)])])),
+
+    // Atomics on byte address buffers
+    on(RW_BYTE_ADDRESS, def("InterlockedAdd", BYTE_ADDRESS_INTERLOCKED).since(5, 0)),
+    on(RW_BYTE_ADDRESS, def("InterlockedAnd", BYTE_ADDRESS_INTERLOCKED).since(5, 0)),
+    on(RW_BYTE_ADDRESS, def("InterlockedCompareExchange", &[sig(VOID, &[
+        arg("dest", UINT), arg("compare_value", UINT), arg("value", UINT), out("original_value", UINT),
+    ])]).since(5, 0)),
+    on(RW_BYTE_ADDRESS, def("InterlockedCompareStore", &[sig(VOID, &[
+        arg("dest", UINT), arg("compare_value", UINT), arg("value", UINT),
+    ])]).since(5, 0)),
+    on(RW_BYTE_ADDRESS, def("InterlockedExchange", &[sig(VOID, &[
+        arg("dest", UINT), arg("value", UINT), out("original_value", UINT),
+    ])]).since(5, 0)),
+    on(RW_BYTE_ADDRESS, def("InterlockedMax", BYTE_ADDRESS_INTERLOCKED).since(5, 0)),
+    on(RW_BYTE_ADDRESS, def("InterlockedMin", BYTE_ADDRESS_INTERLOCKED).since(5, 0)),
+    on(RW_BYTE_ADDRESS, def("InterlockedOr", BYTE_ADDRESS_INTERLOCKED).since(5, 0)),
+    on(RW_BYTE_ADDRESS, def("InterlockedXor", BYTE_ADDRESS_INTERLOCKED).since(5, 0)),
+    on(RW_BYTE_ADDRESS, def("InterlockedAdd64", BYTE_ADDRESS_INTERLOCKED64).since(6, 6)),
+    on(RW_BYTE_ADDRESS, def("InterlockedAnd64", BYTE_ADDRESS_INTERLOCKED64).since(6, 6)),
+    on(RW_BYTE_ADDRESS, def("InterlockedCompareExchange64", &[sig(VOID, &[
+        arg("dest", UINT), arg("compare_value", UINT64), arg("value", UINT64), out("original_value", UINT64),
+    ])]).since(6, 6)),
+    on(RW_BYTE_ADDRESS, def("InterlockedCompareStore64", &[sig(VOID, &[
+        arg("dest", UINT), arg("compare_value", UINT64), arg("value", UINT64),
+    ])]).since(6, 6)),
+    on(RW_BYTE_ADDRESS, def("InterlockedExchange64", &[sig(VOID, &[
+        arg("dest", UINT), arg("value", UINT64), out("original_value", UINT64),
+    ])]).since(6, 6)),
+    on(RW_BYTE_ADDRESS, def("InterlockedMax64", BYTE_ADDRESS_INTERLOCKED64).since(6, 6)),
+    on(RW_BYTE_ADDRESS, def("InterlockedMin64", BYTE_ADDRESS_INTERLOCKED64).since(6, 6)),
+    on(RW_BYTE_ADDRESS, def("InterlockedOr64", BYTE_ADDRESS_INTERLOCKED64).since(6, 6)),
+    on(RW_BYTE_ADDRESS, def("InterlockedXor64", BYTE_ADDRESS_INTERLOCKED64).since(6, 6)),
+
+    // Structured buffers
+    on(&[ObjectKind::AppendStructuredBuffer], def("Append", &[sig(VOID, &[arg("value", P::Element)])]).since(5, 0)),
+    on(&[ObjectKind::ConsumeStructuredBuffer], def("Consume", &[sig(P::Element, &[])]).since(5, 0)),
+    on(&[ObjectKind::RWStructuredBuffer], def("DecrementCounter", NO_ARGS_UINT).since(5, 0)),
+    on(&[ObjectKind::RWStructuredBuffer], def("IncrementCounter", NO_ARGS_UINT).since(5, 0)),
+
+    // Dimension queries
+    on(&[ObjectKind::Texture1D], def("GetDimensions", mip_dimensions!("width"))),
+    on(&[ObjectKind::Texture1DArray], def("GetDimensions", mip_dimensions!("width", "elements"))),
+    on(&[ObjectKind::Texture2D, ObjectKind::TextureCube], def("GetDimensions", mip_dimensions!("width", "height"))),
+    on(&[ObjectKind::Texture2DArray, ObjectKind::TextureCubeArray], def("GetDimensions", mip_dimensions!("width", "height", "elements"))),
+    on(&[ObjectKind::Texture3D], def("GetDimensions", mip_dimensions!("width", "height", "depth"))),
+    on(&[ObjectKind::Texture2DMS], def("GetDimensions", &[sig(VOID, &[
+        out("width", UINT), out("height", UINT), out("number_of_samples", UINT),
+    ])])),
+    on(&[ObjectKind::Texture2DMSArray], def("GetDimensions", &[sig(VOID, &[
+        out("width", UINT), out("height", UINT), out("elements", UINT), out("number_of_samples", UINT),
+    ])])),
+    on(&[ObjectKind::RWTexture1D], def("GetDimensions", &[sig(VOID, &[out("width", UINT)])])),
+    on(&[ObjectKind::RWTexture1DArray], def("GetDimensions", &[sig(VOID, &[out("width", UINT), out("elements", UINT)])])),
+    on(&[ObjectKind::RWTexture2D], def("GetDimensions", &[sig(VOID, &[out("width", UINT), out("height", UINT)])])),
+    on(&[ObjectKind::RWTexture2DArray], def("GetDimensions", &[sig(VOID, &[
+        out("width", UINT), out("height", UINT), out("elements", UINT),
+    ])])),
+    on(&[ObjectKind::RWTexture3D], def("GetDimensions", &[sig(VOID, &[
+        out("width", UINT), out("height", UINT), out("depth", UINT),
+    ])])),
+    on(&[ObjectKind::Buffer, ObjectKind::RWBuffer, ObjectKind::ByteAddressBuffer, ObjectKind::RWByteAddressBuffer],
+        def("GetDimensions", &[sig(VOID, &[out("dim", UINT)])])),
+    on(STRUCTURED, def("GetDimensions", &[sig(VOID, &[out("num_structs", UINT), out("stride", UINT)])])),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intrinsic_table() {
+        let mut names = std::collections::HashSet::new();
+        for f in intrinsics() {
+            assert!(names.insert(f.name), "duplicate intrinsic {}", f.name);
+            assert!(!f.overloads.is_empty());
+        }
+
+        assert!(intrinsic("frac").is_some());
+        assert!(intrinsic("texture").is_none());
+        assert_eq!(
+            intrinsic("isnan").unwrap().signatures().collect::<Vec<_>>(),
+            ["bool<$type0> isnan(float_like<> x)"]
+        );
+        assert_eq!(
+            intrinsic("InterlockedAdd")
+                .unwrap()
+                .signatures()
+                .collect::<Vec<_>>(),
+            [
+                "void InterlockedAdd(inout int_like dest, int_like value)",
+                "void InterlockedAdd(inout int_like dest, int_like value, out int_like original_value)"
+            ]
+        );
+    }
+
+    #[test]
+    fn availability() {
+        let ps_5_0 = ShaderModel::new(ShaderStage::Pixel, 5, 0);
+        let ps_6_0 = ShaderModel::new(ShaderStage::Pixel, 6, 0);
+        let cs_6_0 = ShaderModel::new(ShaderStage::Compute, 6, 0);
+
+        let ddx = intrinsic("ddx").unwrap();
+        assert!(ddx.is_available(&ps_5_0));
+        assert!(!ddx.is_available(&cs_6_0));
+
+        let sum = intrinsic("WaveActiveSum").unwrap();
+        assert!(!sum.is_available(&ps_5_0));
+        assert!(sum.is_available(&ps_6_0));
+        assert!(sum.is_available(&cs_6_0));
+
+        let sync = intrinsic("GroupMemoryBarrierWithGroupSync").unwrap();
+        assert_eq!(
+            sync.stages.iter().collect::<Vec<_>>(),
+            [
+                ShaderStage::Compute,
+                ShaderStage::Library,
+                ShaderStage::Mesh,
+                ShaderStage::Amplification
+            ]
+        );
+    }
+
+    #[test]
+    fn object_methods() {
+        let names: Vec<_> = methods(ObjectKind::ByteAddressBuffer)
+            .map(|method| method.name)
+            .collect();
+        assert_eq!(names, ["Load", "Load2", "Load3", "Load4", "GetDimensions"]);
+
+        assert!(method(ObjectKind::RWByteAddressBuffer, "Store4").is_some());
+        assert!(method(ObjectKind::Texture2DMS, "Sample").is_none());
+        assert_eq!(
+            method(ObjectKind::Texture2DArray, "SampleLevel")
+                .unwrap()
+                .signatures()
+                .next()
+                .unwrap(),
+            "$element SampleLevel(SamplerState s, $location location, float lod)"
+        );
+
+        // Each object has a single set of overloads for a given method
+        for object in ObjectKind::ALL {
+            let mut names = std::collections::HashSet::new();
+            for method in methods(object) {
+                assert!(names.insert(method.name), "{}.{}", object, method.name);
+            }
+        }
+    }
+
+    #[test]
+    fn coordinates() {
+        use Coordinates::*;
+
+        assert_eq!(
+            Location.of(ObjectKind::Texture2DArray),
+            Some((ScalarType::Float, 3))
+        );
+        assert_eq!(Offset.of(ObjectKind::TextureCube), None);
+        assert_eq!(Load.of(ObjectKind::Texture2D), Some((ScalarType::Int, 3)));
+        assert_eq!(Load.of(ObjectKind::RWTexture2D), Some((ScalarType::Int, 2)));
+        assert_eq!(Load.of(ObjectKind::Texture2DMS), Some((ScalarType::Int, 2)));
+        assert_eq!(Load.of(ObjectKind::Buffer), Some((ScalarType::Int, 1)));
+        assert_eq!(
+            Gradient.of(ObjectKind::TextureCubeArray),
+            Some((ScalarType::Float, 3))
+        );
+    }
+}
@@ -0,0 +1,413 @@
+//! Overload resolution
+//!
+//! Calls are resolved the way DXC does: every argument of a candidate is ranked by the implicit
+//! conversion it needs (see [Rank]), and a candidate is selected if it is at least as good as
+//! every other candidate for all the arguments, and strictly better for at least one of them.
+//! Intrinsic overloads are generic, and are first instantiated for the argument types with
+//! [instantiate].
+
+use super::{
+    intrinsics::{Components, Intrinsic, Layout, Overload, ParamType},
+    types::{conversion, Conversion, ScalarType, Shape, Type},
+};
+use crate::resolver::SymbolId;
+
+/// Rank of the implicit conversion of an argument to a parameter type, from best to worst
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rank {
+    /// The types are identical, or a literal has the type it defaults to
+    Exact,
+    /// Widening within the same kind of components, e.g. `half` to `float`
+    Promotion,
+    /// Change of the kind of components, e.g. `int` to `float`
+    Conversion,
+    /// A scalar is splatted to a vector or matrix
+    Splat,
+    /// Trailing components are dropped
+    Truncation,
+}
+
+/// Rank of the implicit conversion from `from` to `to`, or `None` if there is no such conversion
+pub fn rank(from: &Type, to: &Type) -> Option<Rank> {
+    match conversion(from, to) {
+        Conversion::Identity => Some(Rank::Exact),
+        Conversion::Invalid => None,
+        Conversion::Truncation => Some(Rank::Truncation),
+        Conversion::Implicit => {
+            let (Some(from_scalar), Some(to_scalar)) = (from.scalar(), to.scalar()) else {
+                return Some(Rank::Exact);
+            };
+
+            let components = scalar_rank(from_scalar, to_scalar);
+            if from.shape() == to.shape() {
+                Some(components)
+            } else {
+                Some(components.max(Rank::Splat))
+            }
+        }
+    }
+}
+
+fn scalar_rank(from: ScalarType, to: ScalarType) -> Rank {
+    let same_kind = (from.is_float() && to.is_float())
+        || (from.is_integer() && to.is_integer() && from.is_unsigned() == to.is_unsigned());
+
+    if from == to || (from.is_literal() && from.concrete() == to) {
+        Rank::Exact
+    } else if same_kind && (from.is_literal() || from < to) {
+        Rank::Promotion
+    } else {
+        Rank::Conversion
+    }
+}
+
+/// Outcome of overload selection
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Selection<T> {
+    /// A single candidate is better than all the others
+    Unique(T),
+    /// Several candidates match, and none of them is better than the others
+    Ambiguous(Vec<T>),
+    /// No candidate matches
+    NoMatch,
+}
+
+/// Select the best of a set of viable candidates, given the rank of each of their arguments
+pub fn select<T>(candidates: Vec<(T, Vec<Rank>)>) -> Selection<T> {
+    fn better(a: &[Rank], b: &[Rank]) -> bool {
+        a.iter().zip(b).all(|(a, b)| a <= b) && a != b
+    }
+
+    let best = (0..candidates.len()).find(|&i| {
+        (0..candidates.len()).all(|j| i == j || better(&candidates[i].1, &candidates[j].1))
+    });
+
+    match best {
+        Some(index) => Selection::Unique(candidates.into_iter().nth(index).unwrap().0),
+        None if candidates.is_empty() => Selection::NoMatch,
+        None => {
+            // Report the candidates which are not worse than any other one
+            let ties: Vec<_> = candidates
+                .iter()
+                .map(|(_, ranks)| !candidates.iter().any(|(_, other)| better(other, ranks)))
+                .collect();
+
+            Selection::Ambiguous(
+                candidates
+                    .into_iter()
+                    .zip(ties)
+                    .filter_map(|((candidate, _), tie)| tie.then_some(candidate))
+                    .collect(),
+            )
+        }
+    }
+}
+
+/// Ranks of the conversions of `args` to `params`, or `None` if one of them cannot be converted
+pub fn rank_arguments(args: &[Type], params: &[Type]) -> Option<Vec<Rank>> {
+    args.iter()
+        .zip(params)
+        .map(|(arg, param)| rank(arg, param))
+        .collect()
+}
+
+/// Function called by a call expression
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Callee {
+    /// User-defined function or struct method
+    Function(SymbolId),
+    /// Intrinsic function or object method, with the selected overload
+    Intrinsic(&'static Intrinsic, &'static Overload),
+}
+
+/// Resolved call
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Call {
+    /// Selected function
+    pub callee: Callee,
+    /// Types of the parameters the arguments are converted to
+    pub params: Vec<Type>,
+    /// Return type
+    pub ret: Type,
+}
+
+/// Intrinsic overload instantiated for some argument types
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instance {
+    /// Parameter types
+    pub params: Vec<Type>,
+    /// Return type
+    pub ret: Type,
+}
+
+/// Instantiate a generic intrinsic overload for a call with arguments of types `args`, on
+/// `object` for methods. Returns `None` if the overload cannot accept these arguments.
+pub fn instantiate(overload: &Overload, object: Option<&Type>, args: &[Type]) -> Option<Instance> {
+    if overload.params.len() != args.len() {
+        return None;
+    }
+
+    // Bind each generic type to the arguments given for it
+    let mut generics = Vec::new();
+    for (param, arg) in overload.params.iter().zip(args) {
+        if let ParamType::Generic(index, _, _) = param.ty {
+            let index = index as usize;
+            if generics.len() <= index {
+                generics.resize(index + 1, Vec::new());
+            }
+
+            generics[index].push((param.ty, arg));
+        }
+    }
+
+    let bound = generics
+        .into_iter()
+        .map(|args| bind(&args))
+        .collect::<Option<Vec<_>>>()?;
+
+    let params = overload
+        .params
+        .iter()
+        .zip(args)
+        .map(|(param, arg)| instantiate_type(param.ty, &bound, object, Some(arg)))
+        .collect::<Option<Vec<_>>>()?;
+    let ret = instantiate_type(overload.ret, &bound, object, None)?;
+
+    Some(Instance { params, ret })
+}
+
+/// Type a generic parameter binds to given the arguments passed to it
+fn bind(args: &[(ParamType, &Type)]) -> Option<Type> {
+    let Some((ParamType::Generic(_, components, layout), _)) = args.first().copied() else {
+        return Some(Type::Error);
+    };
+
+    if args.iter().any(|(_, arg)| arg.is_error()) {
+        return Some(Type::Error);
+    }
+
+    // Component type: usual arithmetic conversions, then the closest accepted type
+    let mut scalar = None;
+    let mut shape = Shape::Scalar;
+    for (_, arg) in args {
+        let (s, arg_shape) = (arg.scalar()?, arg.shape()?);
+        scalar = Some(match scalar {
+            Some(scalar) => ScalarType::common(scalar, s),
+            None => s,
+        });
+
+        shape = match (shape, arg_shape) {
+            (Shape::Scalar, other) | (other, Shape::Scalar) => other,
+            (Shape::Vector(n), Shape::Vector(m)) => Shape::Vector(n.min(m)),
+            (Shape::Matrix(r, c), Shape::Matrix(r2, c2)) => Shape::Matrix(r.min(r2), c.min(c2)),
+            _ => return None,
+        };
+    }
+
+    let mut scalar = scalar?;
+    if !components.accepts(scalar) {
+        scalar = match components {
+            Components::Float => ScalarType::Float,
+            Components::Double => ScalarType::Double,
+            Components::UInt => ScalarType::UInt,
+            Components::Bool => ScalarType::Bool,
+            _ => ScalarType::Int,
+        };
+    }
+
+    let shape = match (layout, shape) {
+        (Layout::Vector(n), Shape::Scalar) => Shape::Vector(n),
+        (Layout::Vector(n), Shape::Vector(m)) if m >= n => Shape::Vector(n),
+        (layout, shape) if layout.accepts(shape) => shape,
+        _ => return None,
+    };
+
+    Some(Type::numeric(scalar.concrete(), shape))
+}
+
+fn instantiate_type(
+    ty: ParamType,
+    bound: &[Type],
+    object: Option<&Type>,
+    arg: Option<&Type>,
+) -> Option<Type> {
+    let generic = |index: u8| bound.get(index as usize).cloned().unwrap_or(Type::Error);
+    let element = || match object {
+        Some(Type::Object(kind, element)) => element
+            .as_deref()
+            .cloned()
+            .or_else(|| kind.default_element())
+            .unwrap_or(Type::Error),
+        _ => Type::Error,
+    };
+
+    Some(match ty {
+        ParamType::Void => Type::Void,
+        ParamType::Generic(index, _, _) => generic(index),
+        ParamType::ShapeOf(index, scalar) => match generic(index).shape() {
+            Some(shape) => Type::numeric(scalar, shape),
+            None => Type::Error,
+        },
+        ParamType::ComponentOf(index) => match generic(index).scalar() {
+            Some(scalar) => Type::Scalar(scalar),
+            None => Type::Error,
+        },
+        ParamType::Transposed(index) => match generic(index) {
+            Type::Matrix(scalar, rows, cols) => Type::Matrix(scalar, cols, rows),
+            _ => Type::Error,
+        },
+        ParamType::Product => match (generic(0), generic(1)) {
+            (Type::Error, _) | (_, Type::Error) => Type::Error,
+            (left, right) => Type::numeric(
+                left.scalar()?.common(right.scalar()?).concrete(),
+                mul_shape(left.shape()?, right.shape()?)?,
+            ),
+        },
+        ParamType::Fixed(scalar, shape) => Type::numeric(scalar, shape),
+        ParamType::Element => element(),
+        ParamType::Gathered => match element().scalar() {
+            Some(scalar) => Type::Vector(scalar, 4),
+            None => Type::Error,
+        },
+        ParamType::Coordinates(coordinates) => match object {
+            Some(Type::Object(kind, _)) => {
+                let (scalar, count) = coordinates.of(*kind)?;
+                if count == 1 {
+                    Type::Scalar(scalar)
+                } else {
+                    Type::Vector(scalar, count)
+                }
+            }
+            _ => Type::Error,
+        },
+        ParamType::Object(kind) => Type::Object(kind, None),
+        ParamType::UserDefined => match arg {
+            Some(arg @ (Type::Struct(_, _) | Type::Error)) => arg.clone(),
+            _ => return None,
+        },
+    })
+}
+
+/// Shape of `mul(left, right)`, where vectors are treated as row vectors on the left and column
+/// vectors on the right
+pub fn mul_shape(left: Shape, right: Shape) -> Option<Shape> {
+    match (left, right) {
+        (Shape::Scalar, other) | (other, Shape::Scalar) => Some(other),
+        (Shape::Vector(n), Shape::Vector(m)) => (n == m).then_some(Shape::Scalar),
+        (Shape::Vector(n), Shape::Matrix(rows, cols)) => (n == rows).then_some(Shape::Vector(cols)),
+        (Shape::Matrix(rows, cols), Shape::Vector(n)) => (n == cols).then_some(Shape::Vector(rows)),
+        (Shape::Matrix(rows, k), Shape::Matrix(k2, cols)) => {
+            (k == k2).then_some(Shape::Matrix(rows, cols))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::{intrinsics, types::ObjectKind};
+
+    fn ty(name: &str) -> Type {
+        Type::from_name(name).unwrap()
+    }
+
+    #[test]
+    fn conversion_ranks() {
+        assert_eq!(rank(&ty("float"), &ty("float")), Some(Rank::Exact));
+        assert_eq!(
+            rank(&Type::Scalar(ScalarType::LiteralFloat), &ty("float")),
+            Some(Rank::Exact)
+        );
+        assert_eq!(rank(&ty("half"), &ty("float")), Some(Rank::Promotion));
+        assert_eq!(
+            rank(&Type::Scalar(ScalarType::LiteralFloat), &ty("half")),
+            Some(Rank::Promotion)
+        );
+        assert_eq!(rank(&ty("int"), &ty("float")), Some(Rank::Conversion));
+        assert_eq!(rank(&ty("int"), &ty("uint")), Some(Rank::Conversion));
+        assert_eq!(rank(&ty("float"), &ty("float3")), Some(Rank::Splat));
+        assert_eq!(rank(&ty("float4"), &ty("float3")), Some(Rank::Truncation));
+        assert_eq!(rank(&ty("float2"), &ty("float3")), None);
+    }
+
+    #[test]
+    fn selection() {
+        use Rank::*;
+
+        assert_eq!(
+            select(vec![
+                ("a", vec![Exact, Conversion]),
+                ("b", vec![Exact, Exact])
+            ]),
+            Selection::Unique("b")
+        );
+        assert_eq!(
+            select(vec![
+                ("a", vec![Exact, Conversion]),
+                ("b", vec![Conversion, Exact]),
+                ("c", vec![Conversion, Conversion])
+            ]),
+            Selection::Ambiguous(vec!["a", "b"])
+        );
+        assert_eq!(select::<()>(vec![]), Selection::NoMatch);
+    }
+
+    #[test]
+    fn instantiate_intrinsics() {
+        let instance = |name: &str, args: &[&str]| {
+            let args: Vec<_> = args.iter().map(|arg| ty(arg)).collect();
+            let overload = &intrinsics::intrinsic(name).unwrap().overloads[0];
+            instantiate(overload, None, &args).map(|instance| {
+                let params: Vec<_> = instance.params.iter().map(Type::to_string).collect();
+                format!("{} ({})", instance.ret, params.join(", "))
+            })
+        };
+
+        assert_eq!(
+            instance("lerp", &["float3", "float3", "float"]).as_deref(),
+            Some("float3 (float3, float3, float3)")
+        );
+        assert_eq!(
+            instance("clamp", &["half2", "float", "float"]).as_deref(),
+            Some("float2 (float2, float2, float2)")
+        );
+        assert_eq!(instance("sin", &["int"]).as_deref(), Some("float (float)"));
+        assert_eq!(
+            instance("isnan", &["float2"]).as_deref(),
+            Some("bool2 (float2)")
+        );
+        assert_eq!(
+            instance("dot", &["int3", "int3"]).as_deref(),
+            Some("int (int3, int3)")
+        );
+        assert_eq!(
+            instance("cross", &["float4", "float3"]).as_deref(),
+            Some("float3 (float3, float3)")
+        );
+        assert_eq!(
+            instance("transpose", &["float2x3"]).as_deref(),
+            Some("float3x2 (float2x3)")
+        );
+        assert_eq!(
+            instance("mul", &["float4", "float4x3"]).as_deref(),
+            Some("float3 (float4, float4x3)")
+        );
+        assert_eq!(instance("mul", &["float3", "float4x3"]), None);
+        assert_eq!(instance("dot", &["float3x3", "float3x3"]), None);
+    }
+
+    #[test]
+    fn instantiate_methods() {
+        let texture = Type::Object(ObjectKind::Texture2DArray, Some(Box::new(ty("half4"))));
+        let sample = intrinsics::method(ObjectKind::Texture2DArray, "SampleLevel").unwrap();
+        let args = [ty("SamplerState"), ty("float3"), ty("float")];
+
+        assert_eq!(
+            instantiate(&sample.overloads[0], Some(&texture), &args),
+            Some(Instance {
+                params: vec![ty("SamplerState"), ty("float3"), ty("float")],
+                ret: ty("half4"),
+            })
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Register binding validation
+//!
+//! [check] validates the `register(...)` annotations of the resources declared at global scope:
+//! each resource must be bound to a register of its class (`b` for constant buffers, `t` for
+//! shader resource views, `u` for unordered access views and `s` for samplers), its range of
+//! registers must fit in the registers available to the target, and no two resources may claim
+//! the same register of the same space. Arrays claim one register per element, and unbounded
+//! arrays claim every register up to the end of their space.
+//!
+//! # Example
+//!
+//! ```
+//! use hlsl_lang::{ast, parse::Parsable, resolver, semantic::registers};
+//!
+//! let tu = ast::TranslationUnit::parse(
+//!     "Texture2D a[4] : register(t0); Texture2D b : register(t2);",
+//! )
+//! .unwrap();
+//! let symbols = resolver::resolve(&tu);
+//! let diagnostics = registers::check(&symbols, &registers::RegisterLimits::default());
+//!
+//! assert_eq!(
+//!     diagnostics[0].to_string(),
+//!     "`b` overlaps `a` at register `t2`"
+//! );
+//! ```
+
+use crate::{
+    reflection::bindings::annotated_resources,
+    resolver::SymbolTable,
+    root_signature::{DescriptorCount, Register, RegisterClass},
+};
+
+use super::{consteval, Diagnostic, DiagnosticKind};
+
+/// Number of registers of each class available to a shader
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RegisterLimits {
+    /// Number of `b` registers
+    pub constant_buffers: u64,
+    /// Number of `t` registers
+    pub shader_resources: u64,
+    /// Number of `u` registers
+    pub unordered_access: u64,
+    /// Number of `s` registers
+    pub samplers: u64,
+}
+
+impl RegisterLimits {
+    /// Shader model 5.0, i.e. Direct3D 11.1 slots
+    pub const SM5_0: Self = Self {
+        constant_buffers: 14,
+        shader_resources: 128,
+        unordered_access: 64,
+        samplers: 16,
+    };
+
+    /// Shader model 5.1 and later, where register indices span the whole 32-bit range
+    pub const SM5_1: Self = Self {
+        constant_buffers: 1 << 32,
+        shader_resources: 1 << 32,
+        unordered_access: 1 << 32,
+        samplers: 1 << 32,
+    };
+
+    /// Number of registers of the given class
+    pub fn count(&self, class: RegisterClass) -> u64 {
+        match class {
+            RegisterClass::ConstantBuffer => self.constant_buffers,
+            RegisterClass::ShaderResource => self.shader_resources,
+            RegisterClass::UnorderedAccess => self.unordered_access,
+            RegisterClass::Sampler => self.samplers,
+        }
+    }
+}
+
+impl Default for RegisterLimits {
+    fn default() -> Self {
+        Self::SM5_1
+    }
+}
+
+/// Validate the register annotations of the resources declared at global scope. Diagnostics
+/// point at the offending `register(...)` clause.
+pub fn check(symbols: &SymbolTable<'_>, limits: &RegisterLimits) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut claimed: Vec<(String, Register, u64)> = Vec::new();
+
+    for (res, binding) in annotated_resources(symbols) {
+        let Some(binding) = binding else {
+            continue;
+        };
+
+        let expected = res.register_class();
+        let register = match res.register {
+            Some(register) if register.class == expected => register,
+            register => {
+                let register = match register {
+                    Some(register) => register.to_string(),
+                    None => match consteval::eval(&binding.index, symbols)
+                        .and_then(|index| index.as_int())
+                    {
+                        Some(index) => format!("{}{}", binding.register_type.as_str(), index),
+                        None => binding.register_type.as_str().to_owned(),
+                    },
+                };
+
+                diagnostics.push(Diagnostic::new(
+                    DiagnosticKind::RegisterClassMismatch {
+                        name: res.name.as_str().into(),
+                        kind: res.kind,
+                        register: register.into(),
+                        expected,
+                    },
+                    binding.span,
+                ));
+                continue;
+            }
+        };
+
+        let count = limits.count(register.class);
+        let end = match res.count {
+            DescriptorCount::Bounded(n) => u64::from(register.index) + u64::from(n),
+            DescriptorCount::Unbounded => count.max(u64::from(register.index) + 1),
+        };
+
+        if end > count {
+            diagnostics.push(Diagnostic::new(
+                DiagnosticKind::RegisterOutOfRange {
+                    name: res.name.as_str().into(),
+                    register,
+                    last: u32::try_from(count.saturating_sub(1)).unwrap_or(u32::MAX),
+                },
+                binding.span,
+            ));
+        }
+
+        if let Some((other, other_register, _)) = claimed.iter().find(|(_, other, other_end)| {
+            other.class == register.class
+                && other.space == register.space
+                && u64::from(other.index) < end
+                && u64::from(register.index) < *other_end
+        }) {
+            diagnostics.push(Diagnostic::new(
+                DiagnosticKind::RegisterOverlap {
+                    name: res.name.as_str().into(),
+                    other: other.as_str().into(),
+                    register: Register {
+                        index: register.index.max(other_register.index),
+                        ..register
+                    },
+                },
+                binding.span,
+            ));
+        }
+
+        claimed.push((res.name, register, end));
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ast, parse::Parsable, resolver};
+
+    fn check_src(src: &str, limits: RegisterLimits) -> Vec<String> {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+        let symbols = resolver::resolve(&tu);
+        let diagnostics = check(&symbols, &limits);
+        assert!(diagnostics.iter().all(|diag| diag.span.is_some()));
+        diagnostics.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn register_class_mismatch() {
+        let diagnostics = check_src(
+            r#"
+            Texture2D a : register(u0);
+            RWStructuredBuffer<float4> b : register(t0);
+            SamplerState c : register(b0);
+            cbuffer D : register(s0) { float d; };
+            Texture2D e : register(x1);
+            Texture2D f : register(t1);
+            RWStructuredBuffer<uint> g : register(u1, space2);
+            SamplerComparisonState h : register(s1);
+            cbuffer I : register(b0) { float i; };
+            "#,
+            RegisterLimits::default(),
+        );
+
+        assert_eq!(
+            diagnostics,
+            [
+                "`a` of type `Texture2D` cannot be bound to register `u0`; only `t` registers are allowed",
+                "`b` of type `RWStructuredBuffer` cannot be bound to register `t0`; only `u` registers are allowed",
+                "`c` of type `SamplerState` cannot be bound to register `b0`; only `s` registers are allowed",
+                "`D` of type `cbuffer` cannot be bound to register `s0`; only `b` registers are allowed",
+                "`e` of type `Texture2D` cannot be bound to register `x1`; only `t` registers are allowed",
+            ]
+        );
+    }
+
+    #[test]
+    fn register_overlap() {
+        let diagnostics = check_src(
+            r#"
+            Texture2D a[4] : register(t2);
+            Texture2D b : register(t5);
+            Texture2D c : register(t6);
+            Texture2D d : register(t5, space1);
+            Texture2D e[] : register(t10);
+            Texture2D f : register(t100);
+            RWStructuredBuffer<float> g : register(u5);
+            cbuffer H : register(b0) { float h; };
+            cbuffer I : register(b0) { float i; };
+            "#,
+            RegisterLimits::default(),
+        );
+
+        assert_eq!(
+            diagnostics,
+            [
+                "`b` overlaps `a` at register `t5`",
+                "`f` overlaps `e` at register `t100`",
+                "`I` overlaps `H` at register `b0`",
+            ]
+        );
+    }
+
+    #[test]
+    fn register_out_of_range() {
+        let src = r#"
+            cbuffer A : register(b13) { float a; };
+            cbuffer B : register(b14) { float b; };
+            Texture2D c[4] : register(t126);
+            SamplerState d : register(s15);
+            SamplerState e : register(s16);
+            Texture2D f[] : register(t0, space1);
+            "#;
+
+        assert_eq!(
+            check_src(src, RegisterLimits::SM5_0),
+            [
+                "`B` at register `b14` extends past the last `b` register, `b13`",
+                "`c` at register `t126` extends past the last `t` register, `t127`",
+                "`e` at register `s16` extends past the last `s` register, `s15`",
+            ]
+        );
+        assert!(check_src(src, RegisterLimits::SM5_1).is_empty());
+    }
+}
@@ -0,0 +1,956 @@
+//! HLSL types
+//!
+//! The AST describes types the way they were spelled. This module defines [Type], the canonical
+//! form used by semantic analysis: numeric types are scalars, vectors or matrices of a
+//! [ScalarType], and resources are [ObjectKind] instances with an optional element type.
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use rserde::{Deserialize, Serialize};
+
+use crate::{
+    ast::{self, SmolStr},
+    resolver::{SymbolId, SymbolTable},
+};
+
+use super::consteval;
+
+/// Scalar component type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ScalarType {
+    /// `bool`
+    Bool,
+    /// Type of an unsuffixed integer literal, which adopts the type of the other operand
+    LiteralInt,
+    /// `min12int`
+    Min12Int,
+    /// `min16int`
+    Min16Int,
+    /// `min16uint`
+    Min16UInt,
+    /// `int16_t`
+    Int16,
+    /// `uint16_t`
+    UInt16,
+    /// `int`
+    Int,
+    /// `uint`
+    UInt,
+    /// `int64_t`
+    Int64,
+    /// `uint64_t`
+    UInt64,
+    /// Type of an unsuffixed floating-point literal, which adopts the type of the other operand
+    LiteralFloat,
+    /// `min10float`
+    Min10Float,
+    /// `min16float`
+    Min16Float,
+    /// `half`
+    Half,
+    /// `float16_t`
+    Float16,
+    /// `float`
+    Float,
+    /// `double`
+    Double,
+}
+
+impl ScalarType {
+    /// HLSL spelling of this type
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bool => "bool",
+            Self::LiteralInt => "literal int",
+            Self::Min12Int => "min12int",
+            Self::Min16Int => "min16int",
+            Self::Min16UInt => "min16uint",
+            Self::Int16 => "int16_t",
+            Self::UInt16 => "uint16_t",
+            Self::Int => "int",
+            Self::UInt => "uint",
+            Self::Int64 => "int64_t",
+            Self::UInt64 => "uint64_t",
+            Self::LiteralFloat => "literal float",
+            Self::Min10Float => "min10float",
+            Self::Min16Float => "min16float",
+            Self::Half => "half",
+            Self::Float16 => "float16_t",
+            Self::Float => "float",
+            Self::Double => "double",
+        }
+    }
+
+    /// Parse a scalar type name, e.g. `min16float`
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "bool" => Self::Bool,
+            "min12int" => Self::Min12Int,
+            "min16int" => Self::Min16Int,
+            "min16uint" => Self::Min16UInt,
+            "int16_t" => Self::Int16,
+            "uint16_t" => Self::UInt16,
+            "int" | "int32_t" | "dword" => Self::Int,
+            "uint" | "uint32_t" => Self::UInt,
+            "int64_t" => Self::Int64,
+            "uint64_t" => Self::UInt64,
+            "min10float" => Self::Min10Float,
+            "min16float" => Self::Min16Float,
+            "half" => Self::Half,
+            "float16_t" => Self::Float16,
+            "float" | "float32_t" => Self::Float,
+            "double" | "float64_t" => Self::Double,
+            _ => return None,
+        })
+    }
+
+    /// `true` for floating-point types, including floating-point literals
+    pub fn is_float(self) -> bool {
+        self >= Self::LiteralFloat
+    }
+
+    /// `true` for integer types, including integer literals
+    pub fn is_integer(self) -> bool {
+        !self.is_float() && self != Self::Bool
+    }
+
+    /// `true` for unsigned integer types
+    pub fn is_unsigned(self) -> bool {
+        matches!(
+            self,
+            Self::Min16UInt | Self::UInt16 | Self::UInt | Self::UInt64
+        )
+    }
+
+    /// `true` for the types of unsuffixed literals
+    pub fn is_literal(self) -> bool {
+        matches!(self, Self::LiteralInt | Self::LiteralFloat)
+    }
+
+    /// Type a literal takes when it has no other operand to adopt the type of
+    pub fn concrete(self) -> Self {
+        match self {
+            Self::LiteralInt => Self::Int,
+            Self::LiteralFloat => Self::Float,
+            other => other,
+        }
+    }
+
+    /// Component type of a binary arithmetic operation between `self` and `other`, following
+    /// the usual arithmetic conversions: literals adopt the type of the other operand, booleans
+    /// are promoted to `int`, and the operand with the highest rank wins.
+    pub fn common(self, other: Self) -> Self {
+        match (self.is_literal(), other.is_literal()) {
+            (true, false) => Self::adopt(self, other),
+            (false, true) => Self::adopt(other, self),
+            _ => {
+                let promote = |t: Self| if t == Self::Bool { Self::Int } else { t };
+                promote(self).max(promote(other))
+            }
+        }
+    }
+
+    fn adopt(literal: Self, other: Self) -> Self {
+        if other.is_float() || (literal == Self::LiteralInt && other != Self::Bool) {
+            other
+        } else {
+            literal.concrete()
+        }
+    }
+}
+
+impl fmt::Display for ScalarType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Scalar type names which can be followed by vector or matrix dimensions
+const SCALAR_NAMES: &[&str] = &[
+    "bool",
+    "int",
+    "uint",
+    "dword",
+    "half",
+    "float",
+    "double",
+    "min10float",
+    "min16float",
+    "min12int",
+    "min16int",
+    "min16uint",
+    "int16_t",
+    "uint16_t",
+    "int32_t",
+    "uint32_t",
+    "int64_t",
+    "uint64_t",
+    "float16_t",
+    "float32_t",
+    "float64_t",
+];
+
+/// Kind of resource or sampler object
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum ObjectKind {
+    /// `Texture1D`
+    Texture1D,
+    /// `Texture1DArray`
+    Texture1DArray,
+    /// `Texture2D`
+    Texture2D,
+    /// `Texture2DArray`
+    Texture2DArray,
+    /// `Texture2DMS`
+    Texture2DMS,
+    /// `Texture2DMSArray`
+    Texture2DMSArray,
+    /// `Texture3D`
+    Texture3D,
+    /// `TextureCube`
+    TextureCube,
+    /// `TextureCubeArray`
+    TextureCubeArray,
+    /// `RWTexture1D`
+    RWTexture1D,
+    /// `RWTexture1DArray`
+    RWTexture1DArray,
+    /// `RWTexture2D`
+    RWTexture2D,
+    /// `RWTexture2DArray`
+    RWTexture2DArray,
+    /// `RWTexture3D`
+    RWTexture3D,
+    /// `Buffer`
+    Buffer,
+    /// `RWBuffer`
+    RWBuffer,
+    /// `ByteAddressBuffer`
+    ByteAddressBuffer,
+    /// `RWByteAddressBuffer`
+    RWByteAddressBuffer,
+    /// `StructuredBuffer`
+    StructuredBuffer,
+    /// `RWStructuredBuffer`
+    RWStructuredBuffer,
+    /// `AppendStructuredBuffer`
+    AppendStructuredBuffer,
+    /// `ConsumeStructuredBuffer`
+    ConsumeStructuredBuffer,
+    /// `ConstantBuffer`
+    ConstantBuffer,
+    /// `TextureBuffer`
+    TextureBuffer,
+    /// `SamplerState`
+    SamplerState,
+    /// `SamplerComparisonState`
+    SamplerComparisonState,
+    /// `RaytracingAccelerationStructure`
+    RaytracingAccelerationStructure,
+}
+
+impl ObjectKind {
+    /// All the object kinds
+    pub const ALL: [Self; 27] = [
+        Self::Texture1D,
+        Self::Texture1DArray,
+        Self::Texture2D,
+        Self::Texture2DArray,
+        Self::Texture2DMS,
+        Self::Texture2DMSArray,
+        Self::Texture3D,
+        Self::TextureCube,
+        Self::TextureCubeArray,
+        Self::RWTexture1D,
+        Self::RWTexture1DArray,
+        Self::RWTexture2D,
+        Self::RWTexture2DArray,
+        Self::RWTexture3D,
+        Self::Buffer,
+        Self::RWBuffer,
+        Self::ByteAddressBuffer,
+        Self::RWByteAddressBuffer,
+        Self::StructuredBuffer,
+        Self::RWStructuredBuffer,
+        Self::AppendStructuredBuffer,
+        Self::ConsumeStructuredBuffer,
+        Self::ConstantBuffer,
+        Self::TextureBuffer,
+        Self::SamplerState,
+        Self::SamplerComparisonState,
+        Self::RaytracingAccelerationStructure,
+    ];
+
+    /// HLSL spelling of this object type
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Texture1D => "Texture1D",
+            Self::Texture1DArray => "Texture1DArray",
+            Self::Texture2D => "Texture2D",
+            Self::Texture2DArray => "Texture2DArray",
+            Self::Texture2DMS => "Texture2DMS",
+            Self::Texture2DMSArray => "Texture2DMSArray",
+            Self::Texture3D => "Texture3D",
+            Self::TextureCube => "TextureCube",
+            Self::TextureCubeArray => "TextureCubeArray",
+            Self::RWTexture1D => "RWTexture1D",
+            Self::RWTexture1DArray => "RWTexture1DArray",
+            Self::RWTexture2D => "RWTexture2D",
+            Self::RWTexture2DArray => "RWTexture2DArray",
+            Self::RWTexture3D => "RWTexture3D",
+            Self::Buffer => "Buffer",
+            Self::RWBuffer => "RWBuffer",
+            Self::ByteAddressBuffer => "ByteAddressBuffer",
+            Self::RWByteAddressBuffer => "RWByteAddressBuffer",
+            Self::StructuredBuffer => "StructuredBuffer",
+            Self::RWStructuredBuffer => "RWStructuredBuffer",
+            Self::AppendStructuredBuffer => "AppendStructuredBuffer",
+            Self::ConsumeStructuredBuffer => "ConsumeStructuredBuffer",
+            Self::ConstantBuffer => "ConstantBuffer",
+            Self::TextureBuffer => "TextureBuffer",
+            Self::SamplerState => "SamplerState",
+            Self::SamplerComparisonState => "SamplerComparisonState",
+            Self::RaytracingAccelerationStructure => "RaytracingAccelerationStructure",
+        }
+    }
+
+    /// Parse an object type name, e.g. `RWTexture2D`
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// Element type of this object when no template argument is given
+    pub fn default_element(self) -> Option<Type> {
+        match self {
+            Self::ByteAddressBuffer
+            | Self::RWByteAddressBuffer
+            | Self::SamplerState
+            | Self::SamplerComparisonState
+            | Self::RaytracingAccelerationStructure
+            | Self::StructuredBuffer
+            | Self::RWStructuredBuffer
+            | Self::AppendStructuredBuffer
+            | Self::ConsumeStructuredBuffer
+            | Self::ConstantBuffer
+            | Self::TextureBuffer => None,
+            _ => Some(Type::Vector(ScalarType::Float, 4)),
+        }
+    }
+
+    /// `true` for read-write (unordered access) resources
+    pub fn is_rw(self) -> bool {
+        matches!(
+            self,
+            Self::RWTexture1D
+                | Self::RWTexture1DArray
+                | Self::RWTexture2D
+                | Self::RWTexture2DArray
+                | Self::RWTexture3D
+                | Self::RWBuffer
+                | Self::RWByteAddressBuffer
+                | Self::RWStructuredBuffer
+                | Self::AppendStructuredBuffer
+                | Self::ConsumeStructuredBuffer
+        )
+    }
+
+    /// `true` for sampler objects
+    pub fn is_sampler(self) -> bool {
+        matches!(self, Self::SamplerState | Self::SamplerComparisonState)
+    }
+
+    /// Number of coordinates addressing a texel, not counting the array index, for textures and
+    /// typed buffers
+    pub fn dimension(self) -> Option<u8> {
+        match self {
+            Self::Texture1D
+            | Self::Texture1DArray
+            | Self::RWTexture1D
+            | Self::RWTexture1DArray
+            | Self::Buffer
+            | Self::RWBuffer => Some(1),
+            Self::Texture2D
+            | Self::Texture2DArray
+            | Self::Texture2DMS
+            | Self::Texture2DMSArray
+            | Self::RWTexture2D
+            | Self::RWTexture2DArray => Some(2),
+            Self::Texture3D | Self::TextureCube | Self::TextureCubeArray | Self::RWTexture3D => {
+                Some(3)
+            }
+            _ => None,
+        }
+    }
+
+    /// `true` for texture arrays
+    pub fn is_array(self) -> bool {
+        matches!(
+            self,
+            Self::Texture1DArray
+                | Self::Texture2DArray
+                | Self::Texture2DMSArray
+                | Self::TextureCubeArray
+                | Self::RWTexture1DArray
+                | Self::RWTexture2DArray
+        )
+    }
+
+    /// `true` for cube textures
+    pub fn is_cube(self) -> bool {
+        matches!(self, Self::TextureCube | Self::TextureCubeArray)
+    }
+
+    /// `true` for multisampled textures
+    pub fn is_multisampled(self) -> bool {
+        matches!(self, Self::Texture2DMS | Self::Texture2DMSArray)
+    }
+
+    /// `true` for textures which have a mip chain, i.e. read-only textures which are not
+    /// multisampled
+    pub fn has_mips(self) -> bool {
+        self.dimension().is_some()
+            && !self.is_rw()
+            && !self.is_multisampled()
+            && !matches!(self, Self::Buffer)
+    }
+}
+
+impl fmt::Display for ObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A semantic type
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Type {
+    /// `void`
+    Void,
+    /// Scalar type
+    Scalar(ScalarType),
+    /// Vector type, e.g. `float3`
+    Vector(ScalarType, u8),
+    /// Matrix type, with its number of rows and columns, e.g. `float3x4`
+    Matrix(ScalarType, u8, u8),
+    /// Array type, with its size if known
+    Array(Box<Type>, Option<u32>),
+    /// User-defined struct type
+    Struct(SymbolId, SmolStr),
+    /// Resource or sampler object, with its element type
+    Object(ObjectKind, Option<Box<Type>>),
+    /// `string`
+    String,
+    /// Type of an expression which could not be typed. Never reported in diagnostics.
+    Error,
+}
+
+impl Type {
+    /// `float` type
+    pub const FLOAT: Self = Self::Scalar(ScalarType::Float);
+    /// `int` type
+    pub const INT: Self = Self::Scalar(ScalarType::Int);
+    /// `uint` type
+    pub const UINT: Self = Self::Scalar(ScalarType::UInt);
+    /// `bool` type
+    pub const BOOL: Self = Self::Scalar(ScalarType::Bool);
+
+    /// Numeric type with the given component type and shape. Vectors and matrices of size 1 are
+    /// kept as such, since they are distinct types in HLSL.
+    pub fn numeric(scalar: ScalarType, shape: Shape) -> Self {
+        match shape {
+            Shape::Scalar => Self::Scalar(scalar),
+            Shape::Vector(n) => Self::Vector(scalar, n),
+            Shape::Matrix(r, c) => Self::Matrix(scalar, r, c),
+        }
+    }
+
+    /// Component type of a numeric type
+    pub fn scalar(&self) -> Option<ScalarType> {
+        match self {
+            Self::Scalar(s) | Self::Vector(s, _) | Self::Matrix(s, _, _) => Some(*s),
+            _ => None,
+        }
+    }
+
+    /// Shape of a numeric type
+    pub fn shape(&self) -> Option<Shape> {
+        match self {
+            Self::Scalar(_) => Some(Shape::Scalar),
+            Self::Vector(_, n) => Some(Shape::Vector(*n)),
+            Self::Matrix(_, r, c) => Some(Shape::Matrix(*r, *c)),
+            _ => None,
+        }
+    }
+
+    /// Number of scalar components of a numeric type
+    pub fn component_count(&self) -> Option<usize> {
+        self.shape().map(Shape::len)
+    }
+
+    /// `true` for scalars, vectors and matrices
+    pub fn is_numeric(&self) -> bool {
+        self.scalar().is_some()
+    }
+
+    /// `true` for [Type::Error]
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Error)
+    }
+
+    /// Same shape, with another component type
+    pub fn with_scalar(&self, scalar: ScalarType) -> Self {
+        match self.shape() {
+            Some(shape) => Self::numeric(scalar, shape),
+            None => self.clone(),
+        }
+    }
+
+    /// Replace literal component types by the type they default to
+    pub fn concrete(&self) -> Self {
+        match self.scalar() {
+            Some(scalar) => self.with_scalar(scalar.concrete()),
+            None => self.clone(),
+        }
+    }
+
+    /// Element type of an indexing operation on this type
+    pub fn index(&self) -> Option<Type> {
+        match self {
+            Self::Array(element, _) => Some((**element).clone()),
+            Self::Vector(s, _) => Some(Self::Scalar(*s)),
+            Self::Matrix(s, _, c) => Some(Self::Vector(*s, *c)),
+            Self::Object(kind, element) if !kind.is_sampler() => {
+                element.as_deref().cloned().or_else(|| match kind {
+                    ObjectKind::ByteAddressBuffer | ObjectKind::RWByteAddressBuffer => {
+                        Some(Self::UINT)
+                    }
+                    _ => None,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse a built-in type name, e.g. `half3`, `min16float2x2`, `SamplerState`
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some(kind) = ObjectKind::from_name(name) {
+            return Some(Self::Object(kind, kind.default_element().map(Box::new)));
+        }
+
+        if name == "string" {
+            return Some(Self::String);
+        }
+
+        if name == "void" {
+            return Some(Self::Void);
+        }
+
+        if let Some(scalar) = ScalarType::from_name(name) {
+            return Some(Self::Scalar(scalar));
+        }
+
+        // Vector and matrix types: <scalar><rows> or <scalar><rows>x<cols>. Try the longest
+        // scalar names first, so that `int16_t2` is not read as `int` followed by `16_t2`.
+        let (scalar, dims) = SCALAR_NAMES
+            .iter()
+            .filter_map(|scalar| Some((*scalar, name.strip_prefix(scalar)?)))
+            .max_by_key(|(scalar, _)| scalar.len())?;
+        let scalar = ScalarType::from_name(scalar)?;
+
+        let dim = |s: &str| match s {
+            "1" | "2" | "3" | "4" => s.parse::<u8>().ok(),
+            _ => None,
+        };
+
+        match dims.split_once('x') {
+            Some((rows, cols)) => Some(Self::Matrix(scalar, dim(rows)?, dim(cols)?)),
+            None => Some(Self::Vector(scalar, dim(dims)?)),
+        }
+    }
+
+    /// Semantic type of a type specifier, with the array specifier attached to the declared name
+    pub fn from_specifier(
+        ty: &ast::TypeSpecifier,
+        array: Option<&ast::ArraySpecifier>,
+        symbols: &SymbolTable<'_>,
+    ) -> Self {
+        Self::from_non_array(&ty.ty, symbols)
+            .with_array(array, symbols)
+            .with_array(ty.array_specifier.as_ref(), symbols)
+    }
+
+    /// Semantic type of a type specifier without its array dimensions
+    pub fn from_non_array(ty: &ast::TypeSpecifierNonArray, symbols: &SymbolTable<'_>) -> Self {
+        use ast::TypeSpecifierNonArrayData as T;
+        use ScalarType::*;
+
+        let object = |kind: ObjectKind| Self::Object(kind, kind.default_element().map(Box::new));
+
+        match &**ty {
+            T::Void => Self::Void,
+            T::Bool => Self::Scalar(Bool),
+            T::Int => Self::Scalar(Int),
+            T::UInt => Self::Scalar(UInt),
+            T::Float => Self::Scalar(Float),
+            T::Double => Self::Scalar(Double),
+            T::Vec2 => Self::Vector(Float, 2),
+            T::Vec3 => Self::Vector(Float, 3),
+            T::Vec4 => Self::Vector(Float, 4),
+            T::DVec2 => Self::Vector(Double, 2),
+            T::DVec3 => Self::Vector(Double, 3),
+            T::DVec4 => Self::Vector(Double, 4),
+            T::BVec2 => Self::Vector(Bool, 2),
+            T::BVec3 => Self::Vector(Bool, 3),
+            T::BVec4 => Self::Vector(Bool, 4),
+            T::IVec2 => Self::Vector(Int, 2),
+            T::IVec3 => Self::Vector(Int, 3),
+            T::IVec4 => Self::Vector(Int, 4),
+            T::UVec2 => Self::Vector(UInt, 2),
+            T::UVec3 => Self::Vector(UInt, 3),
+            T::UVec4 => Self::Vector(UInt, 4),
+            T::Mat2 | T::Mat22 => Self::Matrix(Float, 2, 2),
+            T::Mat3 | T::Mat33 => Self::Matrix(Float, 3, 3),
+            T::Mat4 | T::Mat44 => Self::Matrix(Float, 4, 4),
+            T::Mat23 => Self::Matrix(Float, 2, 3),
+            T::Mat24 => Self::Matrix(Float, 2, 4),
+            T::Mat32 => Self::Matrix(Float, 3, 2),
+            T::Mat34 => Self::Matrix(Float, 3, 4),
+            T::Mat42 => Self::Matrix(Float, 4, 2),
+            T::Mat43 => Self::Matrix(Float, 4, 3),
+            T::DMat2 | T::DMat22 => Self::Matrix(Double, 2, 2),
+            T::DMat3 | T::DMat33 => Self::Matrix(Double, 3, 3),
+            T::DMat4 | T::DMat44 => Self::Matrix(Double, 4, 4),
+            T::DMat23 => Self::Matrix(Double, 2, 3),
+            T::DMat24 => Self::Matrix(Double, 2, 4),
+            T::DMat32 => Self::Matrix(Double, 3, 2),
+            T::DMat34 => Self::Matrix(Double, 3, 4),
+            T::DMat42 => Self::Matrix(Double, 4, 2),
+            T::DMat43 => Self::Matrix(Double, 4, 3),
+            T::UMat2 | T::UMat22 => Self::Matrix(UInt, 2, 2),
+            T::UMat3 | T::UMat33 => Self::Matrix(UInt, 3, 3),
+            T::UMat4 | T::UMat44 => Self::Matrix(UInt, 4, 4),
+            T::UMat23 => Self::Matrix(UInt, 2, 3),
+            T::UMat24 => Self::Matrix(UInt, 2, 4),
+            T::UMat32 => Self::Matrix(UInt, 3, 2),
+            T::UMat34 => Self::Matrix(UInt, 3, 4),
+            T::UMat42 => Self::Matrix(UInt, 4, 2),
+            T::UMat43 => Self::Matrix(UInt, 4, 3),
+            T::Texture1D => object(ObjectKind::Texture1D),
+            T::Texture1DArray => object(ObjectKind::Texture1DArray),
+            T::Texture2D | T::Texture2DRect => object(ObjectKind::Texture2D),
+            T::Texture2DArray => object(ObjectKind::Texture2DArray),
+            T::Texture2DMs => object(ObjectKind::Texture2DMS),
+            T::Texture2DMsArray => object(ObjectKind::Texture2DMSArray),
+            T::Texture3D => object(ObjectKind::Texture3D),
+            T::TextureCube => object(ObjectKind::TextureCube),
+            T::TextureCubeArray => object(ObjectKind::TextureCubeArray),
+            T::Image1D => object(ObjectKind::RWTexture1D),
+            T::Image1DArray => object(ObjectKind::RWTexture1DArray),
+            T::Image2D | T::Image2DRect => object(ObjectKind::RWTexture2D),
+            T::Image2DArray => object(ObjectKind::RWTexture2DArray),
+            T::Image3D => object(ObjectKind::RWTexture3D),
+            T::SamplerBuffer => object(ObjectKind::Buffer),
+            T::ImageBuffer => object(ObjectKind::RWBuffer),
+            T::TextureBuffer => object(ObjectKind::TextureBuffer),
+            T::Sampler => object(ObjectKind::SamplerState),
+            T::StructuredBuffer => object(ObjectKind::StructuredBuffer),
+            T::RWStructuredBuffer => object(ObjectKind::RWStructuredBuffer),
+            T::StructuredBufferTemplate(inner) => Self::Object(
+                ObjectKind::StructuredBuffer,
+                Some(Box::new(Self::from_specifier(inner, None, symbols))),
+            ),
+            T::RWStructuredBufferTemplate(inner) => Self::Object(
+                ObjectKind::RWStructuredBuffer,
+                Some(Box::new(Self::from_specifier(inner, None, symbols))),
+            ),
+            T::String => Self::String,
+            T::Struct(spec) => match symbols.struct_specifier(spec) {
+                Some(id) => Self::Struct(id, symbols.symbol(id).name.clone()),
+                None => Self::Error,
+            },
+            T::TypeName(name) => match symbols.type_name(name) {
+                Some(id) => Self::Struct(id, symbols.symbol(id).name.clone()),
+                None => Self::from_name(name.as_str()).unwrap_or(Self::Error),
+            },
+            // GLSL-only types
+            _ => Self::Error,
+        }
+    }
+
+    /// Wrap this type in the dimensions of an array specifier, outermost dimension first. Sizes
+    /// may refer to constants declared in `symbols`.
+    pub fn with_array(
+        self,
+        array: Option<&ast::ArraySpecifier>,
+        symbols: &SymbolTable<'_>,
+    ) -> Self {
+        let Some(array) = array else {
+            return self;
+        };
+
+        array.dimensions.iter().rev().fold(self, |ty, dim| {
+            let size = match &**dim {
+                ast::ArraySpecifierDimensionData::Unsized => None,
+                ast::ArraySpecifierDimensionData::ExplicitlySized(size) => {
+                    consteval::eval(size, symbols)
+                        .and_then(|size| size.as_int())
+                        .and_then(|n| u32::try_from(n).ok())
+                }
+            };
+
+            Self::Array(Box::new(ty), size)
+        })
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Void => f.write_str("void"),
+            Self::Scalar(s) => write!(f, "{}", s),
+            Self::Vector(s, n) => write!(f, "{}{}", s, n),
+            Self::Matrix(s, r, c) => write!(f, "{}{}x{}", s, r, c),
+            Self::Array(_, _) => {
+                // Print the element type first, then all the dimensions
+                let mut dims = Vec::new();
+                let mut ty = self;
+                while let Self::Array(inner, size) = ty {
+                    dims.push(*size);
+                    ty = inner;
+                }
+
+                write!(f, "{}", ty)?;
+                for dim in dims {
+                    match dim {
+                        Some(n) => write!(f, "[{}]", n)?,
+                        None => f.write_str("[]")?,
+                    }
+                }
+
+                Ok(())
+            }
+            Self::Struct(_, name) => f.write_str(name),
+            Self::Object(kind, element) => match element {
+                Some(element) if Some(&**element) != kind.default_element().as_ref() => {
+                    write!(f, "{}<{}>", kind, element)
+                }
+                _ => write!(f, "{}", kind),
+            },
+            Self::String => f.write_str("string"),
+            Self::Error => f.write_str("<error>"),
+        }
+    }
+}
+
+/// Shape of a numeric type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Shape {
+    /// Scalar
+    Scalar,
+    /// Vector with the given number of components
+    Vector(u8),
+    /// Matrix with the given number of rows and columns
+    Matrix(u8, u8),
+}
+
+impl Shape {
+    /// Number of scalar components
+    pub fn len(self) -> usize {
+        match self {
+            Self::Scalar => 1,
+            Self::Vector(n) => n as usize,
+            Self::Matrix(r, c) => r as usize * c as usize,
+        }
+    }
+
+    /// `true` if this shape has no components, which never happens for valid types
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Shape of a binary operation between operands of shapes `self` and `other`. Single
+    /// component operands are splatted, and vectors and matrices are truncated to the smallest
+    /// operand.
+    pub fn common(self, other: Self) -> Option<Self> {
+        Some(match (self, other) {
+            // Of two single component shapes, the one which is not a scalar
+            (Self::Scalar, t) if t.len() == 1 => t,
+            (s, t) if s.len() == 1 && t.len() == 1 => s,
+            (s, other) | (other, s) if s.len() == 1 => other,
+            (Self::Vector(n), Self::Vector(m)) => Self::Vector(n.min(m)),
+            (Self::Matrix(r1, c1), Self::Matrix(r2, c2)) => Self::Matrix(r1.min(r2), c1.min(c2)),
+            _ => return None,
+        })
+    }
+}
+
+/// Result of an implicit conversion between two types
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Conversion {
+    /// The types are identical
+    Identity,
+    /// The value is converted without loss of components
+    Implicit,
+    /// Trailing components are dropped, e.g. `float4` to `float3`
+    Truncation,
+    /// No implicit conversion exists
+    Invalid,
+}
+
+/// Implicit conversion from `from` to `to`
+pub fn conversion(from: &Type, to: &Type) -> Conversion {
+    if from == to {
+        return Conversion::Identity;
+    }
+
+    if from.is_error() || to.is_error() {
+        return Conversion::Implicit;
+    }
+
+    let (Some(from_shape), Some(to_shape)) = (from.shape(), to.shape()) else {
+        // Arrays of the same size convert element-wise
+        return match (from, to) {
+            (Type::Array(a, n), Type::Array(b, m)) if n == m => match conversion(a, b) {
+                Conversion::Identity => Conversion::Identity,
+                Conversion::Implicit => Conversion::Implicit,
+                _ => Conversion::Invalid,
+            },
+            (Type::Object(a, _), Type::Object(b, _)) if a == b => Conversion::Implicit,
+            _ => Conversion::Invalid,
+        };
+    };
+
+    match (from_shape, to_shape) {
+        (Shape::Scalar, _) => Conversion::Implicit,
+        (Shape::Vector(1), _) | (Shape::Matrix(1, 1), _) => Conversion::Implicit,
+        (Shape::Vector(_), Shape::Scalar) | (Shape::Matrix(_, _), Shape::Scalar) => {
+            Conversion::Truncation
+        }
+        (Shape::Vector(n), Shape::Vector(m)) => match n.cmp(&m) {
+            std::cmp::Ordering::Equal => Conversion::Implicit,
+            std::cmp::Ordering::Greater => Conversion::Truncation,
+            std::cmp::Ordering::Less => Conversion::Invalid,
+        },
+        (Shape::Matrix(r, c), Shape::Matrix(r2, c2)) => {
+            if (r, c) == (r2, c2) {
+                Conversion::Implicit
+            } else if r >= r2 && c >= c2 {
+                Conversion::Truncation
+            } else {
+                Conversion::Invalid
+            }
+        }
+        // Vectors and single row or column matrices convert into each other
+        (Shape::Matrix(r, c), Shape::Vector(n)) if r == 1 || c == 1 => match (r.max(c)).cmp(&n) {
+            std::cmp::Ordering::Equal => Conversion::Implicit,
+            std::cmp::Ordering::Greater => Conversion::Truncation,
+            std::cmp::Ordering::Less => Conversion::Invalid,
+        },
+        (Shape::Vector(n), Shape::Matrix(r, c)) if n as usize == r as usize * c as usize => {
+            Conversion::Implicit
+        }
+        _ => Conversion::Invalid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_type_names() {
+        assert_eq!(
+            Type::from_name("half3"),
+            Some(Type::Vector(ScalarType::Half, 3))
+        );
+        assert_eq!(
+            Type::from_name("min16float2x4"),
+            Some(Type::Matrix(ScalarType::Min16Float, 2, 4))
+        );
+        assert_eq!(
+            Type::from_name("uint16_t2"),
+            Some(Type::Vector(ScalarType::UInt16, 2))
+        );
+        assert_eq!(
+            Type::from_name("int64_t"),
+            Some(Type::Scalar(ScalarType::Int64))
+        );
+        assert_eq!(Type::from_name("float5"), None);
+        assert_eq!(Type::from_name("Foo3"), None);
+        assert_eq!(
+            Type::from_name("SamplerComparisonState"),
+            Some(Type::Object(ObjectKind::SamplerComparisonState, None))
+        );
+    }
+
+    #[test]
+    fn display_types() {
+        let ty = Type::Array(
+            Box::new(Type::Array(
+                Box::new(Type::Matrix(ScalarType::Float, 3, 4)),
+                None,
+            )),
+            Some(2),
+        );
+        assert_eq!(ty.to_string(), "float3x4[2][]");
+
+        let buffer = Type::Object(ObjectKind::StructuredBuffer, Some(Box::new(Type::UINT)));
+        assert_eq!(buffer.to_string(), "StructuredBuffer<uint>");
+        assert_eq!(
+            Type::from_name("Texture2D").unwrap().to_string(),
+            "Texture2D"
+        );
+    }
+
+    #[test]
+    fn arithmetic_promotion() {
+        use ScalarType::*;
+
+        assert_eq!(Int.common(Float), Float);
+        assert_eq!(Int.common(UInt), UInt);
+        assert_eq!(Bool.common(Bool), Int);
+        assert_eq!(Half.common(Float), Float);
+        assert_eq!(LiteralFloat.common(Half), Half);
+        assert_eq!(LiteralFloat.common(Int), Float);
+        assert_eq!(LiteralInt.common(UInt), UInt);
+        assert_eq!(LiteralInt.common(LiteralFloat), LiteralFloat);
+        assert_eq!(LiteralInt.common(Bool), Int);
+    }
+
+    #[test]
+    fn implicit_conversions() {
+        use ScalarType::*;
+
+        let conv = |a: Type, b: Type| conversion(&a, &b);
+        assert_eq!(
+            conv(Type::FLOAT, Type::Vector(Float, 4)),
+            Conversion::Implicit
+        );
+        assert_eq!(
+            conv(Type::Vector(Float, 4), Type::Vector(Int, 3)),
+            Conversion::Truncation
+        );
+        assert_eq!(
+            conv(Type::Vector(Float, 2), Type::Vector(Float, 3)),
+            Conversion::Invalid
+        );
+        assert_eq!(
+            conv(Type::Matrix(Float, 4, 4), Type::Matrix(Float, 3, 3)),
+            Conversion::Truncation
+        );
+        assert_eq!(
+            conv(Type::Vector(Float, 4), Type::Matrix(Float, 2, 2)),
+            Conversion::Implicit
+        );
+        assert_eq!(
+            conv(
+                Type::Vector(Float, 4),
+                Type::Object(ObjectKind::SamplerState, None)
+            ),
+            Conversion::Invalid
+        );
+    }
+}
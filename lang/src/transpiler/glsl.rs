@@ -626,9 +626,12 @@ impl HasPrecedence for ast::ExprData {
             | Self::BoolConst(_)
             | Self::FloatConst(_)
             | Self::DoubleConst(_)
-            | Self::StringConst(_) => 0,
+            | Self::StringConst(_)
+            | Self::StringConcat(_)
+            | Self::CharConst(_) => 0,
             // Precedence operator expression is precedence of operator
             Self::Unary(op, _) => op.precedence(),
+            Self::Cast(_, _) => 3,
             Self::Binary(op, _, _) => op.precedence(),
             Self::Ternary(_, _, _) => 15,
             Self::Assignment(_, op, _) => op.precedence(),
@@ -871,9 +874,9 @@ where
         ast::TypeSpecifierNonArrayData::USubpassInputMs => f.write_str("usubpassInputMS"),
         ast::TypeSpecifierNonArrayData::Struct(ref st) => show_struct_non_declaration(f, st, state),
         ast::TypeSpecifierNonArrayData::TypeName(ref tn) => show_type_name(f, tn, state),
-        ast::TypeSpecifierNonArrayData::TextureBuffer => f.write_str("textureBuffer"),
         ast::TypeSpecifierNonArrayData::StructuredBuffer => f.write_str("StructuredBuffer"),
         ast::TypeSpecifierNonArrayData::RWStructuredBuffer => f.write_str("RWStructuredBuffer"),
+        ast::TypeSpecifierNonArrayData::String => f.write_str("string"),
         ast::TypeSpecifierNonArrayData::StructuredBufferTemplate(ref t) => {
             f.write_str("StructuredBuffer<")?;
             show_type_specifier(f, t, state)?;
@@ -914,7 +917,7 @@ pub fn show_fully_specified_type<F>(
 where
     F: Write + ?Sized,
 {
-    if let Some(ref qual) = t.qualifier {
+    if let Some(qual) = t.qualifier.as_ref().filter(|q| shows_type_qualifier(q)) {
         show_type_qualifier(f, qual, state)?;
         f.write_char(' ')?;
     }
@@ -931,12 +934,28 @@ pub fn show_struct_non_declaration<F>(
 where
     F: Write + ?Sized,
 {
-    f.write_str("struct ")?;
+    f.write_str(match st.kind {
+        ast::StructKind::Struct => "struct ",
+        ast::StructKind::Class => "class ",
+        ast::StructKind::Interface => "interface ",
+    })?;
 
     if let Some(ref name) = st.name {
         write!(f, "{}", name)?;
     }
 
+    if !st.bases.is_empty() {
+        f.write_str(" : ")?;
+
+        let mut bases = st.bases.iter();
+        write!(f, "{}", bases.next().unwrap())?;
+
+        for base in bases {
+            state.write_list_separator(f)?;
+            write!(f, "{}", base)?;
+        }
+    }
+
     state.enter_block(f)?;
 
     for field in &st.fields {
@@ -945,11 +964,36 @@ where
         state.write_struct_field_separator(f)?;
     }
 
+    for method in &st.methods {
+        state.flush_line(f)?;
+        show_method(f, method, state)?;
+    }
+
     state.exit_block(f)?;
 
     Ok(())
 }
 
+/// Transpile a struct, class or interface method to GLSL
+pub fn show_method<F>(
+    f: &mut F,
+    m: &ast::Method,
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    show_function_prototype(f, &m.prototype, state)?;
+
+    if let Some(ref statement) = m.statement {
+        state.enter_function_definition_statement();
+        show_compound_statement(f, statement, state)?;
+        state.flush_line(f)
+    } else {
+        state.write_struct_field_separator(f)
+    }
+}
+
 /// Transpile a struct to GLSL
 pub fn show_struct<F>(
     f: &mut F,
@@ -972,7 +1016,7 @@ pub fn show_struct_field<F>(
 where
     F: Write + ?Sized,
 {
-    if let Some(ref qual) = field.qualifier {
+    if let Some(qual) = field.qualifier.as_ref().filter(|q| shows_type_qualifier(q)) {
         show_type_qualifier(f, qual, state)?;
         f.write_char(' ')?;
     }
@@ -1033,6 +1077,11 @@ where
         show_array_spec(f, arr_spec, state)?;
     }
 
+    if let Some(ref width) = a.bit_width {
+        f.write_str(" : ")?;
+        show_expr(f, width, state)?;
+    }
+
     Ok(())
 }
 
@@ -1045,19 +1094,44 @@ pub fn show_type_qualifier<F>(
 where
     F: Write + ?Sized,
 {
-    let mut qualifiers = q.qualifiers.iter();
-    let first = qualifiers.next().unwrap();
+    let qualifiers = q.qualifiers.iter().filter(|q| !is_dropped_qualifier(q));
 
-    show_type_qualifier_spec(f, first, state)?;
+    for (i, qual_spec) in qualifiers.enumerate() {
+        if i > 0 {
+            f.write_char(' ')?;
+        }
 
-    for qual_spec in qualifiers {
-        f.write_char(' ')?;
         show_type_qualifier_spec(f, qual_spec, state)?;
     }
 
     Ok(())
 }
 
+/// Return true if a type qualifier has at least one specification with a GLSL equivalent
+fn shows_type_qualifier(q: &ast::TypeQualifier) -> bool {
+    q.qualifiers.iter().any(|q| !is_dropped_qualifier(q))
+}
+
+/// Return true if a type qualifier specification is left out of the GLSL output
+///
+/// Geometry shader input primitives are declared by [show_function_definition] as an input
+/// layout instead.
+fn is_dropped_qualifier(q: &ast::TypeQualifierSpec) -> bool {
+    matches!(**q, ast::TypeQualifierSpecData::Storage(ref s) if input_primitive(s).is_some())
+}
+
+/// GLSL input layout of a geometry shader input primitive qualifier
+fn input_primitive(q: &ast::StorageQualifier) -> Option<&'static str> {
+    match **q {
+        ast::StorageQualifierData::Point => Some("points"),
+        ast::StorageQualifierData::Line => Some("lines"),
+        ast::StorageQualifierData::Triangle => Some("triangles"),
+        ast::StorageQualifierData::LineAdj => Some("lines_adjacency"),
+        ast::StorageQualifierData::TriangleAdj => Some("triangles_adjacency"),
+        _ => None,
+    }
+}
+
 /// Transpile a type_qualifier_spec to GLSL
 pub fn show_type_qualifier_spec<F>(
     f: &mut F,
@@ -1099,6 +1173,17 @@ where
         ast::StorageQualifierData::Centroid => f.write_str("centroid"),
         ast::StorageQualifierData::Patch => f.write_str("patch"),
         ast::StorageQualifierData::Sample => f.write_str("sample"),
+        ast::StorageQualifierData::Point
+        | ast::StorageQualifierData::Line
+        | ast::StorageQualifierData::Triangle
+        | ast::StorageQualifierData::LineAdj
+        | ast::StorageQualifierData::TriangleAdj => {
+            f.write_str("layout(")?;
+            f.write_str(input_primitive(q).unwrap_or_default())?;
+            f.write_str(") in")
+        }
+        ast::StorageQualifierData::RowMajor => f.write_str("row_major"),
+        ast::StorageQualifierData::ColumnMajor => f.write_str("column_major"),
         ast::StorageQualifierData::Uniform => f.write_str("uniform"),
         ast::StorageQualifierData::Shared => f.write_str("shared"),
         ast::StorageQualifierData::Coherent => f.write_str("coherent"),
@@ -1214,6 +1299,8 @@ where
         ast::InterpolationQualifierData::Smooth => f.write_str("smooth"),
         ast::InterpolationQualifierData::Flat => f.write_str("flat"),
         ast::InterpolationQualifierData::NoPerspective => f.write_str("noperspective"),
+        ast::InterpolationQualifierData::Linear => f.write_str("smooth"),
+        ast::InterpolationQualifierData::NoInterpolation => f.write_str("flat"),
     }
 }
 
@@ -1241,6 +1328,25 @@ where
     }
 }
 
+/// Transpile a character literal to GLSL
+pub fn show_char<F>(f: &mut F, x: char, _: &mut FormattingState<'_>) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    match x {
+        '\u{07}' => f.write_str("'\\a'"),
+        '\u{08}' => f.write_str("'\\b'"),
+        '\t' => f.write_str("'\\t'"),
+        '\n' => f.write_str("'\\n'"),
+        '\u{0b}' => f.write_str("'\\v'"),
+        '\u{0c}' => f.write_str("'\\f'"),
+        '\r' => f.write_str("'\\r'"),
+        '\0' => f.write_str("'\\0'"),
+        '\'' | '\\' => write!(f, "'\\{}'", x),
+        _ => write!(f, "'{}'", x),
+    }
+}
+
 /// Transpile an expr to GLSL
 pub fn show_expr<F>(
     f: &mut F,
@@ -1258,6 +1364,18 @@ where
         ast::ExprData::FloatConst(ref x) => show_float(f, *x, state),
         ast::ExprData::DoubleConst(ref x) => show_double(f, *x, state),
         ast::ExprData::StringConst(ref x) => write!(f, "\"{}\"", x),
+        ast::ExprData::StringConcat(ref pieces) => {
+            for (i, piece) in pieces.iter().enumerate() {
+                if i > 0 {
+                    f.write_char(' ')?;
+                }
+
+                show_expr(f, piece, state)?;
+            }
+
+            Ok(())
+        }
+        ast::ExprData::CharConst(x) => show_char(f, x, state),
         ast::ExprData::Unary(ref op, ref e) => {
             // Note: all unary ops are right-to-left associative
             show_unary_op(f, op, state)?;
@@ -1280,6 +1398,19 @@ where
                 show_expr(f, e, state)
             }
         }
+        ast::ExprData::Cast(ref ty, ref e) => {
+            f.write_char('(')?;
+            show_type_specifier(f, ty, state)?;
+            f.write_char(')')?;
+
+            if e.precedence() > expr.precedence() {
+                f.write_char('(')?;
+                show_expr(f, e, state)?;
+                f.write_char(')')
+            } else {
+                show_expr(f, e, state)
+            }
+        }
         ast::ExprData::Binary(ref op, ref l, ref r) => {
             // Note: all binary ops are left-to-right associative (<= for left part)
 
@@ -1595,6 +1726,11 @@ where
 {
     show_fully_specified_type(f, &fp.ty, state)?;
     f.write_char(' ')?;
+
+    if let Some(ref scope) = fp.scope {
+        write!(f, "{}::", scope)?;
+    }
+
     show_identifier(f, &fp.name, state)?;
 
     f.write_char('(')?;
@@ -1623,7 +1759,7 @@ where
 {
     match **p {
         ast::FunctionParameterDeclarationData::Named(ref qual, ref fpd) => {
-            if let Some(ref q) = *qual {
+            if let Some(q) = qual.as_ref().filter(|q| shows_type_qualifier(q)) {
                 show_type_qualifier(f, q, state)?;
                 f.write_char(' ')?;
             }
@@ -1631,7 +1767,7 @@ where
             show_function_parameter_declarator(f, fpd, state)
         }
         ast::FunctionParameterDeclarationData::Unnamed(ref qual, ref ty) => {
-            if let Some(ref q) = *qual {
+            if let Some(q) = qual.as_ref().filter(|q| shows_type_qualifier(q)) {
                 show_type_qualifier(f, q, state)?;
                 f.write_char(' ')?;
             }
@@ -1652,7 +1788,14 @@ where
 {
     show_type_specifier(f, &p.ty, state)?;
     f.write_char(' ')?;
-    show_arrayed_identifier(f, &p.ident, state)
+    show_arrayed_identifier(f, &p.ident, state)?;
+
+    if let Some(ref default_value) = p.default_value {
+        state.write_binary_op(f, "=")?;
+        show_initializer(f, default_value, state)?;
+    }
+
+    Ok(())
 }
 
 /// Transpile an init_declarator_list to GLSL
@@ -1788,7 +1931,11 @@ where
     if let Some(ref resource_binding) = cb.resource_binding {
         f.write_str(" : register(")?;
         show_identifier(f, &resource_binding.register_type, state)?;
-        show_expr(f, &resource_binding.index, state)?;
+        match *resource_binding.index {
+            // Register indices are written without the `u` suffix
+            ast::ExprData::UIntConst(index) => write!(f, "{}", index)?,
+            _ => show_expr(f, &resource_binding.index, state)?,
+        }
         f.write_str(")")?;
     }
     
@@ -1802,6 +1949,69 @@ where
     Ok(())
 }
 
+/// Transpile the input layout declarations implied by a function definition to GLSL
+///
+/// Only `[numthreads(x, y, z)]` and the input primitive qualifiers of geometry shader parameters
+/// have GLSL equivalents, the compute and geometry shader input layout declarations; other
+/// attributes are dropped.
+fn show_function_layouts<F>(
+    f: &mut F,
+    fd: &ast::FunctionDefinition,
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    for parameter in &fd.prototype.parameters {
+        let qualifier = match **parameter {
+            ast::FunctionParameterDeclarationData::Named(ref qualifier, _)
+            | ast::FunctionParameterDeclarationData::Unnamed(ref qualifier, _) => qualifier,
+        };
+
+        for spec in qualifier.iter().flat_map(|q| &q.qualifiers) {
+            if let ast::TypeQualifierSpecData::Storage(ref storage) = **spec {
+                if input_primitive(storage).is_some() {
+                    show_storage_qualifier(f, storage, state)?;
+                    state.write_declaration_terminator(f)?;
+                    state.flush_line(f)?;
+                }
+            }
+        }
+    }
+
+    for attribute in fd.attributes.iter().flatten() {
+        let spec = &attribute.spec;
+        if spec.namespace.is_some() || !spec.name.as_str().eq_ignore_ascii_case("numthreads") {
+            continue;
+        }
+
+        let params = match spec.params.as_deref() {
+            Some(params @ [_, _, _]) => params,
+            _ => continue,
+        };
+
+        f.write_str("layout(")?;
+        for (i, (name, param)) in ["local_size_x", "local_size_y", "local_size_z"]
+            .iter()
+            .zip(params)
+            .enumerate()
+        {
+            if i > 0 {
+                state.write_list_separator(f)?;
+            }
+
+            f.write_str(name)?;
+            state.write_binary_op(f, "=")?;
+            show_expr(f, param, state)?;
+        }
+        f.write_str(") in")?;
+        state.write_declaration_terminator(f)?;
+        state.flush_line(f)?;
+    }
+
+    Ok(())
+}
+
 /// Transpile a function_definition to GLSL
 pub fn show_function_definition<F>(
     f: &mut F,
@@ -1811,6 +2021,7 @@ pub fn show_function_definition<F>(
 where
     F: Write + ?Sized,
 {
+    show_function_layouts(f, fd, state)?;
     show_function_prototype(f, &fd.prototype, state)?;
     state.enter_function_definition_statement();
     show_compound_statement(f, &fd.statement, state)?;
@@ -1874,6 +2085,8 @@ where
         ast::StatementData::Iteration(ref i) => show_iteration_statement(f, i, state),
         ast::StatementData::Jump(ref j) => show_jump_statement(f, j, state),
         ast::StatementData::Compound(ref c) => show_compound_statement(f, c, state),
+        // GLSL has no statement attributes
+        ast::StatementData::Attributed(_, ref st) => show_statement(f, st, state),
     }
 }
 
@@ -2393,6 +2606,8 @@ where
             show_function_definition(f, fd, state)?
         }
         ast::ExternalDeclarationData::Declaration(ref d) => show_declaration(f, d, state)?,
+        // GLSL has no templates
+        ast::ExternalDeclarationData::Template(_) => {}
     }
 
     state.exit_external_declaration();
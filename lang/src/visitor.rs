@@ -136,6 +136,10 @@ macro_rules! make_visitor_trait {
         Visit::Children
       }
 
+      fn visit_template_declaration(&mut self, _: $($ref)* ast::TemplateDeclaration) -> Visit {
+        Visit::Children
+      }
+
       fn visit_function_definition(&mut self, _: $($ref)* ast::FunctionDefinition) -> Visit {
         Visit::Children
       }
@@ -378,6 +382,18 @@ macro_rules! make_visitor_trait {
       fn visit_resource_binding(&mut self, _: $($ref)* ast::ResourceBinding) -> Visit {
         Visit::Children
       }
+
+      fn visit_pack_offset(&mut self, _: $($ref)* ast::PackOffset) -> Visit {
+        Visit::Children
+      }
+
+      fn visit_method(&mut self, _: $($ref)* ast::Method) -> Visit {
+        Visit::Children
+      }
+
+      fn visit_overloaded_operator(&mut self, _: $($ref)* ast::OverloadedOperator) -> Visit {
+        Visit::Children
+      }
     }
   }
 }
@@ -465,6 +481,7 @@ macro_rules! make_host_trait {
             ast::ExternalDeclarationData::Preprocessor(p) => p.$mthd_name(visitor),
             ast::ExternalDeclarationData::FunctionDefinition(fd) => fd.$mthd_name(visitor),
             ast::ExternalDeclarationData::Declaration(d) => d.$mthd_name(visitor),
+            ast::ExternalDeclarationData::Template(t) => t.$mthd_name(visitor),
           }
         }
       }
@@ -683,7 +700,9 @@ macro_rules! make_host_trait {
 
         if visit == Visit::Children {
           self.ty.$mthd_name(visitor);
+          self.scope.$mthd_name(visitor);
           self.name.$mthd_name(visitor);
+          self.operator.$mthd_name(visitor);
 
           for param in $($ref)* self.parameters {
             param.$mthd_name(visitor);
@@ -723,8 +742,30 @@ macro_rules! make_host_trait {
         let visit = visitor.visit_function_parameter_declarator(self);
 
         if visit == Visit::Children {
+          if let Some(attributes) = $($ref)* self.attributes {
+            for attr in attributes.$iter() {
+              attr.$mthd_name(visitor);
+            }
+          }
           self.ty.$mthd_name(visitor);
           self.ident.$mthd_name(visitor);
+          self.default_value.$mthd_name(visitor);
+        }
+      }
+    }
+
+    impl $host_ty for ast::TemplateDeclaration {
+      fn $mthd_name<V>($($ref)* self, visitor: &mut V)
+      where
+          V: $visitor_ty,
+      {
+        let visit = visitor.visit_template_declaration(self);
+
+        if visit == Visit::Children {
+          for parameter in $($ref)* self.parameters {
+            parameter.$mthd_name(visitor);
+          }
+          self.declaration.$mthd_name(visitor);
         }
       }
     }
@@ -808,6 +849,11 @@ macro_rules! make_host_trait {
         let visit = visitor.visit_cbuffer(self);
 
         if visit == Visit::Children {
+          if let Some(attributes) = $($ref)* self.attributes {
+            for attr in attributes.$iter() {
+              attr.$mthd_name(visitor);
+            }
+          }
           self.name.$mthd_name(visitor);
           self.resource_binding.$mthd_name(visitor);
 
@@ -874,6 +920,11 @@ macro_rules! make_host_trait {
         let visit = visitor.visit_single_declaration(self);
 
         if visit == Visit::Children {
+          if let Some(attributes) = $($ref)* self.attributes {
+            for attr in attributes.$iter() {
+              attr.$mthd_name(visitor);
+            }
+          }
           self.ty.$mthd_name(visitor);
           self.name.$mthd_name(visitor);
           self.array_specifier.$mthd_name(visitor);
@@ -1072,6 +1123,8 @@ macro_rules! make_host_trait {
         if visit == Visit::Children {
           self.ident.$mthd_name(visitor);
           self.array_spec.$mthd_name(visitor);
+          self.packoffset.$mthd_name(visitor);
+          self.bit_width.$mthd_name(visitor);
         }
       }
     }
@@ -1092,6 +1145,11 @@ macro_rules! make_host_trait {
               e.$mthd_name(visitor);
             }
 
+            ast::ExprData::Cast(ty, e) => {
+              ty.$mthd_name(visitor);
+              e.$mthd_name(visitor);
+            }
+
             ast::ExprData::Binary(op, a, b) => {
               op.$mthd_name(visitor);
               a.$mthd_name(visitor);
@@ -1137,6 +1195,12 @@ macro_rules! make_host_trait {
               b.$mthd_name(visitor);
             }
 
+            ast::ExprData::StringConcat(pieces) => {
+              for piece in pieces {
+                piece.$mthd_name(visitor);
+              }
+            }
+
             _ => (),
           }
         }
@@ -1226,9 +1290,17 @@ macro_rules! make_host_trait {
         if visit == Visit::Children {
           self.name.$mthd_name(visitor);
 
+          for base in $($ref)* self.bases {
+            base.$mthd_name(visitor);
+          }
+
           for field in $($ref)* self.fields {
             field.$mthd_name(visitor);
           }
+
+          for method in $($ref)* self.methods {
+            method.$mthd_name(visitor);
+          }
         }
       }
     }
@@ -1241,6 +1313,11 @@ macro_rules! make_host_trait {
         let visit = visitor.visit_struct_field_specifier(self);
 
         if visit == Visit::Children {
+          if let Some(attributes) = $($ref)* self.attributes {
+            for attr in attributes.$iter() {
+              attr.$mthd_name(visitor);
+            }
+          }
           self.qualifier.$mthd_name(visitor);
           self.ty.$mthd_name(visitor);
 
@@ -1268,6 +1345,12 @@ macro_rules! make_host_trait {
             ast::StatementData::Iteration(i) => i.$mthd_name(visitor),
             ast::StatementData::Jump(j) => j.$mthd_name(visitor),
             ast::StatementData::Compound(cs) => cs.$mthd_name(visitor),
+            ast::StatementData::Attributed(attributes, s) => {
+              for attr in attributes.$iter() {
+                attr.$mthd_name(visitor);
+              }
+              s.$mthd_name(visitor);
+            }
           }
         }
       }
@@ -1504,6 +1587,7 @@ macro_rules! make_host_trait {
         let visit = visitor.visit_attribute_spec(self);
 
         if visit == Visit::Children {
+          self.namespace.$mthd_name(visitor);
           self.name.$mthd_name(visitor);
           if let Some(params) = $($ref)* self.params {
             for param in params.$iter() {
@@ -1527,10 +1611,59 @@ macro_rules! make_host_trait {
         }
       }
     }
+
+    impl $host_ty for ast::PackOffset {
+      fn $mthd_name<V>($($ref)* self, visitor: &mut V)
+      where
+          V: $visitor_ty,
+      {
+        let visit = visitor.visit_pack_offset(self);
+
+        if visit == Visit::Children {
+          self.index.$mthd_name(visitor);
+          self.component.$mthd_name(visitor);
+        }
+      }
+    }
+
+    impl $host_ty for ast::OverloadedOperator {
+      fn $mthd_name<V>($($ref)* self, visitor: &mut V)
+      where
+          V: $visitor_ty,
+      {
+        let visit = visitor.visit_overloaded_operator(self);
+
+        if visit == Visit::Children {
+          match $($ref)* **self {
+            ast::OverloadedOperatorData::Unary(op) => op.$mthd_name(visitor),
+            ast::OverloadedOperatorData::Binary(op) => op.$mthd_name(visitor),
+            ast::OverloadedOperatorData::Call | ast::OverloadedOperatorData::Subscript => (),
+          }
+        }
+      }
+    }
+
+    impl $host_ty for ast::Method {
+      fn $mthd_name<V>($($ref)* self, visitor: &mut V)
+      where
+          V: $visitor_ty,
+      {
+        let visit = visitor.visit_method(self);
+
+        if visit == Visit::Children {
+          if let Some(attributes) = $($ref)* self.attributes {
+            for attr in attributes.$iter() {
+              attr.$mthd_name(visitor);
+            }
+          }
+          self.prototype.$mthd_name(visitor);
+          self.statement.$mthd_name(visitor);
+        }
+      }
+    }
   }
 }
 
-
 // immutable
 make_visitor_trait!(Visitor, &);
 make_host_trait!(Host, Visitor, visit, iter, &);
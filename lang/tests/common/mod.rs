@@ -53,10 +53,7 @@ fn parse_tu(
         .open(path)
         .expect("failed to open file")
         .builder()
-        .opts(&ParseOptions {
-            default_version: 100,
-            ..Default::default()
-        })
+        .opts(&ParseOptions::default())
         .parse()
         .map(|(tu, _, _)| tu)
 }
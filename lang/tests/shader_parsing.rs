@@ -24,11 +24,9 @@ fn parse_shader_with_fs_context(path: &Path) -> Result<ast::TranslationUnit, Box
                 match result {
                     Ok(event) => {
                         use hlsl_lang_pp::processor::event::Event;
-                        match event {
-                            Event::Token { token, .. } => {
-                                processed_source.push_str(&token.text());
-                            }
-                            _ => {} // Ignore other events like directives, errors, etc.
+                        // Ignore other events like directives, errors, etc.
+                        if let Event::Token { token, .. } = event {
+                            processed_source.push_str(token.text());
                         }
                     }
                     Err(e) => {
@@ -54,26 +52,18 @@ fn parse_shader_with_fs_context(path: &Path) -> Result<ast::TranslationUnit, Box
 const EXPECTED_FAIL_SHADERS: &[&str] = &[
     "shaders/FxDis/test.hlsl",
     "shaders/HlslCrossCompiler/ps4/fxaa.hlsl",
-    "shaders/HlslCrossCompiler/ps5/interface_arrays.hlsl",
-    "shaders/HlslCrossCompiler/ps5/interfaces.hlsl",
     "shaders/HlslCrossCompiler/hs5/basic.hlsl",
-    "shaders/HlslCrossCompiler/vs5/const_temp.hlsl",
     "shaders/HlslCrossCompiler/ds5/basic.hlsl",
     "shaders/Internal/Textures.hlsl",
     "shaders/Sdk/Direct3D11/ContactHardeningShadows11/ContactHardeningShadows11.hlsl",
     "shaders/Sdk/Direct3D11/SimpleBezier11/SimpleBezier11.hlsl",
-    "shaders/Sdk/Direct3D11/DynamicShaderLinkage11/DynamicShaderLinkage11_PS.hlsl",
-    "shaders/Sdk/Direct3D11/DynamicShaderLinkage11/DynamicShaderLinkage11_VS.hlsl",
     "shaders/Sdk/Direct3D11/DecalTessellation11/DecalTessellation11.hlsl",
     "shaders/Sdk/Direct3D11/PNTriangles11/PNTriangles11.hlsl",
     "shaders/Sdk/Direct3D11/SubD11/SubD11.hlsl",
-    "shaders/Sdk/Direct3D11/CascadedShadowMaps11/RenderCascadeScene.hlsl",
-    "shaders/Sdk/Direct3D11/CascadedShadowMaps11/RenderCascadeShadow.hlsl",
     "shaders/Sdk/Direct3D11/NBodyGravityCS11/NBodyGravityCS11.hlsl",
     "shaders/Sdk/Direct3D11/NBodyGravityCS11/ParticleDraw.hlsl",
     "shaders/Sdk/Direct3D11/FluidCS11/FluidRender.hlsl",
     "shaders/Sdk/Direct3D11/FluidCS11/FluidCS11.hlsl",
-    "shaders/Sdk/Direct3D11/DDSWithoutD3DX11/DDSWithoutD3DX.hlsl",
     "shaders/Sdk/Direct3D11/BasicCompute11/BasicCompute11.hlsl",
     "shaders/Sdk/Direct3D11/DetailTessellation11/POM.hlsl",
     "shaders/Sdk/Direct3D11/DetailTessellation11/Particle.hlsl",
@@ -88,12 +78,9 @@ const EXPECTED_FAIL_SHADERS: &[&str] = &[
     "shaders/Sdk/Direct3D11/BC6HBC7EncoderDecoder11/BC6HDecode.hlsl",
     "shaders/Sdk/Direct3D11/BC6HBC7EncoderDecoder11/BC6HEncode.hlsl",
     "shaders/Sdk/Direct3D11/BC6HBC7EncoderDecoder11/BC7Decode.hlsl",
-    "shaders/Sdk/Direct3D11/VarianceShadows11/RenderVarianceShadow.hlsl",
-    "shaders/Sdk/Direct3D11/VarianceShadows11/2DQuadShaders.hlsl",
     "shaders/Sdk/Direct3D11/VarianceShadows11/RenderVarianceScene.hlsl",
     "shaders/Sdk/Direct3D11/HDRToneMappingCS11/FilterCS.hlsl",
     "shaders/Sdk/Direct3D11/HDRToneMappingCS11/BrightPassAndHorizFilterCS.hlsl",
-    "shaders/Sdk/Direct3D11/HDRToneMappingCS11/skybox11.hlsl",
     "shaders/Sdk/Direct3D11/HDRToneMappingCS11/ReduceToSingleCS.hlsl",
     "shaders/Sdk/Direct3D11/HDRToneMappingCS11/FinalPass.hlsl",
     "shaders/Sdk/Direct3D11/HDRToneMappingCS11/ReduceTo1DCS.hlsl",
@@ -111,7 +98,6 @@ const EXPECTED_FAIL_DATA_HLSL: &[&str] = &[
     "hlsl.reflection.vert",
     "hlsl.domain.2.tese",
     "hlsl.rw.register.frag",
-    "hlsl.memberFunCall.frag",
     "hlsl.buffer.frag",
     "hlsl.type.identifier.frag",
     "hlsl.matType.frag",
@@ -140,14 +126,11 @@ const EXPECTED_FAIL_DATA_HLSL: &[&str] = &[
     "hlsl.load.buffer.float.dx10.frag",
     "hlsl.rw.scalar.bracket.frag",
     "hlsl.samplecmp.negative2.frag",
-    "hlsl.switch.frag",
-    "hlsl.matpack-1.frag",
     "hlsl.texture.struct.frag",
     "hlsl.wavebroadcast.comp",
     "hlsl.this.frag",
     "hlsl.structbuffer.floatidx.comp",
     "hlsl.domain.3.tese",
-    "hlsl.structin.vert",
     "hlsl.samplegrad.array.dx10.frag",
     "hlsl.samplebias.offsetarray.dx10.frag",
     "hlsl.hull.void.tesc",
@@ -155,7 +138,6 @@ const EXPECTED_FAIL_DATA_HLSL: &[&str] = &[
     "hlsl.gathercmpRGBA.basic.dx10.frag",
     "hlsl.load.rwtexture.array.dx10.frag",
     "hlsl.samplegrad.basic.dx10.frag",
-    "hlsl.whileLoop.frag",
     "hlsl.gathercmpRGBA.array.dx10.frag",
     "hlsl.imagefetch-subvec4.comp",
     "hlsl.intrinsics.f1632.frag",
@@ -166,35 +148,24 @@ const EXPECTED_FAIL_DATA_HLSL: &[&str] = &[
     "hlsl.load.rwbuffer.dx10.frag",
     "hlsl.hull.4.tesc",
     "hlsl.load.offsetarray.dx10.frag",
-    "hlsl.specConstant.frag",
-    "hlsl.partialInit.frag",
     "hlsl.intrinsics.promote.outputs.frag",
     "hlsl.sample.dx9.vert",
-    "hlsl.matpack-pragma.frag",
     "hlsl.getdimensions.dx10.frag",
     "hlsl.samplecmplevelzero.offsetarray.dx10.frag",
     "hlsl.intrinsics.negative.vert",
     "hlsl.preprocessor.frag",
-    "hlsl.loopattr.frag",
     "hlsl.calculatelod.dx10.frag",
     "hlsl.intrinsics.vert",
     "hlsl.load.2dms.dx10.frag",
-    "hlsl.charLit.vert",
-    "hlsl.hlslOffset.vert",
     "hlsl.numericsuffixes.frag",
     "hlsl.tx.bracket.frag",
     "hlsl.structbuffer.rwbyte.frag",
     "hlsl.wavereduction.comp",
-    "hlsl.attribute.expression.comp",
     "hlsl.hull.5.tesc",
     "hlsl.samplecmp.offset.dx10.frag",
-    "hlsl.doLoop.frag",
     "hlsl.texture.subvec4.frag",
-    "hlsl.nonstaticMemberFunction.frag",
     "hlsl.earlydepthstencil.frag",
-    "hlsl.params.default.negative.frag",
     "hlsl.load.buffer.dx10.frag",
-    "hlsl.logicalConvert.frag",
     "hlsl.promote.atomic.frag",
     "hlsl.load.basic.dx10.vert",
     "hlsl.color.hull.tesc",
@@ -207,22 +178,18 @@ const EXPECTED_FAIL_DATA_HLSL: &[&str] = &[
     "hlsl.texturebuffer.frag",
     "hlsl.sample.sub-vec4.dx10.frag",
     "hlsl.stringtoken.frag",
-    "hlsl.constructArray.vert",
     "hlsl.samplelevel.offsetarray.dx10.frag",
     "hlsl.sample.basic.dx10.frag",
     "hlsl.attribute.frag",
-    "hlsl.PointSize.vert",
     "hlsl.layout.frag",
     "hlsl.flattenOpaqueInit.vert",
     "hlsl.singleArgIntPromo.vert",
     "hlsl.typedef.frag",
     "hlsl.groupid.comp",
-    "hlsl.cast.frag",
     "hlsl.structbuffer.fn2.comp",
     "hlsl.calculatelodunclamped.dx10.frag",
     "hlsl.structStructName.frag",
     "hlsl.load.offset.dx10.frag",
-    "hlsl.self_cast.frag",
     "hlsl.matType.int.frag",
     "hlsl.gather.offsetarray.dx10.frag",
     "hlsl.hull.6.tesc",
@@ -232,13 +199,10 @@ const EXPECTED_FAIL_DATA_HLSL: &[&str] = &[
     "hlsl.gatherRGBA.array.dx10.frag",
     "hlsl.automap.frag",
     "hlsl.staticMemberFunction.frag",
-    "hlsl.structIoFourWay.frag",
     "hlsl.getsampleposition.dx10.frag",
     "hlsl.reflection.binding.frag",
-    "hlsl.scalar2matrix.frag",
     "hlsl.subpass.frag",
     "hlsl.samplegrad.offset.dx10.frag",
-    "hlsl.forLoop.frag",
     "hlsl.samplebias.array.dx10.frag",
     "hlsl.shapeConv.frag",
     "hlsl.inf.vert",
@@ -260,9 +224,7 @@ const EXPECTED_FAIL_DATA_HLSL: &[&str] = &[
     "hlsl.struct.split.trivial.geom",
     "hlsl.structbuffer.append.frag",
     "hlsl.structbuffer.atomics.frag",
-    "hlsl.params.default.frag",
     "hlsl.string.frag",
-    "hlsl.cbuffer-identifier.vert",
     "hlsl.gs-hs-mix.tesc",
     "hlsl.store.rwbyteaddressbuffer.type.comp",
     "hlsl.overload.frag",
@@ -300,14 +262,10 @@ const EXPECTED_FAIL_DATA_HLSL: &[&str] = &[
     "hlsl.intrinsics.frag",
     "hlsl.float1.frag",
     "hlsl.sample.dx9.frag",
-    "hlsl.multiDescriptorSet.frag",
     "hlsl.struct.split.array.geom",
     "hlsl.typeGraphCopy.vert",
     "hlsl.getdimensions.dx10.vert",
-    "hlsl.identifier.sample.frag",
     "hlsl.constantbuffer.frag",
-    "hlsl.attributeGlobalBuffer.frag",
-    "hlsl.printf.comp",
     "hlsl.intrinsics.promote.frag",
     "hlsl.pp.line3.frag",
     "hlsl.hull.ctrlpt-1.tesc",
@@ -320,7 +278,6 @@ const EXPECTED_FAIL_DATA_HLSL: &[&str] = &[
     "hlsl.domain.1.tese",
     "hlsl.if.frag",
     "hlsl.samplegrad.basic.dx10.vert",
-    "hlsl.scalarCast.vert",
     "hlsl.layoutOverride.vert",
     "hlsl.structarray.flatten.frag",
     "hlsl.aliasOpaque.frag",